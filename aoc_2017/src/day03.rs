@@ -58,6 +58,38 @@ impl SpiralIter {
             direction: Direction::East,
         }
     }
+
+    /// The point and direction of travel at the 1-indexed spiral position
+    /// `n` (`n == 1` is the origin, heading East), computed directly
+    /// instead of walking there step by step. Position `n` lies on ring
+    /// `k = ceil((ceil_sqrt(n) - 1) / 2)`, where ring `k >= 1` covers positions
+    /// `(2k-1)^2+1 ..= (2k+1)^2`: it's entered at `(k, -(k-1))` heading
+    /// North, then turns West at `(k, k)`, South at `(-k, k)`, and East at
+    /// `(-k, -k)`, closing the ring at `(k, -k)`.
+    fn state_at(n: usize) -> (Point, Direction) {
+        if n == 1 {
+            return ((0, 0), Direction::East);
+        }
+
+        let root = n.isqrt();
+        let ceil_root = if root * root == n { root } else { root + 1 };
+        let k = ceil_root.saturating_sub(1).div_ceil(2) as i32;
+        let ring_start = (2 * k - 1).pow(2) as usize + 1;
+        let side_len = 2 * k;
+        let offset = (n - ring_start) as i32;
+
+        match offset / side_len {
+            0 => ((k, -(k - 1) + offset), Direction::North),
+            1 => ((k - 1 - (offset - side_len), k), Direction::West),
+            2 => ((-k, k - 1 - (offset - 2 * side_len)), Direction::South),
+            _ => ((-k + 1 + (offset - 3 * side_len), -k), Direction::East),
+        }
+    }
+
+    /// The point at the 1-indexed spiral position `n`, in O(1).
+    fn coordinate_at(n: usize) -> Point {
+        Self::state_at(n).0
+    }
 }
 
 impl Iterator for SpiralIter {
@@ -82,6 +114,13 @@ impl Iterator for SpiralIter {
 
         Some(std::mem::replace(&mut self.current, next))
     }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let (point, direction) = Self::state_at(n + 1);
+        self.current = point;
+        self.direction = direction;
+        self.next()
+    }
 }
 
 fn manhattan_distance(p: Point) -> i32 {
@@ -150,6 +189,33 @@ mod tests {
         );
     }
 
+    /// A bare, un-overridden stepwise iterator, used as the reference
+    /// implementation that `coordinate_at`/`nth` are checked against.
+    fn stepwise_points() -> impl Iterator<Item = Point> {
+        struct Stepwise(SpiralIter);
+        impl Iterator for Stepwise {
+            type Item = Point;
+            fn next(&mut self) -> Option<Point> {
+                Iterator::next(&mut self.0)
+            }
+        }
+        Stepwise(SpiralIter::new())
+    }
+
+    #[test]
+    fn coordinate_at_matches_points_from_stepwise_iteration() {
+        for (n, point) in (1..=200).zip(stepwise_points()) {
+            assert_eq!(SpiralIter::coordinate_at(n), point, "mismatch at n = {n}");
+        }
+    }
+
+    #[test]
+    fn nth_resumes_stepwise_iteration_correctly() {
+        let mut spiral = SpiralIter::new();
+        assert_eq!(spiral.nth(22), stepwise_points().nth(22));
+        assert_equal(spiral.take(3), stepwise_points().skip(23).take(3));
+    }
+
     #[test]
     fn manhattan_distance_is_correct_for_points_in_all_quadrants() {
         assert_eq!(manhattan_distance((0, 0)), 0);
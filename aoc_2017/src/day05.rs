@@ -1,17 +1,18 @@
 use std::num::ParseIntError;
 
 use aoc_companion::prelude::*;
+use aoc_utils::register_machine::{Instruction, Op, Value, Vm};
 
 use JumpChangePolicy::*;
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
 enum JumpChangePolicy {
     AlwaysIncrease,
     DecreaseLongJumps,
 }
 
 pub(crate) struct Door {
-    jumps: Vec<isize>,
+    jumps: Vec<i64>,
 }
 
 impl<'input> Solution<'input> for Door {
@@ -24,55 +25,37 @@ impl<'input> Solution<'input> for Door {
     }
 
     fn part1(&self) -> usize {
-        Program::new(self.jumps.clone(), AlwaysIncrease).count()
+        jump_maze_step_count(&self.jumps, AlwaysIncrease)
     }
 
     fn part2(&self) -> usize {
-        Program::new(self.jumps.clone(), DecreaseLongJumps).count()
+        jump_maze_step_count(&self.jumps, DecreaseLongJumps)
     }
 }
 
-#[derive(Debug, Eq, PartialEq)]
-struct Program {
-    pc: usize,
-    prog: Vec<isize>,
-    policy: JumpChangePolicy,
-}
+/// Models each jump offset as its own register, jumped to via
+/// [`Op::Jmp`], and counts the steps to walk off the program by
+/// [`Vm::trace`]ing it to exhaustion. `on_jump` self-modifies the offset
+/// just jumped by per `policy`, matching the maze's quirk that every cell
+/// rewrites itself on use.
+fn jump_maze_step_count(jumps: &[i64], policy: JumpChangePolicy) -> usize {
+    let program: Vec<Instruction> = (0..jumps.len())
+        .map(|i| Instruction::new(Op::Jmp(Value::Register(i.to_string()))))
+        .collect();
 
-impl Program {
-    fn new(prog: Vec<isize>, policy: JumpChangePolicy) -> Self {
-        Self {
-            pc: 0,
-            prog,
-            policy,
-        }
-    }
-}
+    let mut vm = Vm::new();
+    vm.registers = jumps
+        .iter()
+        .enumerate()
+        .map(|(i, &jump)| (i.to_string(), jump))
+        .collect();
+    vm.on_jump = Some(Box::new(move |offset| match policy {
+        AlwaysIncrease => offset + 1,
+        DecreaseLongJumps if offset >= 3 => offset - 1,
+        DecreaseLongJumps => offset + 1,
+    }));
 
-impl Iterator for Program {
-    type Item = usize;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        if (0..self.prog.len()).contains(&self.pc) {
-            let jump = &mut self.prog[self.pc];
-            let pc = self.pc;
-            Some(std::mem::replace(
-                &mut self.pc,
-                match std::mem::replace(
-                    jump,
-                    match self.policy {
-                        DecreaseLongJumps if *jump >= 3 => *jump - 1,
-                        _ => *jump + 1,
-                    },
-                ) {
-                    d if d >= 0 => pc.checked_add(d as usize).unwrap(),
-                    d => pc.wrapping_sub(-d as usize),
-                },
-            ))
-        } else {
-            None
-        }
-    }
+    vm.trace(&program, std::iter::empty()).count()
 }
 
 #[cfg(test)]
@@ -81,22 +64,39 @@ mod tests {
 
     use super::*;
 
+    fn jump_maze_pc_trace(jumps: &[i64], policy: JumpChangePolicy) -> Vec<i64> {
+        let program: Vec<Instruction> = (0..jumps.len())
+            .map(|i| Instruction::new(Op::Jmp(Value::Register(i.to_string()))))
+            .collect();
+        let mut vm = Vm::new();
+        vm.registers = jumps
+            .iter()
+            .enumerate()
+            .map(|(i, &jump)| (i.to_string(), jump))
+            .collect();
+        vm.on_jump = Some(Box::new(move |offset| match policy {
+            AlwaysIncrease => offset + 1,
+            DecreaseLongJumps if offset >= 3 => offset - 1,
+            DecreaseLongJumps => offset + 1,
+        }));
+        vm.trace(&program, std::iter::empty())
+            .map(|state| state.pc)
+            .collect()
+    }
+
     #[test]
     fn program_execution_produces_intermediate_program_counters() {
         assert_equal(
-            Program::new(vec![0, 3, 0, 1, -3], AlwaysIncrease),
-            [0, 0, 1, 4, 1],
+            jump_maze_pc_trace(&[0, 3, 0, 1, -3], AlwaysIncrease),
+            [0, 1, 4, 1, 5],
         );
     }
 
     #[test]
     fn program_terminates_after_correct_number_of_jumps() {
+        assert_eq!(jump_maze_step_count(&[0, 3, 0, 1, -3], AlwaysIncrease), 5);
         assert_eq!(
-            Program::new(vec![0, 3, 0, 1, -3], AlwaysIncrease).count(),
-            5
-        );
-        assert_eq!(
-            Program::new(vec![0, 3, 0, 1, -3], DecreaseLongJumps).count(),
+            jump_maze_step_count(&[0, 3, 0, 1, -3], DecreaseLongJumps),
             10
         );
     }
@@ -1,9 +1,7 @@
 use aoc_companion::prelude::*;
+use aoc_utils::cycle::brent;
 use itertools::Itertools;
-use std::{
-    collections::{HashMap, HashSet},
-    num::ParseIntError,
-};
+use std::num::ParseIntError;
 
 pub(crate) struct Door {
     bank: Vec<i32>,
@@ -45,48 +43,27 @@ fn redistribute(bank: &mut [i32]) {
     }
 }
 
-struct Redistributor {
-    current: Vec<i32>,
-}
-
-impl Iterator for Redistributor {
-    type Item = Vec<i32>;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        let result = self.current.clone();
-        redistribute(&mut self.current);
-        Some(result)
-    }
+#[allow(clippy::ptr_arg)] // must take `&Vec<i32>` to match `brent`'s `Fn(&T) -> T`
+fn next_redistribution(bank: &Vec<i32>) -> Vec<i32> {
+    let mut next = bank.clone();
+    redistribute(&mut next);
+    next
 }
 
 fn count_redistribution_cycles_until_recurrence(bank: Vec<i32>) -> usize {
-    Redistributor { current: bank }
-        .scan(HashSet::new(), |seen, state| {
-            if seen.insert(state) {
-                Some(())
-            } else {
-                None
-            }
-        })
-        .count()
+    let (mu, lambda) = brent(bank, next_redistribution);
+    mu + lambda
 }
 
 fn redistribution_cycle_loop_length(bank: Vec<i32>) -> usize {
-    Redistributor { current: bank }
-        .enumerate()
-        .scan(HashMap::new(), |seen, (idx, state)| {
-            if let Some(prev_idx) = seen.insert(state, idx) {
-                Some(idx - prev_idx)
-            } else {
-                Some(0)
-            }
-        })
-        .find(|&x| x > 0)
-        .unwrap()
+    let (_, lambda) = brent(bank, next_redistribution);
+    lambda
 }
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
 
     #[test]
@@ -116,4 +93,32 @@ mod tests {
     fn loop_length_of_redistribution_cycles_matches_example() {
         assert_eq!(redistribution_cycle_loop_length(vec![0, 2, 7, 0]), 4);
     }
+
+    /// The brute-force reference this module used before switching to
+    /// [`brent`]: grow a `HashSet`/`HashMap` of every visited bank state.
+    fn recurrence_and_loop_length_via_hash_set(bank: Vec<i32>) -> (usize, usize) {
+        let mut seen = std::collections::HashMap::new();
+        let mut current = bank;
+        let mut steps = 0;
+        loop {
+            if let Some(first_seen_at) = seen.insert(current.clone(), steps) {
+                return (steps, steps - first_seen_at);
+            }
+            redistribute(&mut current);
+            steps += 1;
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn brent_agrees_with_hash_set_based_search(bank in proptest::collection::vec(0i32..16, 2..8)) {
+            let (steps_until_recurrence, loop_length) =
+                recurrence_and_loop_length_via_hash_set(bank.clone());
+            prop_assert_eq!(
+                count_redistribution_cycles_until_recurrence(bank.clone()),
+                steps_until_recurrence
+            );
+            prop_assert_eq!(redistribution_cycle_loop_length(bank), loop_length);
+        }
+    }
 }
@@ -1,4 +1,5 @@
 use aoc_companion::prelude::*;
+use aoc_utils::parse;
 use itertools::{Itertools, MinMaxResult};
 use std::{
     collections::{HashMap, HashSet},
@@ -68,45 +69,42 @@ impl Display for ProgramName<'_> {
     }
 }
 
+impl Submissible for ProgramName<'_> {}
+
 #[derive(Debug)]
 struct Relation<'input> {
     weight: u32,
     decendents: HashSet<ProgramName<'input>>,
 }
 
+fn program_name(input: &str) -> parse::PResult<'_, ProgramName<'_>> {
+    let (rest, name) = parse::ident(input)?;
+    Ok((rest, ProgramName(name)))
+}
+
+fn relation(input: &str) -> parse::PResult<'_, (ProgramName<'_>, Relation<'_>)> {
+    let (rest, name) = program_name(input)?;
+    let (rest, ()) = parse::tag(" (")(rest)?;
+    let (rest, weight) = parse::unsigned_int(rest)?;
+    let (rest, ()) = parse::tag(")")(rest)?;
+    let (rest, decendents) = if let Ok((rest, ())) = parse::tag(" -> ")(rest) {
+        let (rest, names) = parse::separated_list(program_name, parse::tag(", "))(rest)?;
+        (rest, names.into_iter().collect())
+    } else {
+        (rest, HashSet::new())
+    };
+    Ok((rest, (name, Relation { weight, decendents })))
+}
+
 fn parse_input<'input>(
     input: &'input str,
 ) -> Result<HashMap<ProgramName<'input>, Relation<'input>>, ParseError> {
-    let re = regex::Regex::new(
-        r"^(?P<prog>\w+) \((?P<weight>\d+)\)(?: -> (?P<decendents>(?:\w+)(?:, (?:\w+))*))?$",
-    )
-    .unwrap();
-
     input
         .lines()
         .map(|line| {
-            re.captures(line)
-                .ok_or_else(|| ParseError::LineDoesNotMatch {
-                    line: line.to_string(),
-                })
-        })
-        .map_ok(|caps| {
-            (
-                ProgramName(caps.name("prog").unwrap().as_str()),
-                Relation {
-                    weight: caps["weight"].parse().unwrap(),
-                    decendents: caps
-                        .name("decendents")
-                        .map(|decendents| {
-                            decendents
-                                .as_str()
-                                .split(", ")
-                                .map(|m| ProgramName(m))
-                                .collect()
-                        })
-                        .unwrap_or_default(),
-                },
-            )
+            parse::finish(line, relation).map_err(|_| ParseError::LineDoesNotMatch {
+                line: line.to_string(),
+            })
         })
         .collect()
 }
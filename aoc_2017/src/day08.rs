@@ -1,13 +1,16 @@
 use aoc_companion::prelude::*;
+use aoc_utils::register_machine::{
+    Comparison, Condition, Instruction, MaxBy, Op, State, Value, Vm,
+};
 
 use itertools::Itertools;
 use thiserror::Error;
 
-pub struct Door<'input> {
-    program: Vec<Instruction<'input>>,
+pub struct Door {
+    program: Vec<Instruction>,
 }
 
-impl<'input> ParseInput<'input> for Door<'input> {
+impl<'input> ParseInput<'input> for Door {
     type Error = ParseError;
 
     fn parse(input: &'input str) -> Result<Self, Self::Error> {
@@ -15,93 +18,39 @@ impl<'input> ParseInput<'input> for Door<'input> {
     }
 }
 
-impl Part1 for Door<'_> {
-    type Output = i32;
+impl Part1 for Door {
+    type Output = i64;
     type Error = ExecutionError;
 
     fn part1(&self) -> Result<Self::Output, Self::Error> {
-        largest_register_value(&execute_program(&self.program))
+        let mut vm = Vm::new();
+        vm.run(&self.program, &mut std::iter::empty());
+        vm.registers
+            .values()
+            .max()
+            .copied()
             .ok_or(ExecutionError::RegistersEmpty)
     }
 }
 
-impl Part2 for Door<'_> {
-    type Output = i32;
+impl Part2 for Door {
+    type Output = i64;
     type Error = ExecutionError;
 
     fn part2(&self) -> Result<Self::Output, Self::Error> {
-        largest_intermediate_register_value(&self.program)
-    }
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct Register<'input>(&'input str);
-
-#[derive(Debug, Default, Clone, PartialEq, Eq)]
-struct Registers<'input>(std::collections::HashMap<Register<'input>, i32>);
-
-impl<'input> Registers<'input> {
-    fn execute(mut self, instr: &Instruction<'input>) -> Self {
-        if self.eval(&instr.condition) {
-            let target = self.0.entry(instr.target).or_default();
-            match instr.operation {
-                Operation::Increase(amount) => *target += amount,
-                Operation::Decrease(amount) => *target -= amount,
-            }
-        }
-        self
-    }
-
-    fn eval(&self, cond: &Condition<'input>) -> bool {
-        let lhs = self.0.get(&cond.register).unwrap_or(&0);
-        cond.cmp.as_fn()(lhs, &cond.value)
-    }
-}
-
-#[derive(Debug, Clone, PartialEq, Eq)]
-enum Operation {
-    Increase(i32),
-    Decrease(i32),
-}
-
-#[derive(Debug, PartialEq, Eq)]
-enum Comparison {
-    LessThan,
-    GreaterThan,
-    LessEqual,
-    GreaterEqual,
-    Equal,
-    NotEqual,
-}
-
-impl Comparison {
-    fn as_fn(&self) -> fn(&i32, &i32) -> bool {
-        use Comparison::*;
-        match self {
-            LessThan => PartialOrd::lt,
-            GreaterThan => PartialOrd::gt,
-            LessEqual => PartialOrd::le,
-            GreaterEqual => PartialOrd::ge,
-            Equal => PartialEq::eq,
-            NotEqual => PartialEq::ne,
+        if self.program.is_empty() {
+            return Err(ExecutionError::ProgramEmpty);
         }
+        Vm::new()
+            .run_with_observer(
+                &self.program,
+                &mut std::iter::empty(),
+                MaxBy::new(|state: &State| state.registers.values().max().copied()),
+            )
+            .ok_or(ExecutionError::RegistersEmpty)
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
-struct Condition<'input> {
-    register: Register<'input>,
-    cmp: Comparison,
-    value: i32,
-}
-
-#[derive(Debug, PartialEq, Eq)]
-struct Instruction<'input> {
-    target: Register<'input>,
-    operation: Operation,
-    condition: Condition<'input>,
-}
-
 #[derive(Debug, Error)]
 pub enum ParseError {
     #[error("Line does not match the regular expression: {line:?}")]
@@ -128,158 +77,118 @@ fn parse_input(input: &str) -> Result<Vec<Instruction>, ParseError> {
                 })
         })
         .map_ok(|caps| {
-            let amount = caps.name("amount").unwrap().as_str().parse().unwrap();
-            let value = caps.name("value").unwrap().as_str().parse().unwrap();
-            Instruction {
-                target: Register(caps.name("target").unwrap().as_str()),
-                operation: match caps.name("op").unwrap().as_str() {
-                    "inc" => Operation::Increase(amount),
-                    "dec" => Operation::Decrease(amount),
-                    _ => unreachable!(),
-                },
-                condition: Condition {
-                    register: Register(caps.name("register").unwrap().as_str()),
-                    cmp: match caps.name("cmp").unwrap().as_str() {
-                        "<" => Comparison::LessThan,
-                        ">" => Comparison::GreaterThan,
-                        "<=" => Comparison::LessEqual,
-                        ">=" => Comparison::GreaterEqual,
-                        "==" => Comparison::Equal,
-                        "!=" => Comparison::NotEqual,
-                        _ => unreachable!(),
-                    },
-                    value,
+            let amount: i64 = caps.name("amount").unwrap().as_str().parse().unwrap();
+            let value: i64 = caps.name("value").unwrap().as_str().parse().unwrap();
+            let signed_amount = match caps.name("op").unwrap().as_str() {
+                "inc" => amount,
+                "dec" => -amount,
+                _ => unreachable!(),
+            };
+            let cmp = match caps.name("cmp").unwrap().as_str() {
+                "<" => Comparison::Lt,
+                ">" => Comparison::Gt,
+                "<=" => Comparison::Le,
+                ">=" => Comparison::Ge,
+                "==" => Comparison::Eq,
+                "!=" => Comparison::Ne,
+                _ => unreachable!(),
+            };
+            Instruction::guarded(
+                Op::Add(
+                    caps.name("target").unwrap().as_str().to_owned(),
+                    Value::Literal(signed_amount),
+                ),
+                Condition {
+                    lhs: caps.name("register").unwrap().as_str().to_owned(),
+                    cmp,
+                    rhs: Value::Literal(value),
                 },
-            }
+            )
         })
         .collect()
 }
 
-fn execute_program<'input>(instructions: &[Instruction<'input>]) -> Registers<'input> {
-    instructions
-        .iter()
-        .fold(Registers::default(), Registers::execute)
-}
-
-fn largest_intermediate_register_value(
-    instructions: &[Instruction],
-) -> Result<i32, ExecutionError> {
-    instructions
-        .iter()
-        .scan(Registers::default(), |registers, instr| {
-            *registers = std::mem::take(registers).execute(instr);
-            Some(largest_register_value(registers))
-        })
-        .max()
-        .ok_or(ExecutionError::ProgramEmpty)
-        .and_then(|max| max.ok_or(ExecutionError::RegistersEmpty))
-}
-
-fn largest_register_value(registers: &Registers) -> Option<i32> {
-    registers.0.values().max().copied()
-}
-
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
 
     use assert_matches::assert_matches;
-    use itertools::assert_equal;
 
     use super::*;
 
-    const EXAMPLE_INSTRUCTIONS: &[Instruction] = &[
-        Instruction {
-            target: Register("b"),
-            operation: Operation::Increase(5),
-            condition: Condition {
-                register: Register("a"),
-                cmp: Comparison::GreaterThan,
-                value: 1,
-            },
-        },
-        Instruction {
-            target: Register("a"),
-            operation: Operation::Increase(1),
-            condition: Condition {
-                register: Register("b"),
-                cmp: Comparison::LessThan,
-                value: 5,
-            },
-        },
-        Instruction {
-            target: Register("c"),
-            operation: Operation::Decrease(-10),
-            condition: Condition {
-                register: Register("a"),
-                cmp: Comparison::GreaterEqual,
-                value: 1,
-            },
-        },
-        Instruction {
-            target: Register("c"),
-            operation: Operation::Increase(-20),
-            condition: Condition {
-                register: Register("c"),
-                cmp: Comparison::Equal,
-                value: 10,
-            },
-        },
-    ];
-
-    #[test]
-    fn example_input_is_parsed() {
-        const EXAMPLE_INPUT: &str = r"b inc 5 if a > 1
+    const EXAMPLE_INPUT: &str = r"b inc 5 if a > 1
 a inc 1 if b < 5
 c dec -10 if a >= 1
 c inc -20 if c == 10
 ";
-        assert_eq!(
-            parse_input(EXAMPLE_INPUT).unwrap().as_slice(),
-            EXAMPLE_INSTRUCTIONS,
-        );
+
+    fn example_instructions() -> Vec<Instruction> {
+        vec![
+            Instruction::guarded(
+                Op::Add("b".to_owned(), Value::Literal(5)),
+                Condition {
+                    lhs: "a".to_owned(),
+                    cmp: Comparison::Gt,
+                    rhs: Value::Literal(1),
+                },
+            ),
+            Instruction::guarded(
+                Op::Add("a".to_owned(), Value::Literal(1)),
+                Condition {
+                    lhs: "b".to_owned(),
+                    cmp: Comparison::Lt,
+                    rhs: Value::Literal(5),
+                },
+            ),
+            Instruction::guarded(
+                Op::Add("c".to_owned(), Value::Literal(10)),
+                Condition {
+                    lhs: "a".to_owned(),
+                    cmp: Comparison::Ge,
+                    rhs: Value::Literal(1),
+                },
+            ),
+            Instruction::guarded(
+                Op::Add("c".to_owned(), Value::Literal(-20)),
+                Condition {
+                    lhs: "c".to_owned(),
+                    cmp: Comparison::Eq,
+                    rhs: Value::Literal(10),
+                },
+            ),
+        ]
     }
 
     #[test]
-    fn intermediate_registers() {
-        assert_equal(
-            EXAMPLE_INSTRUCTIONS
-                .into_iter()
-                .scan(Registers::default(), |registers, instr| {
-                    *registers = std::mem::take(registers).execute(instr);
-                    Some(registers.clone())
-                }),
-            [
-                Registers::default(),
-                Registers(HashMap::from([(Register("a"), 1)])),
-                Registers(HashMap::from([(Register("a"), 1), (Register("c"), 10)])),
-                Registers(HashMap::from([(Register("a"), 1), (Register("c"), -10)])),
-            ],
-        );
+    fn example_input_is_parsed() {
+        assert_eq!(parse_input(EXAMPLE_INPUT).unwrap(), example_instructions());
     }
 
     #[test]
     fn final_program_registers() {
+        let mut vm = Vm::new();
+        vm.run(&example_instructions(), &mut std::iter::empty());
         assert_eq!(
-            execute_program(EXAMPLE_INSTRUCTIONS),
-            Registers(HashMap::from([(Register("a"), 1), (Register("c"), -10)]))
+            vm.registers,
+            HashMap::from([("a".to_owned(), 1), ("c".to_owned(), -10)])
         );
     }
 
     #[test]
     fn largest_register_value_is_determined() {
-        assert_matches!(largest_register_value(&Registers::default()), None);
-        assert_matches!(
-            largest_register_value(&Registers(HashMap::from([
-                (Register("a"), 1),
-                (Register("c"), -10)
-            ]))),
-            Some(1)
-        );
+        assert_matches!(Vm::new().registers.values().max(), None);
+        let mut vm = Vm::new();
+        vm.run(&example_instructions(), &mut std::iter::empty());
+        assert_matches!(vm.registers.values().max(), Some(1));
     }
 
     #[test]
     fn largest_intermediate_register_value_is_determined() {
-        assert_matches!(largest_intermediate_register_value(EXAMPLE_INSTRUCTIONS), Ok(10));
+        let max = Vm::new().run_with_observer(
+            &example_instructions(),
+            &mut std::iter::empty(),
+            MaxBy::new(|state: &State| state.registers.values().max().copied()),
+        );
+        assert_matches!(max, Some(10));
     }
 }
@@ -1,209 +1,120 @@
 use aoc_companion::prelude::*;
 
-pub struct Door<'input> {
-    stream: &'input str,
+pub struct Door {
+    score: usize,
+    garbage_count: usize,
 }
 
-impl<'input> ParseInput<'input> for Door<'input> {
-    type Error = std::convert::Infallible;
-
-    fn parse(input: &'input str) -> Result<Self, Self::Error> {
-        Ok(Self { stream: input })
-    }
-}
-
-impl Part1 for Door<'_> {
-    type Output = usize;
-    type Error = std::convert::Infallible;
-
-    fn part1(&self) -> Result<Self::Output, Self::Error> {
-        Ok(stream_group_scores(self.stream).sum())
-    }
-}
-
-impl Part2 for Door<'_> {
-    type Output = usize;
-    type Error = std::convert::Infallible;
-
-    fn part2(&self) -> Result<Self::Output, Self::Error> {
-        Ok(self.stream.chars().ignore_bangs().count_garbage())
-    }
-}
-
-trait StreamIterator: Iterator<Item = char> + Sized {
-    fn ignore_bangs(self) -> IgnoreBangs<Self> {
-        IgnoreBangs { iter: self }
-    }
-
-    fn skip_garbage(self) -> impl Iterator<Item = char> {
-        IdentifyGarbage { iter: self }.filter_map(|elem| match elem {
-            StreamElement::ValidChar(c) => Some(c),
-            StreamElement::GarbageRun { .. } => None,
+impl<'input> Solution<'input> for Door {
+    fn parse(input: &'input str) -> Result<Self, StreamError> {
+        let (score, garbage_count) = parse_stream(input.trim_end())?;
+        Ok(Self {
+            score,
+            garbage_count,
         })
     }
 
-    fn count_garbage(self) -> usize {
-        IdentifyGarbage { iter: self }
-            .filter_map(|elem| match elem {
-                StreamElement::ValidChar(_) => None,
-                StreamElement::GarbageRun { length } => Some(length),
-            })
-            .sum()
+    fn part1(&self) -> usize {
+        self.score
     }
 
-    fn group_scores(self) -> GroupScores<Self> {
-        GroupScores {
-            iter: self,
-            nesting_level: 0,
-        }
+    fn part2(&self) -> usize {
+        self.garbage_count
     }
 }
 
-impl<I> StreamIterator for I where I: Iterator<Item = char> {}
-
-fn stream_group_scores(stream: &str) -> impl Iterator<Item = usize> + '_ {
-    stream.chars().ignore_bangs().skip_garbage().group_scores()
-}
-
-struct IgnoreBangs<I: Iterator<Item = char>> {
-    iter: I,
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum StreamError {
+    #[error("unmatched closing brace at byte offset {0}")]
+    UnmatchedClosingBrace(usize),
+    #[error("unclosed group at byte offset {0}")]
+    UnclosedGroup(usize),
+    #[error("unterminated garbage run at byte offset {0}")]
+    UnterminatedGarbage(usize),
+    #[error("dangling '!' at byte offset {0}")]
+    DanglingBang(usize),
 }
 
-impl<I> Iterator for IgnoreBangs<I>
-where
-    I: Iterator<Item = char>,
-{
-    type Item = char;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        let mut ignore_next = false;
-        for c in self.iter.by_ref() {
-            if std::mem::replace(&mut ignore_next, false) {
-                continue;
+type Chars<'a> = std::iter::Peekable<std::str::CharIndices<'a>>;
+
+/// Parses the whole stream in a single pass, returning the summed group
+/// score (part 1) alongside the garbage character count (part 2), since
+/// both fall out of the same walk over groups and garbage runs. `{` and
+/// `}` outside of garbage nest groups; everything else at that level
+/// (commas, stray letters) is skipped, matching how the original ad-hoc
+/// `GroupScores` iterator treated them.
+fn parse_stream(input: &str) -> Result<(usize, usize), StreamError> {
+    let mut chars = input.char_indices().peekable();
+    let mut depth = 0usize;
+    let mut score = 0usize;
+    let mut garbage_count = 0usize;
+
+    while let Some((offset, c)) = chars.next() {
+        match c {
+            '{' => {
+                depth += 1;
+                score += depth;
             }
-            if c == '!' {
-                ignore_next = true;
-                continue;
+            '}' => {
+                depth = depth
+                    .checked_sub(1)
+                    .ok_or(StreamError::UnmatchedClosingBrace(offset))?;
             }
-            return Some(c);
+            '<' => garbage_count += parse_garbage(input, &mut chars)?,
+            _ => {}
         }
-        None
     }
-}
 
-enum StreamElement {
-    ValidChar(char),
-    GarbageRun { length: usize },
-}
-
-struct IdentifyGarbage<I: Iterator<Item = char>> {
-    iter: I,
-}
-
-impl<I> Iterator for IdentifyGarbage<I>
-where
-    I: Iterator<Item = char>,
-{
-    type Item = StreamElement;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        let mut garbage_run = false;
-        let mut length = 0;
-
-        for c in self.iter.by_ref() {
-            if garbage_run {
-                match c {
-                    '>' => return Some(StreamElement::GarbageRun { length }),
-                    _ => length += 1,
-                }
-            } else {
-                match c {
-                    '<' => garbage_run = true,
-                    c => return Some(StreamElement::ValidChar(c)),
-                }
-            }
-        }
-        None
+    if depth == 0 {
+        Ok((score, garbage_count))
+    } else {
+        Err(StreamError::UnclosedGroup(input.len()))
     }
 }
 
-struct GroupScores<I: Iterator<Item = char>> {
-    iter: I,
-    nesting_level: usize,
-}
-
-impl<I> Iterator for GroupScores<I>
-where
-    I: Iterator<Item = char>,
-{
-    type Item = usize;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        for c in self.iter.by_ref() {
-            match c {
-                '{' => {
-                    self.nesting_level += 1;
-                    return Some(self.nesting_level);
+/// Parses a garbage run whose opening `<` has already been consumed,
+/// returning its length (cancelled characters, and the `!` that cancels
+/// them, don't count towards it).
+fn parse_garbage(input: &str, chars: &mut Chars<'_>) -> Result<usize, StreamError> {
+    let mut length = 0;
+
+    while let Some((offset, c)) = chars.next() {
+        match c {
+            '>' => return Ok(length),
+            '!' => {
+                if chars.next().is_none() {
+                    return Err(StreamError::DanglingBang(offset));
                 }
-                '}' => self.nesting_level -= 1,
-                _ => {}
             }
+            _ => length += 1,
         }
-        None
     }
+
+    Err(StreamError::UnterminatedGarbage(input.len()))
 }
 
 #[cfg(test)]
 mod tests {
+    use assert_matches::assert_matches;
     use rstest::rstest;
 
     use super::*;
 
     #[rstest]
-    #[case("<{}>", "<{}>")]
-    #[case("<{!>}>", "<{}>")]
-    #[case("<!!>", "<>")]
-    #[case("<!!!>>", "<>")]
-    #[case("{{<!>},{<!>},{<!>},{<a>}}", "{{<},{<},{<},{<a>}}")]
-    #[case("{{<!!>},{<!!>},{<!!>},{<!!>}}", "{{<>},{<>},{<>},{<>}}")]
-    #[case("trailing!", "trailing")]
-    fn bangs_and_following_character_are_skipped(#[case] before: &str, #[case] after: &str) {
-        assert_eq!(before.chars().ignore_bangs().collect::<String>(), after);
-    }
-
-    #[rstest]
-    #[case("{}", "{}")]
-    #[case("{<>}", "{}")]
-    #[case("{<random characters>}", "{}")]
-    #[case("{<<<<>}", "{}")]
-    #[case("{<a>,<a>,<a>,<a>}", "{,,,}")]
-    #[case("{{<},{<},{<},{<a>}}", "{{}}")]
-    fn garbage_is_skipped(#[case] before: &str, #[case] after: &str) {
-        assert_eq!(before.chars().skip_garbage().collect::<String>(), after);
-    }
-
-    #[rstest]
-    #[case("no groups", &[])]
-    #[case("{}", &[1])]
-    #[case("one {group}", &[1])]
-    #[case("{{{}}}", &[1, 2, 3])]
-    #[case("{}{}", &[1, 1])]
-    #[case("{{}{}}", &[1, 2, 2])]
-    #[case("{{{},{},{{}}}}", &[1, 2, 3, 3, 3, 4])]
-    fn group_scores_without_garbage_or_bangs(#[case] before: &str, #[case] after: &[usize]) {
-        assert_eq!(
-            before.chars().group_scores().collect::<Vec<_>>().as_slice(),
-            after
-        );
-    }
-
-    #[rstest]
-    #[case("{<a>,<a>,<a>,<a>}", 1)]
-    #[case("{{<ab>},{<ab>},{<ab>},{<ab>}}", 9)]
-    #[case("{{<!!>},{<!!>},{<!!>},{<!!>}}", 9)]
-    #[case("{{<a!>},{<a!>},{<a!>},{<ab>}}", 3)]
-    fn stream_group_score_sums(#[case] stream: &str, #[case] total_score: usize) {
-        assert_eq!(stream_group_scores(stream).sum::<usize>(), total_score);
+    #[case("{}", 1, 0)]
+    #[case("{{{}}}", 6, 0)]
+    #[case("{{},{}}", 5, 0)]
+    #[case("{{{},{},{{}}}}", 16, 0)]
+    #[case("{<a>,<a>,<a>,<a>}", 1, 4)]
+    #[case("{{<ab>},{<ab>},{<ab>},{<ab>}}", 9, 8)]
+    #[case("{{<!!>},{<!!>},{<!!>},{<!!>}}", 9, 0)]
+    #[case("{{<a!>},{<a!>},{<a!>},{<ab>}}", 3, 17)]
+    fn parses_score_and_garbage_count_together(
+        #[case] stream: &str,
+        #[case] score: usize,
+        #[case] garbage_count: usize,
+    ) {
+        assert_eq!(parse_stream(stream), Ok((score, garbage_count)));
     }
 
     #[rstest]
@@ -214,7 +125,35 @@ mod tests {
     #[case("<!!>", 0)]
     #[case("<!!!>>", 0)]
     #[case(r#"<{o"i!a,<{i<a>"#, 10)]
-    fn count_garbabe(#[case] stream: &str, #[case] total_garbage: usize) {
-        assert_eq!(stream.chars().ignore_bangs().count_garbage(), total_garbage);
+    fn garbage_run_lengths(#[case] garbage: &str, #[case] length: usize) {
+        let rest = &garbage[1..];
+        let mut chars = rest.char_indices().peekable();
+        assert_eq!(parse_garbage(garbage, &mut chars), Ok(length));
+    }
+
+    #[test]
+    fn unmatched_closing_brace_is_reported_at_its_offset() {
+        assert_matches!(
+            parse_stream("{}}"),
+            Err(StreamError::UnmatchedClosingBrace(2))
+        );
+    }
+
+    #[test]
+    fn unclosed_group_at_eof_is_reported_at_the_end_of_input() {
+        assert_matches!(parse_stream("{{}"), Err(StreamError::UnclosedGroup(3)));
+    }
+
+    #[test]
+    fn unterminated_garbage_run_is_reported_at_the_end_of_input() {
+        assert_matches!(
+            parse_stream("{<abc}"),
+            Err(StreamError::UnterminatedGarbage(6))
+        );
+    }
+
+    #[test]
+    fn dangling_bang_at_eof_is_reported_at_its_offset() {
+        assert_matches!(parse_stream("{<abc!"), Err(StreamError::DanglingBang(5)));
     }
 }
@@ -1,14 +1,12 @@
-use std::collections::HashSet;
-
 use aoc_companion::prelude::*;
-use aoc_utils::geometry::Point;
-use aoc_utils::linalg::Vector;
+use aoc_utils::geometry::connected_components;
+use aoc_utils::knot_hash::KnotHash;
 use itertools::Itertools;
 
-use crate::day10::KnotHash;
+const SIZE: u8 = 128;
 
 pub(crate) struct Door {
-    rows: [KnotHash; 128],
+    rows: [KnotHash; SIZE as usize],
 }
 
 impl<'input> Solution<'input> for Door {
@@ -33,40 +31,19 @@ impl Door {
     }
 
     fn contains(&self, Vector([col, row]): Vector<u8, 2>) -> bool {
+        if col >= SIZE || row >= SIZE {
+            return false;
+        }
         let byte = (col >> 3) as usize;
         let bit = 7 - (col & 0b00000111);
         self.rows[row as usize].0[byte] & (1 << bit) != 0
     }
 
     fn number_of_regions(&self) -> usize {
-        let grid_points = (0..128)
-            .cartesian_product(0..128)
+        let grid_points = (0..SIZE)
+            .cartesian_product(0..SIZE)
             .map(|(col, row)| Vector([col, row]));
-        let mut visited = HashSet::new();
-        grid_points
-            .filter(|&p| self.contains(p))
-            .filter(|&p| {
-                let new_region = !visited.contains(&p);
-                if new_region {
-                    let mut queue = vec![p];
-                    while let Some(q) = queue.pop() {
-                        if self.contains(q) && visited.insert(q) {
-                            queue.extend(
-                                q.nearest_neighbors()
-                                    .filter(|&Vector([x, y])| x < 128 && y < 128),
-                            );
-                        }
-                    }
-                }
-                new_region
-            })
-            .count()
-    }
-}
-
-impl KnotHash {
-    pub fn count_ones(self) -> u32 {
-        self.0.iter().copied().map(u8::count_ones).sum()
+        connected_components(grid_points, |p| self.contains(p))
     }
 }
 
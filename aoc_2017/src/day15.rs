@@ -1,7 +1,6 @@
-use std::num::ParseIntError;
-
 use aoc_companion::prelude::*;
 use aoc_utils::array;
+use aoc_utils::parse::{self, labeled_value, unsigned_int};
 
 const FACTORS: [u64; 2] = [16807, 48271];
 const MODULUS: u64 = 2147483647;
@@ -14,10 +13,14 @@ pub struct Door {
 pub enum ParseError {
     #[error("expected two lines, got {0}")]
     WrongNumberOfLines(usize),
-    #[error("a line did not start with the expected prefix")]
-    MissingPrefix,
     #[error(transparent)]
-    ParseIntError(#[from] ParseIntError),
+    Parse(#[from] parse::ParseError),
+}
+
+fn generator_line(input: &str) -> parse::PResult<'_, u64> {
+    let (rest, (_label, start)) =
+        labeled_value("Generator ", " starts with ", unsigned_int)(input)?;
+    Ok((rest, start))
 }
 
 impl ParseInput<'_> for Door {
@@ -27,16 +30,7 @@ impl ParseInput<'_> for Door {
         let lines: [&str; 2] = array::from_iter_exact(input.lines())
             .map_err(|lines| ParseError::WrongNumberOfLines(lines.len()))?;
 
-        let start = array::try_map(lines, |line| {
-            let Some(line) = line.strip_prefix("Generator ") else {
-                return Err(ParseError::MissingPrefix);
-            };
-            let line = &line[1..];
-            let Some(line) = line.strip_prefix(" starts with ") else {
-                return Err(ParseError::MissingPrefix);
-            };
-            Ok(line.parse()?)
-        })?;
+        let start = array::try_map(lines, |line| parse::finish(line, generator_line))?;
 
         Ok(Door { start })
     }
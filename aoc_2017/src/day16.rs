@@ -109,17 +109,15 @@ fn initial_sequence(len: usize) -> Vec<char> {
     ('a'..).take(len).collect()
 }
 
+fn check_index(index: usize, len: usize) -> Result<usize, MoveError> {
+    if index < len {
+        Ok(index)
+    } else {
+        Err(MoveError::ProgramIndexOutOfBounds { index, len })
+    }
+}
+
 fn apply_move(mut state: Vec<char>, m: &DanceMove) -> Result<Vec<char>, MoveError> {
-    let check_index = |index: usize, state: &[char]| {
-        if index < state.len() {
-            Ok(index)
-        } else {
-            Err(MoveError::ProgramIndexOutOfBounds {
-                index,
-                len: state.len(),
-            })
-        }
-    };
     let find_name = |name: char, state: &[char]| {
         state
             .iter()
@@ -130,8 +128,8 @@ fn apply_move(mut state: Vec<char>, m: &DanceMove) -> Result<Vec<char>, MoveErro
     match *m {
         DanceMove::Spin(size) => state.rotate_right(size),
         DanceMove::Exchange(lhs, rhs) => {
-            let lhs = check_index(lhs, &state)?;
-            let rhs = check_index(rhs, &state)?;
+            let lhs = check_index(lhs, state.len())?;
+            let rhs = check_index(rhs, state.len())?;
             state.swap(lhs, rhs)
         }
         DanceMove::Partner(lhs, rhs) => {
@@ -144,6 +142,7 @@ fn apply_move(mut state: Vec<char>, m: &DanceMove) -> Result<Vec<char>, MoveErro
     Ok(state)
 }
 
+#[allow(dead_code)]
 fn determine_cycle(len: usize, dance: &[DanceMove]) -> Result<usize, MoveError> {
     let initial = initial_sequence(len);
     let mut state = initial.clone();
@@ -156,14 +155,87 @@ fn determine_cycle(len: usize, dance: &[DanceMove]) -> Result<usize, MoveError>
     unreachable!()
 }
 
+/// Splits `dance` into the positional permutation that `Spin`/`Exchange`
+/// apply to an index array and the symbol permutation that `Partner`
+/// applies to the program names, by running each kind of move against its
+/// own identity sequence in a single pass over `dance`. Because the two
+/// kinds of move act on independent axes (position vs. name) and therefore
+/// commute, these can be exponentiated separately in [`perform_many_dances`]
+/// instead of simulating the interleaved moves a second time.
+fn dance_permutations(
+    len: usize,
+    dance: &[DanceMove],
+) -> Result<(Vec<usize>, Vec<usize>), MoveError> {
+    let mut positions: Vec<usize> = (0..len).collect();
+    let mut symbols = initial_sequence(len);
+
+    for m in dance {
+        match *m {
+            DanceMove::Spin(size) => positions.rotate_right(size),
+            DanceMove::Exchange(lhs, rhs) => {
+                positions.swap(check_index(lhs, len)?, check_index(rhs, len)?)
+            }
+            DanceMove::Partner(lhs, rhs) => {
+                let lhs_pos = symbols
+                    .iter()
+                    .position(|&x| x == lhs)
+                    .ok_or(MoveError::ProgramNameNotFound { name: lhs })?;
+                let rhs_pos = symbols
+                    .iter()
+                    .position(|&x| x == rhs)
+                    .ok_or(MoveError::ProgramNameNotFound { name: rhs })?;
+                symbols.swap(lhs_pos, rhs_pos)
+            }
+        }
+    }
+
+    let symbols = symbols
+        .into_iter()
+        .map(|c| (c as u8 - b'a') as usize)
+        .collect();
+    Ok((positions, symbols))
+}
+
+/// Raises a permutation (`perm[i]` is where index `i` maps to under one
+/// application) to its `n`-th power in `O(len)` by decomposing it into
+/// cycles and advancing every element `n % cycle_len` steps along its own
+/// cycle, rather than composing `perm` with itself `n` times.
+fn permutation_power(perm: &[usize], n: usize) -> Vec<usize> {
+    let mut power = vec![0; perm.len()];
+    let mut visited = vec![false; perm.len()];
+
+    for start in 0..perm.len() {
+        if visited[start] {
+            continue;
+        }
+
+        let mut cycle = vec![start];
+        visited[start] = true;
+        let mut i = perm[start];
+        while i != start {
+            visited[i] = true;
+            cycle.push(i);
+            i = perm[i];
+        }
+
+        let shift = n % cycle.len();
+        for (j, &idx) in cycle.iter().enumerate() {
+            power[idx] = cycle[(j + shift) % cycle.len()];
+        }
+    }
+
+    power
+}
+
 fn perform_many_dances(len: usize, n: usize, dance: &[DanceMove]) -> Result<Vec<char>, MoveError> {
-    let cycle = determine_cycle(len, dance)?;
+    let (positions, symbols) = dance_permutations(len, dance)?;
+    let positions_pow = permutation_power(&positions, n);
+    let symbols_pow = permutation_power(&symbols, n);
 
-    dance
+    Ok(positions_pow
         .iter()
-        .cycle()
-        .take(dance.len() * (n % cycle))
-        .try_fold(initial_sequence(len), apply_move)
+        .map(|&pos| (b'a' + symbols_pow[pos] as u8) as char)
+        .collect())
 }
 
 #[cfg(test)]
@@ -228,4 +300,20 @@ mod tests {
     fn example_cycle() {
         assert_eq!(determine_cycle(5, EXAMPLE_DANCE).unwrap(), 4);
     }
+
+    #[test]
+    fn perform_many_dances_agrees_with_repeated_apply_move() {
+        for n in 0..10 {
+            let expected = (0..n)
+                .try_fold(initial_sequence(5), |state, _| {
+                    EXAMPLE_DANCE.iter().try_fold(state, apply_move)
+                })
+                .unwrap();
+            assert_eq!(
+                perform_many_dances(5, n, EXAMPLE_DANCE).unwrap(),
+                expected,
+                "mismatch after {n} dances"
+            );
+        }
+    }
 }
@@ -20,7 +20,7 @@ impl<'input> Solution<'input> for Door {
     }
 
     fn part2(&self) -> u32 {
-        find_value_after_zero(&resulting_spinlock(50_000_000, self.skip_len))
+        value_after_zero(50_000_000, self.skip_len)
     }
 }
 
@@ -43,6 +43,25 @@ fn find_value_after_zero(ring: &VecDeque<u32>) -> u32 {
     ring.get(zero_pos + 1).copied().unwrap_or_else(|| ring[0])
 }
 
+/// The value ending up immediately after `0` once `final_number` has been
+/// inserted, without materializing the ring: `0` is inserted first and
+/// never moves relative to whichever value follows it, so it's enough to
+/// track the position each insertion lands at (in the ring of size `i` it
+/// sees just before being inserted) and remember `i` whenever that
+/// position is immediately after index `0`. O(n) time, O(1) memory, unlike
+/// [`resulting_spinlock`]'s O(n²) `VecDeque` rotation.
+fn value_after_zero(final_number: u32, skip: usize) -> u32 {
+    let mut pos = 0;
+    let mut after_zero = 0;
+    for i in 1..=final_number {
+        pos = (pos + skip) % i as usize + 1;
+        if pos == 1 {
+            after_zero = i;
+        }
+    }
+    after_zero
+}
+
 #[cfg(test)]
 mod tests {
     use itertools::assert_equal;
@@ -82,4 +101,15 @@ mod tests {
     fn next_after_2017_in_example() {
         assert_eq!(resulting_spinlock(FINAL_NUMBER, EXAMPLE_STEP)[0], 638);
     }
+
+    #[test]
+    fn value_after_zero_matches_the_brute_force_implementation() {
+        for final_number in 0..50 {
+            assert_eq!(
+                value_after_zero(final_number, EXAMPLE_STEP),
+                find_value_after_zero(&resulting_spinlock(final_number, EXAMPLE_STEP)),
+                "final_number = {final_number}"
+            );
+        }
+    }
 }
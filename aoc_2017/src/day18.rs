@@ -1,36 +1,45 @@
 use std::{
     cell::Cell,
     collections::{hash_map, HashMap},
-    num::ParseIntError,
+    fmt,
     str::FromStr,
-    sync::{atomic::AtomicI64, Arc},
+    sync::{
+        atomic::{AtomicI64, Ordering},
+        Arc,
+    },
 };
+
+use futures::stream::{FuturesUnordered, StreamExt};
+use itertools::Itertools;
 use tracing::{info, info_span, instrument};
 
 use aoc_companion::prelude::*;
+use aoc_utils::cycle::brent;
+
+use crate::vm::{Operand, ParseError, Register};
 
 pub(crate) struct Door {
     asm: Vec<Instruction>,
 }
 
-#[derive(Debug, thiserror::Error)]
-pub(crate) enum ParseError {
-    #[error("expected a space delimiting tokens in instruction")]
-    MissingToken,
-    #[error("invalid instruction {0:?}")]
-    InvalidInstruction(String),
-    #[error("invalid immediate value")]
-    InvalidImmediate(#[from] ParseIntError),
-    #[error("invalid register {0:?}")]
-    InvalidRegister(String),
-}
-
 #[derive(Debug, thiserror::Error)]
 pub(crate) enum RuntimeError {
     #[error("executing the rcv instruction before any snd instruction has been executed")]
     NoSnd,
     #[error("program terminated due to jump to PC {0}")]
     InvalidJump(i64),
+    #[error("division by zero at PC {pc}")]
+    DivideByZero {
+        pc: usize,
+        registers: HashMap<Register, i64>,
+    },
+    #[error("arithmetic overflow at PC {pc}")]
+    ArithmeticOverflow {
+        pc: usize,
+        registers: HashMap<Register, i64>,
+    },
+    #[error("program trapped with code {0}")]
+    Trap(i64),
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -42,21 +51,7 @@ enum Instruction {
     Mod(Register, Operand),
     Rcv(Register),
     Jgz(Operand, Operand),
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub(crate) enum Operand {
-    Immediate(i64),
-    Register(Register),
-}
-
-#[derive(Clone, Copy, PartialEq, Eq, Hash)]
-pub(crate) struct Register(pub u8);
-
-impl std::fmt::Debug for Register {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "${}", std::str::from_utf8(&[self.0]).unwrap_or("ï¿½"))
-    }
+    Trp(Operand),
 }
 
 impl<'input> Solution<'input> for Door {
@@ -73,38 +68,90 @@ impl<'input> Solution<'input> for Door {
     }
 
     fn part2(&self) -> Result<i64, RuntimeError> {
-        let pending_recv_count = Arc::new(AtomicI64::default());
-        let (send_0, recv_0) = channel(pending_recv_count.clone());
-        let (send_1, recv_1) = channel(pending_recv_count);
         let runtime = tokio::runtime::Builder::new_current_thread()
             .build()
             .unwrap();
-        let (res_0, _) = runtime.block_on(async {
-            tokio::try_join!(
-                info_span!("program 0").in_scope(|| {
-                    run(
-                        HashMap::from([(Register(b'p'), 0)]),
-                        &self.asm,
-                        send_0,
-                        recv_1,
-                    )
-                }),
-                info_span!("program 1").in_scope(|| {
-                    run(
-                        HashMap::from([(Register(b'p'), 1)]),
-                        &self.asm,
-                        send_1,
-                        recv_0,
-                    )
-                })
-            )
-        })?;
-        Ok(res_0)
+        let send_counts = runtime.block_on(run_ring(&self.asm, 2))?;
+        Ok(send_counts[1])
     }
 }
 
+/// Labels let Duet source name a `jgz` target (`label:` on its own line,
+/// marking the index of the instruction that follows it) instead of
+/// spelling out a brittle relative offset. Parsing runs in two passes, like
+/// an assembler's flatten phase: first every `label:` line is collected into
+/// a name-to-index map (without being counted as an instruction itself),
+/// then each remaining line is parsed into an [`Instruction`], resolving any
+/// symbolic `jgz` target against that map into a concrete relative
+/// [`Operand::Immediate`] delta. [`Instruction`] and [`run`] never see a
+/// label -- by the time parsing finishes, only plain offsets remain.
 fn parse_assembly(input: &str) -> Result<Vec<Instruction>, ParseError> {
-    input.lines().map(str::parse).collect()
+    let labels = collect_labels(input)?;
+    input
+        .lines()
+        .filter(|line| !line.ends_with(':'))
+        .enumerate()
+        .map(|(idx, line)| parse_instruction(line, idx, &labels))
+        .collect()
+}
+
+fn collect_labels(input: &str) -> Result<HashMap<String, usize>, ParseError> {
+    let mut labels = HashMap::new();
+    let mut index = 0;
+    for line in input.lines() {
+        match line.strip_suffix(':') {
+            Some(name) => {
+                if labels.insert(name.to_string(), index).is_some() {
+                    return Err(ParseError::DuplicateLabel(name.to_string()));
+                }
+            }
+            None => index += 1,
+        }
+    }
+    Ok(labels)
+}
+
+fn parse_instruction(
+    line: &str,
+    idx: usize,
+    labels: &HashMap<String, usize>,
+) -> Result<Instruction, ParseError> {
+    let Some(("jgz", args)) = line.split_once(' ') else {
+        return line.parse();
+    };
+    let (cond, target) = args.split_once(' ').ok_or(ParseError::MissingToken)?;
+    let offset = match target.parse::<Operand>() {
+        Ok(offset) => offset,
+        Err(_) => {
+            let &label_idx = labels
+                .get(target)
+                .ok_or_else(|| ParseError::UndefinedLabel(target.to_string()))?;
+            Operand::Immediate(label_idx as i64 - idx as i64)
+        }
+    };
+    Ok(Instruction::Jgz(cond.parse()?, offset))
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Instruction::Snd(op) => write!(f, "snd {op}"),
+            Instruction::Set(reg, op) => write!(f, "set {reg} {op}"),
+            Instruction::Add(reg, op) => write!(f, "add {reg} {op}"),
+            Instruction::Mul(reg, op) => write!(f, "mul {reg} {op}"),
+            Instruction::Mod(reg, op) => write!(f, "mod {reg} {op}"),
+            Instruction::Rcv(reg) => write!(f, "rcv {reg}"),
+            Instruction::Jgz(cond, op) => write!(f, "jgz {cond} {op}"),
+            Instruction::Trp(op) => write!(f, "trp {op}"),
+        }
+    }
+}
+
+/// Re-emits a parsed program as the same canonical `"mnemonic args"` text
+/// that [`Instruction::from_str`] (via [`parse_assembly`]) accepts, one
+/// instruction per line.
+fn disassemble(asm: &[Instruction]) -> String {
+    asm.iter().map(Instruction::to_string).join("\n")
 }
 
 impl FromStr for Instruction {
@@ -114,9 +161,10 @@ impl FromStr for Instruction {
         let (name, args) = s.split_once(' ').ok_or(ParseError::MissingToken)?;
 
         Ok(match name {
-            "snd" | "rcv" => match name {
+            "snd" | "rcv" | "trp" => match name {
                 "snd" => Instruction::Snd(args.parse()?),
                 "rcv" => Instruction::Rcv(args.parse()?),
+                "trp" => Instruction::Trp(args.parse()?),
                 _ => unreachable!(),
             },
             "set" | "add" | "mul" | "mod" => {
@@ -142,47 +190,6 @@ impl FromStr for Instruction {
     }
 }
 
-impl FromStr for Operand {
-    type Err = ParseError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(match s.as_bytes() {
-            [byte @ b'a'..=b'z'] => Operand::Register(Register(*byte)),
-            _ => Operand::Immediate(s.parse()?),
-        })
-    }
-}
-
-impl FromStr for Register {
-    type Err = ParseError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if let [byte @ b'a'..=b'z'] = s.as_bytes() {
-            Ok(Register(*byte))
-        } else {
-            Err(ParseError::InvalidRegister(s.to_string()))
-        }
-    }
-}
-
-impl Register {
-    pub(crate) fn access<'a>(
-        &self,
-        registers: &'a mut HashMap<Register, i64>,
-    ) -> hash_map::Entry<'a, Register, i64> {
-        registers.entry(*self)
-    }
-}
-
-impl Operand {
-    pub(crate) fn fetch(&self, registers: &mut HashMap<Register, i64>) -> i64 {
-        match self {
-            Operand::Immediate(val) => *val,
-            Operand::Register(reg) => *reg.access(registers).or_default(),
-        }
-    }
-}
-
 type RegisterAccess<'a> = hash_map::Entry<'a, Register, i64>;
 
 enum ReceiveResult {
@@ -226,7 +233,7 @@ impl Receiver for &LastValue {
 struct ChannelSender {
     tx: tokio::sync::mpsc::Sender<i64>,
     send_count: Arc<AtomicI64>,
-    pending_recv_count: Arc<AtomicI64>,
+    blocked_count: Arc<AtomicI64>,
 }
 
 impl Sender for ChannelSender {
@@ -234,10 +241,8 @@ impl Sender for ChannelSender {
     async fn send(&self, val: i64) -> Result<(), RuntimeError> {
         info!(val, "send");
         let _ = self.tx.send(val).await;
-        self.send_count
-            .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
-        self.pending_recv_count
-            .fetch_sub(1, std::sync::atomic::Ordering::SeqCst);
+        self.send_count.fetch_add(1, Ordering::SeqCst);
+        self.blocked_count.fetch_sub(1, Ordering::SeqCst);
         Ok(())
     }
 }
@@ -246,25 +251,20 @@ impl Sender for ChannelSender {
 struct ChannelReceiver {
     rx: tokio::sync::mpsc::Receiver<i64>,
     send_count: Arc<AtomicI64>,
-    pending_recv_count: Arc<AtomicI64>,
+    blocked_count: Arc<AtomicI64>,
+    party_count: i64,
 }
 
 impl Receiver for ChannelReceiver {
     #[instrument]
     async fn recv(&mut self, reg: RegisterAccess<'_>) -> ReceiveResult {
-        let concurrent_recv = self
-            .pending_recv_count
-            .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
-        if concurrent_recv > 0 {
-            return ReceiveResult::Interrupt(
-                self.send_count.load(std::sync::atomic::Ordering::SeqCst),
-            );
+        let blocked = self.blocked_count.fetch_add(1, Ordering::SeqCst) + 1;
+        if blocked >= self.party_count {
+            return ReceiveResult::Interrupt(self.send_count.load(Ordering::SeqCst));
         }
-        info!(%concurrent_recv, "recv");
+        info!(blocked, "recv");
         let Some(val) = self.rx.recv().await else {
-            return ReceiveResult::Interrupt(
-                self.send_count.load(std::sync::atomic::Ordering::SeqCst),
-            );
+            return ReceiveResult::Interrupt(self.send_count.load(Ordering::SeqCst));
         };
         info!(val, "received");
         *reg.or_default() = val;
@@ -272,23 +272,64 @@ impl Receiver for ChannelReceiver {
     }
 }
 
-fn channel(pending_recv_count: Arc<AtomicI64>) -> (ChannelSender, ChannelReceiver) {
+fn channel(blocked_count: Arc<AtomicI64>, party_count: i64) -> (ChannelSender, ChannelReceiver) {
     let send_count = Arc::new(AtomicI64::default());
     let (tx, rx) = tokio::sync::mpsc::channel(100);
     (
         ChannelSender {
             tx,
             send_count: send_count.clone(),
-            pending_recv_count: pending_recv_count.clone(),
+            blocked_count: blocked_count.clone(),
         },
         ChannelReceiver {
             rx,
             send_count,
-            pending_recv_count,
+            blocked_count,
+            party_count,
         },
     )
 }
 
+/// Runs `party_count` copies of `asm` wired into a ring, where program `k`
+/// sends to `k + 1` and receives from `k - 1` (mod `party_count`), each
+/// seeded with its own index in register `p`. Returns each program's send
+/// count, indexed by program number.
+///
+/// All `party_count` programs share a single `blocked_count`, incremented by
+/// every [`ChannelReceiver::recv`] on entry; once it reaches `party_count`,
+/// every program is simultaneously waiting and can never be unblocked by a
+/// real send, so the program that observes this interrupts itself. Dropping
+/// its [`ChannelSender`] closes the channel its ring successor is awaiting,
+/// which sees the channel close and interrupts itself in turn -- cascading
+/// the shutdown around the whole ring.
+async fn run_ring(asm: &[Instruction], party_count: usize) -> Result<Vec<i64>, RuntimeError> {
+    let blocked_count = Arc::new(AtomicI64::default());
+    let (senders, mut receivers): (Vec<_>, Vec<_>) = (0..party_count)
+        .map(|_| channel(blocked_count.clone(), party_count as i64))
+        .unzip();
+    receivers.rotate_right(1);
+    let send_counts: Vec<_> = senders.iter().map(|s| s.send_count.clone()).collect();
+
+    let mut programs: FuturesUnordered<_> = senders
+        .into_iter()
+        .zip(receivers)
+        .enumerate()
+        .map(|(k, (send, recv))| {
+            let registers = HashMap::from([(Register(b'p'), k as i64)]);
+            info_span!("program", k).in_scope(|| run(registers, asm, send, recv))
+        })
+        .collect();
+
+    while let Some(result) = programs.next().await {
+        result?;
+    }
+
+    Ok(send_counts
+        .into_iter()
+        .map(|count| count.load(Ordering::SeqCst))
+        .collect())
+}
+
 async fn run(
     mut registers: HashMap<Register, i64>,
     asm: &[Instruction],
@@ -297,8 +338,10 @@ async fn run(
 ) -> Result<i64, RuntimeError> {
     let mut pc = 0;
     loop {
+        let instruction = asm[pc];
+        let _span = info_span!("step", instr = %instruction).entered();
         let mut jump = 1;
-        match asm[pc] {
+        match instruction {
             Instruction::Snd(op) => sender.send(op.fetch(&mut registers)).await?,
             Instruction::Set(reg, op) => {
                 let val = op.fetch(&mut registers);
@@ -306,15 +349,33 @@ async fn run(
             }
             Instruction::Add(reg, op) => {
                 let op_val = op.fetch(&mut registers);
-                *reg.access(&mut registers).or_default() += op_val;
+                let current = *reg.access(&mut registers).or_default();
+                let result = current
+                    .checked_add(op_val)
+                    .ok_or_else(|| overflow(pc, &registers))?;
+                *reg.access(&mut registers).or_default() = result;
             }
             Instruction::Mul(reg, op) => {
                 let op_val = op.fetch(&mut registers);
-                *reg.access(&mut registers).or_default() *= op_val;
+                let current = *reg.access(&mut registers).or_default();
+                let result = current
+                    .checked_mul(op_val)
+                    .ok_or_else(|| overflow(pc, &registers))?;
+                *reg.access(&mut registers).or_default() = result;
             }
             Instruction::Mod(reg, op) => {
                 let op_val = op.fetch(&mut registers);
-                *reg.access(&mut registers).or_default() %= op_val;
+                if op_val == 0 {
+                    return Err(RuntimeError::DivideByZero {
+                        pc,
+                        registers: registers.clone(),
+                    });
+                }
+                let current = *reg.access(&mut registers).or_default();
+                let result = current
+                    .checked_rem(op_val)
+                    .ok_or_else(|| overflow(pc, &registers))?;
+                *reg.access(&mut registers).or_default() = result;
             }
             Instruction::Rcv(reg) => match receiver.recv(reg.access(&mut registers)).await {
                 ReceiveResult::Ok => {}
@@ -326,6 +387,7 @@ async fn run(
                     jump = op.fetch(&mut registers);
                 }
             }
+            Instruction::Trp(op) => return Err(RuntimeError::Trap(op.fetch(&mut registers))),
         }
 
         let new_pc = pc as i64 + jump;
@@ -337,6 +399,113 @@ async fn run(
     }
 }
 
+fn overflow(pc: usize, registers: &HashMap<Register, i64>) -> RuntimeError {
+    RuntimeError::ArithmeticOverflow {
+        pc,
+        registers: registers.clone(),
+    }
+}
+
+/// Steps through `asm` one instruction at a time without any communication:
+/// `snd` is a no-op and `rcv` only ever inspects its register to decide
+/// whether to halt, matching part 1's [`LastValue`] semantics. Yields the
+/// program counter and register file after each executed instruction, and
+/// ends once the program halts (on `rcv`, `trp`, an out-of-range jump, or an
+/// arithmetic fault) -- unlike [`run`], faults are silent endpoints rather
+/// than an [`Err`], since this iterator exists for state inspection, not for
+/// producing a puzzle answer.
+struct Stepper<'asm> {
+    registers: HashMap<Register, i64>,
+    pc: usize,
+    asm: &'asm [Instruction],
+}
+
+impl<'asm> Stepper<'asm> {
+    fn new(asm: &'asm [Instruction]) -> Self {
+        Stepper {
+            registers: HashMap::new(),
+            pc: 0,
+            asm,
+        }
+    }
+}
+
+impl Iterator for Stepper<'_> {
+    type Item = (usize, HashMap<Register, i64>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut jump = 1;
+        match *self.asm.get(self.pc)? {
+            Instruction::Snd(_) => {}
+            Instruction::Set(reg, op) => {
+                let val = op.fetch(&mut self.registers);
+                self.registers.insert(reg, val);
+            }
+            Instruction::Add(reg, op) => {
+                let val = op.fetch(&mut self.registers);
+                let current = *reg.access(&mut self.registers).or_default();
+                *reg.access(&mut self.registers).or_default() = current.checked_add(val)?;
+            }
+            Instruction::Mul(reg, op) => {
+                let val = op.fetch(&mut self.registers);
+                let current = *reg.access(&mut self.registers).or_default();
+                *reg.access(&mut self.registers).or_default() = current.checked_mul(val)?;
+            }
+            Instruction::Mod(reg, op) => {
+                let val = op.fetch(&mut self.registers);
+                let current = *reg.access(&mut self.registers).or_default();
+                *reg.access(&mut self.registers).or_default() = current.checked_rem(val)?;
+            }
+            Instruction::Rcv(reg) => {
+                if *reg.access(&mut self.registers).or_default() != 0 {
+                    return None;
+                }
+            }
+            Instruction::Jgz(cond, op) => {
+                if cond.fetch(&mut self.registers) > 0 {
+                    jump = op.fetch(&mut self.registers);
+                }
+            }
+            Instruction::Trp(_) => return None,
+        }
+
+        let new_pc = self.pc as i64 + jump;
+        self.pc = usize::try_from(new_pc)
+            .ok()
+            .filter(|&pc| pc < self.asm.len())?;
+        Some((self.pc, self.registers.clone()))
+    }
+}
+
+type State = Option<(usize, HashMap<Register, i64>)>;
+
+fn single_step(asm: &[Instruction], pc: usize, registers: HashMap<Register, i64>) -> State {
+    Stepper { registers, pc, asm }.next()
+}
+
+/// Finds a repeating `(pc, registers)` configuration in `asm`'s non-
+/// communicating execution, reusing [`brent`]'s cycle search from the state
+/// sequence `Stepper::new(asm)` would yield. The state space is modelled as
+/// `Option<(pc, registers)>` with `None` as an absorbing "halted" state, so
+/// that a program which halts (rather than looping forever) is recognized as
+/// such and reported as `None`, instead of being mistaken for a cycle of
+/// length one at the point it stopped.
+fn detect_register_cycle(asm: &[Instruction]) -> Option<(usize, usize)> {
+    let step = |state: &State| {
+        state
+            .clone()
+            .and_then(|(pc, regs)| single_step(asm, pc, regs))
+    };
+
+    let x0: State = Some((0, HashMap::new()));
+    let (mu, lambda) = brent(x0.clone(), step);
+
+    let halted_state = std::iter::successors(Some(x0), |state| Some(step(state)))
+        .nth(mu)
+        .unwrap();
+    halted_state.is_some().then_some((mu, lambda))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -424,6 +593,40 @@ jgz a -19";
         assert_eq!(parse_assembly(EXAMPLE_INPUT).unwrap(), EXAMPLE_ASM);
     }
 
+    const LABELED_EXAMPLE_INPUT: &str = "set a 1
+add a 2
+mul a a
+mod a 5
+snd a
+set a 0
+recv_again:
+rcv a
+retry:
+jgz a recv_again
+set a 1
+jgz a retry";
+
+    #[test]
+    fn labels_resolve_to_the_same_offsets_as_their_numeric_equivalents() {
+        assert_eq!(parse_assembly(LABELED_EXAMPLE_INPUT).unwrap(), EXAMPLE_ASM);
+    }
+
+    #[test]
+    fn jump_to_an_undefined_label_is_an_error() {
+        assert!(matches!(
+            parse_assembly("jgz a nowhere"),
+            Err(ParseError::UndefinedLabel(label)) if label == "nowhere"
+        ));
+    }
+
+    #[test]
+    fn duplicate_label_definitions_are_an_error() {
+        assert!(matches!(
+            parse_assembly("loop:\nsnd 1\nloop:\nsnd 2"),
+            Err(ParseError::DuplicateLabel(label)) if label == "loop"
+        ));
+    }
+
     proptest! {
         #[test]
         fn parsing_assembly_does_not_panic(input in r"\PC*") {
@@ -431,6 +634,44 @@ jgz a -19";
         }
     }
 
+    fn register_strategy() -> impl Strategy<Value = Register> {
+        (b'a'..=b'z').prop_map(Register)
+    }
+
+    fn operand_strategy() -> impl Strategy<Value = Operand> {
+        prop_oneof![
+            any::<i64>().prop_map(Operand::Immediate),
+            register_strategy().prop_map(Operand::Register),
+        ]
+    }
+
+    fn instruction_strategy() -> impl Strategy<Value = Instruction> {
+        prop_oneof![
+            operand_strategy().prop_map(Instruction::Snd),
+            (register_strategy(), operand_strategy())
+                .prop_map(|(reg, op)| Instruction::Set(reg, op)),
+            (register_strategy(), operand_strategy())
+                .prop_map(|(reg, op)| Instruction::Add(reg, op)),
+            (register_strategy(), operand_strategy())
+                .prop_map(|(reg, op)| Instruction::Mul(reg, op)),
+            (register_strategy(), operand_strategy())
+                .prop_map(|(reg, op)| Instruction::Mod(reg, op)),
+            register_strategy().prop_map(Instruction::Rcv),
+            (operand_strategy(), operand_strategy())
+                .prop_map(|(cond, op)| Instruction::Jgz(cond, op)),
+            operand_strategy().prop_map(Instruction::Trp),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn disassembling_and_reparsing_reproduces_the_original_program(
+            asm in proptest::collection::vec(instruction_strategy(), 0..16)
+        ) {
+            prop_assert_eq!(parse_assembly(&disassemble(&asm)).unwrap(), asm);
+        }
+    }
+
     #[test]
     fn example_part1() {
         assert_eq!(
@@ -467,4 +708,89 @@ jgz a -19";
         assert_eq!(Door::parse(THIRD_PARTY_INPUT)?.part2()?, 7620);
         Ok(())
     }
+
+    #[traced_test]
+    #[test]
+    fn ring_of_three_programs_deadlocks_with_nobody_having_sent() {
+        const IMMEDIATE_RCV_ASM: [Instruction; 1] = [Instruction::Rcv(Register(b'a'))];
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        let send_counts = runtime.block_on(run_ring(&IMMEDIATE_RCV_ASM, 3)).unwrap();
+        assert_eq!(send_counts, vec![0, 0, 0]);
+    }
+
+    fn run_single(asm: &[Instruction]) -> Result<i64, RuntimeError> {
+        let last_value = LastValue::default();
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        runtime.block_on(run(HashMap::new(), asm, &last_value, &last_value))
+    }
+
+    #[test]
+    fn trp_instruction_is_parsed_and_halts_with_its_operand() {
+        assert_eq!("trp 5".parse(), Ok(Instruction::Trp(Operand::Immediate(5))));
+        assert!(matches!(
+            run_single(&[Instruction::Trp(Operand::Immediate(5))]),
+            Err(RuntimeError::Trap(5))
+        ));
+    }
+
+    #[test]
+    fn mod_by_zero_is_reported_as_divide_by_zero_at_the_faulting_pc() {
+        let asm = [Instruction::Mod(Register(b'a'), Operand::Immediate(0))];
+        assert!(matches!(
+            run_single(&asm),
+            Err(RuntimeError::DivideByZero { pc: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn add_overflow_is_reported_instead_of_wrapping() {
+        let asm = [
+            Instruction::Set(Register(b'a'), Operand::Immediate(1)),
+            Instruction::Add(Register(b'a'), Operand::Immediate(i64::MAX)),
+        ];
+        assert!(matches!(
+            run_single(&asm),
+            Err(RuntimeError::ArithmeticOverflow { pc: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn stepper_replays_the_example_program_and_halts_at_its_rcv() {
+        let states: Vec<_> = Stepper::new(&EXAMPLE_ASM).collect();
+        assert_eq!(states.len(), 11);
+        assert_eq!(
+            states.last(),
+            Some(&(6, HashMap::from([(Register(b'a'), 1)])))
+        );
+    }
+
+    #[test]
+    fn detect_register_cycle_reports_no_cycle_for_a_program_that_halts() {
+        assert_eq!(detect_register_cycle(&EXAMPLE_ASM), None);
+    }
+
+    #[test]
+    fn detect_register_cycle_finds_a_tight_loop_that_never_reaches_rcv() {
+        let asm = [
+            Instruction::Set(Register(b'a'), Operand::Immediate(1)),
+            Instruction::Jgz(Operand::Register(Register(b'a')), Operand::Immediate(-1)),
+        ];
+        assert_eq!(detect_register_cycle(&asm), Some((1, 2)));
+    }
+
+    #[test]
+    fn mul_overflow_is_reported_instead_of_wrapping() {
+        let asm = [
+            Instruction::Set(Register(b'a'), Operand::Immediate(i64::MAX)),
+            Instruction::Mul(Register(b'a'), Operand::Immediate(2)),
+        ];
+        assert!(matches!(
+            run_single(&asm),
+            Err(RuntimeError::ArithmeticOverflow { pc: 1, .. })
+        ));
+    }
 }
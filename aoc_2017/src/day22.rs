@@ -1,7 +1,10 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashSet;
 
 use aoc_companion::prelude::*;
-use aoc_utils::{geometry::map_bounds, linalg::Vector};
+use aoc_utils::{
+    geometry::{map_bounds, DynamicGrid},
+    linalg::Vector,
+};
 use itertools::iterate;
 
 pub(crate) struct Door {
@@ -41,17 +44,21 @@ impl<'input> Solution<'input> for Door {
     }
 }
 
-fn carrier_part1(mut infected: HashSet<Vector<isize, 2>>) -> impl Iterator<Item = bool> {
+fn carrier_part1(initially_infected: HashSet<Vector<isize, 2>>) -> impl Iterator<Item = bool> {
+    let mut infected: DynamicGrid<bool, 2> = DynamicGrid::new();
+    for p in initially_infected {
+        infected.set(p, true);
+    }
     iterate(
         (false, Vector([0, 0]), Vector([0, -1])),
         move |(_, p, d)| {
-            if infected.contains(p) {
+            if infected.get(*p) {
                 let d = Vector([-d[1], d[0]]);
-                infected.remove(p);
+                infected.set(*p, false);
                 (false, *p + d, d)
             } else {
                 let d = Vector([d[1], -d[0]]);
-                infected.insert(*p);
+                infected.set(*p, true);
                 (true, *p + d, d)
             }
         },
@@ -70,14 +77,14 @@ enum InfectionState {
 fn carrier_part2(
     infected: HashSet<Vector<isize, 2>>,
 ) -> impl Iterator<Item = (Vector<isize, 2>, Option<InfectionState>)> {
-    let mut infection_state: HashMap<_, _> = infected
-        .into_iter()
-        .map(|p| (p, InfectionState::Infected))
-        .collect();
+    let mut infection_state: DynamicGrid<Option<InfectionState>, 2> = DynamicGrid::new();
+    for p in infected {
+        infection_state.set(p, Some(InfectionState::Infected));
+    }
     iterate(
         (Vector([0, 0]), Vector([0, -1]), None),
         move |&(p, d, _)| {
-            let (next_p, next_d, new_s) = match infection_state.get(&p).cloned() {
+            let (next_p, next_d, new_s) = match infection_state.get(p) {
                 None => {
                     let d = Vector([d[1], -d[0]]);
                     (p + d, d, Some(InfectionState::Weakened))
@@ -92,11 +99,7 @@ fn carrier_part2(
                     (p + d, d, None)
                 }
             };
-            if let Some(new_s) = new_s {
-                infection_state.insert(p, new_s);
-            } else {
-                infection_state.remove(&p);
-            }
+            infection_state.set(p, new_s);
             (next_p, next_d, new_s)
         },
     )
@@ -1,14 +1,13 @@
 use std::{collections::HashMap, str::FromStr};
 
-use anyhow::anyhow;
 use aoc_companion::prelude::*;
-use enum_map::{Enum, EnumMap};
+use enum_map::Enum;
 use itertools::Itertools;
 
-use crate::day18::{Operand, ParseError, Register};
+use crate::vm::{Instruction, Machine, Opcode, Operand, ParseError, Register};
 
 pub(crate) struct Door {
-    program: Vec<Instruction>,
+    program: Vec<Instruction<Op>>,
 }
 
 impl<'input> Solution<'input> for Door {
@@ -18,26 +17,63 @@ impl<'input> Solution<'input> for Door {
     }
 
     fn part1(&self) -> usize {
-        let profile = run(&self.program, &mut HashMap::new());
+        let profile = Machine::new(&self.program).run(&mut HashMap::new());
         profile[Op::Mul]
     }
 
-    fn part2(&self) -> Result<usize> {
-        if self.program[8..] == PROGRAM[8..] {
-            let mut registers = HashMap::from([(Register(b'a'), 1)]);
-            run(&self.program[0..8], &mut registers);
-            Ok(reverse_engineered_function(
-                registers[&Register(b'b')] as u64,
-                registers[&Register(b'c')] as u64,
-            ))
-        } else {
-            Err(anyhow!(
-                "input program does not match reverse-engineered program"
-            ))
+    fn part2(&self) -> usize {
+        match composite_counting_loop_stride(&self.program) {
+            Some(stride) => {
+                let mut registers = HashMap::from([(Register(b'a'), 1)]);
+                Machine::new(&self.program[0..8]).run(&mut registers);
+                reverse_engineered_function(
+                    registers[&Register(b'b')] as u64,
+                    registers[&Register(b'c')] as u64,
+                    stride,
+                )
+            }
+            None => {
+                let mut registers = HashMap::from([(Register(b'a'), 1)]);
+                Machine::new(&self.program).run(&mut registers);
+                registers[&Register(b'h')] as usize
+            }
         }
     }
 }
 
+/// AoC personalizes each user's day 23 input with different immediate
+/// constants, but every input emits the same register layout and
+/// instruction shape for the nested `d`/`e` loop that counts how many
+/// multiples of some stride in `b..=c` are composite. Rather than requiring
+/// an exact match against [`PROGRAM`], this compares opcodes and operand
+/// *kinds* (same register, or any immediate) so any personalized input with
+/// that shape is recognized, and returns the stride extracted from the
+/// matched program so [`reverse_engineered_function`] can replace the loop
+/// with its closed form. Programs with a different shape fall back to plain
+/// interpretation in [`Door::part2`].
+fn composite_counting_loop_stride(program: &[Instruction<Op>]) -> Option<u64> {
+    fn same_operand_shape(a: Operand, b: Operand) -> bool {
+        match (a, b) {
+            (Operand::Immediate(_), Operand::Immediate(_)) => true,
+            (Operand::Register(a), Operand::Register(b)) => a == b,
+            _ => false,
+        }
+    }
+    fn same_shape(a: &Instruction<Op>, b: &Instruction<Op>) -> bool {
+        a.op == b.op && same_operand_shape(a.lhs, b.lhs) && same_operand_shape(a.rhs, b.rhs)
+    }
+
+    if program.len() != PROGRAM.len()
+        || std::iter::zip(&program[8..], &PROGRAM[8..]).any(|(a, b)| !same_shape(a, b))
+    {
+        return None;
+    }
+    match program[program.len() - 2].rhs {
+        Operand::Immediate(stride) => Some(stride.unsigned_abs()),
+        Operand::Register(_) => None,
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
 enum Op {
     Set,
@@ -60,77 +96,39 @@ impl FromStr for Op {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct Instruction {
-    op: Op,
-    lhs: Operand,
-    rhs: Operand,
-}
-
-impl FromStr for Instruction {
-    type Err = ParseError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (op, args) = s.split_once(' ').ok_or(ParseError::MissingToken)?;
-        let (lhs, rhs) = args.split_once(' ').ok_or(ParseError::MissingToken)?;
-        Ok(Instruction {
-            op: op.parse()?,
-            lhs: lhs.parse()?,
-            rhs: rhs.parse()?,
-        })
-    }
-}
-
-type Profile = EnumMap<Op, usize>;
-
-fn run(program: &[Instruction], registers: &mut HashMap<Register, i64>) -> Profile {
-    let mut pc = 0;
-    let mut profile = Profile::default();
-    while let Some(instruction) = pc.try_into().ok().and_then(|pc: usize| program.get(pc)) {
-        profile[instruction.op] += 1;
-
-        match instruction {
-            Instruction {
-                op: Op::Set,
-                lhs: Operand::Register(reg),
-                rhs: arg,
-            } => {
-                *reg.access(registers).or_default() = arg.fetch(registers);
-            }
-            Instruction {
-                op: Op::Sub,
-                lhs: Operand::Register(reg),
-                rhs: arg,
-            } => {
-                *reg.access(registers).or_default() -= arg.fetch(registers);
+impl Opcode for Op {
+    fn apply(
+        &self,
+        lhs: Operand,
+        rhs: Operand,
+        registers: &mut HashMap<Register, i64>,
+    ) -> Option<i64> {
+        match (self, lhs) {
+            (Op::Set, Operand::Register(reg)) => {
+                *reg.access(registers).or_default() = rhs.fetch(registers);
+                None
             }
-            Instruction {
-                op: Op::Mul,
-                lhs: Operand::Register(reg),
-                rhs: arg,
-            } => {
-                *reg.access(registers).or_default() *= arg.fetch(registers);
+            (Op::Sub, Operand::Register(reg)) => {
+                *reg.access(registers).or_default() -= rhs.fetch(registers);
+                None
             }
-            Instruction {
-                op: Op::Jnz,
-                lhs,
-                rhs,
-            } if lhs.fetch(registers) != 0 => {
-                pc += rhs.fetch(registers);
-                continue;
+            (Op::Mul, Operand::Register(reg)) => {
+                *reg.access(registers).or_default() *= rhs.fetch(registers);
+                None
             }
-            _ => {}
+            (Op::Jnz, _) if lhs.fetch(registers) != 0 => Some(rhs.fetch(registers)),
+            _ => None,
         }
-        pc += 1;
     }
-
-    profile
 }
 
-fn reverse_engineered_function(b: u64, c: u64) -> usize {
+fn reverse_engineered_function(b: u64, c: u64, stride: u64) -> usize {
     use primes::PrimeSet;
     let mut sieve = primes::Sieve::new();
-    (b..=c).step_by(17).filter(|&x| !sieve.is_prime(x)).count()
+    (b..=c)
+        .step_by(stride as usize)
+        .filter(|&x| !sieve.is_prime(x))
+        .count()
 }
 
 #[allow(clippy::items_after_test_module)]
@@ -141,9 +139,31 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn recognizes_canonical_program_shape() {
+        assert_eq!(composite_counting_loop_stride(PROGRAM), Some(17));
+    }
+
+    #[test]
+    fn recognizes_program_shape_with_different_personalized_constants() {
+        let mut personalized = PROGRAM.to_vec();
+        personalized[0].rhs = Operand::Immediate(79);
+        personalized[7].rhs = Operand::Immediate(-12000);
+        personalized[30].rhs = Operand::Immediate(-23);
+        assert_eq!(composite_counting_loop_stride(&personalized), Some(23));
+    }
+
+    #[test]
+    fn rejects_program_with_a_different_shape() {
+        assert_eq!(
+            composite_counting_loop_stride(&PROGRAM[..PROGRAM.len() - 1]),
+            None
+        );
+    }
+
     #[test]
     fn profile_in_debug_mode() {
-        let profile = run(PROGRAM, &mut HashMap::new());
+        let profile = Machine::new(PROGRAM).run(&mut HashMap::new());
         assert_eq!(
             profile,
             enum_map! {
@@ -162,17 +182,17 @@ mod tests {
         fn program_counts_non_prime_multiple_of_17_in_range(start in 2u64..1000, mult in 1u64..25) {
             let end = start + mult * 17;
             let mut registers = HashMap::from([(Register(b'b'), start as i64), (Register(b'c'), end as i64)]);
-            run(&PROGRAM[8..], &mut registers);
+            Machine::new(&PROGRAM[8..]).run(&mut registers);
             assert_eq!(
                 registers[&Register(b'h')] as usize,
-                reverse_engineered_function(start, end)
+                reverse_engineered_function(start, end, 17)
             );
         }
 
     }
 }
 
-const PROGRAM: &[Instruction] = &[
+const PROGRAM: &[Instruction<Op>] = &[
     Instruction {
         op: Op::Set,
         lhs: Operand::Register(Register(b'b')),
@@ -1,9 +1,10 @@
-use std::collections::{HashMap, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::io::{BufRead, Write};
 
 use anyhow::{anyhow, bail};
 use aoc_companion::prelude::*;
 use aoc_utils::array;
-use itertools::{iterate, Itertools};
+use itertools::Itertools;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct Door {
@@ -170,24 +171,188 @@ impl TuringMachine {
         self.tape.iter().filter(|&&b| b).count()
     }
 
+    /// Executes exactly one transition out of `state` and reports the state
+    /// entered afterwards, the head's new position, and the value now under
+    /// it, so a caller driving the machine incrementally (e.g. [`Repl`])
+    /// doesn't have to re-derive those from the tape itself. `run` is just
+    /// this, looped `n` times.
+    fn step(&mut self, state: State, rules: &HashMap<State, [Action; 2]>) -> Step {
+        let Action {
+            write,
+            offset,
+            to_state,
+        } = rules[&state][self.current_value() as usize];
+        self.write(write);
+        self.move_tape(offset);
+        Step {
+            state: to_state,
+            pos: self.pos,
+            current_value: self.current_value(),
+        }
+    }
+
     fn run(
         &mut self,
         initial_state: State,
         n: usize,
         rules: &HashMap<State, [Action; 2]>,
     ) -> State {
-        iterate(initial_state, |state| {
-            let Action {
-                write,
-                offset,
-                to_state,
-            } = rules[state][self.current_value() as usize];
-            self.write(write);
-            self.move_tape(offset);
-            to_state
-        })
-        .nth(n - 1)
-        .unwrap()
+        let mut state = initial_state;
+        for _ in 0..n {
+            state = self.step(state, rules).state;
+        }
+        state
+    }
+
+    /// Renders the tape as a row of `.`/`#`, with a caret on the line below
+    /// marking the head's position, e.g.:
+    /// ```text
+    /// ..##.#.
+    ///    ^
+    /// ```
+    fn render_tape(&self) -> String {
+        let cells: String = self
+            .tape
+            .iter()
+            .map(|&b| if b { '#' } else { '.' })
+            .collect();
+        format!("{cells}\n{}^", " ".repeat(self.pos))
+    }
+}
+
+/// The outcome of a single [`TuringMachine::step`]: the state just entered,
+/// the head's position, and the tape value now under it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Step {
+    state: State,
+    pos: usize,
+    current_value: bool,
+}
+
+/// A command accepted by [`Repl::run`]'s prompt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Command {
+    Step(usize),
+    Run,
+    Break(State),
+    Tape,
+    Quit,
+}
+
+impl std::str::FromStr for Command {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut words = s.split_whitespace();
+        match words.next() {
+            Some("step") => Ok(Command::Step(match words.next() {
+                Some(n) => n.parse()?,
+                None => 1,
+            })),
+            Some("run") => Ok(Command::Run),
+            Some("break") => {
+                let name = words
+                    .next()
+                    .ok_or_else(|| anyhow!("break requires a state, e.g. `break A`"))?;
+                let state = name.chars().exactly_one().map_err(|e| {
+                    anyhow!("expected a single character as state designator: {e}")
+                })?;
+                Ok(Command::Break(State(state)))
+            }
+            Some("tape") => Ok(Command::Tape),
+            Some("quit" | "q") => Ok(Command::Quit),
+            Some(other) => bail!(
+                "unrecognized command {other:?}; try `step[ N]`, `run`, `break <state>`, `tape`, or `quit`"
+            ),
+            None => bail!("empty command"),
+        }
+    }
+}
+
+/// An interactive single-stepping debugger over a [`Door`]'s Turing
+/// machine: `step`/`step N` advances one or `N` transitions, `run` advances
+/// to `checksum_after`, `break <state>` halts stepping as soon as that
+/// state is entered, and `tape` reprints the tape without stepping. Prints
+/// the tape (via [`TuringMachine::render_tape`]) and the current state
+/// after every command that moves the head.
+pub(crate) struct Repl<'d> {
+    door: &'d Door,
+    tm: TuringMachine,
+    state: State,
+    steps_taken: usize,
+    breakpoints: HashSet<State>,
+}
+
+impl<'d> Repl<'d> {
+    pub(crate) fn new(door: &'d Door) -> Self {
+        Self {
+            state: door.initial_state,
+            door,
+            tm: TuringMachine::new(),
+            steps_taken: 0,
+            breakpoints: HashSet::new(),
+        }
+    }
+
+    fn status_line(&self) -> String {
+        format!(
+            "state {} after {} step(s)\n{}",
+            self.state.0,
+            self.steps_taken,
+            self.tm.render_tape()
+        )
+    }
+
+    /// Advances up to `n` steps, stopping early if a breakpointed state is
+    /// entered. Returns how many steps were actually taken.
+    fn step_n(&mut self, n: usize) -> usize {
+        let mut taken = 0;
+        for _ in 0..n {
+            let Step { state, .. } = self.tm.step(self.state, &self.door.rules);
+            self.state = state;
+            self.steps_taken += 1;
+            taken += 1;
+            if self.breakpoints.contains(&self.state) {
+                break;
+            }
+        }
+        taken
+    }
+
+    /// Drives the REPL from `input`, one command per line, writing a
+    /// prompt and the resulting status to `output` after each. Returns once
+    /// a `quit` command is read or `input` is exhausted.
+    pub(crate) fn run(&mut self, input: impl BufRead, mut output: impl Write) -> Result<()> {
+        write!(output, "> ")?;
+        output.flush()?;
+        for line in input.lines() {
+            match line?.parse::<Command>() {
+                Ok(Command::Quit) => break,
+                Ok(Command::Step(n)) => {
+                    self.step_n(n);
+                    writeln!(output, "{}", self.status_line())?;
+                }
+                Ok(Command::Run) => {
+                    let remaining = self.door.checksum_after.saturating_sub(self.steps_taken);
+                    self.step_n(remaining);
+                    writeln!(
+                        output,
+                        "{}\nchecksum: {}",
+                        self.status_line(),
+                        self.tm.checksum()
+                    )?;
+                }
+                Ok(Command::Break(state)) => {
+                    self.breakpoints.insert(state);
+                    writeln!(output, "breakpoint set on state {}", state.0)?;
+                }
+                Ok(Command::Tape) => writeln!(output, "{}", self.status_line())?,
+                Err(e) => writeln!(output, "error: {e}")?,
+            }
+            write!(output, "> ")?;
+            output.flush()?;
+        }
+        Ok(())
     }
 }
 
@@ -283,4 +448,95 @@ In state B:
         );
         assert_eq!(tm.checksum(), 3);
     }
+
+    #[test]
+    fn step_matches_run_over_the_full_example() {
+        let rules = HashMap::from(EXAMPLE_RULES);
+
+        let mut via_run = TuringMachine::new();
+        via_run.run(EXAMPLE_INITIAL_STATE, EXAMPLE_CHECKSUM_AFTER, &rules);
+
+        let mut via_step = TuringMachine::new();
+        let mut state = EXAMPLE_INITIAL_STATE;
+        for _ in 0..EXAMPLE_CHECKSUM_AFTER {
+            state = via_step.step(state, &rules).state;
+        }
+
+        assert_eq!(via_step.tape, via_run.tape);
+        assert_eq!(via_step.pos, via_run.pos);
+        assert_eq!(state, State('A'));
+    }
+
+    #[test]
+    fn render_tape_marks_head_position() {
+        let mut tm = TuringMachine::new();
+        tm.run(
+            EXAMPLE_INITIAL_STATE,
+            EXAMPLE_CHECKSUM_AFTER,
+            &HashMap::from(EXAMPLE_RULES),
+        );
+        assert_eq!(tm.render_tape(), "##.#\n  ^");
+    }
+
+    #[test]
+    fn step_reports_resulting_position_and_value() {
+        let rules = HashMap::from(EXAMPLE_RULES);
+        let mut tm = TuringMachine::new();
+        let step = tm.step(EXAMPLE_INITIAL_STATE, &rules);
+        assert_eq!(
+            step,
+            Step {
+                state: State('B'),
+                pos: 1,
+                current_value: false,
+            }
+        );
+    }
+
+    fn example_door() -> Door {
+        Door::parse(EXAMPLE_INPUT).unwrap()
+    }
+
+    #[test]
+    fn repl_run_command_advances_to_the_checksum() {
+        let door = example_door();
+        let mut repl = Repl::new(&door);
+        let mut output = Vec::new();
+        repl.run("run\nquit\n".as_bytes(), &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("checksum: 3"));
+        assert!(output.contains("##.#"));
+    }
+
+    #[test]
+    fn repl_step_advances_one_transition_at_a_time() {
+        let door = example_door();
+        let mut repl = Repl::new(&door);
+        let mut output = Vec::new();
+        repl.run("step\nstep 5\nquit\n".as_bytes(), &mut output)
+            .unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("state A after 6 step(s)"));
+    }
+
+    #[test]
+    fn repl_breakpoint_halts_stepping_as_soon_as_the_state_is_entered() {
+        let door = example_door();
+        let mut repl = Repl::new(&door);
+        let mut output = Vec::new();
+        repl.run("break B\nstep 10\nquit\n".as_bytes(), &mut output)
+            .unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("state B after 1 step(s)"));
+    }
+
+    #[test]
+    fn repl_reports_unrecognized_commands_without_stepping() {
+        let door = example_door();
+        let mut repl = Repl::new(&door);
+        let mut output = Vec::new();
+        repl.run("bogus\nquit\n".as_bytes(), &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("error: unrecognized command"));
+    }
 }
@@ -20,6 +20,7 @@ mod day17;
 mod day18;
 mod day19;
 mod day20;
+mod vm;
 
 use aoc_companion::prelude::*;
 
@@ -0,0 +1,161 @@
+//! A small assembly-style virtual machine shared by the "duet" family of
+//! 2017 puzzles (days 18 and 23): a named-register file, an `Operand` that's
+//! either an immediate value or a register, and a generic [`Machine`] that
+//! steps through a program of [`Instruction`]s for whichever opcode set a
+//! given day needs.
+
+use std::{collections::HashMap, fmt, num::ParseIntError, str::FromStr};
+
+use enum_map::{Enum, EnumMap};
+
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum ParseError {
+    #[error("expected a space delimiting tokens in instruction")]
+    MissingToken,
+    #[error("invalid instruction {0:?}")]
+    InvalidInstruction(String),
+    #[error("invalid immediate value")]
+    InvalidImmediate(#[from] ParseIntError),
+    #[error("invalid register {0:?}")]
+    InvalidRegister(String),
+    #[error("jump to undefined label {0:?}")]
+    UndefinedLabel(String),
+    #[error("duplicate definition of label {0:?}")]
+    DuplicateLabel(String),
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct Register(pub u8);
+
+impl std::fmt::Debug for Register {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "${}", std::str::from_utf8(&[self.0]).unwrap_or("ï¿½"))
+    }
+}
+
+impl FromStr for Register {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let [byte @ b'a'..=b'z'] = s.as_bytes() {
+            Ok(Register(*byte))
+        } else {
+            Err(ParseError::InvalidRegister(s.to_string()))
+        }
+    }
+}
+
+impl fmt::Display for Register {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0 as char)
+    }
+}
+
+impl Register {
+    pub(crate) fn access<'a>(
+        &self,
+        registers: &'a mut HashMap<Register, i64>,
+    ) -> std::collections::hash_map::Entry<'a, Register, i64> {
+        registers.entry(*self)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Operand {
+    Immediate(i64),
+    Register(Register),
+}
+
+impl FromStr for Operand {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.as_bytes() {
+            [byte @ b'a'..=b'z'] => Operand::Register(Register(*byte)),
+            _ => Operand::Immediate(s.parse()?),
+        })
+    }
+}
+
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Operand::Immediate(val) => write!(f, "{val}"),
+            Operand::Register(reg) => write!(f, "{reg}"),
+        }
+    }
+}
+
+impl Operand {
+    pub(crate) fn fetch(&self, registers: &mut HashMap<Register, i64>) -> i64 {
+        match self {
+            Operand::Immediate(val) => *val,
+            Operand::Register(reg) => *reg.access(registers).or_default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Instruction<Op> {
+    pub(crate) op: Op,
+    pub(crate) lhs: Operand,
+    pub(crate) rhs: Operand,
+}
+
+impl<Op: FromStr<Err = ParseError>> FromStr for Instruction<Op> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (op, args) = s.split_once(' ').ok_or(ParseError::MissingToken)?;
+        let (lhs, rhs) = args.split_once(' ').ok_or(ParseError::MissingToken)?;
+        Ok(Instruction {
+            op: op.parse()?,
+            lhs: lhs.parse()?,
+            rhs: rhs.parse()?,
+        })
+    }
+}
+
+/// An opcode that mutates `registers` in place and reports how the program
+/// counter should move afterwards: `None` to fall through to the next
+/// instruction, `Some(offset)` to jump relative to the current one.
+pub(crate) trait Opcode {
+    fn apply(
+        &self,
+        lhs: Operand,
+        rhs: Operand,
+        registers: &mut HashMap<Register, i64>,
+    ) -> Option<i64>;
+}
+
+/// A tally of how many times each opcode fired during a [`Machine::run`].
+pub(crate) type Profile<Op> = EnumMap<Op, usize>;
+
+/// Steps through a borrowed program, applying each instruction's [`Opcode`]
+/// in turn and following its requested jumps, until the program counter
+/// runs off either end.
+pub(crate) struct Machine<'prog, Op> {
+    program: &'prog [Instruction<Op>],
+}
+
+impl<'prog, Op: Opcode + Enum + Copy> Machine<'prog, Op> {
+    pub(crate) fn new(program: &'prog [Instruction<Op>]) -> Self {
+        Machine { program }
+    }
+
+    pub(crate) fn run(&self, registers: &mut HashMap<Register, i64>) -> Profile<Op> {
+        let mut pc: i64 = 0;
+        let mut profile = Profile::default();
+        while let Some(instruction) = usize::try_from(pc).ok().and_then(|pc| self.program.get(pc)) {
+            profile[instruction.op] += 1;
+            match instruction
+                .op
+                .apply(instruction.lhs, instruction.rhs, registers)
+            {
+                Some(offset) => pc += offset,
+                None => pc += 1,
+            }
+        }
+        profile
+    }
+}
@@ -2,6 +2,7 @@ use std::num::ParseIntError;
 
 use anyhow::anyhow;
 use aoc_companion::prelude::*;
+use aoc_utils::combinatorics::subset_sum;
 use itertools::Itertools;
 
 pub(crate) struct Door(Vec<i32>);
@@ -29,11 +30,7 @@ impl<'input> Solution<'input> for Door {
 }
 
 fn entries_which_sum_to_2020<const N: usize>(entries: &[i32]) -> Option<[i32; N]> {
-    entries
-        .iter()
-        .cloned()
-        .array_combinations::<N>()
-        .find(|array| array.iter().sum::<i32>() == 2020)
+    subset_sum(entries, 2020)
 }
 
 #[cfg(test)]
@@ -46,7 +43,7 @@ mod tests {
     fn two_entries_which_sum_to_2020() {
         assert_eq!(
             entries_which_sum_to_2020(EXAMPLE_ENTRIES).unwrap(),
-            [1721, 299]
+            [299, 1721]
         );
     }
 
@@ -54,7 +51,7 @@ mod tests {
     fn three_entries_which_sum_to_2020() {
         assert_eq!(
             entries_which_sum_to_2020(EXAMPLE_ENTRIES).unwrap(),
-            [979, 366, 675]
+            [366, 675, 979]
         );
     }
 }
@@ -1,96 +1,54 @@
-use anyhow::anyhow;
 use aoc_companion::prelude::*;
-use itertools::Itertools;
-use regex::Regex;
-use std::{collections::HashMap, ops::RangeBounds};
+use aoc_utils::schema::{
+    Record, Schema, integer_in_range, matches_regex, one_of, suffixed_unit_in_range,
+};
 
 pub(crate) struct Door<'input> {
-    passports: Vec<HashMap<&'input str, &'input str>>,
+    schema: Schema,
+    passports: Vec<Record<'input>>,
 }
 
 impl<'input> Solution<'input> for Door<'input> {
     fn parse(input: &'input str) -> Result<Self> {
-        parse_passports(input).map(|passports| Door { passports })
+        let schema = passport_schema();
+        let passports = schema.parse_records(input)?;
+        Ok(Door { schema, passports })
     }
 
     fn part1(&self) -> impl door::IntoResult {
         self.passports
             .iter()
-            .filter(|p| is_passport_complete(p))
+            .filter(|p| self.schema.is_complete(p))
             .count()
     }
 
     fn part2(&self) -> impl door::IntoResult {
         self.passports
             .iter()
-            .filter(|p| is_passport_valid(p))
+            .filter(|p| self.schema.is_valid(p))
             .count()
     }
 }
 
-fn parse_passports(input: &str) -> Result<Vec<HashMap<&str, &str>>> {
-    input
-        .split("\n\n")
-        .map(|paragraph| {
-            paragraph
-                .split_whitespace()
-                .map(|prop| {
-                    prop.split_once(':')
-                        .ok_or_else(|| anyhow!("missing colon delimiting property key from value"))
-                })
-                .try_collect()
-        })
-        .try_collect()
-}
-
-fn is_passport_complete(passport: &HashMap<&str, &str>) -> bool {
-    const REQUIRED_KEYS: &[&str; 7] = &["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid"];
-
-    REQUIRED_KEYS.iter().all(|key| passport.contains_key(key))
-}
+fn passport_schema() -> Schema {
+    let height_cm = suffixed_unit_in_range("cm", 150..=193);
+    let height_in = suffixed_unit_in_range("in", 59..=76);
 
-fn is_passport_valid(passport: &HashMap<&str, &str>) -> bool {
-    is_passport_complete(passport)
-        && passport.iter().all(|(&key, &val)| match key {
-            "byr" => is_year_valid(val, 1920..=2002),
-            "iyr" => is_year_valid(val, 2010..=2020),
-            "eyr" => is_year_valid(val, 2020..=2030),
-            "hgt" => is_height_valid(val),
-            "hcl" => is_hair_color_valid(val),
-            "ecl" => is_eye_color_valid(val),
-            "pid" => is_passport_id_valid(val),
-            "cid" => true,
-            _ => panic!("unknown key {key:?}"),
+    Schema::new()
+        .field("byr", true, integer_in_range(1920..=2002))
+        .field("iyr", true, integer_in_range(2010..=2020))
+        .field("eyr", true, integer_in_range(2020..=2030))
+        .field("hgt", true, move |s: &str| {
+            height_cm(s).or_else(|_| height_in(s))
         })
-}
-
-fn is_year_valid(s: &str, range: impl RangeBounds<i32>) -> bool {
-    s.parse::<i32>()
-        .map(|year| range.contains(&year))
-        .unwrap_or(false)
-}
-
-fn is_height_valid(s: &str) -> bool {
-    if s.ends_with("cm") {
-        (150..=193).contains(&s.trim_end_matches("cm").parse::<i32>().unwrap_or(0))
-    } else if s.ends_with("in") {
-        (59..=76).contains(&s.trim_end_matches("in").parse::<i32>().unwrap_or(0))
-    } else {
-        false
-    }
-}
-
-fn is_hair_color_valid(s: &str) -> bool {
-    let re = Regex::new("#[a-f0-9]{6}").unwrap();
-    re.is_match(s)
-}
-
-fn is_eye_color_valid(s: &str) -> bool {
-    ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"].contains(&s)
-}
-
-fn is_passport_id_valid(s: &str) -> bool {
-    s.len() == 9 && s.chars().all(|c| c.is_ascii_digit())
+        .field("hcl", true, matches_regex("^#[a-f0-9]{6}$"))
+        .field(
+            "ecl",
+            true,
+            one_of(&["amb", "blu", "brn", "gry", "grn", "hzl", "oth"]),
+        )
+        .field("pid", true, matches_regex("^[0-9]{9}$"))
+        .field("cid", false, |_: &str| Ok(()))
 }
 
 #[cfg(test)]
@@ -114,64 +72,32 @@ hgt:179cm
 hcl:#cfa07d eyr:2025 pid:166559648
 iyr:2011 ecl:brn hgt:59in";
 
-    fn example_passports() -> Vec<HashMap<&'static str, &'static str>> {
-        vec![
-            HashMap::from([
-                ("ecl", "gry"),
-                ("pid", "860033327"),
-                ("eyr", "2020"),
-                ("hcl", "#fffffd"),
-                ("byr", "1937"),
-                ("iyr", "2017"),
-                ("cid", "147"),
-                ("hgt", "183cm"),
-            ]),
-            HashMap::from([
-                ("iyr", "2013"),
-                ("ecl", "amb"),
-                ("cid", "350"),
-                ("eyr", "2023"),
-                ("pid", "028048884"),
-                ("hcl", "#cfa07d"),
-                ("byr", "1929"),
-            ]),
-            HashMap::from([
-                ("hcl", "#ae17e1"),
-                ("iyr", "2013"),
-                ("eyr", "2024"),
-                ("ecl", "brn"),
-                ("pid", "760753108"),
-                ("byr", "1931"),
-                ("hgt", "179cm"),
-            ]),
-            HashMap::from([
-                ("hcl", "#cfa07d"),
-                ("eyr", "2025"),
-                ("pid", "166559648"),
-                ("iyr", "2011"),
-                ("ecl", "brn"),
-                ("hgt", "59in"),
-            ]),
-        ]
-    }
-
     #[test]
     fn parse_example_passports() {
-        assert_eq!(parse_passports(EXAMPLE_INPUT).unwrap(), example_passports());
+        let schema = passport_schema();
+        let passports = schema.parse_records(EXAMPLE_INPUT).unwrap();
+        assert_eq!(passports.len(), 4);
+        assert_eq!(passports[0].get("ecl"), Some("gry"));
+        assert_eq!(passports[0].get("hgt"), Some("183cm"));
+        assert_eq!(passports[1].get("cid"), Some("350"));
     }
 
     #[test]
     fn complete_passports() {
+        let schema = passport_schema();
+        let passports = schema.parse_records(EXAMPLE_INPUT).unwrap();
         assert_equal(
-            example_passports().iter().map(is_passport_complete),
+            passports.iter().map(|p| schema.is_complete(p)),
             [true, false, true, false],
         );
     }
 
     #[test]
     fn invalid_passports() {
-        let passports = parse_passports(
-            "eyr:1972 cid:100
+        let schema = passport_schema();
+        let passports = schema
+            .parse_records(
+                "eyr:1972 cid:100
 hcl:#18171d ecl:amb hgt:170 pid:186cm iyr:2018 byr:1926
 
 iyr:2019
@@ -184,18 +110,18 @@ ecl:brn hgt:182cm pid:021572410 eyr:2020 byr:1992 cid:277
 hgt:59cm ecl:zzz
 eyr:2038 hcl:74454a iyr:2023
 pid:3556412378 byr:2007",
-        )
-        .unwrap();
+            )
+            .unwrap();
 
-        passports
-            .iter()
-            .for_each(|p| assert!(!is_passport_valid(p)));
+        passports.iter().for_each(|p| assert!(!schema.is_valid(p)));
     }
 
     #[test]
     fn valid_passports() {
-        let passports = parse_passports(
-            "pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980
+        let schema = passport_schema();
+        let passports = schema
+            .parse_records(
+                "pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980
 hcl:#623a2f
 
 eyr:2029 ecl:blu cid:129 byr:1989
@@ -207,46 +133,9 @@ pid:545766238 ecl:hzl
 eyr:2022
 
 iyr:2010 hgt:158cm hcl:#b6652a ecl:blu byr:1944 eyr:2021 pid:093154719",
-        )
-        .unwrap();
-
-        passports.iter().for_each(|p| assert!(is_passport_valid(p)));
-    }
-
-    #[test]
-    fn validate_birth_year() {
-        assert!(is_year_valid("2002", 1920..=2002));
-        assert!(!is_year_valid("2003", 1920..=2002));
-    }
-
-    #[test]
-    fn validate_height() {
-        assert!(is_height_valid("60in"));
-        assert!(is_height_valid("190cm"));
-        assert!(!is_height_valid("190in"));
-        assert!(!is_height_valid("190"));
-    }
-
-    #[test]
-    fn validate_hair_color() {
-        assert!(is_hair_color_valid("#123abc"));
-        assert!(!is_hair_color_valid("#123abz"));
-        assert!(!is_hair_color_valid("123abc"));
-    }
+            )
+            .unwrap();
 
-    #[test]
-    fn validate_eye_color() {
-        assert!(is_eye_color_valid("brn"));
-        assert!(!is_eye_color_valid("wat"));
-    }
-
-    #[test]
-    fn validate_passport_id() {
-        assert!(is_passport_id_valid("000000001"));
-        assert!(is_passport_id_valid("012345678"));
-        assert!(is_passport_id_valid("123456789"));
-        assert!(!is_passport_id_valid("01234a678"));
-        assert!(!is_passport_id_valid("0123456789"));
-        assert!(!is_passport_id_valid("12345678"));
+        passports.iter().for_each(|p| assert!(schema.is_valid(p)));
     }
 }
@@ -1,40 +1,24 @@
-use anyhow::bail;
+use std::collections::HashSet;
+
 use aoc_companion::prelude::*;
 
 use aoc_utils::cache::cached;
-use itertools::Itertools;
-use regex::Regex;
-use std::collections::HashMap;
+use aoc_utils::hash::FastHashMap;
+use aoc_utils::parse::{self, separated_list, tag, two_words, unsigned_int};
 
 pub(crate) struct Door<'input> {
-    regulations: HashMap<Color<'input>, Vec<Requirement<'input>>>,
+    regulations: FastHashMap<Color<'input>, Vec<Requirement<'input>>>,
 }
 
 impl<'input> Solution<'input> for Door<'input> {
-    fn parse(input: &'input str) -> Result<Self> {
-        let outer_re =
-            Regex::new(r"([a-z]+ [a-z]+) bags contain ((?:\d+ [a-z]+ [a-z]+ bags?(?:, )?)+)\.")
-                .unwrap();
-
-        let regulations = outer_re
-            .captures_iter(input)
-            .map(|cap| cap.extract())
-            .map(|(_, [color, requirements])| {
-                Ok::<_, anyhow::Error>((
-                    Color(color),
-                    requirements
-                        .split(", ")
-                        .map(Requirement::parse)
-                        .try_collect()?,
-                ))
-            })
-            .try_collect()?;
-
-        Ok(Door { regulations })
+    fn parse(input: &'input str) -> Result<Self, ParseError> {
+        Ok(Door {
+            regulations: parse_input(input)?,
+        })
     }
 
     fn part1(&self) -> usize {
-        number_of_bags_containing_a_shiny_gold_bag(&self.regulations)
+        reachable_from(&reverse_graph(&self.regulations), SHINY_GOLD).len()
     }
 
     fn part2(&self) -> usize {
@@ -42,6 +26,14 @@ impl<'input> Solution<'input> for Door<'input> {
     }
 }
 
+#[derive(Debug, thiserror::Error)]
+#[error("failed to parse regulation line {line:?}: {source}")]
+pub(crate) struct ParseError {
+    line: String,
+    #[source]
+    source: parse::ParseError,
+}
+
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 struct Color<'input>(&'input str);
 
@@ -53,47 +45,92 @@ struct Requirement<'input> {
     color: Color<'input>,
 }
 
-impl<'input> Requirement<'input> {
-    fn parse(req: &'input str) -> Result<Requirement<'input>> {
-        let inner_re = Regex::new(r"^(\d+) ([a-z]+ [a-z]+) bags?$").unwrap();
-        let Some((_, [quantity, color])) = inner_re.captures(req).map(|cap| cap.extract()) else {
-            bail!("requirement regex didn't match");
-        };
+fn color(input: &str) -> parse::PResult<'_, Color<'_>> {
+    let (rest, name) = two_words(input)?;
+    Ok((rest, Color(name)))
+}
 
-        Ok(Requirement {
-            quantity: quantity.parse()?,
-            color: Color(color),
-        })
+/// Parses `"bag"` or `"bags"`, discarding the optional plural `s`.
+fn bag_word(input: &str) -> parse::PResult<'_, ()> {
+    let (rest, ()) = tag("bag")(input)?;
+    Ok((rest.strip_prefix('s').unwrap_or(rest), ()))
+}
+
+fn requirement(input: &str) -> parse::PResult<'_, Requirement<'_>> {
+    let (rest, quantity) = unsigned_int(input)?;
+    let (rest, ()) = tag(" ")(rest)?;
+    let (rest, color) = color(rest)?;
+    let (rest, ()) = tag(" ")(rest)?;
+    let (rest, ()) = bag_word(rest)?;
+    Ok((rest, Requirement { quantity, color }))
+}
+
+fn contents(input: &str) -> parse::PResult<'_, Vec<Requirement<'_>>> {
+    if let Ok((rest, ())) = tag("no other bags")(input) {
+        Ok((rest, Vec::new()))
+    } else {
+        separated_list(requirement, tag(", "))(input)
     }
 }
 
-fn contains_shiny_gold_bag<'input>(
-    regulations: &HashMap<Color<'input>, impl AsRef<[Requirement<'input>]>>,
-    bag: Color<'input>,
-) -> bool {
-    regulations.get(&bag).is_some_and(|this_bags_requirements| {
-        this_bags_requirements
-            .as_ref()
-            .iter()
-            .any(|r| r.color == SHINY_GOLD)
-            || this_bags_requirements
-                .as_ref()
-                .iter()
-                .any(|r| contains_shiny_gold_bag(regulations, r.color))
-    })
+fn regulation(input: &str) -> parse::PResult<'_, (Color<'_>, Vec<Requirement<'_>>)> {
+    let (rest, outer_color) = color(input)?;
+    let (rest, ()) = tag(" bags contain ")(rest)?;
+    let (rest, requirements) = contents(rest)?;
+    let (rest, ()) = tag(".")(rest)?;
+    Ok((rest, (outer_color, requirements)))
 }
 
-fn number_of_bags_containing_a_shiny_gold_bag<'input>(
-    regulations: &HashMap<Color<'input>, impl AsRef<[Requirement<'input>]>>,
-) -> usize {
-    regulations
-        .keys()
-        .filter(|&&c| contains_shiny_gold_bag(regulations, c))
-        .count()
+fn parse_input(input: &str) -> Result<FastHashMap<Color<'_>, Vec<Requirement<'_>>>, ParseError> {
+    input
+        .lines()
+        .map(|line| {
+            parse::finish(line, regulation).map_err(|source| ParseError {
+                line: line.to_owned(),
+                source,
+            })
+        })
+        .collect()
+}
+
+/// Inverts `regulations` into, for each inner color, the outer colors that
+/// directly list it as a requirement. Answering "what can eventually
+/// contain `bag`" is then a single BFS/DFS over these reversed edges
+/// instead of re-walking the containment tree downward from every color.
+fn reverse_graph<'input>(
+    regulations: &FastHashMap<Color<'input>, impl AsRef<[Requirement<'input>]>>,
+) -> FastHashMap<Color<'input>, Vec<Color<'input>>> {
+    let mut reverse: FastHashMap<Color, Vec<Color>> = FastHashMap::default();
+    for (&outer, requirements) in regulations {
+        for requirement in requirements.as_ref() {
+            reverse.entry(requirement.color).or_default().push(outer);
+        }
+    }
+    reverse
+}
+
+/// Every color that can eventually contain `start`, found by walking
+/// `reverse` (as built by [`reverse_graph`]) outward from `start`. A
+/// visited set makes this safe against cycles, which the forward
+/// containment tree has no protection against.
+fn reachable_from<'input>(
+    reverse: &FastHashMap<Color<'input>, Vec<Color<'input>>>,
+    start: Color<'input>,
+) -> HashSet<Color<'input>> {
+    let mut visited = HashSet::new();
+    let mut stack = vec![start];
+    while let Some(bag) = stack.pop() {
+        for &outer in reverse.get(&bag).into_iter().flatten() {
+            if visited.insert(outer) {
+                stack.push(outer);
+            }
+        }
+    }
+    visited
 }
 
 fn number_of_bags_in_total<'input>(
-    regulations: &HashMap<Color<'input>, impl AsRef<[Requirement<'input>]>>,
+    regulations: &FastHashMap<Color<'input>, impl AsRef<[Requirement<'input>]>>,
     bag: Color<'input>,
 ) -> usize {
     let mut cached_func = cached(move |bag, recurse| {
@@ -222,46 +259,69 @@ dotted black bags contain no other bags.";
         let Door { regulations } = Door::parse(EXAMPLE_INPUT).unwrap();
         assert_eq!(
             regulations,
-            HashMap::from(EXAMPLE_REGULATIONS.map(|(color, req)| (color, req.to_vec())))
+            FastHashMap::from(EXAMPLE_REGULATIONS.map(|(color, req)| (color, req.to_vec())))
         );
     }
 
     #[test]
-    fn bright_white_bag_contains_shiny_gold_bag() {
-        assert!(contains_shiny_gold_bag(
-            &HashMap::from(EXAMPLE_REGULATIONS),
-            Color("bright white")
-        ));
+    fn malformed_line_is_reported_with_its_offset() {
+        let err = parse_input("light red bags contain 1 bright white bog.").unwrap_err();
+        assert_eq!(err.source.offset, 23);
+    }
+
+    #[test]
+    fn bright_white_bag_can_eventually_contain_shiny_gold_bag() {
+        let reverse = reverse_graph(&FastHashMap::from(EXAMPLE_REGULATIONS));
+        assert!(reachable_from(&reverse, SHINY_GOLD).contains(&Color("bright white")));
+    }
+
+    #[test]
+    fn dark_orange_bag_can_eventually_contain_shiny_gold_bag() {
+        let reverse = reverse_graph(&FastHashMap::from(EXAMPLE_REGULATIONS));
+        assert!(reachable_from(&reverse, SHINY_GOLD).contains(&Color("dark orange")));
     }
 
     #[test]
-    fn dark_orange_bag_contains_shiny_gold_bag() {
-        assert!(contains_shiny_gold_bag(
-            &HashMap::from(EXAMPLE_REGULATIONS),
-            Color("dark orange")
-        ));
+    fn faded_blue_bag_cannot_eventually_contain_shiny_gold_bag() {
+        let reverse = reverse_graph(&FastHashMap::from(EXAMPLE_REGULATIONS));
+        assert!(!reachable_from(&reverse, SHINY_GOLD).contains(&Color("faded blue")));
     }
 
     #[test]
-    fn faded_blue_bag_does_not_contain_shiny_gold_bag() {
-        assert!(!contains_shiny_gold_bag(
-            &HashMap::from(EXAMPLE_REGULATIONS),
-            Color("faded blue")
-        ));
+    fn number_of_bags_eventually_containing_a_shiny_gold_bag() {
+        let reverse = reverse_graph(&FastHashMap::from(EXAMPLE_REGULATIONS));
+        assert_eq!(reachable_from(&reverse, SHINY_GOLD).len(), 4);
     }
 
     #[test]
-    fn number_of_bags_containing_a_shiny_gold_bag() {
+    fn reachable_from_terminates_on_a_cycle() {
+        let regulations = FastHashMap::from([
+            (
+                Color("shiny gold"),
+                vec![Requirement {
+                    quantity: 1,
+                    color: Color("looping"),
+                }],
+            ),
+            (
+                Color("looping"),
+                vec![Requirement {
+                    quantity: 1,
+                    color: Color("shiny gold"),
+                }],
+            ),
+        ]);
+        let reverse = reverse_graph(&regulations);
         assert_eq!(
-            super::number_of_bags_containing_a_shiny_gold_bag(&HashMap::from(EXAMPLE_REGULATIONS)),
-            4
+            reachable_from(&reverse, SHINY_GOLD),
+            HashSet::from([Color("looping"), SHINY_GOLD])
         );
     }
 
     #[test]
     fn number_of_bags_in_total() {
         assert_eq!(
-            super::number_of_bags_in_total(&HashMap::from(EXAMPLE_REGULATIONS), SHINY_GOLD),
+            super::number_of_bags_in_total(&FastHashMap::from(EXAMPLE_REGULATIONS), SHINY_GOLD),
             33
         );
     }
@@ -24,31 +24,91 @@ impl<'input> Solution<'input> for Door {
     }
 }
 
+/// Whether `sorted` holds two distinct-valued numbers summing to `target`,
+/// via the classic two-pointer sweep from both ends of a sorted slice: O(n)
+/// and allocation-free, versus the O(n²) of pairing every element against a
+/// linear `contains` scan.
+fn has_pair_summing_to(sorted: &[i64], target: i64) -> bool {
+    if sorted.is_empty() {
+        return false;
+    }
+    let mut lo = 0;
+    let mut hi = sorted.len() - 1;
+    while lo < hi {
+        let sum = sorted[lo] + sorted[hi];
+        match sum.cmp(&target) {
+            std::cmp::Ordering::Equal => return sorted[lo] != sorted[hi],
+            std::cmp::Ordering::Less => lo += 1,
+            std::cmp::Ordering::Greater => hi -= 1,
+        }
+    }
+    false
+}
+
 fn is_sum_of_two_numbers(x: i64, nums: &[i64]) -> bool {
-    // x=6
-    // nums=[2,3,7,3,4,-1]
-    nums.iter().any(|&lhs| {
-        let rhs = x - lhs;
-        lhs != rhs && nums.contains(&rhs)
-    })
+    let mut sorted = nums.to_vec();
+    sorted.sort_unstable();
+    has_pair_summing_to(&sorted, x)
 }
 
+/// Slides a window of `n` preceding numbers (kept sorted, so each step's
+/// pair check is a two-pointer sweep rather than a linear `contains` scan)
+/// across `nums`, looking for the first one that isn't the sum of two of
+/// its predecessors. O(n·window) overall, versus the O(n·window²) of
+/// re-scanning each raw window from scratch.
 fn find_first_offending_number(nums: &[i64], n: usize) -> Result<i64> {
-    nums.windows(n + 1)
-        .find_map(|window| {
-            let (&elem, predecessors) = window.split_last().unwrap();
-            (!is_sum_of_two_numbers(elem, predecessors)).then_some(elem)
-        })
-        .ok_or_else(|| {
-            anyhow!("did not find a number that isn't the sum of two of the preceding {n} numbers")
-        })
+    let Some(preamble) = nums.get(..n) else {
+        return Err(anyhow!(
+            "did not find a number that isn't the sum of two of the preceding {n} numbers"
+        ));
+    };
+    let mut window: Vec<i64> = preamble.to_vec();
+    window.sort_unstable();
+
+    for i in n..nums.len() {
+        let candidate = nums[i];
+        if !has_pair_summing_to(&window, candidate) {
+            return Ok(candidate);
+        }
+
+        let leaving = nums[i - n];
+        let leaving_at = window
+            .binary_search(&leaving)
+            .expect("number leaving the window should still be in it");
+        window.remove(leaving_at);
+        let insert_at = window.binary_search(&candidate).unwrap_or_else(|at| at);
+        window.insert(insert_at, candidate);
+    }
+
+    Err(anyhow!(
+        "did not find a number that isn't the sum of two of the preceding {n} numbers"
+    ))
 }
 
+/// Finds the contiguous range of `source` summing to `sum` via a two-pointer
+/// sliding window: since every input number is positive, growing the window
+/// (advancing `hi`) only ever increases the running sum and shrinking it
+/// (advancing `lo`) only ever decreases it, so `lo`/`hi` each sweep the
+/// slice at most once. O(n) and O(1) extra space, versus re-summing every
+/// window of every size from scratch.
 fn find_slice_with_sum(source: &[i64], sum: i64) -> Result<&[i64]> {
-    (2..source.len())
-        .flat_map(|window_size| source.windows(window_size))
-        .find(|window| window.iter().sum::<i64>() == sum)
-        .ok_or_else(|| anyhow!("did not find a contiguous range"))
+    let (mut lo, mut hi) = (0, 0);
+    let mut running_sum = 0;
+    while hi <= source.len() {
+        if running_sum == sum && hi - lo >= 2 {
+            return Ok(&source[lo..hi]);
+        } else if running_sum <= sum {
+            let Some(&next) = source.get(hi) else {
+                break;
+            };
+            running_sum += next;
+            hi += 1;
+        } else {
+            running_sum -= source[lo];
+            lo += 1;
+        }
+    }
+    Err(anyhow!("did not find a contiguous range"))
 }
 
 fn sum_of_min_and_max(nums: &[i64]) -> Option<i64> {
@@ -1,7 +1,11 @@
 use std::{array, collections::HashSet, ops::RangeInclusive};
 
 use aoc_companion::prelude::*;
-use aoc_utils::{geometry::Point, linalg::Vector};
+use aoc_utils::{
+    automaton::{CellularAutomaton, Dimension, Neighborhood},
+    geometry::Point,
+    linalg::Vector,
+};
 use itertools::Itertools;
 
 pub(crate) struct Door {
@@ -24,7 +28,9 @@ impl<'input> Solution<'input> for Door {
     }
 
     fn part1(&self) -> usize {
-        fixed_point_occupancy(&self.seats, &DirectNeighborSeatPolicy).len()
+        build_direct_automaton(&self.seats)
+            .run_to_fixed_point(direct_neighbor_transition)
+            .count(|&seat| seat == Seat::Occupied)
     }
 
     fn part2(&self) -> usize {
@@ -32,21 +38,53 @@ impl<'input> Solution<'input> for Door {
     }
 }
 
-trait SeatPolicy {
-    const THRESHOLD: usize;
-    fn neighbors(&self, seat: Vector<isize, 2>) -> impl Iterator<Item = Vector<isize, 2>>;
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Seat {
+    Floor,
+    Empty,
+    Occupied,
 }
 
-struct DirectNeighborSeatPolicy;
-
-impl SeatPolicy for DirectNeighborSeatPolicy {
-    const THRESHOLD: usize = 4;
+fn build_direct_automaton(seats: &HashSet<Vector<isize, 2>>) -> CellularAutomaton<2, Seat> {
+    let rows = seats.iter().map(|s| s[0]).max().unwrap_or(0) + 1;
+    let cols = seats.iter().map(|s| s[1]).max().unwrap_or(0) + 1;
+    seats
+        .iter()
+        .fold(
+            CellularAutomaton::new(
+                [Dimension::new(rows as u32), Dimension::new(cols as u32)],
+                Seat::Floor,
+                Neighborhood::Moore,
+            ),
+            |mut automaton, &seat| {
+                automaton.set(Vector([seat[0] as i32, seat[1] as i32]), Seat::Empty);
+                automaton
+            },
+        )
+        .without_expansion()
+}
 
-    fn neighbors(&self, seat: Vector<isize, 2>) -> impl Iterator<Item = Vector<isize, 2>> {
-        seat.neighbors()
+fn direct_neighbor_transition(current: &Seat, neighbors: &[Seat]) -> Seat {
+    match current {
+        Seat::Floor => Seat::Floor,
+        Seat::Empty if !neighbors.contains(&Seat::Occupied) => Seat::Occupied,
+        Seat::Occupied if neighbors.iter().filter(|&&n| n == Seat::Occupied).count() >= 4 => {
+            Seat::Empty
+        }
+        &other => other,
     }
 }
 
+/// Day 11's sightline seats don't fit [`CellularAutomaton`]'s
+/// fixed-neighborhood-offset model: a sightline neighbor sits an arbitrary,
+/// direction-dependent distance away rather than one cell over, so Part 2
+/// keeps this bespoke [`SeatPolicy`]/[`evolve`] machinery instead of
+/// migrating onto the engine [`Door::part1`] uses.
+trait SeatPolicy {
+    const THRESHOLD: usize;
+    fn neighbors(&self, seat: Vector<isize, 2>) -> impl Iterator<Item = Vector<isize, 2>>;
+}
+
 struct SightlineSeatPolicy<'s> {
     seats: &'s HashSet<Vector<isize, 2>>,
     bounds: [RangeInclusive<isize>; 2],
@@ -204,35 +242,27 @@ L.LLLLL.LL";
     #[test]
     fn all_seats_occupied_after_first_round() {
         let seats = HashSet::from(EXAMPLE_SEATS);
-        assert_eq!(
-            evolve(&HashSet::new(), &seats, &DirectNeighborSeatPolicy),
-            seats
-        );
+        let automaton = build_direct_automaton(&seats).step(direct_neighbor_transition);
+        assert_eq!(automaton.count(|&seat| seat == Seat::Occupied), seats.len());
     }
 
     #[test]
     fn number_of_occupied_seats_evolves() {
         let seats = HashSet::from(EXAMPLE_SEATS);
-        let mut occupied = HashSet::new();
-        occupied = evolve(&occupied, &seats, &DirectNeighborSeatPolicy);
-        assert_eq!(occupied.len(), EXAMPLE_SEATS.len());
-        occupied = evolve(&occupied, &seats, &DirectNeighborSeatPolicy);
-        assert_eq!(occupied.len(), 20);
-        occupied = evolve(&occupied, &seats, &DirectNeighborSeatPolicy);
-        assert_eq!(occupied.len(), 51);
-        occupied = evolve(&occupied, &seats, &DirectNeighborSeatPolicy);
-        assert_eq!(occupied.len(), 30);
-        occupied = evolve(&occupied, &seats, &DirectNeighborSeatPolicy);
-        assert_eq!(occupied.len(), 37);
-        occupied = evolve(&occupied, &seats, &DirectNeighborSeatPolicy);
-        assert_eq!(occupied.len(), 37);
+        let mut automaton = build_direct_automaton(&seats);
+        for expected in [EXAMPLE_SEATS.len(), 20, 51, 30, 37, 37] {
+            automaton = automaton.step(direct_neighbor_transition);
+            assert_eq!(automaton.count(|&seat| seat == Seat::Occupied), expected);
+        }
     }
 
     #[test]
     fn find_fixed_point_occupancy() {
         let seats = HashSet::from(EXAMPLE_SEATS);
         assert_eq!(
-            fixed_point_occupancy(&seats, &DirectNeighborSeatPolicy).len(),
+            build_direct_automaton(&seats)
+                .run_to_fixed_point(direct_neighbor_transition)
+                .count(|&seat| seat == Seat::Occupied),
             37
         );
         assert_eq!(
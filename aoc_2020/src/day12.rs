@@ -1,5 +1,4 @@
 use aoc_companion::prelude::*;
-use aoc_utils::linalg::Vector;
 use itertools::Itertools;
 
 pub(crate) struct Door {
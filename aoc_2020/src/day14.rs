@@ -1,7 +1,7 @@
 use std::{collections::HashMap, fmt::Write};
 
-use anyhow::Context as _;
 use aoc_companion::prelude::*;
+use aoc_utils::parse;
 use itertools::Itertools as _;
 
 pub(crate) struct Door {
@@ -30,28 +30,38 @@ enum Instruction {
     Write(MemWrite),
 }
 
+enum InstructionShape<'a> {
+    SetMask(&'a str),
+    Write { addr: u64, value: u64 },
+}
+
+fn assignment(input: &str) -> parse::PResult<'_, ()> {
+    let (rest, ()) = parse::ws(input)?;
+    let (rest, ()) = parse::tag("=")(rest)?;
+    parse::ws(rest)
+}
+
+fn instruction_shape(input: &str) -> parse::PResult<'_, InstructionShape<'_>> {
+    if let Ok((rest, ())) = parse::tag("mask")(input) {
+        let (rest, ()) = assignment(rest)?;
+        return Ok(("", InstructionShape::SetMask(rest)));
+    }
+
+    let (rest, ()) = parse::tag("mem[")(input)?;
+    let (rest, addr) = parse::unsigned_int(rest)?;
+    let (rest, ()) = parse::tag("]")(rest)?;
+    let (rest, ()) = assignment(rest)?;
+    let (rest, value) = parse::unsigned_int(rest)?;
+    Ok((rest, InstructionShape::Write { addr, value }))
+}
+
 impl std::str::FromStr for Instruction {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self> {
-        let Some((lhs, rhs)) = s.split_once('=') else {
-            anyhow::bail!("instruction does not contain assignment operator '='");
-        };
-        let lhs = lhs.trim_end();
-        let rhs = rhs.trim_start();
-        let (introducer, rest) = lhs.split_at_checked(4).unwrap_or((lhs, ""));
-
-        Ok(match introducer {
-            "mask" => Instruction::SetMask(rhs.parse()?),
-            "mem[" => Instruction::Write(MemWrite {
-                addr: rest
-                    .strip_suffix(']')
-                    .with_context(|| "missing closing bracket")?
-                    .parse()
-                    .with_context(|| "invalid memory address")?,
-                value: rhs.parse().with_context(|| "invalid value to write")?,
-            }),
-            _ => anyhow::bail!("illegal instruction, introduced by {introducer:?}"),
+        Ok(match parse::finish(s, instruction_shape)? {
+            InstructionShape::SetMask(mask) => Instruction::SetMask(mask.parse()?),
+            InstructionShape::Write { addr, value } => Instruction::Write(MemWrite { addr, value }),
         })
     }
 }
@@ -146,6 +156,110 @@ impl ApplyMask for DecoderV2 {
     }
 }
 
+/// A masked range of addresses: every bit `floating` doesn't claim is
+/// pinned to its value in `base` (a floating bit reads as `0` in `base`,
+/// since it's never actually consulted). Stands in for the `2^k` concrete
+/// addresses `DecoderV2::apply` would otherwise enumerate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct MaskSet {
+    base: u64,
+    floating: u64,
+}
+
+impl MaskSet {
+    fn from_mask_and_addr(mask: Mask, addr: u64) -> Self {
+        let fixed_ones = mask
+            .bits()
+            .filter_map(|(b, i)| (b == Some(true)).then_some(i))
+            .fold(0, std::ops::BitOr::bitor);
+        let floating = mask
+            .bits()
+            .filter_map(|(b, i)| b.is_none().then_some(i))
+            .fold(0, std::ops::BitOr::bitor);
+        MaskSet {
+            base: (addr | fixed_ones) & !floating,
+            floating,
+        }
+    }
+
+    /// True iff some address is covered by both sets: wherever neither set
+    /// is floating, both must already agree on the bit they pin down.
+    fn overlaps(&self, other: &MaskSet) -> bool {
+        let both_fixed = !(self.floating | other.floating);
+        (self.base ^ other.base) & both_fixed == 0
+    }
+
+    /// Carves the portion of `self` that `subtrahend` also covers back out
+    /// of `self`, returning the (possibly empty) list of sets that together
+    /// cover exactly what's left. Only meaningful when the two overlap.
+    fn minus(&self, subtrahend: &MaskSet) -> Vec<MaskSet> {
+        let mut pieces = Vec::new();
+        let mut already_matched = 0u64;
+
+        for bit in (0..36).map(|i| 1u64 << i) {
+            let narrows_here = self.floating & bit != 0 && subtrahend.floating & bit == 0;
+            if !narrows_here {
+                continue;
+            }
+
+            let opposite = !subtrahend.base & bit;
+            pieces.push(MaskSet {
+                base: (self.base & !already_matched & !bit)
+                    | (subtrahend.base & already_matched)
+                    | opposite,
+                floating: self.floating & !already_matched & !bit,
+            });
+            already_matched |= bit;
+        }
+
+        pieces
+    }
+
+    fn addr_count(&self) -> u64 {
+        1 << self.floating.count_ones()
+    }
+}
+
+/// An alternate backend for `DecoderV2`'s "floating bit" rule that tracks
+/// each write as a [`MaskSet`] rather than enumerating its addresses:
+/// inserting a later write splits away the portion of any earlier set it
+/// overwrites, so the total falls out of a sum over surviving sets instead
+/// of a `HashMap` dedup over `2^k` concrete addresses.
+struct MaskSetDecoder;
+
+impl MaskSetDecoder {
+    fn total_memory(program: impl IntoIterator<Item = Instruction>) -> u64 {
+        let mut writes: Vec<(MaskSet, u64)> = Vec::new();
+        let mut mask = Mask::default();
+
+        for instr in program {
+            match instr {
+                Instruction::SetMask(new_mask) => mask = new_mask,
+                Instruction::Write(MemWrite { addr, value }) => {
+                    let new_set = MaskSet::from_mask_and_addr(mask, addr);
+                    writes = writes
+                        .into_iter()
+                        .flat_map(|(set, value)| {
+                            let pieces = if set.overlaps(&new_set) {
+                                set.minus(&new_set)
+                            } else {
+                                vec![set]
+                            };
+                            pieces.into_iter().map(move |set| (set, value))
+                        })
+                        .collect();
+                    writes.push((new_set, value));
+                }
+            }
+        }
+
+        writes
+            .into_iter()
+            .map(|(set, value)| value * set.addr_count())
+            .sum()
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct MemWrite {
     addr: u64,
@@ -343,4 +457,30 @@ mem[26] = 1",
         .unwrap();
         assert_eq!(total_memory(generate_writes::<DecoderV2>(program)), 208);
     }
+
+    #[test]
+    fn mask_set_minus_carves_out_the_overlapping_region() {
+        let whole = MaskSet {
+            base: 0,
+            floating: 0b11,
+        };
+        let single_address = MaskSet {
+            base: 0b01,
+            floating: 0,
+        };
+        let remainder = whole.minus(&single_address);
+        assert_eq!(remainder.iter().map(MaskSet::addr_count).sum::<u64>(), 3);
+    }
+
+    #[test]
+    fn mask_set_total_matches_brute_force_for_example_v2() {
+        let Door { program } = Door::parse(
+            "mask = 000000000000000000000000000000X1001X
+mem[42] = 100
+mask = 00000000000000000000000000000000X0XX
+mem[26] = 1",
+        )
+        .unwrap();
+        assert_eq!(MaskSetDecoder::total_memory(program), 208);
+    }
 }
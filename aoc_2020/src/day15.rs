@@ -23,20 +23,71 @@ impl<'input> Solution<'input> for Door {
     }
 
     fn part2(&self) -> usize {
-        Game::from(self.starting_numbers.iter().copied())
+        Game::with_capacity(self.starting_numbers.iter().copied(), 29_999_999)
             .nth(29_999_999)
             .unwrap()
     }
 }
 
-struct Game<I: Iterator<Item = usize>> {
+/// Tracks the turn a value was last spoken, abstracting over the backing
+/// storage so `Game` can pick a `HashMap` (works for any value) or a flat,
+/// pre-sized `Vec` (much faster when every spoken value is turn-bounded).
+trait LastSeen {
+    fn last_seen(&self, value: usize) -> Option<usize>;
+    fn record(&mut self, value: usize, turn: usize);
+}
+
+impl LastSeen for HashMap<usize, usize> {
+    fn last_seen(&self, value: usize) -> Option<usize> {
+        self.get(&value).copied()
+    }
+
+    fn record(&mut self, value: usize, turn: usize) {
+        self.insert(value, turn);
+    }
+}
+
+/// Flat-array `LastSeen`, indexed directly by spoken value with `0` as the
+/// "never seen" sentinel (valid since turns are numbered starting from 1).
+/// Trades the generality of `HashMap` for far better cache behavior over
+/// tens of millions of turns.
+struct FlatLastSeen(Vec<u32>);
+
+impl FlatLastSeen {
+    fn with_capacity(upper_bound: usize) -> Self {
+        FlatLastSeen(vec![0; upper_bound + 1])
+    }
+
+    fn ensure_capacity(&mut self, value: usize) {
+        if value >= self.0.len() {
+            self.0.resize(value + 1, 0);
+        }
+    }
+}
+
+impl LastSeen for FlatLastSeen {
+    fn last_seen(&self, value: usize) -> Option<usize> {
+        self.0
+            .get(value)
+            .copied()
+            .filter(|&turn| turn != 0)
+            .map(|turn| turn as usize)
+    }
+
+    fn record(&mut self, value: usize, turn: usize) {
+        self.ensure_capacity(value);
+        self.0[value] = turn as u32;
+    }
+}
+
+struct Game<I: Iterator<Item = usize>, S> {
     starting_numbers: I,
     next: Option<usize>,
     n: usize,
-    last_seen: HashMap<usize, usize>,
+    last_seen: S,
 }
 
-impl<I: Iterator<Item = usize>> Game<I> {
+impl<I: Iterator<Item = usize>> Game<I, HashMap<usize, usize>> {
     fn from(starting_numbers: impl IntoIterator<Item = usize, IntoIter = I>) -> Self {
         Self {
             starting_numbers: starting_numbers.into_iter(),
@@ -47,17 +98,35 @@ impl<I: Iterator<Item = usize>> Game<I> {
     }
 }
 
-impl<I: Iterator<Item = usize>> Iterator for Game<I> {
+impl<I: Iterator<Item = usize>> Game<I, FlatLastSeen> {
+    /// Builds a `Game` backed by a flat `Vec`, pre-sized to `upper_bound`
+    /// (the highest turn index that will be requested, since no spoken
+    /// number can exceed it).
+    fn with_capacity(
+        starting_numbers: impl IntoIterator<Item = usize, IntoIter = I>,
+        upper_bound: usize,
+    ) -> Self {
+        Self {
+            starting_numbers: starting_numbers.into_iter(),
+            next: None,
+            n: 0,
+            last_seen: FlatLastSeen::with_capacity(upper_bound),
+        }
+    }
+}
+
+impl<I: Iterator<Item = usize>, S: LastSeen> Iterator for Game<I, S> {
     type Item = usize;
 
     fn next(&mut self) -> Option<Self::Item> {
         let current = self.starting_numbers.next().or_else(|| self.next.take())?;
         self.n += 1;
-        if let Some(before) = self.last_seen.insert(current, self.n) {
+        if let Some(before) = self.last_seen.last_seen(current) {
             self.next = Some(self.n - before);
         } else {
             self.next = Some(0);
         }
+        self.last_seen.record(current, self.n);
         Some(current)
     }
 }
@@ -85,6 +154,16 @@ mod tests {
         assert_eq!(Game::from([3, 1, 2]).nth(2019), Some(1836));
     }
 
+    #[test]
+    fn flat_backend_agrees_with_hash_backend() {
+        for starting_numbers in [[0, 3, 6], [1, 3, 2], [2, 1, 3], [1, 2, 3], [2, 3, 1]] {
+            assert_eq!(
+                Game::with_capacity(starting_numbers, 2019).nth(2019),
+                Game::from(starting_numbers).nth(2019),
+            );
+        }
+    }
+
     #[test]
     #[ignore = "slow"]
     fn correct_30_000_000th_number() {
@@ -1,7 +1,8 @@
-use std::{collections::HashSet, ops::RangeInclusive};
+use std::collections::HashSet;
 
-use anyhow::{Context, anyhow, bail};
+use anyhow::{anyhow, bail, Context};
 use aoc_companion::prelude::*;
+use aoc_utils::range::RangeSet;
 use itertools::Itertools as _;
 
 pub(crate) struct Door<'input> {
@@ -78,7 +79,7 @@ impl Door<'_> {
 #[derive(Clone, Debug, PartialEq, Eq)]
 struct Rule<'input> {
     field_name: &'input str,
-    domain: [RangeInclusive<u64>; 2],
+    domain: RangeSet<u64>,
 }
 
 fn parse_rule<'input>(s: &'input str) -> Result<Rule<'input>> {
@@ -87,22 +88,22 @@ fn parse_rule<'input>(s: &'input str) -> Result<Rule<'input>> {
     };
     Ok(Rule {
         field_name,
-        domain: aoc_utils::array::try_from_iter_exact(ranges.trim_start().split("or").map(|r| {
-            r.trim()
-                .split_once('-')
-                .ok_or_else(|| anyhow!("missing dash in range {r:?}"))
-                .and_then(|(from, to)| Ok(from.parse()?..=to.parse()?))
-                .with_context(|| anyhow!("failed to parse range {r:?}"))
-        }))?
-        .map_err(|v| anyhow!("expected exactly two ranges, got {}", v.len()))?,
+        domain: ranges
+            .trim_start()
+            .split("or")
+            .map(|r| {
+                r.trim()
+                    .split_once('-')
+                    .ok_or_else(|| anyhow!("missing dash in range {r:?}"))
+                    .and_then(|(from, to)| Ok(from.parse()?..=to.parse()?))
+                    .with_context(|| anyhow!("failed to parse range {r:?}"))
+            })
+            .try_collect()?,
     })
 }
 
 fn is_valid_for_any_field(x: u64, rules: &[Rule]) -> bool {
-    rules
-        .iter()
-        .flat_map(|rule| rule.domain.iter())
-        .any(|r| r.contains(&x))
+    rules.iter().any(|rule| rule.domain.contains(&x))
 }
 
 fn find_field_mapping<'a>(
@@ -114,7 +115,7 @@ fn find_field_mapping<'a>(
     for ticket in tickets {
         for (i, field) in ticket.iter().enumerate() {
             for (rule_indices, rule) in possible_indices.iter_mut().zip(rules) {
-                if !rule.domain.iter().any(|r| r.contains(field)) {
+                if !rule.domain.contains(field) {
                     rule_indices.remove(&i);
                 }
             }
@@ -125,7 +126,7 @@ fn find_field_mapping<'a>(
     while let Some((i, s)) = possible_indices
         .iter()
         .enumerate()
-        .find(|(_, s)| s.len() == 1)
+        .find(|(i, s)| res[*i].is_none() && s.len() == 1)
     {
         let j = *s.iter().next().unwrap();
         res[i] = Some(j);
@@ -134,11 +135,43 @@ fn find_field_mapping<'a>(
         }
     }
 
+    for i in 0..rules.len() {
+        if res[i].is_none() {
+            let mut visited = HashSet::new();
+            if !augment(i, &possible_indices, &mut res, &mut visited) {
+                bail!("no valid field mapping exists");
+            }
+        }
+    }
+
     res.into_iter()
         .map(|o| o.ok_or_else(|| anyhow!("field mapping ambiguous")))
         .try_collect()
 }
 
+/// Kuhn's algorithm: looks for an augmenting path that lets rule `i` claim
+/// one of its candidate columns, recursively re-routing whichever rule
+/// currently holds a column onto a different one of its own candidates if
+/// that frees it up.
+fn augment(
+    i: usize,
+    possible_indices: &[HashSet<usize>],
+    res: &mut [Option<usize>],
+    visited: &mut HashSet<usize>,
+) -> bool {
+    for &j in &possible_indices[i] {
+        if !visited.insert(j) {
+            continue;
+        }
+        let displaced = res.iter().position(|&r| r == Some(j));
+        if displaced.is_none_or(|d| augment(d, possible_indices, res, visited)) {
+            res[i] = Some(j);
+            return true;
+        }
+    }
+    false
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -157,20 +190,22 @@ nearby tickets:
 55,2,20
 38,6,12";
 
-    const EXAMPLE_RULES: &[Rule] = &[
-        Rule {
-            field_name: "class",
-            domain: [1..=3, 5..=7],
-        },
-        Rule {
-            field_name: "row",
-            domain: [6..=11, 33..=44],
-        },
-        Rule {
-            field_name: "seat",
-            domain: [13..=40, 45..=50],
-        },
-    ];
+    fn example_rules() -> Vec<Rule<'static>> {
+        vec![
+            Rule {
+                field_name: "class",
+                domain: [1..=3, 5..=7].into_iter().collect(),
+            },
+            Rule {
+                field_name: "row",
+                domain: [6..=11, 33..=44].into_iter().collect(),
+            },
+            Rule {
+                field_name: "seat",
+                domain: [13..=40, 45..=50].into_iter().collect(),
+            },
+        ]
+    }
 
     const EXAMPLE_MY_TICKET: [u64; 3] = [7, 1, 14];
 
@@ -197,7 +232,7 @@ nearby tickets:
             my_ticket,
             nearby_tickets,
         } = Door::parse(EXAMPLE_INPUT).unwrap();
-        itertools::assert_equal(&rules, EXAMPLE_RULES);
+        itertools::assert_equal(&rules, &example_rules());
         assert_eq!(my_ticket, EXAMPLE_MY_TICKET);
         itertools::assert_equal(&nearby_tickets, EXAMPLE_NEARBY_TICKETS);
     }
@@ -208,7 +243,7 @@ nearby tickets:
             EXAMPLE_NEARBY_TICKETS
                 .iter()
                 .flatten()
-                .filter(|&&x| !is_valid_for_any_field(x, EXAMPLE_RULES)),
+                .filter(|&&x| !is_valid_for_any_field(x, &example_rules())),
             &[4, 55, 12],
         );
     }
@@ -29,33 +29,102 @@ impl<'input> Solution<'input> for Door {
     }
 }
 
-fn evolve<const N: usize>(active_cubes: &HashSet<Vector<i64, N>>) -> HashSet<Vector<i64, N>>
+// The initial configuration lives entirely on the z=0 (and w=0) hyperplane,
+// and the rule is invariant under negating any coordinate beyond x/y, so the
+// active set stays mirror-symmetric in those higher coordinates for every
+// generation. `evolve_canonical` and `active_after_boot` only ever store one
+// representative per mirror orbit (the one with non-negative higher
+// coordinates), which roughly halves the work per higher dimension; `evolve`
+// itself still takes and returns the full, un-folded set so it keeps
+// behaving like a plain cellular-automaton step for callers (and existing
+// tests) that don't care about the internal representation.
+
+/// The representative of `p`'s mirror orbit: `p` with every coordinate past
+/// x/y folded onto its non-negative side.
+fn canonical<const N: usize>(mut p: Vector<i64, N>) -> Vector<i64, N> {
+    for c in &mut p[2..] {
+        *c = c.abs();
+    }
+    p
+}
+
+/// How many real cells `p`'s orbit stands in for: one for each higher
+/// coordinate that's strictly positive (and thus has a distinct negative
+/// mirror image), doubling per such coordinate.
+fn orbit_multiplicity<const N: usize>(p: Vector<i64, N>) -> usize {
+    1 << p[2..].iter().filter(|&&c| c > 0).count()
+}
+
+/// Expands a canonical (folded) active set back into the full, real set of
+/// active cubes it represents.
+fn expand_from_canonical<const N: usize>(
+    canonical_cubes: &HashSet<Vector<i64, N>>,
+) -> HashSet<Vector<i64, N>> {
+    canonical_cubes
+        .iter()
+        .flat_map(|&p| {
+            let higher: Vec<i64> = p[2..].to_vec();
+            higher
+                .into_iter()
+                .map(|c| if c > 0 { vec![c, -c] } else { vec![c] })
+                .multi_cartesian_product()
+                .map(move |signs| {
+                    let mut q = p;
+                    q[2..].copy_from_slice(&signs);
+                    q
+                })
+        })
+        .collect()
+}
+
+/// `evolve`'s core step, operating entirely on canonical (folded)
+/// coordinates. A real neighbor's activity is looked up by canonicalizing it
+/// first; this is done per-neighbor-offset rather than per-axis, so a
+/// candidate cell sitting on a mirror plane still counts both of its real
+/// neighbors along that axis instead of only one.
+fn evolve_canonical<const N: usize>(active: &HashSet<Vector<i64, N>>) -> HashSet<Vector<i64, N>>
 where
     Vector<i64, N>: Point,
 {
-    let mut new_cubes: HashSet<_> = active_cubes
+    let active_neighbor_count = |p: Vector<i64, N>| {
+        p.neighbors()
+            .filter(|&n| active.contains(&canonical(n)))
+            .count()
+    };
+
+    let mut new_active: HashSet<_> = active
         .iter()
         .copied()
-        .filter(|p| (2..=3).contains(&p.neighbors().filter(|n| active_cubes.contains(n)).count()))
+        .filter(|&p| (2..=3).contains(&active_neighbor_count(p)))
         .collect();
-    new_cubes.extend(
-        active_cubes
+    new_active.extend(
+        active
             .iter()
-            .flat_map(|p| p.neighbors())
-            .filter(|p| !active_cubes.contains(p))
-            .filter(|p| p.neighbors().filter(|n| active_cubes.contains(n)).count() == 3),
+            .flat_map(|&p| p.neighbors())
+            .map(canonical)
+            .filter(|p| !active.contains(p))
+            .filter(|&p| active_neighbor_count(p) == 3),
     );
-    new_cubes
+    new_active
+}
+
+fn evolve<const N: usize>(active_cubes: &HashSet<Vector<i64, N>>) -> HashSet<Vector<i64, N>>
+where
+    Vector<i64, N>: Point,
+{
+    let canonical_active: HashSet<_> = active_cubes.iter().copied().map(canonical).collect();
+    expand_from_canonical(&evolve_canonical(&canonical_active))
 }
 
 fn active_after_boot<const N: usize>(active_cubes: HashSet<Vector<i64, N>>) -> usize
 where
     Vector<i64, N>: Point,
 {
-    let after_boot = std::iter::successors(Some(active_cubes), |cubes| Some(evolve(cubes)))
+    let initial = active_cubes.into_iter().map(canonical).collect();
+    let after_boot = std::iter::successors(Some(initial), |cubes| Some(evolve_canonical(cubes)))
         .nth(6)
         .unwrap();
-    after_boot.len()
+    after_boot.iter().copied().map(orbit_multiplicity).sum()
 }
 
 #[cfg(test)]
@@ -3,15 +3,22 @@ use aoc_companion::prelude::*;
 use aoc_utils::iter::IterUtils as _;
 use itertools::Itertools;
 
+use winnow::ascii::{digit1, multispace0};
+use winnow::combinator::{alt, delimited, eof, repeat, terminated};
+use winnow::error::{StrContext, StrContextValue};
+use winnow::prelude::*;
+use winnow::stream::{LocatingSlice, TokenSlice};
+use winnow::token::any;
+
 pub(crate) struct Door {
-    expressions: Vec<Vec<Token>>,
+    expressions: Vec<Vec<(Token, usize)>>,
 }
 
 impl<'input> Solution<'input> for Door {
     fn parse(input: &'input str) -> Result<Self> {
         input
             .lines()
-            .map(|line| tokenize(line).try_collect())
+            .map(tokenize)
             .try_collect()
             .map(|expressions| Door { expressions })
     }
@@ -19,7 +26,7 @@ impl<'input> Solution<'input> for Door {
     fn part1(&self) -> Result<u64> {
         self.expressions
             .iter()
-            .map(parse_part1)
+            .map(|tokens| parse_part1(tokens))
             .map_ok(|ast| ast.eval())
             .try_sum()
     }
@@ -27,7 +34,7 @@ impl<'input> Solution<'input> for Door {
     fn part2(&self) -> Result<u64> {
         self.expressions
             .iter()
-            .map(parse_part2)
+            .map(|tokens| parse_part2(tokens))
             .map_ok(|ast| ast.eval())
             .try_sum()
     }
@@ -42,21 +49,41 @@ enum Token {
     Number(u64),
 }
 
-fn tokenize(expr: &str) -> impl Iterator<Item = Result<Token>> {
-    expr.bytes()
-        .filter(|b| !b.is_ascii_whitespace())
-        .map(|b| match b {
-            b'(' => Ok(Token::LParen),
-            b')' => Ok(Token::RParen),
-            b'+' => Ok(Token::Plus),
-            b'*' => Ok(Token::Times),
-            b'0'..=b'9' => Ok(Token::Number((b - b'0') as u64)),
-            0..128 => Err(anyhow!(
-                "invalid token {:?}",
-                char::from_u32(b as u32).unwrap()
-            )),
-            _ => Err(anyhow!("invalid token: non-ASCII character")),
-        })
+/// Lexes a single token, spanned with its starting byte offset so a later
+/// structural error can point back at it. `digit1` (rather than a
+/// single-digit branch) is what lets real puzzle input numbers like `12` or
+/// `345` tokenize as one [`Token::Number`] instead of a run of single-digit
+/// ones.
+fn lex_token(input: &mut LocatingSlice<&str>) -> winnow::Result<(Token, usize)> {
+    alt((
+        '('.value(Token::LParen),
+        ')'.value(Token::RParen),
+        '+'.value(Token::Plus),
+        '*'.value(Token::Times),
+        digit1
+            .try_map(|s: &str| s.parse::<u64>())
+            .map(Token::Number),
+    ))
+    .with_span()
+    .map(|(token, span)| (token, span.start))
+    .context(StrContext::Label("token"))
+    .context(StrContext::Expected(StrContextValue::Description(
+        "'(', ')', '+', '*', or a number",
+    )))
+    .parse_next(input)
+}
+
+/// Tokenizes `line`, skipping whitespace between tokens. On failure, the
+/// [`winnow`]-reported error already carries the byte offset and a
+/// caret-annotated snippet of `line`, so it's surfaced as-is via `{err}`
+/// rather than being reduced to a bare description.
+fn tokenize(line: &str) -> Result<Vec<(Token, usize)>> {
+    terminated(
+        repeat(0.., delimited(multispace0, lex_token, multispace0)),
+        eof,
+    )
+    .parse(LocatingSlice::new(line))
+    .map_err(|err| anyhow!("{err}"))
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -76,67 +103,148 @@ impl Ast {
     }
 }
 
-fn parse_part1<'a, I: IntoIterator<Item = &'a Token>>(tokens: I) -> Result<Ast> {
-    fn do_parse<'a, I: Iterator<Item = &'a Token>>(tokens: &mut I) -> Result<Ast> {
-        let parse_operand = |tokens: &mut I| -> Result<Ast> {
-            match tokens.next() {
-                Some(Token::LParen) => do_parse(tokens),
-                Some(Token::Number(n)) => Ok(Ast::Number(*n)),
-                Some(Token::Plus) | Some(Token::Times) => {
-                    Err(anyhow!("expected a number or expression, got an operator"))
-                }
-                Some(Token::RParen) | None => Err(anyhow!("unexpected EOL or ')'")),
-            }
-        };
-        let mut ast = parse_operand(tokens)?;
-        loop {
-            ast = match tokens.next() {
-                Some(Token::Plus) => Ast::Add(Box::new(ast), Box::new(parse_operand(tokens)?)),
-                Some(Token::Times) => Ast::Mul(Box::new(ast), Box::new(parse_operand(tokens)?)),
-                Some(Token::RParen) | None => return Ok(ast),
-                _ => bail!("unexpected token"),
-            }
-        }
+type Tokens<'t> = TokenSlice<'t, (Token, usize)>;
+
+fn number(input: &mut Tokens<'_>) -> winnow::Result<()> {
+    any.verify(|(token, _): &(Token, usize)| matches!(token, Token::Number(_)))
+        .void()
+        .parse_next(input)
+}
+
+fn lparen(input: &mut Tokens<'_>) -> winnow::Result<()> {
+    any.verify(|(token, _): &(Token, usize)| *token == Token::LParen)
+        .void()
+        .parse_next(input)
+}
+
+fn rparen(input: &mut Tokens<'_>) -> winnow::Result<()> {
+    any.verify(|(token, _): &(Token, usize)| *token == Token::RParen)
+        .void()
+        .context(StrContext::Expected(StrContextValue::Description("')'")))
+        .parse_next(input)
+}
+
+fn binop(input: &mut Tokens<'_>) -> winnow::Result<()> {
+    any.verify(|(token, _): &(Token, usize)| matches!(token, Token::Plus | Token::Times))
+        .void()
+        .parse_next(input)
+}
+
+fn term(input: &mut Tokens<'_>) -> winnow::Result<()> {
+    alt((number, (lparen, expr, rparen).void())).parse_next(input)
+}
+
+fn expr(input: &mut Tokens<'_>) -> winnow::Result<()> {
+    (term, repeat(0.., (binop, term)).map(|_: ()| ()))
+        .void()
+        .parse_next(input)
+}
+
+fn describe(token: Token) -> &'static str {
+    match token {
+        Token::LParen => "'('",
+        Token::RParen => "')'",
+        Token::Plus => "'+'",
+        Token::Times => "'*'",
+        Token::Number(_) => "a number",
     }
-    do_parse(&mut tokens.into_iter())
 }
 
-fn parse_part2<'a, I: IntoIterator<Item = &'a Token>>(tokens: I) -> Result<Ast> {
-    fn do_parse<'a, I: Iterator<Item = &'a Token>>(tokens: &mut I) -> Result<Ast> {
-        enum Parens {
-            Yes(Ast),
-            No(Ast),
-        }
+/// Checks `tokens` against the grammar `expr := term ((+|*) term)*`,
+/// `term := number | '(' expr ')'`, which both parts share (they differ only
+/// in how [`shunting_yard`] weighs `+` against `*`, not in token structure).
+/// On failure, reports the offending token and the column (1-based byte
+/// offset) it starts at, e.g. "unexpected ')' at column 14"; running out of
+/// tokens before the grammar is satisfied is reported as running out at the
+/// column just past the last token.
+fn validate_structure(tokens: &[(Token, usize)]) -> Result<()> {
+    terminated(expr, eof)
+        .parse(Tokens::new(tokens))
+        .map(|_| ())
+        .map_err(|err| match tokens.get(err.offset()) {
+            Some((token, offset)) => {
+                anyhow!("unexpected {} at column {}", describe(*token), offset + 1)
+            }
+            None => anyhow!(
+                "unexpected end of expression at column {}",
+                tokens.last().map(|(_, offset)| offset + 1).unwrap_or(1)
+            ),
+        })
+}
+
+fn parse_part1(tokens: &[(Token, usize)]) -> Result<Ast> {
+    validate_structure(tokens)?;
+    shunting_yard(tokens.iter().map(|(token, _)| token), |_| 0)
+}
 
-        let parse_operand = |tokens: &mut I| -> Result<Ast> {
-            match tokens.next() {
-                Some(Token::LParen) => do_parse(tokens),
-                Some(Token::Number(n)) => Ok(Ast::Number(*n)),
-                Some(Token::Plus) | Some(Token::Times) => {
-                    Err(anyhow!("expected a number or expression, got an operator"))
+fn parse_part2(tokens: &[(Token, usize)]) -> Result<Ast> {
+    validate_structure(tokens)?;
+    shunting_yard(tokens.iter().map(|(token, _)| token), |token| match token {
+        Token::Plus => 1,
+        _ => 0,
+    })
+}
+
+/// The classic shunting-yard algorithm: scans `tokens` left to right onto an
+/// operator stack and an output sequence, then folds the resulting RPN into
+/// an [`Ast`]. `precedence` is only ever queried for [`Token::Plus`] and
+/// [`Token::Times`], so parts 1 and 2 differ only in the table they pass:
+/// part 1 ranks both operators equally, part 2 ranks `+` above `*`. Callers
+/// run [`validate_structure`] first, so the `bail!`s below are a defensive
+/// backstop rather than the primary source of parse errors.
+fn shunting_yard<'a, I: IntoIterator<Item = &'a Token>>(
+    tokens: I,
+    precedence: impl Fn(&Token) -> u8,
+) -> Result<Ast> {
+    let mut operators = Vec::new();
+    let mut output = Vec::new();
+
+    for &token in tokens {
+        match token {
+            Token::Number(_) => output.push(token),
+            Token::Plus | Token::Times => {
+                while matches!(operators.last(), Some(op) if *op != Token::LParen && precedence(op) >= precedence(&token))
+                {
+                    output.push(operators.pop().unwrap());
                 }
-                Some(Token::RParen) | None => Err(anyhow!("unexpected EOL or ')'")),
+                operators.push(token);
             }
-        };
-        let mut ast = Parens::Yes(parse_operand(tokens)?);
-        loop {
-            ast = match (ast, tokens.next()) {
-                (Parens::No(Ast::Mul(lhs, rhs)), Some(Token::Plus)) => Parens::No(Ast::Mul(
-                    lhs,
-                    Box::new(Ast::Add(rhs, Box::new(parse_operand(tokens)?))),
-                )),
-                (Parens::Yes(ast) | Parens::No(ast), Some(Token::Plus)) => {
-                    Parens::No(Ast::Add(Box::new(ast), Box::new(parse_operand(tokens)?)))
+            Token::LParen => operators.push(token),
+            Token::RParen => loop {
+                match operators.pop() {
+                    Some(Token::LParen) => break,
+                    Some(op) => output.push(op),
+                    None => bail!("unbalanced parentheses: unmatched ')'"),
                 }
-                (Parens::Yes(ast) | Parens::No(ast), Some(Token::Times)) => {
-                    Parens::No(Ast::Mul(Box::new(ast), Box::new(parse_operand(tokens)?)))
-                }
-                (Parens::Yes(ast) | Parens::No(ast), Some(Token::RParen) | None) => return Ok(ast),
-                _ => bail!("unexpected token"),
+            },
+        }
+    }
+
+    while let Some(op) = operators.pop() {
+        if op == Token::LParen {
+            bail!("unbalanced parentheses: unmatched '('");
+        }
+        output.push(op);
+    }
+
+    let mut operands: Vec<Ast> = Vec::new();
+    for token in output {
+        match token {
+            Token::Number(n) => operands.push(Ast::Number(n)),
+            Token::Plus | Token::Times => {
+                let rhs = operands.pop().ok_or_else(|| anyhow!("missing operand"))?;
+                let lhs = operands.pop().ok_or_else(|| anyhow!("missing operand"))?;
+                operands.push(if token == Token::Plus {
+                    Ast::Add(Box::new(lhs), Box::new(rhs))
+                } else {
+                    Ast::Mul(Box::new(lhs), Box::new(rhs))
+                });
             }
+            Token::LParen | Token::RParen => unreachable!("RPN output contains no parentheses"),
         }
     }
-    do_parse(&mut tokens.into_iter())
+
+    operands.pop().ok_or_else(|| anyhow!("empty expression"))
 }
 
 #[cfg(test)]
@@ -305,19 +413,50 @@ mod tests {
     #[test]
     fn example_expressions_are_tokenized() {
         itertools::assert_equal(
-            EXAMPLE_INPUTS
-                .iter()
-                .map(|line| -> Vec<Token> { tokenize(line).try_collect().unwrap() }),
+            EXAMPLE_INPUTS.iter().map(|line| -> Vec<Token> {
+                tokenize(line)
+                    .unwrap()
+                    .into_iter()
+                    .map(|(token, _)| token)
+                    .collect()
+            }),
             EXAMPLE_EXPRESSIONS.iter().cloned(),
         );
     }
 
+    #[test]
+    fn tokenizing_a_multi_digit_number_yields_a_single_token() {
+        assert_eq!(
+            tokenize("12 + (345 * 6)").unwrap(),
+            vec![
+                (Token::Number(12), 0),
+                (Token::Plus, 3),
+                (Token::LParen, 5),
+                (Token::Number(345), 6),
+                (Token::Times, 10),
+                (Token::Number(6), 12),
+                (Token::RParen, 13),
+            ]
+        );
+    }
+
+    #[test]
+    fn an_unmatched_closing_paren_is_reported_with_its_column() {
+        let tokens = tokenize("1 + 2)").unwrap();
+        let err = validate_structure(&tokens).unwrap_err();
+        assert_eq!(err.to_string(), "unexpected ')' at column 6");
+    }
+
+    fn with_offsets(tokens: &[Token]) -> Vec<(Token, usize)> {
+        tokens.iter().map(|&token| (token, 0)).collect()
+    }
+
     #[test]
     fn example_expressions_are_parsed_for_part1() {
         itertools::assert_equal(
             EXAMPLE_EXPRESSIONS
                 .iter()
-                .map(|tokens| parse_part1(*tokens).unwrap()),
+                .map(|tokens| parse_part1(&with_offsets(tokens)).unwrap()),
             example_asts_part1(),
         );
     }
@@ -327,7 +466,7 @@ mod tests {
         itertools::assert_equal(
             EXAMPLE_EXPRESSIONS
                 .iter()
-                .map(|tokens| parse_part2(*tokens).unwrap()),
+                .map(|tokens| parse_part2(&with_offsets(tokens)).unwrap()),
             example_asts_part2(),
         );
     }
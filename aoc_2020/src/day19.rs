@@ -1,9 +1,9 @@
-use anyhow::{Context, bail};
+use anyhow::bail;
 use aoc_companion::prelude::*;
-use itertools::{Either, Itertools};
+use aoc_utils::grammar::{Grammar, Term};
 
 pub(crate) struct Door {
-    rules: Vec<Rule>,
+    grammar: Grammar,
     messages: Vec<Box<[u8]>>,
 }
 
@@ -12,104 +12,58 @@ impl<'input> Solution<'input> for Door {
         let Some((rules, messages)) = input.split_once("\n\n") else {
             bail!("missing empty line separating rules from messages");
         };
-        let rules: Vec<Rule> = rules
-            .lines()
-            .map(|line| -> Result<(usize, Rule)> {
-                let Some((idx, body)) = line.split_once(':') else {
-                    bail!("missing colon separating rule index from body");
-                };
-                let idx = idx.parse().context("failed to parse rule index")?;
-
-                let rule = if let Some(lit_str) = body.trim().strip_prefix('"') {
-                    let Some(lit_str) = lit_str.strip_suffix('"') else {
-                        bail!("missing closing quotation mark in string literal rule");
-                    };
-                    let &[byte] = lit_str.as_bytes() else {
-                        bail!("string literal rule is more than one ASCII character");
-                    };
-                    Rule::Literal(byte)
-                } else {
-                    body.split('|')
-                        .map(|alt| {
-                            alt.split_whitespace()
-                                .map(|ref_str| {
-                                    ref_str
-                                        .parse()
-                                        .context("failed to parse rule reference index")
-                                        .map(RuleRef)
-                                })
-                                .try_collect()
-                                .map(All)
-                        })
-                        .try_collect()
-                        .map(Rule::Any)?
-                };
-
-                Ok((idx, rule))
-            })
-            .try_fold(Vec::new(), |mut rules, res| -> Result<Vec<Rule>> {
-                let (idx, rule) = res?;
-                if rules.len() <= idx {
-                    rules.resize_with(idx + 1, || Rule::Any(vec![]));
-                }
-                rules[idx] = rule;
-                Ok(rules)
-            })?;
 
+        let grammar = Grammar::parse(rules)?;
         let messages = messages
             .lines()
             .map(|line| line.as_bytes().to_vec().into_boxed_slice())
             .collect();
 
-        Ok(Self { rules, messages })
+        Ok(Self { grammar, messages })
     }
 
     fn part1(&self) -> usize {
         self.messages
             .iter()
-            .filter(matches(RuleRef(0), &self.rules))
+            .filter(|message| self.grammar.matches(0, message))
             .count()
     }
-}
 
-fn munch<'c>(
-    RuleRef(rule_idx): RuleRef,
-    rules: &[Rule],
-    candidate: &'c [u8],
-) -> impl Iterator<Item = &'c [u8]> {
-    let rule = &rules[rule_idx];
-    match rule {
-        Rule::Literal(b) => Either::Left(candidate.strip_prefix(&[*b]).into_iter()),
-        Rule::Any(alternatives) => {
-            Either::Right(alternatives.iter().flat_map(move |All(rule_seq)| {
-                rule_seq
-                    .iter()
-                    .copied()
-                    .fold(vec![candidate], |rests, rule_ref| {
-                        rests
-                            .into_iter()
-                            .flat_map(|rest| munch(rule_ref, rules, rest))
-                            .collect()
-                    })
-            }))
-        }
+    fn part2(&self) -> usize {
+        let grammar = with_recursive_rules(&self.grammar);
+        self.messages
+            .iter()
+            .filter(|message| grammar.matches(0, message))
+            .count()
     }
 }
 
-fn matches<C: AsRef<[u8]>>(rule_ref: RuleRef, rules: &[Rule]) -> impl Fn(&C) -> bool {
-    move |candidate| munch(rule_ref, rules, candidate.as_ref()).contains(b"".as_slice())
-}
-
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
-struct RuleRef(usize);
-
-#[derive(Clone, Debug, PartialEq, Eq)]
-struct All(Vec<RuleRef>);
-
-#[derive(Clone, Debug, PartialEq, Eq)]
-enum Rule {
-    Literal(u8),
-    Any(Vec<All>),
+/// Patches in the two recursive rules from part 2's puzzle text, replacing
+/// rule 8 (`42`) with `42 | 42 8` and rule 11 (`42 31`) with
+/// `42 31 | 42 11 31`. [`Grammar::matches`] still terminates on the
+/// resulting cycle, since each recursive step consumes at least what rule
+/// 42 matches.
+fn with_recursive_rules(grammar: &Grammar) -> Grammar {
+    let mut grammar = grammar.clone();
+    grammar.set_rule(
+        8,
+        Term::Alternation(vec![
+            Term::Concat(vec![Term::Reference(42)]),
+            Term::Concat(vec![Term::Reference(42), Term::Reference(8)]),
+        ]),
+    );
+    grammar.set_rule(
+        11,
+        Term::Alternation(vec![
+            Term::Concat(vec![Term::Reference(42), Term::Reference(31)]),
+            Term::Concat(vec![
+                Term::Reference(42),
+                Term::Reference(11),
+                Term::Reference(31),
+            ]),
+        ]),
+    );
+    grammar
 }
 
 #[cfg(test)]
@@ -129,32 +83,13 @@ abbbab
 aaabbb
 aaaabbb"#;
 
-    fn example_rules() -> Vec<Rule> {
-        vec![
-            Rule::Any(vec![All(vec![RuleRef(4), RuleRef(1), RuleRef(5)])]),
-            Rule::Any(vec![
-                All(vec![RuleRef(2), RuleRef(3)]),
-                All(vec![RuleRef(3), RuleRef(2)]),
-            ]),
-            Rule::Any(vec![
-                All(vec![RuleRef(4), RuleRef(4)]),
-                All(vec![RuleRef(5), RuleRef(5)]),
-            ]),
-            Rule::Any(vec![
-                All(vec![RuleRef(4), RuleRef(5)]),
-                All(vec![RuleRef(5), RuleRef(4)]),
-            ]),
-            Rule::Literal(b'a'),
-            Rule::Literal(b'b'),
-        ]
-    }
-
     const EXAMPLE_MESSAGES: &[&[u8]] = &[b"ababbb", b"bababa", b"abbbab", b"aaabbb", b"aaaabbb"];
 
     #[test]
     fn parse_example_input() {
-        let Door { rules, messages } = Door::parse(EXAMPLE_INPUT).unwrap();
-        itertools::assert_equal(rules, example_rules());
+        let (rules, _) = EXAMPLE_INPUT.split_once("\n\n").unwrap();
+        let Door { grammar, messages } = Door::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(grammar, Grammar::parse(rules).unwrap());
         itertools::assert_equal(
             messages.iter().map(|m| m.as_ref()),
             EXAMPLE_MESSAGES.iter().copied(),
@@ -162,26 +97,68 @@ aaaabbb"#;
     }
 
     #[test]
-    fn munch_some_rules() {
-        let rules = example_rules();
-        itertools::assert_equal(munch(RuleRef(4), &rules, b"ababbb"), [b"babbb"]);
-        itertools::assert_equal(munch(RuleRef(3), &rules, b"babbb"), [b"bbb"]);
-        itertools::assert_equal(
-            munch(RuleRef(2), &rules, b"babbb"),
-            std::iter::empty::<&[u8]>(),
-        );
-        itertools::assert_equal(munch(RuleRef(2), &rules, b"bbb"), [b"b"]);
-        itertools::assert_equal(munch(RuleRef(1), &rules, b"babbb"), [b"b"]);
-        itertools::assert_equal(munch(RuleRef(0), &rules, b"ababbb"), [b""]);
+    fn matching_example_messages() {
+        let door = Door::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(door.part1(), 3);
+    }
+
+    const RECURSIVE_EXAMPLE_INPUT: &str = r#"42: 9 14 | 10 1
+9: 14 27 | 1 26
+10: 23 14 | 28 1
+1: "a"
+11: 42 31
+5: 1 14 | 15 1
+19: 14 1 | 14 14
+12: 24 14 | 19 1
+16: 15 1 | 14 14
+31: 14 17 | 1 13
+6: 14 14 | 1 14
+2: 1 24 | 14 4
+0: 8 11
+13: 14 3 | 1 12
+15: 1 | 14
+17: 14 2 | 1 7
+23: 25 1 | 22 14
+28: 16 1
+4: 1 1
+20: 14 14 | 1 15
+3: 5 14 | 16 1
+27: 1 6 | 14 18
+14: "b"
+21: 14 1 | 1 14
+25: 1 1 | 1 14
+22: 14 14
+8: 42
+26: 14 22 | 1 20
+18: 15 15
+7: 14 5 | 1 21
+24: 14 1
+
+abbbbbabbbaaaababbaabbbbabababbbabbbbbbabaaaa
+bbabbbbaabaabba
+babbbbaabbbbbabbbbbbaabaaabaaa
+aaabbbbbbaaaabaababaabababbabaaabbababababaaa
+bbbbbbbaaaabbbbaaabbabaaa
+bbbababbbbaaaaaaaabbababaaababaabab
+ababaaaaaabaaab
+ababaaaaabbbaba
+baabbaaaabbaaaababbaababb
+abbbbabbbbaaaababbbbbbaaaababb
+aaaaabbaabaaaaababaa
+aaaabbaaaabbaaa
+aaaabbaabbaaaaaaabbbabbbaaabbaabaab
+babaaabbbaaabaababbaabababaaab
+aabbbbbaabbbaaaaaabbbbbababaaaaabbaaabba"#;
+
+    #[test]
+    fn without_recursive_rules_three_messages_match() {
+        let door = Door::parse(RECURSIVE_EXAMPLE_INPUT).unwrap();
+        assert_eq!(door.part1(), 3);
     }
 
     #[test]
-    fn matching_example_messages() {
-        itertools::assert_equal(
-            EXAMPLE_MESSAGES
-                .iter()
-                .map(matches(RuleRef(0), &example_rules())),
-            [true, false, true, false, false],
-        );
+    fn with_recursive_rules_twelve_messages_match() {
+        let door = Door::parse(RECURSIVE_EXAMPLE_INPUT).unwrap();
+        assert_eq!(door.part2(), 12);
     }
 }
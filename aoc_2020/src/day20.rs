@@ -45,12 +45,28 @@ impl<'input> Solution<'input> for Door {
     }
 
     fn part2(&self) -> Result<usize> {
-        Ok(
-            purge_monsters(puzzle(self.tiles.iter().map(|(&id, tile)| (id, tile.view()))).view())?
-                .into_iter()
-                .filter(|&x| x == 1)
-                .count(),
-        )
+        Ok(purge_monsters(self.assembled_image()?.view())
+            .into_iter()
+            .filter(|&x| x == 1)
+            .count())
+    }
+}
+
+impl Door {
+    /// The grid of tile placements found by [`arrange`], exposed so that
+    /// downstream consumers can render the reconstructed image, verify
+    /// individual placements, or feed the arrangement into other tooling,
+    /// rather than only ever seeing the two puzzle-answer numbers.
+    pub(crate) fn arrangement(&self) -> Result<ndarray::Array2<(TileId, Orientation)>> {
+        arrange(self.tiles.iter().map(|(&id, tile)| (id, tile.view())))
+    }
+
+    /// The fully stitched, border-stripped bitmap described by
+    /// [`Self::arrangement`].
+    pub(crate) fn assembled_image(&self) -> Result<Tile> {
+        let arrangement = self.arrangement()?;
+        let tiles = HashMap::from_iter(self.tiles.iter().map(|(&id, tile)| (id, tile.view())));
+        Ok(stitch(arrangement.view(), &tiles))
     }
 }
 
@@ -67,12 +83,25 @@ type Tile = ndarray::Array2<u8>;
 type TileView<'a> = ndarray::ArrayView2<'a, u8>;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
-struct EdgeSignature(u16);
+struct EdgeSignature {
+    len: u8,
+    mask: u32,
+}
 
 impl EdgeSignature {
-    fn new<'a>(edge: impl IntoIterator<Item = &'a u8>) -> Self {
-        let sig = edge.into_iter().fold(0, |acc, e| (acc << 1) | *e as u16);
-        Self(sig.min(sig.reverse_bits() >> 6))
+    fn new<'a, I>(edge: I) -> Self
+    where
+        I: IntoIterator<Item = &'a u8>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        let edge = edge.into_iter();
+        let len = edge.len() as u8;
+        let mask = edge.fold(0u32, |acc, e| (acc << 1) | *e as u32);
+        let rev = mask.reverse_bits() >> (32 - len);
+        Self {
+            len,
+            mask: mask.min(rev),
+        }
     }
 }
 
@@ -106,173 +135,363 @@ fn corner_tiles<'a>(
         .duplicates()
 }
 
-fn border_tiles<'a>(
-    tiles_with_same_edges: impl IntoIterator<Item = &'a Vec<TileId>>,
-) -> HashSet<TileId> {
-    tiles_with_same_edges
-        .into_iter()
-        .filter_map(|v| match v[..] {
-            [tile_id] => Some(tile_id),
-            _ => None,
-        })
-        .collect()
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Side {
+    North,
+    East,
+    South,
+    West,
 }
 
-fn shares_edge_with(edge: EdgeSignature, tile: TileView) -> bool {
-    edge_signatures(tile).into_iter().any(|e| e == edge)
+/// The eight elements of the dihedral group D4 acting on a square tile: the
+/// four rotations, and those same four rotations applied after an initial
+/// mirroring along the vertical axis (`Flip*`). Replaces the bare
+/// `[TileView; 8]` that `rotoreflections` used to return, so the solver can
+/// name *which* orientation a tile was placed in instead of an opaque array
+/// index.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Orientation {
+    Identity,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    Flip,
+    FlipRotate90,
+    FlipRotate180,
+    FlipRotate270,
 }
 
-fn puzzle<'a>(tiles: impl IntoIterator<Item = (TileId, TileView<'a>)>) -> ndarray::Array2<u8> {
-    let tiles = HashMap::<_, _>::from_iter(tiles);
-    let tiles_by_edges = tiles_by_edge_signature(tiles.iter().map(|(&k, &v)| (k, v)));
+impl Orientation {
+    const ALL: [Orientation; 8] = [
+        Orientation::Identity,
+        Orientation::Rotate90,
+        Orientation::Rotate180,
+        Orientation::Rotate270,
+        Orientation::Flip,
+        Orientation::FlipRotate90,
+        Orientation::FlipRotate180,
+        Orientation::FlipRotate270,
+    ];
 
-    let dim = tiles.len().isqrt();
-    let mut puzzled_ids = ndarray::Array2::from_elem((dim, dim), None);
-
-    // Identify corner and border tiles based on edge-sharing statistics
-    let top_left_corner = corner_tiles(tiles_by_edges.values()).next().unwrap();
-    let mut border_tiles = border_tiles(tiles_by_edges.values());
-
-    // Trace out the border tiles in order
-    border_tiles.remove(&top_left_corner);
-    let mut border_ids = std::iter::successors(Some(top_left_corner), |prev| {
-        let prev_edges = edge_signatures(tiles[prev]);
-        border_tiles
-            .extract_if(|b| {
-                prev_edges
-                    .iter()
-                    .any(|&edge| shares_edge_with(edge, tiles[b]))
-            })
-            .next()
-    });
+    fn rotation(self) -> u8 {
+        match self {
+            Orientation::Identity | Orientation::Flip => 0,
+            Orientation::Rotate90 | Orientation::FlipRotate90 => 1,
+            Orientation::Rotate180 | Orientation::FlipRotate180 => 2,
+            Orientation::Rotate270 | Orientation::FlipRotate270 => 3,
+        }
+    }
 
-    // Spread the border tile IDs on the square border
-    for slice in [s![0, ..-1], s![..-1, -1], s![-1,1..;-1], s![1..;-1,0]] {
-        for (dest, source) in puzzled_ids.slice_mut(slice).iter_mut().zip(&mut border_ids) {
-            *dest = Some(source);
+    fn is_flipped(self) -> bool {
+        matches!(
+            self,
+            Orientation::Flip
+                | Orientation::FlipRotate90
+                | Orientation::FlipRotate180
+                | Orientation::FlipRotate270
+        )
+    }
+
+    fn from_parts(rotation: u8, flipped: bool) -> Self {
+        match (rotation % 4, flipped) {
+            (0, false) => Orientation::Identity,
+            (1, false) => Orientation::Rotate90,
+            (2, false) => Orientation::Rotate180,
+            (3, false) => Orientation::Rotate270,
+            (0, true) => Orientation::Flip,
+            (1, true) => Orientation::FlipRotate90,
+            (2, true) => Orientation::FlipRotate180,
+            (3, true) => Orientation::FlipRotate270,
+            _ => unreachable!("rotation was just reduced mod 4"),
         }
     }
 
-    // Fill in the inner tiles based two neighboring known tiles
-    for (i, j) in (0..dim - 2).cartesian_product(0..dim - 2) {
-        let upper_edges = edge_signatures(tiles[&puzzled_ids[(i, j + 1)].unwrap()]);
-        let left_edges = edge_signatures(tiles[&puzzled_ids[(i + 1, j)].unwrap()]);
-        puzzled_ids[(i + 1, j + 1)] = Some(
-            *tiles
-                .iter()
-                .filter(|(id, _)| **id != puzzled_ids[(i, j)].unwrap())
-                .find(|(_, tile)| {
-                    upper_edges
-                        .iter()
-                        .any(|&edge| shares_edge_with(edge, **tile))
-                        && left_edges
-                            .iter()
-                            .any(|&edge| shares_edge_with(edge, **tile))
-                })
-                .unwrap()
-                .0,
-        );
+    /// The group product such that `self.compose(other).apply_to_view(t)`
+    /// equals `self.apply_to_view(other.apply_to_view(t))`, i.e. `other` is
+    /// applied first. Follows the standard dihedral presentation `r^4 = e`,
+    /// `f^2 = e`, `fr = r^{-1}f`: a flip on the left inverts the rotation
+    /// it commutes past.
+    fn compose(self, other: Orientation) -> Orientation {
+        let rotation = if self.is_flipped() {
+            4 - other.rotation()
+        } else {
+            other.rotation()
+        };
+        Orientation::from_parts(
+            self.rotation() + rotation,
+            self.is_flipped() ^ other.is_flipped(),
+        )
     }
 
-    // Place matching rotoreflection of tile in the final grid
-    let cell_dim = tiles.values().next().unwrap().dim().0 - 2;
-    let grid_dim = cell_dim * dim;
-    let mut grid = ndarray::Array2::from_elem((grid_dim, grid_dim), 0);
+    /// Every flipped orientation is its own inverse (reflections have order
+    /// two); an unflipped orientation is undone by rotating the other way.
+    fn inverse(self) -> Orientation {
+        if self.is_flipped() {
+            self
+        } else {
+            Orientation::from_parts(4 - self.rotation(), false)
+        }
+    }
+
+    fn apply_to_view(self, tile: TileView) -> TileView {
+        let tile = if self.is_flipped() {
+            tile.slice_move(s![.., ..;-1])
+        } else {
+            tile
+        };
+        match self.rotation() {
+            0 => tile,
+            2 => tile.slice_move(s![..;-1, ..;-1]),
+            rotation => {
+                let mut transposed = tile;
+                transposed.swap_axes(0, 1);
+                if rotation == 1 {
+                    transposed.slice_move(s![..;-1, ..])
+                } else {
+                    transposed.slice_move(s![.., ..;-1])
+                }
+            }
+        }
+    }
+
+    /// Where the original tile's `side` edge ends up after `self` is
+    /// applied, and whether its pixels run in reverse relative to the
+    /// original. Derived by composing the primitive flip and single-step
+    /// rotation edge maps below, the same way `apply_to_view` composes the
+    /// underlying array operations.
+    fn map_edge(self, side: Side) -> (Side, bool) {
+        let (mut side, mut reversed) = if self.is_flipped() {
+            flip_edge(side)
+        } else {
+            (side, false)
+        };
+        for _ in 0..self.rotation() {
+            let (new_side, rev) = rotate_edge(side);
+            side = new_side;
+            reversed ^= rev;
+        }
+        (side, reversed)
+    }
+}
 
-    dbg!(puzzled_ids.clone().map(|t| t.unwrap().0));
+fn rotate_edge(side: Side) -> (Side, bool) {
+    match side {
+        Side::North => (Side::West, true),
+        Side::East => (Side::North, false),
+        Side::South => (Side::East, true),
+        Side::West => (Side::South, false),
+    }
+}
+
+fn flip_edge(side: Side) -> (Side, bool) {
+    match side {
+        Side::North => (Side::North, true),
+        Side::South => (Side::South, true),
+        Side::East => (Side::West, false),
+        Side::West => (Side::East, false),
+    }
+}
+
+/// Assembles the full image from its scrambled tiles via constraint
+/// propagation: each cell is placed in row-major order, candidates are
+/// narrowed down by an edge-signature cache, and the first candidate whose
+/// *exact* (not just signature-equal) north/west edge fits is kept,
+/// backtracking whenever a cell runs out of candidates. This is provably
+/// correct even when an edge signature collides between non-adjacent
+/// tiles, unlike the border-tracing-then-fill heuristic it replaces: a
+/// spurious same-signature candidate simply fails the bit-exact check in
+/// `assemble` and is skipped rather than silently accepted.
+fn arrange<'a>(
+    tiles: impl IntoIterator<Item = (TileId, TileView<'a>)>,
+) -> Result<ndarray::Array2<(TileId, Orientation)>> {
+    let tiles = HashMap::<_, _>::from_iter(tiles);
+    let edge_cache = tiles_by_edge_signature(tiles.iter().map(|(&k, &v)| (k, v)));
+    let dim = tiles.len().isqrt();
+    let corners: Vec<TileId> = corner_tiles(edge_cache.values()).collect();
 
-    for (((i, j), id), mut dest) in puzzled_ids
-        .indexed_iter()
+    let mut placed: ndarray::Array2<Option<(TileId, Orientation)>> =
+        ndarray::Array2::from_elem((dim, dim), None);
+    let mut free: HashSet<TileId> = tiles.keys().copied().collect();
+
+    if !assemble(0, dim, &tiles, &edge_cache, &corners, &mut placed, &mut free) {
+        bail!("found no valid tile arrangement for this jigsaw");
+    }
+
+    Ok(placed.map(|cell| cell.expect("every cell was placed by `assemble`")))
+}
+
+/// Stitches the tiles named by `arrangement` into a single bitmap, stripping
+/// each tile's one-pixel border in the process.
+fn stitch(
+    arrangement: ndarray::ArrayView2<(TileId, Orientation)>,
+    tiles: &HashMap<TileId, TileView>,
+) -> Tile {
+    let cell_dim = tiles.values().next().unwrap().dim().0 - 2;
+    let grid_dim = cell_dim * arrangement.nrows();
+    let mut grid = ndarray::Array2::from_elem((grid_dim, grid_dim), 0);
+    for (&(id, orientation), mut dest) in arrangement
+        .iter()
         .zip_eq(grid.exact_chunks_mut((cell_dim, cell_dim)))
     {
-        let id = id.unwrap();
-        let fitting_rotoreflection = rotoreflections(tiles[&id])
-            .iter()
-            .copied()
-            .filter(|&rr| {
-                if i > 0 {
-                    shares_edge_with(
-                        edge_signatures(rr)[1],
-                        tiles[&puzzled_ids[(i - 1, j)].unwrap()],
-                    )
-                } else {
-                    shares_edge_with(
-                        edge_signatures(rr)[3],
-                        tiles[&puzzled_ids[(i + 1, j)].unwrap()],
-                    )
+        let oriented = orientation.apply_to_view(tiles[&id]);
+        dest.assign(&oriented.slice(s![1..-1, 1..-1]));
+    }
+
+    grid
+}
+
+/// Fills `placed` (a `dim`×`dim` grid, read in row-major order) starting at
+/// the `pos`-th cell, returning whether a complete, consistent arrangement
+/// was found. `corners` seeds the very first cell, since nothing yet
+/// constrains it; every other cell is constrained by its already-placed
+/// north and/or west neighbor, whose touching edge the candidate's own
+/// must equal bit-for-bit in some orientation. On backtracking, the tried
+/// tile is restored to `free` so a sibling branch can still use it.
+fn assemble(
+    pos: usize,
+    dim: usize,
+    tiles: &HashMap<TileId, TileView>,
+    edge_cache: &HashMap<EdgeSignature, Vec<TileId>>,
+    corners: &[TileId],
+    placed: &mut ndarray::Array2<Option<(TileId, Orientation)>>,
+    free: &mut HashSet<TileId>,
+) -> bool {
+    if pos == dim * dim {
+        return true;
+    }
+    let (i, j) = (pos / dim, pos % dim);
+
+    let west_edge = (j > 0).then(|| {
+        let (id, orientation) = placed[(i, j - 1)].unwrap();
+        orientation
+            .apply_to_view(tiles[&id])
+            .slice(s![.., -1])
+            .to_owned()
+    });
+    let north_edge = (i > 0).then(|| {
+        let (id, orientation) = placed[(i - 1, j)].unwrap();
+        orientation
+            .apply_to_view(tiles[&id])
+            .slice(s![-1, ..])
+            .to_owned()
+    });
+
+    let candidates: Vec<TileId> = match (&west_edge, &north_edge) {
+        (None, None) => corners.to_vec(),
+        (Some(edge), None) | (None, Some(edge)) => edge_cache
+            .get(&EdgeSignature::new(edge.iter()))
+            .cloned()
+            .unwrap_or_default(),
+        (Some(west), Some(north)) => {
+            let west_candidates: HashSet<TileId> = edge_cache
+                .get(&EdgeSignature::new(west.iter()))
+                .into_iter()
+                .flatten()
+                .copied()
+                .collect();
+            edge_cache
+                .get(&EdgeSignature::new(north.iter()))
+                .into_iter()
+                .flatten()
+                .filter(|id| west_candidates.contains(id))
+                .copied()
+                .collect()
+        }
+    };
+
+    for id in candidates {
+        if !free.contains(&id) {
+            continue;
+        }
+        for orientation in Orientation::ALL {
+            let view = orientation.apply_to_view(tiles[&id]);
+            if let Some(edge) = &west_edge {
+                if view.slice(s![.., 0]) != *edge {
+                    continue;
                 }
-            })
-            .find(|&rr| {
-                if j > 0 {
-                    shares_edge_with(
-                        edge_signatures(rr)[0],
-                        tiles[&puzzled_ids[(i, j - 1)].unwrap()],
-                    )
-                } else {
-                    shares_edge_with(
-                        edge_signatures(rr)[2],
-                        tiles[&puzzled_ids[(i, j + 1)].unwrap()],
-                    )
+            }
+            if let Some(edge) = &north_edge {
+                if view.slice(s![0, ..]) != *edge {
+                    continue;
                 }
-            })
-            .with_context(|| format!("failed to find fitting rotoreflection for {:?}", (i, j)))
-            .unwrap();
+            }
 
-        dest.assign(
-            &fitting_rotoreflection
-                .slice(s![1.., 1..])
-                .slice(s![..-1, ..-1]),
-        );
+            free.remove(&id);
+            placed[(i, j)] = Some((id, orientation));
+            if assemble(pos + 1, dim, tiles, edge_cache, corners, placed, free) {
+                return true;
+            }
+            placed[(i, j)] = None;
+            free.insert(id);
+        }
     }
 
-    grid
+    false
 }
 
 fn rotoreflections(tile: TileView) -> [TileView; 8] {
-    let mut swapped = tile;
-    swapped.swap_axes(0, 1);
-    [
-        tile.slice_move(s![.., ..]),
-        tile.slice_move(s![..;-1,..]),
-        tile.slice_move(s![..,..;-1]),
-        tile.slice_move(s![..;-1,..;-1]),
-        swapped.slice_move(s![.., ..]),
-        swapped.slice_move(s![..;-1,..]),
-        swapped.slice_move(s![..,..;-1]),
-        swapped.slice_move(s![..;-1,..;-1]),
-    ]
+    Orientation::ALL.map(|orientation| orientation.apply_to_view(tile))
 }
 
-fn purge_monsters(puzzle: TileView) -> Result<Tile> {
-    const MONSTER_MASK: TileView = ndarray::aview2(&[
-        [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0],
-        [1, 0, 0, 0, 0, 1, 1, 0, 0, 0, 0, 1, 1, 0, 0, 0, 0, 1, 1, 1],
-        [0, 1, 0, 0, 1, 0, 0, 1, 0, 0, 1, 0, 0, 1, 0, 0, 1, 0, 0, 0],
-    ]);
+const SEA_MONSTER: &str = "\
+                  # 
+#    ##    ##    ###
+ #  #  #  #  #  #   ";
 
-    let Some(puzzle) = rotoreflections(puzzle).iter().copied().find(|puzzle| {
-        puzzle
-            .windows(MONSTER_MASK.dim())
-            .into_iter()
-            .any(|window| &window * &MONSTER_MASK == MONSTER_MASK)
-    }) else {
-        bail!("did not find any sea monsters");
-    };
+fn purge_monsters(puzzle: TileView) -> Tile {
+    let pattern = aoc_utils::geometry::try_parse_map(SEA_MONSTER, |b| match b {
+        b' ' => Ok(0),
+        b'#' => Ok(1),
+        _ => Err(InvalidTileChar { byte: b }),
+    })
+    .expect("built-in sea monster pattern is well-formed");
+
+    let (_, monsters) = count_pattern(puzzle, pattern.view());
+    monsters ^ puzzle
+}
+
+/// Searches every orientation of `image` for all (possibly overlapping)
+/// occurrences of `pattern`'s set cells via windowed masking, stopping at
+/// the first orientation with at least one match. Returns how many matches
+/// were found and a mask, in `image`'s original orientation, with every
+/// matched cell set; if `pattern` doesn't occur in any orientation, returns
+/// `(0, ...)` with an all-zero mask rather than failing, since "found
+/// nothing" is a legitimate answer for a stamp-matching search.
+fn count_pattern(image: TileView, pattern: TileView) -> (usize, Tile) {
+    for orientation in Orientation::ALL {
+        let oriented = orientation.apply_to_view(image);
+        let (count, mask) = count_pattern_once(oriented, pattern);
+        if count > 0 {
+            return (
+                count,
+                orientation.inverse().apply_to_view(mask.view()).to_owned(),
+            );
+        }
+    }
 
-    let mut monsters = Tile::from_elem(puzzle.dim(), 0);
-    for (puzzle_win, monsters_win) in puzzle
-        .windows(MONSTER_MASK.dim())
+    (0, Tile::from_elem(image.dim(), 0))
+}
+
+/// Counts (possibly overlapping) occurrences of `pattern`'s set cells in
+/// `image` without trying any other orientation, returning a mask of every
+/// matched cell alongside the count.
+fn count_pattern_once(image: TileView, pattern: TileView) -> (usize, Tile) {
+    let mut count = 0;
+    let mut mask = Tile::from_elem(image.dim(), 0);
+    for (image_win, mask_win) in image
+        .windows(pattern.dim())
         .into_iter()
-        .zip_eq(monsters.cell_view().windows(MONSTER_MASK.dim()))
+        .zip_eq(mask.cell_view().windows(pattern.dim()))
     {
-        if &puzzle_win * &MONSTER_MASK == MONSTER_MASK {
-            ndarray::Zip::from(monsters_win)
-                .and(MONSTER_MASK)
+        if &image_win * &pattern == pattern {
+            count += 1;
+            ndarray::Zip::from(mask_win)
+                .and(pattern)
                 .for_each(|dest, mask| dest.update(|d| d.max(*mask)));
         }
     }
 
-    Ok(monsters ^ puzzle)
+    (count, mask)
 }
 
 #[cfg(test)]
@@ -314,38 +533,140 @@ mod tests {
         }
     }
 
+    #[test]
+    fn orientations_are_pairwise_distinct() {
+        assert_eq!(
+            HashSet::<Orientation>::from_iter(Orientation::ALL).len(),
+            Orientation::ALL.len()
+        );
+    }
+
+    #[test]
+    fn orientation_compose_matches_sequential_application() {
+        let tile = EXAMPLE_TILES[0].1;
+        for a in Orientation::ALL {
+            for b in Orientation::ALL {
+                assert_eq!(
+                    a.compose(b).apply_to_view(tile),
+                    a.apply_to_view(b.apply_to_view(tile)),
+                    "composing {a:?} after {b:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn orientation_inverse_undoes_the_orientation() {
+        let tile = EXAMPLE_TILES[0].1;
+        for orientation in Orientation::ALL {
+            assert_eq!(
+                orientation
+                    .inverse()
+                    .apply_to_view(orientation.apply_to_view(tile)),
+                tile,
+                "{orientation:?} followed by its inverse"
+            );
+        }
+    }
+
+    #[test]
+    fn orientation_map_edge_agrees_with_apply_to_view() {
+        let tile = EXAMPLE_TILES[0].1;
+        for orientation in Orientation::ALL {
+            let oriented = orientation.apply_to_view(tile);
+            for side in [Side::North, Side::East, Side::South, Side::West] {
+                let original_edge: Vec<u8> = match side {
+                    Side::North => tile.slice(s![0, ..]).to_vec(),
+                    Side::East => tile.slice(s![.., -1]).to_vec(),
+                    Side::South => tile.slice(s![-1, ..]).to_vec(),
+                    Side::West => tile.slice(s![.., 0]).to_vec(),
+                };
+                let (mapped_side, reversed) = orientation.map_edge(side);
+                let mut mapped_edge: Vec<u8> = match mapped_side {
+                    Side::North => oriented.slice(s![0, ..]).to_vec(),
+                    Side::East => oriented.slice(s![.., -1]).to_vec(),
+                    Side::South => oriented.slice(s![-1, ..]).to_vec(),
+                    Side::West => oriented.slice(s![.., 0]).to_vec(),
+                };
+                if reversed {
+                    mapped_edge.reverse();
+                }
+                assert_eq!(
+                    mapped_edge, original_edge,
+                    "{side:?} edge under {orientation:?}"
+                );
+            }
+        }
+    }
+
     #[test]
     fn example_tiles_by_edge_signature() {
         assert_eq!(
             tiles_by_edge_signature(EXAMPLE_TILES),
             HashMap::from([
                 // corner tiles
-                (EdgeSignature(391), vec![TileId(1171)]),
-                (EdgeSignature(24), vec![TileId(1171)]),
-                (EdgeSignature(587), vec![TileId(1951)]),
-                (EdgeSignature(177), vec![TileId(1951)]),
-                (EdgeSignature(78), vec![TileId(2971)]),
-                (EdgeSignature(161), vec![TileId(2971)]),
-                (EdgeSignature(501), vec![TileId(3079)]),
-                (EdgeSignature(66), vec![TileId(3079)]),
+                (EdgeSignature { len: 10, mask: 391 }, vec![TileId(1171)]),
+                (EdgeSignature { len: 10, mask: 24 }, vec![TileId(1171)]),
+                (EdgeSignature { len: 10, mask: 587 }, vec![TileId(1951)]),
+                (EdgeSignature { len: 10, mask: 177 }, vec![TileId(1951)]),
+                (EdgeSignature { len: 10, mask: 78 }, vec![TileId(2971)]),
+                (EdgeSignature { len: 10, mask: 161 }, vec![TileId(2971)]),
+                (EdgeSignature { len: 10, mask: 501 }, vec![TileId(3079)]),
+                (EdgeSignature { len: 10, mask: 66 }, vec![TileId(3079)]),
                 // border tiles
-                (EdgeSignature(43), vec![TileId(1489)]),
-                (EdgeSignature(481), vec![TileId(2473)]),
-                (EdgeSignature(231), vec![TileId(2311)]),
-                (EdgeSignature(271), vec![TileId(2729)]),
+                (EdgeSignature { len: 10, mask: 43 }, vec![TileId(1489)]),
+                (EdgeSignature { len: 10, mask: 481 }, vec![TileId(2473)]),
+                (EdgeSignature { len: 10, mask: 231 }, vec![TileId(2311)]),
+                (EdgeSignature { len: 10, mask: 271 }, vec![TileId(2729)]),
                 // inner tiles
-                (EdgeSignature(234), vec![TileId(1427), TileId(2473)]),
-                (EdgeSignature(85), vec![TileId(2971), TileId(2729)]),
-                (EdgeSignature(399), vec![TileId(1171), TileId(2473)]),
-                (EdgeSignature(9), vec![TileId(1427), TileId(2729)]),
-                (EdgeSignature(318), vec![TileId(2311), TileId(1951)]),
-                (EdgeSignature(89), vec![TileId(2311), TileId(3079)]),
-                (EdgeSignature(210), vec![TileId(2311), TileId(1427)]),
-                (EdgeSignature(18), vec![TileId(1171), TileId(1489)]),
-                (EdgeSignature(565), vec![TileId(1489), TileId(2971)]),
-                (EdgeSignature(116), vec![TileId(2473), TileId(3079)]),
-                (EdgeSignature(183), vec![TileId(1427), TileId(1489)]),
-                (EdgeSignature(397), vec![TileId(1951), TileId(2729)]),
+                (
+                    EdgeSignature { len: 10, mask: 234 },
+                    vec![TileId(1427), TileId(2473)]
+                ),
+                (
+                    EdgeSignature { len: 10, mask: 85 },
+                    vec![TileId(2971), TileId(2729)]
+                ),
+                (
+                    EdgeSignature { len: 10, mask: 399 },
+                    vec![TileId(1171), TileId(2473)]
+                ),
+                (
+                    EdgeSignature { len: 10, mask: 9 },
+                    vec![TileId(1427), TileId(2729)]
+                ),
+                (
+                    EdgeSignature { len: 10, mask: 318 },
+                    vec![TileId(2311), TileId(1951)]
+                ),
+                (
+                    EdgeSignature { len: 10, mask: 89 },
+                    vec![TileId(2311), TileId(3079)]
+                ),
+                (
+                    EdgeSignature { len: 10, mask: 210 },
+                    vec![TileId(2311), TileId(1427)]
+                ),
+                (
+                    EdgeSignature { len: 10, mask: 18 },
+                    vec![TileId(1171), TileId(1489)]
+                ),
+                (
+                    EdgeSignature { len: 10, mask: 565 },
+                    vec![TileId(1489), TileId(2971)]
+                ),
+                (
+                    EdgeSignature { len: 10, mask: 116 },
+                    vec![TileId(2473), TileId(3079)]
+                ),
+                (
+                    EdgeSignature { len: 10, mask: 183 },
+                    vec![TileId(1427), TileId(1489)]
+                ),
+                (
+                    EdgeSignature { len: 10, mask: 397 },
+                    vec![TileId(1951), TileId(2729)]
+                ),
             ])
         );
     }
@@ -361,29 +682,40 @@ mod tests {
     }
 
     #[test]
-    fn identify_example_border_tiles() {
-        assert_eq!(
-            border_tiles(tiles_by_edge_signature(EXAMPLE_TILES).values()),
-            HashSet::from([
-                TileId(1951),
-                TileId(2311),
-                TileId(3079),
-                TileId(2473),
-                TileId(1171),
-                TileId(1489),
-                TileId(2971),
-                TileId(2729),
-            ])
+    fn solve_example_puzzle() {
+        let arrangement = arrange(EXAMPLE_TILES).unwrap();
+        let tiles = HashMap::from(EXAMPLE_TILES);
+        let image = stitch(arrangement.view(), &tiles);
+        assert!(
+            rotoreflections(image.view())
+                .iter()
+                .contains(&EXAMPLE_PUZZLE)
         );
     }
 
     #[test]
-    fn solve_example_puzzle() {
+    fn arrangement_places_every_tile_exactly_once() {
+        let arrangement = arrange(EXAMPLE_TILES).unwrap();
+        itertools::assert_equal(
+            arrangement.iter().map(|&(id, _)| id).sorted(),
+            EXAMPLE_TILES.iter().map(|&(id, _)| id).sorted(),
+        );
+    }
+
+    #[test]
+    fn door_exposes_the_same_arrangement_and_image() {
+        let door = Door::parse(EXAMPLE_INPUT).unwrap();
+        let arrangement = door.arrangement().unwrap();
+        let image = door.assembled_image().unwrap();
         assert!(
-            rotoreflections(puzzle(EXAMPLE_TILES).view())
+            rotoreflections(image.view())
                 .iter()
                 .contains(&EXAMPLE_PUZZLE)
         );
+        itertools::assert_equal(
+            arrangement.iter().map(|&(id, _)| id).sorted(),
+            door.tiles.keys().copied().sorted(),
+        );
     }
 
     #[test]
@@ -394,15 +726,65 @@ mod tests {
             })
             .unwrap();
         for puzzle_roto in rotoreflections(EXAMPLE_PUZZLE) {
-            assert_eq!(purge_monsters(puzzle_roto).unwrap(), expected);
+            let purged = purge_monsters(puzzle_roto);
+            assert!(
+                rotoreflections(expected.view())
+                    .iter()
+                    .contains(&purged.view()),
+                "purging {puzzle_roto:?} gave {purged:?}"
+            );
         }
     }
 
+    #[test]
+    fn count_pattern_finds_overlapping_occurrences() {
+        let image: Tile = aoc_utils::geometry::try_parse_map::<_, Infallible>("###\n###", |b| {
+            Ok((b == b'#') as u8)
+        })
+        .unwrap();
+        let pattern: Tile =
+            aoc_utils::geometry::try_parse_map::<_, Infallible>("##", |b| Ok((b == b'#') as u8))
+                .unwrap();
+
+        let (count, mask) = count_pattern(image.view(), pattern.view());
+
+        assert_eq!(count, 4);
+        assert_eq!(mask, image);
+    }
+
+    #[test]
+    fn count_pattern_reports_zero_matches_without_failing() {
+        let image: Tile = aoc_utils::geometry::try_parse_map::<_, Infallible>("...\n...", |b| {
+            Ok((b == b'#') as u8)
+        })
+        .unwrap();
+        let pattern: Tile =
+            aoc_utils::geometry::try_parse_map::<_, Infallible>("##", |b| Ok((b == b'#') as u8))
+                .unwrap();
+
+        assert_eq!(
+            count_pattern(image.view(), pattern.view()),
+            (0, Tile::from_elem(image.dim(), 0))
+        );
+    }
+
     const TILE_2311_EDGE_SIGNATURES: [EdgeSignature; 4] = [
-        EdgeSignature(0b0100111110),
-        EdgeSignature(0b0001011001),
-        EdgeSignature(0b0011010010),
-        EdgeSignature(0b0011100111),
+        EdgeSignature {
+            len: 10,
+            mask: 0b0100111110,
+        },
+        EdgeSignature {
+            len: 10,
+            mask: 0b0001011001,
+        },
+        EdgeSignature {
+            len: 10,
+            mask: 0b0011010010,
+        },
+        EdgeSignature {
+            len: 10,
+            mask: 0b0011100111,
+        },
     ];
 
     const EXAMPLE_INPUT: &str = "\
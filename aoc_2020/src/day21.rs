@@ -72,57 +72,49 @@ where
     Is: Borrow<BTreeSet<Ingredient<'a>>> + Ord,
     As: Borrow<BTreeSet<Allergen<'a>>> + Ord,
 {
-    let mut relations = foods
+    // Phase 1: for each allergen, intersect the ingredient sets of every food
+    // that lists it, giving the set of ingredients that could possibly be it.
+    let candidates: BTreeMap<Allergen, BTreeSet<Ingredient>> = foods
         .into_iter()
-        .map(|(i, a)| (a.borrow().clone(), i.borrow().clone()))
+        .flat_map(|(i, a)| {
+            a.borrow()
+                .iter()
+                .copied()
+                .map(move |allergen| (allergen, i.borrow().clone()))
+        })
         .btree_merge(intersect);
 
-    let mut solution = HashMap::new();
-
-    while !relations.is_empty() {
-        dbg!(relations.len());
-        // Generate non-empty intersections
-        let mut intersections = relations
-            .iter()
-            .tuple_combinations()
-            .filter_map(|((lhs_a, lhs_i), (rhs_a, rhs_i))| {
-                let int_a: BTreeSet<_> = lhs_a.intersection(rhs_a).copied().collect();
-                if int_a.is_empty() {
-                    return None;
-                }
-                let int_i = lhs_i.intersection(rhs_i).copied().collect();
-                Some((int_a, int_i))
-            })
-            .btree_merge(intersect);
-        relations.append(&mut intersections);
+    // Phase 2: find a perfect matching of allergens to candidate ingredients
+    // via Kuhn's augmenting-path algorithm.
+    let mut assignment: HashMap<Ingredient, Allergen> = HashMap::new();
+    for &allergen in candidates.keys() {
+        let mut visited = BTreeSet::new();
+        try_assign(allergen, &candidates, &mut assignment, &mut visited);
+    }
 
-        // Identify and eliminate unique relations
-        while let Some((allergen, ingredient)) =
-            relations.iter().find_map(|(allergens, ingredients)| {
-                let allergen = allergens.iter().exactly_one().ok()?;
-                let ingredient = ingredients
-                    .iter()
-                    .filter(|&i| !solution.contains_key(i))
-                    .exactly_one()
-                    .ok()?;
-                Some((*allergen, *ingredient))
-            })
-        {
-            solution.insert(ingredient, allergen);
+    assignment
+}
 
-            relations = relations
-                .into_iter()
-                .map(|(mut allergens, mut ingredients)| {
-                    allergens.remove(&allergen);
-                    ingredients.remove(&ingredient);
-                    (allergens, ingredients)
-                })
-                .filter(|(allergens, _)| !allergens.is_empty())
-                .btree_merge(intersect);
+fn try_assign<'a>(
+    allergen: Allergen<'a>,
+    candidates: &BTreeMap<Allergen<'a>, BTreeSet<Ingredient<'a>>>,
+    assignment: &mut HashMap<Ingredient<'a>, Allergen<'a>>,
+    visited: &mut BTreeSet<Ingredient<'a>>,
+) -> bool {
+    for &ingredient in &candidates[&allergen] {
+        if !visited.insert(ingredient) {
+            continue;
+        }
+        let vacant = match assignment.get(&ingredient) {
+            None => true,
+            Some(&incumbent) => try_assign(incumbent, candidates, assignment, visited),
+        };
+        if vacant {
+            assignment.insert(ingredient, allergen);
+            return true;
         }
     }
-
-    solution
+    false
 }
 
 fn safe_ingredients<'a>(
@@ -284,6 +276,57 @@ sqjhc mxmxvkd sbzzf (contains fish)";
         assert_eq!(infer_mapping(foods), HashMap::from(CUSTOM_MAPPING));
     }
 
+    #[test]
+    fn infer_mapping_with_no_initial_singletons() {
+        // Every allergen's candidate set has two ingredients from the start,
+        // so the old singleton-peeling heuristic would never make progress;
+        // the cyclic overlap (dairy-fish-nuts over X-Y-Z) can only be broken
+        // by Kuhn's algorithm re-assigning an incumbent along an augmenting
+        // path.
+        const FOODS: [(&[Ingredient], &[Allergen]); 6] = [
+            (
+                &[Ingredient("X"), Ingredient("Y"), Ingredient("W")],
+                &[Allergen("dairy")],
+            ),
+            (
+                &[Ingredient("X"), Ingredient("Y"), Ingredient("Z")],
+                &[Allergen("dairy")],
+            ),
+            (
+                &[Ingredient("Y"), Ingredient("Z"), Ingredient("W")],
+                &[Allergen("fish")],
+            ),
+            (
+                &[Ingredient("Y"), Ingredient("Z"), Ingredient("X")],
+                &[Allergen("fish")],
+            ),
+            (
+                &[Ingredient("X"), Ingredient("Z"), Ingredient("W")],
+                &[Allergen("nuts")],
+            ),
+            (
+                &[Ingredient("X"), Ingredient("Z"), Ingredient("Y")],
+                &[Allergen("nuts")],
+            ),
+        ];
+
+        let foods = FOODS.iter().map(|(k, v)| {
+            (
+                BTreeSet::from_iter(k.iter().copied()),
+                BTreeSet::from_iter(v.iter().copied()),
+            )
+        });
+
+        assert_eq!(
+            infer_mapping(foods),
+            HashMap::from([
+                (Ingredient("X"), Allergen("nuts")),
+                (Ingredient("Y"), Allergen("dairy")),
+                (Ingredient("Z"), Allergen("fish")),
+            ])
+        );
+    }
+
     #[test]
     fn list_safe_example_ingredients() {
         let foods = HashMap::from_iter(EXAMPLE_FOODS.iter().map(|(k, v)| {
@@ -1,4 +1,4 @@
-use std::collections::VecDeque;
+use std::collections::{HashSet, VecDeque};
 
 use anyhow::Context as _;
 use anyhow::bail;
@@ -34,6 +34,13 @@ impl<'input> Solution<'input> for Door {
             self.decks.each_ref().map(|deck| deck.clone().into()),
         ))
     }
+
+    fn part2(&self) -> u64 {
+        let (_, deck) = play_recursive_combat(
+            self.decks.each_ref().map(|deck| deck.clone().into()),
+        );
+        score(&Vec::from(deck))
+    }
 }
 
 fn play_combat(mut decks: [VecDeque<u64>; 2]) -> Vec<u64> {
@@ -55,6 +62,45 @@ fn play_combat(mut decks: [VecDeque<u64>; 2]) -> Vec<u64> {
     winner.into()
 }
 
+/// Plays Recursive Combat to completion, returning the index of the
+/// winning player along with their final deck.
+fn play_recursive_combat(mut decks: [VecDeque<u64>; 2]) -> (usize, VecDeque<u64>) {
+    let mut seen = HashSet::new();
+
+    while decks.iter().all(|deck| !deck.is_empty()) {
+        if !seen.insert(decks.clone()) {
+            return (0, decks[0].clone());
+        }
+
+        let top_cards = decks.each_mut().map(|deck| deck.pop_front().unwrap());
+
+        let winner = if decks
+            .iter()
+            .zip(top_cards)
+            .all(|(deck, card)| deck.len() as u64 >= card)
+        {
+            let sub_decks = std::array::from_fn(|i| {
+                decks[i]
+                    .iter()
+                    .take(top_cards[i] as usize)
+                    .copied()
+                    .collect()
+            });
+            play_recursive_combat(sub_decks).0
+        } else if top_cards[0] > top_cards[1] {
+            0
+        } else {
+            1
+        };
+
+        let loser = 1 - winner;
+        decks[winner].extend([top_cards[winner], top_cards[loser]]);
+    }
+
+    let winner = decks.iter().position(|deck| !deck.is_empty()).unwrap();
+    (winner, decks[winner].clone())
+}
+
 fn score(deck: &[u64]) -> u64 {
     deck.iter()
         .rev()
@@ -100,4 +146,17 @@ Player 2:
     fn winning_score() {
         assert_eq!(score(&WINNING_DECK), 306);
     }
+
+    #[test]
+    fn recursive_winning_score_in_example() {
+        let (_, deck) = play_recursive_combat(EXAMPLE_DECKS.map(VecDeque::from));
+        assert_eq!(score(&Vec::from(deck)), 291);
+    }
+
+    #[test]
+    fn infinite_games_are_won_by_player_one() {
+        let decks = [VecDeque::from([43, 19]), VecDeque::from([2, 29, 14])];
+        let (winner, _) = play_recursive_combat(decks);
+        assert_eq!(winner, 0);
+    }
 }
@@ -3,7 +3,7 @@ use aoc_companion::prelude::*;
 use itertools::Itertools;
 use thiserror::Error;
 
-use std::collections::HashSet;
+const ELF_GROUP_SIZE: usize = 3;
 
 pub(crate) struct Door {
     rucksacks: Vec<Vec<u32>>,
@@ -24,7 +24,7 @@ impl<'input> Solution<'input> for Door {
     }
 
     fn part2(&self) -> Result<u32, Error> {
-        group_batch_priorities(&self.rucksacks).map(|batches| batches.iter().sum())
+        group_batch_priorities(&self.rucksacks, ELF_GROUP_SIZE).map(|batches| batches.iter().sum())
     }
 }
 
@@ -42,26 +42,41 @@ pub(crate) enum Error {
     EmptyGroup,
 }
 
+/// A bitmask over item priorities `1..=52`: bit `p` is set iff some item of
+/// priority `p` is present. Fits comfortably in a `u64` and turns
+/// "find the common item(s)" into a single `&`, with no per-line hashing
+/// or allocation.
+fn priorities_mask(prios: &[u32]) -> u64 {
+    prios.iter().fold(0, |mask, &p| mask | (1 << p))
+}
+
+/// The priority of the single item set in `mask`, or `NoUniqueCommonItem`
+/// if zero or more than one bit is set.
+fn common_priority(mask: u64) -> Result<u32, Error> {
+    if mask.count_ones() == 1 {
+        Ok(mask.trailing_zeros())
+    } else {
+        Err(Error::NoUniqueCommonItem)
+    }
+}
+
 #[derive(Debug)]
 struct Rucksack {
-    first_compartment: HashSet<u32>,
-    second_compartment: HashSet<u32>,
+    first_compartment: u64,
+    second_compartment: u64,
 }
 
 impl Rucksack {
     fn new(prios: &[u32]) -> Self {
         let (first, second) = prios.split_at(prios.len() / 2);
         Self {
-            first_compartment: HashSet::from_iter(first.iter().copied()),
-            second_compartment: HashSet::from_iter(second.iter().copied()),
+            first_compartment: priorities_mask(first),
+            second_compartment: priorities_mask(second),
         }
     }
 
     fn common_item_priority(&self) -> Result<u32, Error> {
-        HashSet::intersection(&self.first_compartment, &self.second_compartment)
-            .copied()
-            .exactly_one()
-            .map_err(|_| Error::NoUniqueCommonItem)
+        common_priority(self.first_compartment & self.second_compartment)
     }
 }
 
@@ -79,19 +94,20 @@ fn priority(c: char) -> Result<u32, ParseError> {
 
 fn batch_priority<'a>(group: impl Iterator<Item = &'a [u32]>) -> Result<u32, Error> {
     group
-        .map(|slice| HashSet::from_iter(slice.iter().copied()))
-        .reduce(|lhs: HashSet<u32>, rhs| HashSet::intersection(&lhs, &rhs).copied().collect())
-        .ok_or(Error::EmptyGroup)?
-        .into_iter()
-        .exactly_one()
-        .map_err(|_| Error::NoUniqueCommonItem)
+        .map(priorities_mask)
+        .reduce(std::ops::BitAnd::bitand)
+        .ok_or(Error::EmptyGroup)
+        .and_then(common_priority)
 }
 
-fn group_batch_priorities<R: AsRef<[u32]>>(rucksacks: &[R]) -> Result<Vec<u32>, Error> {
+fn group_batch_priorities<R: AsRef<[u32]>>(
+    rucksacks: &[R],
+    group_size: usize,
+) -> Result<Vec<u32>, Error> {
     rucksacks
         .iter()
         .map(AsRef::as_ref)
-        .chunks(3)
+        .chunks(group_size)
         .into_iter()
         .map(batch_priority)
         .collect()
@@ -143,6 +159,16 @@ mod tests {
         ]
         .map(|line| parse_priorities(line).unwrap());
 
-        assert_eq!(group_batch_priorities(&prios).unwrap(), [18, 52]);
+        assert_eq!(
+            group_batch_priorities(&prios, ELF_GROUP_SIZE).unwrap(),
+            [18, 52]
+        );
+    }
+
+    #[test]
+    fn group_size_is_configurable() {
+        let prios = ["ab", "bc"].map(|line| parse_priorities(line).unwrap());
+
+        assert_eq!(group_batch_priorities(&prios, 2).unwrap(), [2]);
     }
 }
@@ -1,7 +1,7 @@
 use aoc_companion::prelude::*;
+use aoc_utils::parse::{finish, range_inclusive, tag, PResult, ParseError};
 
 use itertools::Itertools;
-use thiserror::Error;
 
 use std::ops::RangeInclusive;
 use std::str::FromStr;
@@ -38,14 +38,6 @@ impl Part2 for Door {
     }
 }
 
-#[derive(Debug, Error)]
-pub enum ParseError {
-    #[error("Could not find the separator {0:?}")]
-    SeparatorNotFound(char),
-    #[error(transparent)]
-    ParseInt(#[from] std::num::ParseIntError),
-}
-
 #[derive(Debug, Clone, PartialEq)]
 struct Assignment(RangeInclusive<u32>);
 
@@ -59,16 +51,16 @@ impl Assignment {
     }
 }
 
+fn assignment(input: &str) -> PResult<'_, Assignment> {
+    let (rest, range) = range_inclusive(input)?;
+    Ok((rest, Assignment(range)))
+}
+
 impl FromStr for Assignment {
     type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (begin, end) = s
-            .split_once('-')
-            .ok_or(ParseError::SeparatorNotFound('-'))?;
-        Ok(Self(
-            begin.parse().map_err(ParseError::from)?..=end.parse().map_err(ParseError::from)?,
-        ))
+        finish(s, assignment)
     }
 }
 
@@ -89,16 +81,19 @@ impl FromStr for Pair {
     type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (first, second) = s
-            .split_once(',')
-            .ok_or(ParseError::SeparatorNotFound(','))?;
-        Ok(Pair(first.parse()?, second.parse()?))
+        finish(s, |input| {
+            let (rest, first) = assignment(input)?;
+            let (rest, ()) = tag(",")(rest)?;
+            let (rest, second) = assignment(rest)?;
+            Ok((rest, Pair(first, second)))
+        })
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_utils::parse::ParseErrorKind;
     use assert_matches::assert_matches;
     use itertools::assert_equal;
 
@@ -134,20 +129,53 @@ mod tests {
     fn invalid_input_produces_errors() {
         assert_matches!(
             "1-2;3-4".parse::<Pair>(),
-            Err(ParseError::SeparatorNotFound(','))
+            Err(ParseError {
+                kind: ParseErrorKind::Tag(","),
+                ..
+            })
         );
         assert_matches!(
             "1-2,3,4".parse::<Pair>(),
-            Err(ParseError::SeparatorNotFound('-'))
+            Err(ParseError {
+                kind: ParseErrorKind::Tag("-"),
+                ..
+            })
         );
         assert_matches!(
             "1—2,3-4".parse::<Pair>(),
-            Err(ParseError::SeparatorNotFound('-'))
+            Err(ParseError {
+                kind: ParseErrorKind::Tag("-"),
+                ..
+            })
+        );
+        assert_matches!(
+            "NaN-2,3-4".parse::<Pair>(),
+            Err(ParseError {
+                kind: ParseErrorKind::Integer,
+                ..
+            })
+        );
+        assert_matches!(
+            "1-2-3,3-4".parse::<Pair>(),
+            Err(ParseError {
+                kind: ParseErrorKind::Tag(","),
+                ..
+            })
+        );
+        assert_matches!(
+            "1-2,3.0-4".parse::<Pair>(),
+            Err(ParseError {
+                kind: ParseErrorKind::Tag("-"),
+                ..
+            })
+        );
+        assert_matches!(
+            "1-2,3-∞".parse::<Pair>(),
+            Err(ParseError {
+                kind: ParseErrorKind::Integer,
+                ..
+            })
         );
-        assert_matches!("NaN-2,3-4".parse::<Pair>(), Err(ParseError::ParseInt(_)));
-        assert_matches!("1-2-3,3-4".parse::<Pair>(), Err(ParseError::ParseInt(_)));
-        assert_matches!("1-2,3.0-4".parse::<Pair>(), Err(ParseError::ParseInt(_)));
-        assert_matches!("1-2,3-∞".parse::<Pair>(), Err(ParseError::ParseInt(_)));
     }
 
     #[test]
@@ -1,6 +1,5 @@
 use aoc_companion::prelude::*;
 
-use itertools::Itertools;
 use thiserror::Error;
 
 pub(crate) struct Door<'input> {
@@ -30,11 +29,22 @@ pub(crate) enum Error {
 }
 
 fn disjoint_subseq_index(signal: &str, n: usize) -> Option<usize> {
-    signal
-        .as_bytes()
-        .windows(n)
-        .position(|window| window.iter().all_unique())
-        .map(|i| i + n)
+    let signal = signal.as_bytes();
+    let mut counts = [0u32; 256];
+    let mut left = 0;
+
+    for right in 0..signal.len() {
+        counts[signal[right] as usize] += 1;
+        while counts[signal[right] as usize] > 1 {
+            counts[signal[left] as usize] -= 1;
+            left += 1;
+        }
+        if right - left + 1 == n {
+            return Some(right + 1);
+        }
+    }
+
+    None
 }
 
 #[cfg(test)]
@@ -3,28 +3,32 @@ use aoc_companion::prelude::*;
 use itertools::Itertools;
 use thiserror::Error;
 
-use std::collections::HashMap;
+use std::cell::Cell;
+use std::collections::BTreeMap;
+use std::ffi::OsString;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
 pub struct Door {
-    session: Vec<Command>,
+    fs: Filesystem,
 }
 
 impl ParseInput<'_> for Door {
-    type Error = ParseError;
+    type Error = Error;
 
     fn parse(input: &str) -> Result<Self, Self::Error> {
-        parse_session(input).map(|session| Self { session })
+        let session = parse_session(input)?;
+        let fs = Filesystem::from_session(&session)?;
+        Ok(Self { fs })
     }
 }
 
 impl Part1 for Door {
     type Output = usize;
-    type Error = RuntimeError;
+    type Error = std::convert::Infallible;
 
     fn part1(&self) -> Result<Self::Output, Self::Error> {
-        Filesystem::from_session(&self.session).map(|fs| fs.total_size_of_small_directories())
+        Ok(self.fs.total_size_of_small_directories())
     }
 }
 
@@ -33,8 +37,8 @@ impl Part2 for Door {
     type Error = RuntimeError;
 
     fn part2(&self) -> Result<Self::Output, Self::Error> {
-        Filesystem::from_session(&self.session)
-            .and_then(|fs| fs.size_of_directory_to_delete_to_make_space_for(30000000))
+        self.fs
+            .size_of_directory_to_delete_to_make_space_for(30000000)
     }
 }
 
@@ -60,12 +64,25 @@ pub enum RuntimeError {
     InconsistentDirectoryListing,
     #[error("Not enough space on device")]
     NotEnoughSpace,
+    #[error("Directory not empty: {0}")]
+    DirectoryNotEmpty(PathBuf),
+}
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+    #[error(transparent)]
+    Runtime(#[from] RuntimeError),
 }
 
 #[derive(Debug, Clone, PartialEq)]
 enum Command {
     Cd(PathBuf),
     Ls(Vec<DirListingEntry>),
+    Mkdir(PathBuf),
+    Rm { path: PathBuf, recursive: bool },
+    Mv { from: PathBuf, to: PathBuf },
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -84,6 +101,29 @@ impl FromStr for Command {
             Ok(Command::Ls(
                 output.trim().lines().map(str::parse).try_collect()?,
             ))
+        } else if let Some(dirname) = s.strip_prefix("mkdir ") {
+            Ok(Command::Mkdir(dirname.trim_end().into()))
+        } else if let Some(rest) = s.strip_prefix("rm ") {
+            let rest = rest.trim_end();
+            match rest.strip_prefix("-r ") {
+                Some(path) => Ok(Command::Rm {
+                    path: path.into(),
+                    recursive: true,
+                }),
+                None => Ok(Command::Rm {
+                    path: rest.into(),
+                    recursive: false,
+                }),
+            }
+        } else if let Some(rest) = s.strip_prefix("mv ") {
+            let (from, to) = rest
+                .trim_end()
+                .split_once(' ')
+                .ok_or_else(|| ParseError::UnknownCommand(s.to_owned()))?;
+            Ok(Command::Mv {
+                from: from.into(),
+                to: to.into(),
+            })
         } else {
             Err(ParseError::UnknownCommand(s.to_owned()))
         }
@@ -121,111 +161,298 @@ fn parse_session(input: &str) -> Result<Vec<Command>, ParseError> {
     }
 }
 
-#[derive(Debug, Default, Clone)]
+type NodeId = usize;
+
+const ROOT: NodeId = 0;
+
+/// One directory in a [`Filesystem`]'s arena: its own listing (once seen)
+/// plus the already-computed size of the whole subtree rooted here, filled
+/// in once by [`Filesystem::from_session`] so later queries are plain
+/// reads instead of re-walking the tree.
+#[derive(Debug, Clone, Default)]
+struct DirNode {
+    path: PathBuf,
+    children: BTreeMap<OsString, NodeId>,
+    files: Vec<(usize, OsString)>,
+    listed: bool,
+    cached_size: Cell<Option<usize>>,
+}
+
+#[derive(Debug, Clone)]
 struct SessionState {
-    cwd: PathBuf,
+    cwd: Vec<NodeId>,
     fs: Filesystem,
 }
 
+impl Default for SessionState {
+    fn default() -> Self {
+        SessionState {
+            cwd: vec![ROOT],
+            fs: Filesystem {
+                nodes: vec![DirNode {
+                    path: PathBuf::from("/"),
+                    ..Default::default()
+                }],
+            },
+        }
+    }
+}
+
 impl SessionState {
+    fn cwd(&self) -> NodeId {
+        *self.cwd.last().expect("cwd stack always has the root on it")
+    }
+
     fn execute(mut self, cmd: &Command) -> Result<Self, RuntimeError> {
         match cmd {
             Command::Cd(dir) if dir == Path::new("..") => {
-                let ok = self.cwd.pop();
-                ok.then_some(self)
-                    .ok_or(RuntimeError::NoSuchDirectory(PathBuf::from("/..")))
+                if self.cwd.len() > 1 {
+                    self.cwd.pop();
+                    Ok(self)
+                } else {
+                    Err(RuntimeError::NoSuchDirectory(PathBuf::from("/..")))
+                }
+            }
+            Command::Cd(dir) if dir == Path::new("/") => {
+                self.cwd.truncate(1);
+                Ok(self)
             }
             Command::Cd(dir) => {
-                if let Some(parent) = self.fs.0.get(&self.cwd) {
-                    self.cwd.push(dir);
-                    match parent.iter().find(|node| {
-                        matches!(node, FsNode::Directory(path) | FsNode::File(_, path) if path == &self.cwd)
-                    }) {
-                        Some(FsNode::Directory(_)) => Ok(self),
-                        Some(FsNode::File(..)) => Err(RuntimeError::NotADirectory(self.cwd)),
-                        None => Err(RuntimeError::NoSuchDirectory(self.cwd)),
+                let cwd = self.cwd();
+                let name = dir.as_os_str().to_owned();
+                let path = self.fs.nodes[cwd].path.join(dir);
+
+                if self.fs.nodes[cwd].listed {
+                    match self.fs.nodes[cwd].children.get(&name).copied() {
+                        Some(child) => {
+                            self.cwd.push(child);
+                            Ok(self)
+                        }
+                        None if self.fs.nodes[cwd].files.iter().any(|(_, n)| *n == name) => {
+                            Err(RuntimeError::NotADirectory(path))
+                        }
+                        None => Err(RuntimeError::NoSuchDirectory(path)),
                     }
                 } else {
-                    self.cwd.push(dir);
+                    let existing = self.fs.nodes[cwd].children.get(&name).copied();
+                    let child = existing.unwrap_or_else(|| {
+                        self.fs.nodes.push(DirNode {
+                            path,
+                            ..Default::default()
+                        });
+                        let id = self.fs.nodes.len() - 1;
+                        self.fs.nodes[cwd].children.insert(name, id);
+                        id
+                    });
+                    self.cwd.push(child);
                     Ok(self)
                 }
             }
-            Command::Ls(contents) => {
-                let prev = self.fs.0.insert(
-                    self.cwd.clone(),
-                    contents
-                        .iter()
-                        .map(|entry| FsNode::from_dir_entry(&self.cwd, entry))
-                        .collect(),
-                );
-
-                match prev {
-                    None => Ok(self),
-                    Some(p) if p == self.fs.0[&self.cwd] => Ok(self),
-                    Some(_) => Err(RuntimeError::InconsistentDirectoryListing),
+            Command::Ls(entries) => {
+                let cwd = self.cwd();
+                let mut children = BTreeMap::new();
+                let mut files = Vec::new();
+                for entry in entries {
+                    match entry {
+                        DirListingEntry::Directory(name) => {
+                            let name = name.as_os_str().to_owned();
+                            let path = self.fs.nodes[cwd].path.join(&name);
+                            let id = self.fs.nodes[cwd].children.get(&name).copied();
+                            let id = id.unwrap_or_else(|| {
+                                self.fs.nodes.push(DirNode {
+                                    path,
+                                    ..Default::default()
+                                });
+                                self.fs.nodes.len() - 1
+                            });
+                            children.insert(name, id);
+                        }
+                        DirListingEntry::File(size, name) => {
+                            files.push((*size, name.as_os_str().to_owned()));
+                        }
+                    }
+                }
+
+                let node = &self.fs.nodes[cwd];
+                if node.listed && (node.children != children || node.files != files) {
+                    return Err(RuntimeError::InconsistentDirectoryListing);
                 }
+
+                let node = &mut self.fs.nodes[cwd];
+                node.children = children;
+                node.files = files;
+                node.listed = true;
+                Ok(self)
             }
-        }
-    }
-}
+            Command::Mkdir(dir) => {
+                let cwd = self.cwd();
+                let name = dir.as_os_str().to_owned();
+                if self.fs.nodes[cwd].children.contains_key(&name)
+                    || self.fs.nodes[cwd].files.iter().any(|(_, n)| *n == name)
+                {
+                    return Err(RuntimeError::InconsistentDirectoryListing);
+                }
 
-#[derive(Debug, Clone, PartialEq)]
-enum FsNode {
-    File(usize, PathBuf),
-    Directory(PathBuf),
-}
+                let path = self.fs.nodes[cwd].path.join(&name);
+                self.fs.nodes.push(DirNode {
+                    path,
+                    listed: true,
+                    ..Default::default()
+                });
+                let id = self.fs.nodes.len() - 1;
+                self.fs.nodes[cwd].children.insert(name, id);
+                Ok(self)
+            }
+            Command::Rm { path, recursive } => {
+                let cwd = self.cwd();
+                let name = path.as_os_str().to_owned();
+
+                if let Some(&child) = self.fs.nodes[cwd].children.get(&name) {
+                    let child_node = &self.fs.nodes[child];
+                    if !recursive
+                        && (!child_node.children.is_empty() || !child_node.files.is_empty())
+                    {
+                        return Err(RuntimeError::DirectoryNotEmpty(child_node.path.clone()));
+                    }
+                    self.fs.nodes[cwd].children.remove(&name);
+                    Ok(self)
+                } else if let Some(idx) =
+                    self.fs.nodes[cwd].files.iter().position(|(_, n)| *n == name)
+                {
+                    self.fs.nodes[cwd].files.remove(idx);
+                    Ok(self)
+                } else {
+                    Err(RuntimeError::NoSuchDirectory(
+                        self.fs.nodes[cwd].path.join(path),
+                    ))
+                }
+            }
+            Command::Mv { from, to } => {
+                let cwd = self.cwd();
+                let from_name = from.as_os_str().to_owned();
+                let to_name = to.as_os_str().to_owned();
+
+                if self.fs.nodes[cwd].children.contains_key(&to_name)
+                    || self.fs.nodes[cwd].files.iter().any(|(_, n)| *n == to_name)
+                {
+                    return Err(RuntimeError::InconsistentDirectoryListing);
+                }
 
-impl FsNode {
-    fn from_dir_entry(cwd: &Path, entry: &DirListingEntry) -> Self {
-        match entry {
-            DirListingEntry::File(size, name) => FsNode::File(*size, cwd.join(name)),
-            DirListingEntry::Directory(name) => FsNode::Directory(cwd.join(name)),
+                if let Some(child) = self.fs.nodes[cwd].children.remove(&from_name) {
+                    let new_path = self.fs.nodes[cwd].path.join(&to_name);
+                    self.fs.rename_subtree(child, new_path);
+                    self.fs.nodes[cwd].children.insert(to_name, child);
+                    Ok(self)
+                } else if let Some(idx) = self.fs.nodes[cwd]
+                    .files
+                    .iter()
+                    .position(|(_, n)| *n == from_name)
+                {
+                    self.fs.nodes[cwd].files[idx].1 = to_name;
+                    Ok(self)
+                } else {
+                    Err(RuntimeError::NoSuchDirectory(
+                        self.fs.nodes[cwd].path.join(from),
+                    ))
+                }
+            }
         }
     }
 }
 
-#[derive(Debug, Default, Clone, PartialEq)]
-struct Filesystem(HashMap<PathBuf, Vec<FsNode>>);
+/// A directory tree built once from a terminal session, with every
+/// directory's subtree size computed up front: an arena of [`DirNode`]s
+/// rooted at `/`, each child addressed by [`NodeId`] rather than by
+/// re-walking paths. `total_size_of_small_directories` and
+/// `size_of_directory_to_delete_to_free` then reduce to a single linear
+/// scan over [`Self::directory_sizes`] instead of recomputing every
+/// directory's size from scratch on every call.
+#[derive(Debug, Clone)]
+struct Filesystem {
+    nodes: Vec<DirNode>,
+}
 
 impl Filesystem {
     fn from_session(session: &[Command]) -> Result<Self, RuntimeError> {
-        session
+        let state = session
             .iter()
-            .try_fold(SessionState::default(), SessionState::execute)
-            .map(|state| state.fs)
-    }
-
-    fn directory_size(&self, path: &Path) -> usize {
-        self.0.get(path).map_or(0, |nodes| {
-            nodes
-                .iter()
-                .map(|node| match node {
-                    FsNode::File(size, _) => *size,
-                    FsNode::Directory(path) => self.directory_size(path),
-                })
-                .sum()
+            .try_fold(SessionState::default(), SessionState::execute)?;
+        state.fs.fill_cached_sizes(ROOT);
+        Ok(state.fs)
+    }
+
+    /// Post-order: a directory's size is the sum of its own files' sizes
+    /// plus its children's (by now already cached) sizes.
+    fn fill_cached_sizes(&self, id: NodeId) -> usize {
+        if let Some(size) = self.nodes[id].cached_size.get() {
+            return size;
+        }
+        let node = &self.nodes[id];
+        let own_files_size = node.files.iter().map(|(size, _)| size).sum::<usize>();
+        let children_size = node
+            .children
+            .values()
+            .map(|&child| self.fill_cached_sizes(child))
+            .sum::<usize>();
+        let size = own_files_size + children_size;
+        node.cached_size.set(Some(size));
+        size
+    }
+
+    /// Rewrites `id`'s own path to `new_path`, along with every descendant's
+    /// path, so a directory moved or renamed elsewhere in the tree still
+    /// reports the path it's now reachable at.
+    fn rename_subtree(&mut self, id: NodeId, new_path: PathBuf) {
+        let children: Vec<(OsString, NodeId)> = self.nodes[id]
+            .children
+            .iter()
+            .map(|(name, &child)| (name.clone(), child))
+            .collect();
+        for (name, child) in children {
+            let child_path = new_path.join(&name);
+            self.rename_subtree(child, child_path);
+        }
+        self.nodes[id].path = new_path;
+    }
+
+    /// Walks the tree from the root rather than the arena's raw node list,
+    /// so a directory `rm`ed out of its parent's listing (and thus no
+    /// longer reachable, though its arena slot lingers) is excluded.
+    fn directory_sizes(&self) -> impl Iterator<Item = (&Path, usize)> + '_ {
+        let mut stack = vec![ROOT];
+        std::iter::from_fn(move || {
+            let id = stack.pop()?;
+            let node = &self.nodes[id];
+            stack.extend(node.children.values().copied());
+            Some((
+                node.path.as_path(),
+                node.cached_size
+                    .get()
+                    .expect("cached_size is filled for every reachable node by from_session"),
+            ))
         })
     }
 
     fn total_size_of_small_directories(&self) -> usize {
-        self.0
-            .keys()
-            .map(|dir| self.directory_size(dir))
+        self.directory_sizes()
+            .map(|(_, size)| size)
             .filter(|size| *size <= 100000)
             .sum()
     }
 
     fn size_of_directory_to_delete_to_free(&self, to_free: usize) -> Result<usize, RuntimeError> {
-        self.0
-            .keys()
-            .map(|dir| self.directory_size(dir))
+        self.directory_sizes()
+            .map(|(_, size)| size)
             .filter(|size| *size >= to_free)
             .min()
             .ok_or(RuntimeError::NotEnoughSpace)
     }
 
     fn total_size(&self) -> usize {
-        self.directory_size(Path::new("/"))
+        self.nodes[ROOT]
+            .cached_size
+            .get()
+            .expect("cached_size is filled for every node by from_session")
     }
 
     fn size_of_directory_to_delete_to_make_space_for(
@@ -302,43 +529,6 @@ $ ls
         ]
     }
 
-    fn example_filesystem() -> Filesystem {
-        use FsNode::*;
-        Filesystem(HashMap::from([
-            (
-                PathBuf::from("/"),
-                vec![
-                    Directory(PathBuf::from("/a")),
-                    File(14848514, PathBuf::from("/b.txt")),
-                    File(8504156, PathBuf::from("/c.dat")),
-                    Directory(PathBuf::from("/d")),
-                ],
-            ),
-            (
-                PathBuf::from("/a"),
-                vec![
-                    Directory(PathBuf::from("/a/e")),
-                    File(29116, PathBuf::from("/a/f")),
-                    File(2557, PathBuf::from("/a/g")),
-                    File(62596, PathBuf::from("/a/h.lst")),
-                ],
-            ),
-            (
-                PathBuf::from("/a/e"),
-                vec![File(584, PathBuf::from("/a/e/i"))],
-            ),
-            (
-                PathBuf::from("/d"),
-                vec![
-                    File(4060174, PathBuf::from("/d/j")),
-                    File(8033020, PathBuf::from("/d/d.log")),
-                    File(5626152, PathBuf::from("/d/d.ext")),
-                    File(7214296, PathBuf::from("/d/k")),
-                ],
-            ),
-        ]))
-    }
-
     #[test]
     fn session_is_parsed() {
         assert_eq!(parse_session(EXAMPLE_INPUT).unwrap(), example_session());
@@ -361,10 +551,16 @@ b.txt -> a.txt";
     }
 
     #[test]
-    fn example_filesystem_is_reconstructed() {
+    fn example_filesystem_is_reconstructed_with_cached_subtree_sizes() {
+        let fs = Filesystem::from_session(&example_session()).unwrap();
         assert_eq!(
-            Filesystem::from_session(&example_session()).unwrap(),
-            example_filesystem()
+            fs.directory_sizes().collect::<BTreeMap<_, _>>(),
+            BTreeMap::from([
+                (Path::new("/"), 48381165),
+                (Path::new("/a"), 94853),
+                (Path::new("/a/e"), 584),
+                (Path::new("/d"), 24933642),
+            ])
         );
     }
 
@@ -374,39 +570,31 @@ b.txt -> a.txt";
 $ cd foo
 $ ls
 42000 bar.txt";
+        let fs = Filesystem::from_session(&parse_session(TEST_SESSION).unwrap()).unwrap();
         assert_eq!(
-            Filesystem::from_session(&parse_session(TEST_SESSION).unwrap()).unwrap(),
-            Filesystem(HashMap::from([(
-                PathBuf::from("/foo"),
-                vec![FsNode::File(42000, PathBuf::from("/foo/bar.txt"))]
-            )]))
+            fs.directory_sizes().collect::<BTreeMap<_, _>>(),
+            BTreeMap::from([(Path::new("/"), 42000), (Path::new("/foo"), 42000)])
         );
     }
 
     #[test]
     fn directories_have_to_exist_when_parent_has_been_listed() {
-        const TEST_SESSION: &str = r"$ cd /
+        const BASE: &str = r"$ cd /
 $ ls
 dir foo
-42000 bar";
-        let fs = Filesystem::from_session(&parse_session(TEST_SESSION).unwrap()).unwrap();
-        let state = SessionState {
-            cwd: PathBuf::from("/"),
-            fs: fs.clone(),
-        };
+42000 bar
+";
 
         assert_matches!(
-            state.clone().execute(&Command::Cd(PathBuf::from("foo"))),
+            Filesystem::from_session(&parse_session(&format!("{BASE}$ cd foo")).unwrap()),
             Ok(..)
         );
-
         assert_matches!(
-            state.clone().execute(&Command::Cd(PathBuf::from("bar"))),
+            Filesystem::from_session(&parse_session(&format!("{BASE}$ cd bar")).unwrap()),
             Err(RuntimeError::NotADirectory(file)) if file == Path::new("/bar")
         );
-
         assert_matches!(
-            state.clone().execute(&Command::Cd(PathBuf::from("baz"))),
+            Filesystem::from_session(&parse_session(&format!("{BASE}$ cd baz")).unwrap()),
             Err(RuntimeError::NoSuchDirectory(dir)) if dir == Path::new("/baz")
         );
     }
@@ -414,7 +602,9 @@ dir foo
     #[test]
     fn total_size_of_small_directories() {
         assert_eq!(
-            example_filesystem().total_size_of_small_directories(),
+            Filesystem::from_session(&example_session())
+                .unwrap()
+                .total_size_of_small_directories(),
             95437
         );
     }
@@ -422,7 +612,8 @@ dir foo
     #[test]
     fn size_of_directory_to_delete() {
         assert_eq!(
-            example_filesystem()
+            Filesystem::from_session(&example_session())
+                .unwrap()
                 .size_of_directory_to_delete_to_make_space_for(30000000)
                 .unwrap(),
             24933642
@@ -431,10 +622,10 @@ dir foo
 
     #[test]
     fn trying_to_free_more_space_than_occupied_yields_error() {
-        let fs = Filesystem(HashMap::from([(
-            PathBuf::from("/"),
-            vec![FsNode::File(42000, PathBuf::from("/foo"))],
-        )]));
+        const TEST_SESSION: &str = r"$ cd /
+$ ls
+42000 foo";
+        let fs = Filesystem::from_session(&parse_session(TEST_SESSION).unwrap()).unwrap();
 
         assert_matches!(fs.size_of_directory_to_delete_to_free(40000), Ok(42000));
         assert_matches!(
@@ -446,8 +637,154 @@ dir foo
     #[test]
     fn trying_to_exceed_capacity_of_device_yields_error() {
         assert_matches!(
-            example_filesystem().size_of_directory_to_delete_to_make_space_for(80000000),
+            Filesystem::from_session(&example_session())
+                .unwrap()
+                .size_of_directory_to_delete_to_make_space_for(80000000),
             Err(RuntimeError::NotEnoughSpace)
         );
     }
+
+    #[test]
+    fn mkdir_creates_an_empty_directory() {
+        const SESSION: &str = r"$ cd /
+$ mkdir foo";
+        let fs = Filesystem::from_session(&parse_session(SESSION).unwrap()).unwrap();
+        assert_eq!(
+            fs.directory_sizes().collect::<BTreeMap<_, _>>(),
+            BTreeMap::from([(Path::new("/"), 0), (Path::new("/foo"), 0)])
+        );
+    }
+
+    #[test]
+    fn mkdir_on_an_existing_entry_errors() {
+        const SESSION: &str = r"$ cd /
+$ ls
+dir foo
+$ mkdir foo";
+        assert_matches!(
+            Filesystem::from_session(&parse_session(SESSION).unwrap()),
+            Err(RuntimeError::InconsistentDirectoryListing)
+        );
+    }
+
+    #[test]
+    fn rm_removes_a_file() {
+        const SESSION: &str = r"$ cd /
+$ ls
+100 foo
+$ rm foo";
+        let fs = Filesystem::from_session(&parse_session(SESSION).unwrap()).unwrap();
+        assert_eq!(
+            fs.directory_sizes().collect::<BTreeMap<_, _>>(),
+            BTreeMap::from([(Path::new("/"), 0)])
+        );
+    }
+
+    #[test]
+    fn rm_of_a_nonempty_directory_without_recursive_flag_errors() {
+        const SESSION: &str = r"$ cd /
+$ ls
+dir foo
+$ cd foo
+$ ls
+100 bar
+$ cd ..
+$ rm foo";
+        assert_matches!(
+            Filesystem::from_session(&parse_session(SESSION).unwrap()),
+            Err(RuntimeError::DirectoryNotEmpty(path)) if path == Path::new("/foo")
+        );
+    }
+
+    #[test]
+    fn rm_recursive_drops_the_whole_subtree() {
+        const SESSION: &str = r"$ cd /
+$ ls
+dir foo
+$ cd foo
+$ ls
+100 bar
+$ cd ..
+$ rm -r foo";
+        let fs = Filesystem::from_session(&parse_session(SESSION).unwrap()).unwrap();
+        assert_eq!(
+            fs.directory_sizes().collect::<BTreeMap<_, _>>(),
+            BTreeMap::from([(Path::new("/"), 0)])
+        );
+    }
+
+    #[test]
+    fn mv_renames_a_directory_and_rewrites_every_descendant_path() {
+        const SESSION: &str = r"$ cd /
+$ ls
+dir foo
+$ cd foo
+$ ls
+100 bar
+dir baz
+$ cd baz
+$ ls
+50 qux
+$ cd ..
+$ cd ..
+$ mv foo moved";
+        let fs = Filesystem::from_session(&parse_session(SESSION).unwrap()).unwrap();
+        assert_eq!(
+            fs.directory_sizes().collect::<BTreeMap<_, _>>(),
+            BTreeMap::from([
+                (Path::new("/"), 150),
+                (Path::new("/moved"), 150),
+                (Path::new("/moved/baz"), 50),
+            ])
+        );
+    }
+
+    #[test]
+    fn mv_renames_a_file_so_the_old_name_no_longer_resolves() {
+        const SESSION: &str = r"$ cd /
+$ ls
+100 foo
+$ mv foo bar
+$ rm foo";
+        assert_matches!(
+            Filesystem::from_session(&parse_session(SESSION).unwrap()),
+            Err(RuntimeError::NoSuchDirectory(path)) if path == Path::new("/foo")
+        );
+    }
+
+    #[test]
+    fn mv_onto_an_existing_name_errors() {
+        const SESSION: &str = r"$ cd /
+$ ls
+dir foo
+dir bar
+$ mv foo bar";
+        assert_matches!(
+            Filesystem::from_session(&parse_session(SESSION).unwrap()),
+            Err(RuntimeError::InconsistentDirectoryListing)
+        );
+    }
+
+    #[test]
+    fn directory_sizes_reflect_final_state_after_interleaved_mutations() {
+        const SESSION: &str = r"$ cd /
+$ ls
+dir a
+100 keep.txt
+$ cd a
+$ ls
+50 temp.txt
+$ cd ..
+$ rm -r a
+$ mkdir b
+$ cd b
+$ ls
+30 new.txt
+$ cd ..";
+        let fs = Filesystem::from_session(&parse_session(SESSION).unwrap()).unwrap();
+        assert_eq!(
+            fs.directory_sizes().collect::<BTreeMap<_, _>>(),
+            BTreeMap::from([(Path::new("/"), 130), (Path::new("/b"), 30)])
+        );
+    }
 }
@@ -19,6 +19,8 @@ pub(crate) enum ParseError {
 }
 
 impl<'input> ParseInput<'input> for Door {
+    type Error = ParseError;
+
     fn parse(input: &'input str) -> Result<Self, ParseError> {
         Ok(Self {
             map: input.parse()?,
@@ -27,14 +29,20 @@ impl<'input> ParseInput<'input> for Door {
 }
 
 impl Part1 for Door {
-    fn part1(&self) -> usize {
-        self.map.visible_tree_count()
+    type Output = usize;
+    type Error = std::convert::Infallible;
+
+    fn part1(&self) -> Result<usize, Self::Error> {
+        Ok(self.map.visible_tree_count())
     }
 }
 
 impl Part2 for Door {
-    fn part2(&self) -> usize {
-        self.map.max_scenic_score()
+    type Output = usize;
+    type Error = std::convert::Infallible;
+
+    fn part2(&self) -> Result<usize, Self::Error> {
+        Ok(self.map.max_scenic_score())
     }
 }
 
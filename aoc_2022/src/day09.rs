@@ -10,22 +10,34 @@ pub(crate) struct Door {
 }
 
 impl<'input> ParseInput<'input> for Door {
+    type Error = ParseError;
+
     fn parse(input: &'input str) -> Result<Self, ParseError> {
         parse_input(input).map(|motions| Self { motions })
     }
 }
 
 impl Part1 for Door {
-    fn part1(&self) -> usize {
+    type Output = usize;
+    type Error = std::convert::Infallible;
+
+    fn part1(&self) -> Result<usize, Self::Error> {
         let directions: Vec<_> = as_directions(self.motions.iter().cloned()).collect();
-        count_unique_positions(directions.into_iter().head_positions().tail_positions())
+        Ok(count_unique_positions(
+            directions.into_iter().head_positions().tail_positions(),
+        ))
     }
 }
 
 impl Part2 for Door {
-    fn part2(&self) -> usize {
+    type Output = usize;
+    type Error = std::convert::Infallible;
+
+    fn part2(&self) -> Result<usize, Self::Error> {
         let directions: Vec<_> = as_directions(self.motions.iter().cloned()).collect();
-        count_unique_positions(directions.into_iter().head_positions().tie_knots(10))
+        Ok(count_unique_positions(
+            directions.into_iter().head_positions().tie_knots(10),
+        ))
     }
 }
 
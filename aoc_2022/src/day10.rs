@@ -1,4 +1,5 @@
 use aoc_companion::prelude::*;
+use aoc_utils::ocr;
 
 use itertools::Itertools;
 use thiserror::Error;
@@ -10,18 +11,26 @@ pub(crate) struct Door {
 }
 
 impl<'input> ParseInput<'input> for Door {
+    type Error = ParseError;
+
     fn parse(input: &'input str) -> Result<Self, ParseError> {
         parse_input(input).map(|program| Self { program })
     }
 }
 
 impl Part1 for Door {
-    fn part1(&self) -> isize {
-        relevant_signal_strengths(execute(&self.program)).sum()
+    type Output = isize;
+    type Error = std::convert::Infallible;
+
+    fn part1(&self) -> Result<isize, Self::Error> {
+        Ok(relevant_signal_strengths(execute(&self.program)).sum())
     }
 }
 
 impl Part2 for Door {
+    type Output = String;
+    type Error = ReadError;
+
     fn part2(&self) -> Result<String, ReadError> {
         read_screen(&render(execute(&self.program)))
     }
@@ -41,8 +50,8 @@ pub(crate) enum ParseError {
 
 #[derive(Debug, Error)]
 pub(crate) enum ReadError {
-    #[error("Human help is needed in reading the displayed string:\n{0}")]
-    NeedToRead(String),
+    #[error("Could not decode the displayed string: {0}")]
+    Ocr(#[from] ocr::OcrError),
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -131,16 +140,148 @@ fn render(values: impl Iterator<Item = isize>) -> String {
 }
 
 fn read_screen(screen: &str) -> Result<String, ReadError> {
-    const FJUBULRZ_SCREEN: &str = "####...##.#..#.###..#..#.#....###..####.\n\
-                                   #.......#.#..#.#..#.#..#.#....#..#....#.\n\
-                                   ###.....#.#..#.###..#..#.#....#..#...#..\n\
-                                   #.......#.#..#.#..#.#..#.#....###...#...\n\
-                                   #....#..#.#..#.#..#.#..#.#....#.#..#....\n\
-                                   #.....##...##..###...##..####.#..#.####.";
-
-    match screen {
-        FJUBULRZ_SCREEN => Ok("FJUBULRZ".to_owned()),
-        _ => Err(ReadError::NeedToRead(screen.to_owned())),
+    Ok(ocr::decode_screen(&ocr::LARGE, screen)?)
+}
+
+/// One cycle of CPU state, as produced by stepping a [`Cpu`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Cycle {
+    number: usize,
+    x: isize,
+}
+
+/// A resumable stepper over [`execute`]'s per-cycle `x` values. `execute`
+/// already returns a lazy generator-backed iterator, so stepping is just
+/// pulling it one item at a time; this wrapper exists to give that a name and
+/// a place to track the current cycle for commands like `regs`.
+struct Cpu<I> {
+    cycles: std::iter::Enumerate<I>,
+    current: Option<Cycle>,
+}
+
+impl<I: Iterator<Item = isize>> Cpu<I> {
+    fn new(cycles: I) -> Self {
+        Self {
+            cycles: cycles.enumerate(),
+            current: None,
+        }
+    }
+
+    fn step(&mut self) -> Option<Cycle> {
+        let (index, x) = self.cycles.next()?;
+        let cycle = Cycle {
+            number: index + 1,
+            x,
+        };
+        self.current = Some(cycle);
+        Some(cycle)
+    }
+
+    fn current(&self) -> Option<Cycle> {
+        self.current
+    }
+}
+
+/// Renders the CRT buffer for however many cycles have run so far, mirroring
+/// [`render`]'s sprite-overlap rule per pixel, and marking the
+/// most-recently-drawn pixel with `@` instead of `#` so the sprite/beam
+/// interaction that produced it stands out.
+fn render_partial(history: &[isize]) -> String {
+    const WIDTH: usize = 40;
+
+    let last = history.len().saturating_sub(1);
+    let continuous: String = history
+        .iter()
+        .enumerate()
+        .map(|(idx, &sprite_pos)| {
+            let lit = (sprite_pos - 1..=sprite_pos + 1).contains(&((idx % WIDTH) as isize));
+            match (lit, idx == last) {
+                (true, true) => '@',
+                (true, false) => '#',
+                (false, _) => '.',
+            }
+        })
+        .collect();
+
+    continuous
+        .as_bytes()
+        .chunks(WIDTH)
+        .map(|line| String::from_utf8_lossy(line).into_owned())
+        .join("\n")
+}
+
+fn step_and_report<I: Iterator<Item = isize>>(
+    cpu: &mut Cpu<I>,
+    history: &mut Vec<isize>,
+    out: &mut impl std::io::Write,
+) -> std::io::Result<Option<Cycle>> {
+    let Some(cycle) = cpu.step() else {
+        writeln!(out, "program finished")?;
+        return Ok(None);
+    };
+    history.push(cycle.x);
+    writeln!(out, "x = {}, cycle = {}", cycle.x, cycle.number)?;
+    writeln!(out, "{}", render_partial(history))?;
+    Ok(Some(cycle))
+}
+
+/// Interactive cycle-stepping debugger, reading `step`, `run <n>`,
+/// `break <cycle>`, `regs` and `quit` commands and printing the `x` register,
+/// cycle number, and CRT buffer rendered so far after each step.
+///
+/// There's no `rustyline`-style line editor anywhere in this workspace to
+/// give this prompt history or completion, and no CLI plumbing in
+/// `aoc_companion` for an interactive per-door mode (its `Solution` trait
+/// only exposes `parse`/`part1`/`part2`), so rather than invent both from
+/// scratch for one day, this reads bare lines from stdin and is meant to be
+/// driven by hand (see the `debug_repl` test below) rather than through a
+/// `--debug` flag.
+fn debug(program: &[Instruction]) -> std::io::Result<()> {
+    use std::io::{BufRead, Write};
+
+    let mut cpu = Cpu::new(execute(program));
+    let mut history = Vec::new();
+    let mut breakpoint: Option<usize> = None;
+
+    let stdin = std::io::stdin();
+    let mut stdout = std::io::stdout();
+    loop {
+        write!(stdout, "(cycle {}) > ", history.len())?;
+        stdout.flush()?;
+
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line)? == 0 {
+            return Ok(());
+        }
+        let mut tokens = line.split_whitespace();
+        match tokens.next() {
+            Some("step" | "s") => {
+                step_and_report(&mut cpu, &mut history, &mut stdout)?;
+            }
+            Some("run") => {
+                let n: usize = tokens.next().and_then(|arg| arg.parse().ok()).unwrap_or(1);
+                for _ in 0..n {
+                    if step_and_report(&mut cpu, &mut history, &mut stdout)?.is_none() {
+                        break;
+                    }
+                }
+            }
+            Some("break") => {
+                breakpoint = tokens.next().and_then(|arg| arg.parse().ok());
+                writeln!(stdout, "breakpoint set at cycle {breakpoint:?}")?;
+            }
+            Some("regs") => match cpu.current() {
+                Some(cycle) => writeln!(stdout, "x = {}, cycle = {}", cycle.x, cycle.number)?,
+                None => writeln!(stdout, "program has not started")?,
+            },
+            Some("quit" | "q") => return Ok(()),
+            _ => writeln!(stdout, "commands: step, run <n>, break <cycle>, regs, quit")?,
+        }
+
+        if breakpoint.is_some_and(|target| history.len() >= target) {
+            writeln!(stdout, "-- breakpoint reached --")?;
+            breakpoint = None;
+        }
     }
 }
 
@@ -184,6 +325,38 @@ addx -5";
         assert_eq!(render(execute(EXAMPLE_INSTRUCTIONS)), EXAMPLE_RENDER);
     }
 
+    #[test]
+    #[ignore = "interactive; run explicitly (`cargo test -- --ignored debug_repl`) to step \
+                through the example program by hand"]
+    fn debug_repl() {
+        debug(EXAMPLE_INSTRUCTIONS).unwrap();
+    }
+
+    #[test]
+    fn rendered_letters_are_read_off_the_screen() {
+        const FJUBULRZ_SCREEN: &str = "####...##.#..#.###..#..#.#....###..####.\n\
+                                       #.......#.#..#.#..#.#..#.#....#..#....#.\n\
+                                       ###.....#.#..#.###..#..#.#....#..#...#..\n\
+                                       #.......#.#..#.#..#.#..#.#....###...#...\n\
+                                       #....#..#.#..#.#..#.#..#.#....#.#..#....\n\
+                                       #.....##...##..###...##..####.#..#.####.";
+        assert_eq!(read_screen(FJUBULRZ_SCREEN).unwrap(), "FJUBULRZ");
+    }
+
+    #[test]
+    fn read_screen_is_not_tied_to_one_specific_message() {
+        // read_screen defers entirely to aoc_utils::ocr's general decoder, so
+        // it isn't special-cased to the FJUBULRZ puzzle input above; any
+        // message built from the font's known glyphs decodes the same way.
+        const HELLO_SCREEN: &str = "#..#.####.#....#.....##..\n\
+                                    #..#.#....#....#....#..#.\n\
+                                    ####.###..#....#....#..#.\n\
+                                    #..#.#....#....#....#..#.\n\
+                                    #..#.#....#....#....#..#.\n\
+                                    #..#.####.####.####..##..";
+        assert_eq!(read_screen(HELLO_SCREEN).unwrap(), "HELLO");
+    }
+
     const EXAMPLE_INPUT: &str = r"addx 15
 addx -11
 addx 6
@@ -1,6 +1,8 @@
 use aoc_companion::prelude::*;
 
 use itertools::Itertools;
+use logos::Logos;
+use num_bigint::BigUint;
 use thiserror::Error;
 
 use std::collections::HashMap;
@@ -16,36 +18,76 @@ impl<'input> Solution<'input> for Door {
     }
 
     fn part1(&self) -> usize {
-        let mut game = Game::<u64>::from(&self.monkeys, Operation::DivByThree);
+        let mut game = Game::<u64, _>::from(&self.monkeys, DivideByThree);
         game.play_rounds(20);
         game.monkey_business()
     }
 
     fn part2(&self) -> usize {
-        let mut game = Game::<ModuloTableWorryLevel>::from(&self.monkeys, Operation::NoOp);
+        let mut game = Game::<ModuloTableWorryLevel, _>::from(&self.monkeys, NoRelief);
         game.play_rounds(10000);
         game.monkey_business()
     }
 }
 
+/// Tokens making up a monkey block, scanned out by [`Token::lexer`] ahead of
+/// parsing. Whitespace and the punctuation that merely separates tokens
+/// (`:` and `,`) carry no grammatical meaning of their own and are skipped
+/// by the lexer rather than being emitted as tokens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Logos)]
+#[logos(skip r"[ \t\r\n:,]+")]
+pub(crate) enum Token {
+    #[token("Monkey")]
+    #[token("monkey")]
+    Monkey,
+    #[token("Starting")]
+    Starting,
+    #[token("items")]
+    Items,
+    #[token("Operation")]
+    Operation,
+    #[token("new")]
+    New,
+    #[token("old")]
+    Old,
+    #[token("Test")]
+    Test,
+    #[token("divisible")]
+    Divisible,
+    #[token("by")]
+    By,
+    #[token("If")]
+    If,
+    #[token("true")]
+    True,
+    #[token("false")]
+    False,
+    #[token("throw")]
+    Throw,
+    #[token("to")]
+    To,
+    #[token("+")]
+    Plus,
+    #[token("*")]
+    Star,
+    #[token("=")]
+    Equals,
+    #[regex(r"[0-9]+", |lex| lex.slice().parse::<u64>().ok())]
+    Number(u64),
+}
+
 #[derive(Debug, Error)]
 pub(crate) enum ParseError {
-    #[error("Incomplete monkey input: not enough lines")]
-    NotEnoughLinesForMonkey,
-    #[error("Could not find monkey's starting items on line {0:?}")]
-    NoStartingItems(String),
-    #[error("Could not find monkey's operation on line {0:?}")]
-    NoOperation(String),
-    #[error("Could not find monkey's test divisor on line {0:?}")]
-    NoTestDivisor(String),
-    #[error("Could not find monkey's {0} target on line {1:?}")]
-    NoTarget(bool, String),
-    #[error("Failed to tokenize operation: {0:?}")]
-    OperationTokenization(String),
-    #[error("Unknown operator: {0:?}")]
-    UnknownOperator(String),
-    #[error(transparent)]
-    ParseIntError(#[from] std::num::ParseIntError),
+    #[error("unrecognized token at byte offset {0}")]
+    UnrecognizedToken(usize),
+    #[error("expected {expected} at byte offset {offset}, found {found:?}")]
+    UnexpectedToken {
+        expected: &'static str,
+        offset: usize,
+        found: Token,
+    },
+    #[error("expected {0}, but the monkey block ended")]
+    UnexpectedEof(&'static str),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -67,83 +109,164 @@ impl Monkey {
     }
 }
 
+/// A cursor over a monkey block's tokens, each paired with the byte offset
+/// it started at so [`ParseError`]s can point back at the offending token
+/// instead of echoing a whole line.
+struct Cursor<'t> {
+    tokens: &'t [(Token, usize)],
+    pos: usize,
+}
+
+impl<'t> Cursor<'t> {
+    fn new(tokens: &'t [(Token, usize)]) -> Self {
+        Cursor { tokens, pos: 0 }
+    }
+
+    fn next(&mut self) -> Option<(Token, usize)> {
+        let next = self.tokens.get(self.pos).copied();
+        self.pos += next.is_some() as usize;
+        next
+    }
+
+    fn expect(&mut self, expected: Token, what: &'static str) -> Result<(), ParseError> {
+        match self.next() {
+            Some((token, _)) if token == expected => Ok(()),
+            Some((found, offset)) => Err(ParseError::UnexpectedToken {
+                expected: what,
+                offset,
+                found,
+            }),
+            None => Err(ParseError::UnexpectedEof(what)),
+        }
+    }
+
+    fn number(&mut self, what: &'static str) -> Result<u64, ParseError> {
+        match self.next() {
+            Some((Token::Number(n), _)) => Ok(n),
+            Some((found, offset)) => Err(ParseError::UnexpectedToken {
+                expected: what,
+                offset,
+                found,
+            }),
+            None => Err(ParseError::UnexpectedEof(what)),
+        }
+    }
+
+    /// Consumes a run of comma-separated numbers, stopping as soon as the
+    /// next token isn't one (the comma itself was already dropped by the
+    /// lexer).
+    fn number_list(&mut self) -> Vec<u64> {
+        let mut items = Vec::new();
+        while let Some(&(Token::Number(n), _)) = self.tokens.get(self.pos) {
+            items.push(n);
+            self.pos += 1;
+        }
+        items
+    }
+
+    fn operand(&mut self) -> Result<Operand, ParseError> {
+        match self.next() {
+            Some((Token::Old, _)) => Ok(Operand::Old),
+            Some((Token::Number(n), _)) => Ok(Operand::Const(n)),
+            Some((found, offset)) => Err(ParseError::UnexpectedToken {
+                expected: "an operand",
+                offset,
+                found,
+            }),
+            None => Err(ParseError::UnexpectedEof("an operand")),
+        }
+    }
+
+    fn operator(&mut self) -> Result<Operator, ParseError> {
+        match self.next() {
+            Some((Token::Plus, _)) => Ok(Operator::Add),
+            Some((Token::Star, _)) => Ok(Operator::Mul),
+            Some((found, offset)) => Err(ParseError::UnexpectedToken {
+                expected: "an operator",
+                offset,
+                found,
+            }),
+            None => Err(ParseError::UnexpectedEof("an operator")),
+        }
+    }
+}
+
 impl FromStr for Monkey {
     type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if let &[
-            starting_items_line,
-            operation_line,
-            test_line,
-            true_line,
-            false_line,
-            ..,
-        ] = &s.lines().collect_vec()[1..]
-        {
-            Ok(Monkey {
-                starting_items: starting_items_line
-                    .trim()
-                    .strip_prefix("Starting items: ")
-                    .ok_or_else(|| ParseError::NoStartingItems(starting_items_line.to_owned()))?
-                    .split(", ")
-                    .map(str::parse)
-                    .try_collect()?,
-                operation: operation_line
-                    .trim()
-                    .strip_prefix("Operation: new = ")
-                    .ok_or_else(|| ParseError::NoOperation(operation_line.to_owned()))?
-                    .parse()?,
-                test_divisor: test_line
-                    .trim()
-                    .strip_prefix("Test: divisible by ")
-                    .ok_or_else(|| ParseError::NoTestDivisor(test_line.to_owned()))?
-                    .parse()?,
-                true_target: true_line
-                    .trim()
-                    .strip_prefix("If true: throw to monkey ")
-                    .ok_or_else(|| ParseError::NoTarget(true, true_line.to_owned()))?
-                    .parse()?,
-                false_target: false_line
-                    .trim()
-                    .strip_prefix("If false: throw to monkey ")
-                    .ok_or_else(|| ParseError::NoTarget(false, false_line.to_owned()))?
-                    .parse()?,
+        let tokens: Vec<(Token, usize)> = Token::lexer(s)
+            .spanned()
+            .map(|(token, span)| {
+                token
+                    .map(|token| (token, span.start))
+                    .map_err(|()| ParseError::UnrecognizedToken(span.start))
             })
-        } else {
-            Err(ParseError::NotEnoughLinesForMonkey)
-        }
+            .try_collect()?;
+        let mut tokens = Cursor::new(&tokens);
+
+        tokens.expect(Token::Monkey, "\"Monkey\"")?;
+        tokens.number("the monkey's index")?;
+
+        tokens.expect(Token::Starting, "\"Starting\"")?;
+        tokens.expect(Token::Items, "\"items\"")?;
+        let starting_items = tokens.number_list();
+
+        tokens.expect(Token::Operation, "\"Operation\"")?;
+        tokens.expect(Token::New, "\"new\"")?;
+        tokens.expect(Token::Equals, "\"=\"")?;
+        let lhs = tokens.operand()?;
+        let op = tokens.operator()?;
+        let rhs = tokens.operand()?;
+
+        tokens.expect(Token::Test, "\"Test\"")?;
+        tokens.expect(Token::Divisible, "\"divisible\"")?;
+        tokens.expect(Token::By, "\"by\"")?;
+        let test_divisor = tokens.number("the test divisor")?;
+
+        tokens.expect(Token::If, "\"If\"")?;
+        tokens.expect(Token::True, "\"true\"")?;
+        tokens.expect(Token::Throw, "\"throw\"")?;
+        tokens.expect(Token::To, "\"to\"")?;
+        tokens.expect(Token::Monkey, "\"monkey\"")?;
+        let true_target = tokens.number("the true branch's target monkey")? as usize;
+
+        tokens.expect(Token::If, "\"If\"")?;
+        tokens.expect(Token::False, "\"false\"")?;
+        tokens.expect(Token::Throw, "\"throw\"")?;
+        tokens.expect(Token::To, "\"to\"")?;
+        tokens.expect(Token::Monkey, "\"monkey\"")?;
+        let false_target = tokens.number("the false branch's target monkey")? as usize;
+
+        Ok(Monkey {
+            starting_items,
+            operation: Operation { lhs, op, rhs },
+            test_divisor,
+            true_target,
+            false_target,
+        })
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-enum Operation {
-    NoOp,
-    Add(u64),
-    Mul(u64),
-    MulBySelf,
-    DivByThree,
+/// One side of an [`Operation`]'s `lhs op rhs`: either a literal value or
+/// `old`, standing for the item's current worry level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operand {
+    Const(u64),
+    Old,
 }
 
-impl FromStr for Operation {
-    type Err = ParseError;
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operator {
+    Add,
+    Mul,
+}
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if let Some((op_str, rhs_str)) =
-            s.strip_prefix("old ").and_then(|rest| rest.split_once(' '))
-        {
-            match op_str {
-                "+" => Ok(Operation::Add(rhs_str.parse()?)),
-                "*" => Ok(if rhs_str == "old" {
-                    Operation::MulBySelf
-                } else {
-                    Operation::Mul(rhs_str.parse()?)
-                }),
-                _ => Err(ParseError::UnknownOperator(op_str.to_owned())),
-            }
-        } else {
-            Err(ParseError::OperationTokenization(s.to_owned()))
-        }
-    }
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Operation {
+    lhs: Operand,
+    op: Operator,
+    rhs: Operand,
 }
 
 fn parse_input(input: &str) -> Result<Vec<Monkey>, ParseError> {
@@ -166,12 +289,41 @@ impl WorryLevel for u64 {
     }
 
     fn apply(&self, op: &Operation) -> Self {
-        match op {
-            Operation::NoOp => *self,
-            Operation::Add(x) => self + x,
-            Operation::Mul(x) => self * x,
-            Operation::MulBySelf => self * self,
-            Operation::DivByThree => self / 3,
+        let value_of = |operand: &Operand| match operand {
+            Operand::Const(x) => *x,
+            Operand::Old => *self,
+        };
+        match op.op {
+            Operator::Add => value_of(&op.lhs) + value_of(&op.rhs),
+            Operator::Mul => value_of(&op.lhs) * value_of(&op.rhs),
+        }
+    }
+}
+
+/// A correctness oracle for [`ModuloTableWorryLevel`]: never truncates worry
+/// levels at all, so part 2 can be solved directly without the CRT residue
+/// tracking. Only fit for a handful of rounds in a test, since `old * old`
+/// makes worry levels grow without bound.
+impl WorryLevel for BigUint {
+    fn for_monkeys(monkeys: &[Monkey]) -> Vec<Vec<Self>> {
+        monkeys
+            .iter()
+            .map(|m| m.starting_items.iter().map(|&num| num.into()).collect())
+            .collect()
+    }
+
+    fn divisible_by(&self, divisor: &u64) -> bool {
+        self % divisor == BigUint::ZERO
+    }
+
+    fn apply(&self, op: &Operation) -> Self {
+        let value_of = |operand: &Operand| match operand {
+            Operand::Const(x) => BigUint::from(*x),
+            Operand::Old => self.clone(),
+        };
+        match op.op {
+            Operator::Add => value_of(&op.lhs) + value_of(&op.rhs),
+            Operator::Mul => value_of(&op.lhs) * value_of(&op.rhs),
         }
     }
 }
@@ -202,49 +354,132 @@ impl WorryLevel for ModuloTableWorryLevel {
     }
 
     fn apply(&self, op: &Operation) -> Self {
-        match op {
-            Operation::NoOp => self.clone(),
-            Operation::Add(x) => Self {
-                modulos: self
-                    .modulos
-                    .iter()
-                    .map(|(div, modulo)| (*div, (modulo + x) % div))
-                    .collect(),
-            },
-            Operation::Mul(x) => Self {
-                modulos: self
-                    .modulos
-                    .iter()
-                    .map(|(div, modulo)| (*div, (modulo * x) % div))
-                    .collect(),
+        let lhs = self.operand(&op.lhs);
+        let rhs = self.operand(&op.rhs);
+        match op.op {
+            Operator::Add => lhs.combine(&rhs, |div, a, b| (a + b) % div),
+            Operator::Mul => lhs.combine(&rhs, |div, a, b| (a * b) % div),
+        }
+    }
+}
+
+impl ModuloTableWorryLevel {
+    /// Resolves `operand` to a full per-divisor residue table: `Old` is the
+    /// current worry level as-is, `Const(c)` is `c` reduced modulo every
+    /// divisor this item is tracked against.
+    fn operand(&self, operand: &Operand) -> Self {
+        match operand {
+            Operand::Old => self.clone(),
+            Operand::Const(c) => Self {
+                modulos: self.modulos.keys().map(|div| (*div, c % div)).collect(),
             },
-            Operation::MulBySelf => Self {
-                modulos: self
-                    .modulos
+        }
+    }
+
+    /// Combines `self` and `other`'s residue tables divisor-by-divisor via
+    /// `f(divisor, self's residue, other's residue)`.
+    fn combine(&self, other: &Self, f: impl Fn(u64, u64, u64) -> u64) -> Self {
+        Self {
+            modulos: self
+                .modulos
+                .iter()
+                .map(|(div, a)| (*div, f(*div, *a, other.modulos[div])))
+                .collect(),
+        }
+    }
+}
+
+/// Like [`ModuloTableWorryLevel`], but reduces each item modulo a single
+/// common multiple of all the monkeys' test divisors instead of maintaining
+/// a per-divisor residue table. Divisibility by any individual divisor is
+/// preserved since the modulus is a multiple of it, and every inspection
+/// avoids the `HashMap` allocation and hashing `ModuloTableWorryLevel` pays
+/// for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct SingleModulusWorryLevel {
+    value: u64,
+    modulus: u64,
+}
+
+impl WorryLevel for SingleModulusWorryLevel {
+    fn for_monkeys(monkeys: &[Monkey]) -> Vec<Vec<Self>> {
+        let modulus = monkeys.iter().map(|m| m.test_divisor).product();
+        monkeys
+            .iter()
+            .map(|m| {
+                m.starting_items
                     .iter()
-                    .map(|(div, modulo)| (*div, (modulo * modulo) % div))
-                    .collect(),
-            },
-            Operation::DivByThree => panic!("Not implemented"),
+                    .map(|num| Self {
+                        value: num % modulus,
+                        modulus,
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn divisible_by(&self, divisor: &u64) -> bool {
+        self.value.is_multiple_of(*divisor)
+    }
+
+    fn apply(&self, op: &Operation) -> Self {
+        let value_of = |operand: &Operand| match operand {
+            Operand::Const(x) => x % self.modulus,
+            Operand::Old => self.value,
+        };
+        let value = match op.op {
+            Operator::Add => (value_of(&op.lhs) + value_of(&op.rhs)) % self.modulus,
+            Operator::Mul => (value_of(&op.lhs) * value_of(&op.rhs)) % self.modulus,
+        };
+        Self {
+            value,
+            modulus: self.modulus,
         }
     }
 }
 
+/// The relief a monkey applies to an item's worry level once it's done
+/// inspecting it, before throwing it on. Kept separate from [`Operation`]
+/// since it's a property of the game being played, not of any one monkey.
+trait Relief<W> {
+    fn apply(&self, worry: &W) -> W;
+}
+
+/// Part 1's relief: the worry level is divided by three and rounded down.
+#[derive(Debug, Clone, Copy)]
+struct DivideByThree;
+
+impl Relief<u64> for DivideByThree {
+    fn apply(&self, worry: &u64) -> u64 {
+        worry / 3
+    }
+}
+
+/// Part 2's relief: there is none, so the worry level passes through as-is.
+#[derive(Debug, Clone, Copy)]
+struct NoRelief;
+
+impl<W: Clone> Relief<W> for NoRelief {
+    fn apply(&self, worry: &W) -> W {
+        worry.clone()
+    }
+}
+
 #[derive(Debug)]
-struct Game<'m, W: WorryLevel> {
+struct Game<'m, W: WorryLevel, R: Relief<W>> {
     monkeys: &'m [Monkey],
     worry_levels: Vec<Vec<W>>,
     inspection_counts: Vec<usize>,
-    end_of_round_op: Operation,
+    relief: R,
 }
 
-impl<'m, W: WorryLevel> Game<'m, W> {
-    fn from(monkeys: &'m [Monkey], end_of_round_op: Operation) -> Self {
+impl<'m, W: WorryLevel, R: Relief<W>> Game<'m, W, R> {
+    fn from(monkeys: &'m [Monkey], relief: R) -> Self {
         Game {
             monkeys,
             worry_levels: W::for_monkeys(monkeys),
             inspection_counts: vec![0; monkeys.len()],
-            end_of_round_op,
+            relief,
         }
     }
 
@@ -253,7 +488,7 @@ impl<'m, W: WorryLevel> Game<'m, W> {
             let items = std::mem::take(&mut self.worry_levels[idx]);
             self.inspection_counts[idx] += items.len();
             for item in items {
-                let new_worry = item.apply(&monkey.operation).apply(&self.end_of_round_op);
+                let new_worry = self.relief.apply(&item.apply(&monkey.operation));
                 let target = monkey.target(&new_worry);
                 self.worry_levels[target].push(new_worry);
             }
@@ -290,7 +525,7 @@ mod tests {
     #[test]
     fn worry_levels_after_first_round() {
         let monkeys = example_monkeys();
-        let mut game = Game::<u64>::from(&monkeys, Operation::DivByThree);
+        let mut game = Game::<u64, _>::from(&monkeys, DivideByThree);
         game.play_round();
         assert_eq!(game.worry_levels, WORRY_LEVELS_AFTER_FIRST_ROUND);
     }
@@ -298,7 +533,7 @@ mod tests {
     #[test]
     fn inspection_counts_after_20_rounds() {
         let monkeys = example_monkeys();
-        let mut game = Game::<u64>::from(&monkeys, Operation::DivByThree);
+        let mut game = Game::<u64, _>::from(&monkeys, DivideByThree);
         game.play_rounds(20);
         assert_eq!(game.inspection_counts, INSPECTION_COUNTS_AFTER_20_ROUNDS);
     }
@@ -306,7 +541,7 @@ mod tests {
     #[test]
     fn answer_for_part_1() {
         let monkeys = example_monkeys();
-        let mut game = Game::<u64>::from(&monkeys, Operation::DivByThree);
+        let mut game = Game::<u64, _>::from(&monkeys, DivideByThree);
         game.play_rounds(20);
         assert_eq!(game.monkey_business(), 10605);
     }
@@ -314,7 +549,35 @@ mod tests {
     #[test]
     fn answer_for_part_2() {
         let monkeys = example_monkeys();
-        let mut game = Game::<ModuloTableWorryLevel>::from(&monkeys, Operation::NoOp);
+        let mut game = Game::<ModuloTableWorryLevel, _>::from(&monkeys, NoRelief);
+        game.play_rounds(10000);
+        assert_eq!(game.monkey_business(), 2713310158);
+    }
+
+    // Without any relief, worry levels grow without bound under repeated
+    // squaring ("old * old"), so the raw `BigUint` oracle is only run for a
+    // handful of rounds rather than the full 10000 part 2 plays: by then its
+    // numbers are already thousands of digits long. That's still enough
+    // rounds for every monkey to pass items through several times and for
+    // the residue tables to diverge from the raw values if they were wrong.
+    #[test]
+    fn modulo_table_agrees_with_bignum_oracle() {
+        let monkeys = example_monkeys();
+        let rounds = 200;
+
+        let mut modulo_game = Game::<ModuloTableWorryLevel, _>::from(&monkeys, NoRelief);
+        modulo_game.play_rounds(rounds);
+
+        let mut bignum_game = Game::<BigUint, _>::from(&monkeys, NoRelief);
+        bignum_game.play_rounds(rounds);
+
+        assert_eq!(modulo_game.inspection_counts, bignum_game.inspection_counts);
+    }
+
+    #[test]
+    fn single_modulus_matches_modulo_table_for_answer_to_part_2() {
+        let monkeys = example_monkeys();
+        let mut game = Game::<SingleModulusWorryLevel, _>::from(&monkeys, NoRelief);
         game.play_rounds(10000);
         assert_eq!(game.monkey_business(), 2713310158);
     }
@@ -361,28 +624,44 @@ Monkey 3:
         [
             Monkey {
                 starting_items: vec![79, 98],
-                operation: Operation::Mul(19),
+                operation: Operation {
+                    lhs: Operand::Old,
+                    op: Operator::Mul,
+                    rhs: Operand::Const(19),
+                },
                 test_divisor: 23,
                 true_target: 2,
                 false_target: 3,
             },
             Monkey {
                 starting_items: vec![54, 65, 75, 74],
-                operation: Operation::Add(6),
+                operation: Operation {
+                    lhs: Operand::Old,
+                    op: Operator::Add,
+                    rhs: Operand::Const(6),
+                },
                 test_divisor: 19,
                 true_target: 2,
                 false_target: 0,
             },
             Monkey {
                 starting_items: vec![79, 60, 97],
-                operation: Operation::MulBySelf,
+                operation: Operation {
+                    lhs: Operand::Old,
+                    op: Operator::Mul,
+                    rhs: Operand::Old,
+                },
                 test_divisor: 13,
                 true_target: 1,
                 false_target: 3,
             },
             Monkey {
                 starting_items: vec![74],
-                operation: Operation::Add(3),
+                operation: Operation {
+                    lhs: Operand::Old,
+                    op: Operator::Add,
+                    rhs: Operand::Const(3),
+                },
                 test_divisor: 17,
                 true_target: 0,
                 false_target: 1,
@@ -1,11 +1,11 @@
 use aoc_companion::prelude::*;
-use aoc_utils::{geometry::Point, linalg::Vector};
+use aoc_utils::{geometry::Point, graph::dijkstra, linalg::Vector};
 
 use itertools::Itertools;
 use ndarray::Array2;
 use thiserror::Error;
 
-use std::collections::VecDeque;
+use std::collections::HashMap;
 use std::str::FromStr;
 
 pub struct Door {
@@ -26,7 +26,7 @@ impl Part1 for Door {
 
     fn part1(&self) -> Result<Self::Output, Self::Error> {
         let flow = self.map.dijkstra_flow();
-        Ok(flow[self.map.start])
+        Ok(flow[&self.map.start])
     }
 }
 
@@ -39,7 +39,7 @@ impl Part2 for Door {
         Ok(self
             .map
             .points_with_elevation(0)
-            .map(|p| flow[p])
+            .filter_map(|p| flow.get(&p).copied())
             .min()
             .unwrap())
     }
@@ -72,24 +72,18 @@ fn neighbors_checked(
 }
 
 impl Map {
-    fn dijkstra_flow(&self) -> Array2<usize> {
-        let mut flow = Array2::from_elem(self.elevations.raw_dim(), usize::MAX);
-        flow[self.end] = 0;
-
-        let mut active = VecDeque::from([self.end]);
-        while let Some(p) = active.pop_front() {
-            let distance = flow[p] + 1;
-            for n in neighbors_checked(p, self.elevations.shape()) {
-                if self.elevations[n] + 1 >= self.elevations[p] {
-                    if flow[n] > distance {
-                        flow[n] = distance;
-                        active.push_back(n);
-                    }
-                }
-            }
-        }
-
-        flow
+    fn dijkstra_flow(&self) -> HashMap<Vector<usize, 2>, usize> {
+        let shape = self.elevations.shape().to_vec();
+        dijkstra(
+            self.end,
+            |&p| {
+                neighbors_checked(p, &shape)
+                    .filter(|&n| self.elevations[n] + 1 >= self.elevations[p])
+                    .map(|n| (n, 1))
+                    .collect::<Vec<_>>()
+            },
+            |_| false,
+        )
     }
 
     fn points_with_elevation(
@@ -166,7 +160,7 @@ mod tests {
     fn dijkstra_flow_yields_correct_number_of_steps() {
         let map: Map = EXAMPLE_INPUT.parse().unwrap();
         let flow = map.dijkstra_flow();
-        assert_eq!(flow[map.start], 31);
+        assert_eq!(flow[&map.start], 31);
     }
 
     #[test]
@@ -174,7 +168,9 @@ mod tests {
         let map: Map = EXAMPLE_INPUT.parse().unwrap();
         let flow = map.dijkstra_flow();
         assert_eq!(
-            map.points_with_elevation(0).map(|p| flow[p]).min(),
+            map.points_with_elevation(0)
+                .filter_map(|p| flow.get(&p).copied())
+                .min(),
             Some(29)
         );
     }
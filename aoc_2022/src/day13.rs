@@ -11,20 +11,28 @@ pub(crate) struct Door {
 }
 
 impl<'input> ParseInput<'input> for Door {
+    type Error = ParseError;
+
     fn parse(input: &'input str) -> Result<Self, ParseError> {
         parse_input(input).map(|pairs| Self { pairs })
     }
 }
 
 impl Part1 for Door {
-    fn part1(&self) -> usize {
-        correctly_ordered_index_sum(&self.pairs)
+    type Output = usize;
+    type Error = std::convert::Infallible;
+
+    fn part1(&self) -> Result<usize, Self::Error> {
+        Ok(correctly_ordered_index_sum(&self.pairs))
     }
 }
 
 impl Part2 for Door {
-    fn part2(&self) -> usize {
-        decoder_key(&self.pairs)
+    type Output = usize;
+    type Error = std::convert::Infallible;
+
+    fn part2(&self) -> Result<usize, Self::Error> {
+        Ok(decoder_key(&self.pairs))
     }
 }
 
@@ -5,6 +5,7 @@ use itertools::Itertools;
 use thiserror::Error;
 
 use std::collections::HashSet;
+use std::fmt::{self, Display, Formatter};
 use std::str::FromStr;
 
 pub struct Door {
@@ -53,21 +54,28 @@ pub enum ParseError {
 struct Path(Vec<Vector<i32, 2>>);
 
 impl Path {
-    fn contains(&self, q: &Vector<i32, 2>) -> bool {
-        self.0.windows(2).any(|window| {
+    /// Every integer point covered by this path's segments (always
+    /// axis-aligned), including both endpoints of each one.
+    fn cells(&self) -> impl Iterator<Item = Vector<i32, 2>> + '_ {
+        self.0.windows(2).flat_map(|window| {
             let &[p1, p2] = window else {
                 panic!("Window size should match length of slice destructuring")
             };
-            (q[0] == p1[0]
-                && q[0] == p2[0]
-                && (p1[1].min(p2[1])..=p1[1].max(p2[1])).contains(&q[1]))
-                || (q[1] == p1[1]
-                    && q[1] == p2[1]
-                    && (p1[0].min(p2[0])..=p1[0].max(p2[0])).contains(&q[0]))
+            let step = Vector([(p2[0] - p1[0]).signum(), (p2[1] - p1[1]).signum()]);
+            let len = (p2[0] - p1[0]).abs().max((p2[1] - p1[1]).abs());
+            (0..=len).map(move |i| p1 + step * i)
         })
     }
 }
 
+/// Rasterizes every path's segments into the set of rock cells they
+/// occupy, so [`Pit::is_obstructed`] can check a single hash lookup
+/// instead of re-walking every path's segments on every step of every
+/// falling grain of sand.
+fn rasterize(paths: &[Path]) -> HashSet<Vector<i32, 2>> {
+    paths.iter().flat_map(Path::cells).collect()
+}
+
 impl FromStr for Path {
     type Err = ParseError;
 
@@ -97,8 +105,9 @@ const RIGHT_DOWN: Vector<i32, 2> = Vector([1, 1]);
 struct Pit {
     falling_sand_stack: Vec<Vector<i32, 2>>,
     settled_sand: HashSet<Vector<i32, 2>>,
-    paths: Vec<Path>,
+    rock: HashSet<Vector<i32, 2>>,
     floor: i32,
+    has_floor: bool,
 }
 
 impl Pit {
@@ -111,8 +120,9 @@ impl Pit {
         Pit {
             falling_sand_stack: vec![SOURCE],
             settled_sand: HashSet::new(),
-            paths: paths.to_vec(),
+            rock: rasterize(paths),
             floor,
+            has_floor: false,
         }
     }
 
@@ -123,27 +133,19 @@ impl Pit {
             .max()
             .unwrap_or(0)
             + 2;
-        let paths = paths
-            .iter()
-            .chain(
-                [Path(vec![
-                    Vector([i32::MIN, floor]),
-                    Vector([i32::MAX, floor]),
-                ])]
-                .iter(),
-            )
-            .cloned()
-            .collect();
         Pit {
             falling_sand_stack: vec![SOURCE],
             settled_sand: HashSet::new(),
-            paths,
+            rock: rasterize(paths),
             floor,
+            has_floor: true,
         }
     }
 
     fn is_obstructed(&self, q: &Vector<i32, 2>) -> bool {
-        self.settled_sand.contains(q) || self.paths.iter().any(|p| p.contains(q))
+        (self.has_floor && q[1] == self.floor)
+            || self.settled_sand.contains(q)
+            || self.rock.contains(q)
     }
 
     fn try_point(&self, q: Vector<i32, 2>) -> Option<Vector<i32, 2>> {
@@ -176,6 +178,37 @@ impl Pit {
     }
 }
 
+impl Display for Pit {
+    /// Draws the bounding box enclosing every rock cell, settled grain, and
+    /// the source, so a mismatch in [`Self::settled_sand`]'s count can be
+    /// tracked down to the exact cells responsible.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let cells = self.rock.iter().chain(&self.settled_sand).chain([&SOURCE]);
+        let min_x = cells.clone().map(|q| q[0]).min().unwrap_or(SOURCE[0]);
+        let max_x = cells.clone().map(|q| q[0]).max().unwrap_or(SOURCE[0]);
+        let min_y = cells.clone().map(|q| q[1]).min().unwrap_or(SOURCE[1]);
+        let max_y = cells.map(|q| q[1]).max().unwrap_or(SOURCE[1]);
+
+        for y in min_y..=max_y {
+            for x in min_x..=max_x {
+                let q = Vector([x, y]);
+                let ch = if q == SOURCE {
+                    '+'
+                } else if self.settled_sand.contains(&q) {
+                    'o'
+                } else if self.rock.contains(&q) {
+                    '#'
+                } else {
+                    '.'
+                };
+                write!(f, "{ch}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -223,4 +256,24 @@ mod tests {
         pit.fill_up();
         assert_eq!(pit.settled_sand.len(), 93);
     }
+
+    #[test]
+    fn pit_is_rendered_at_equilibrium() {
+        let paths = example_paths();
+        let mut pit = Pit::new_bottomless(&paths);
+        pit.fill_up();
+        assert_eq!(
+            pit.to_string(),
+            "......+...\n\
+             ..........\n\
+             ......o...\n\
+             .....ooo..\n\
+             ....#ooo##\n\
+             ...o#ooo#.\n\
+             ..###ooo#.\n\
+             ....oooo#.\n\
+             .o.ooooo#.\n\
+             #########.\n"
+        );
+    }
 }
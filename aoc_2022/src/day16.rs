@@ -6,6 +6,7 @@ use std::collections::{HashMap, VecDeque};
 use std::num::ParseIntError;
 
 const MINUTES: u32 = 30;
+const MINUTES_PART2: u32 = 26;
 
 pub struct Door {
     cave: Cave,
@@ -24,7 +25,16 @@ impl Part1 for Door {
     type Error = std::convert::Infallible;
 
     fn part1(&self) -> Result<Self::Output, Self::Error> {
-        Ok(find_optimal_strategy(&self.cave).flow)
+        Ok(best_release(&self.cave, 1, MINUTES))
+    }
+}
+
+impl Part2 for Door {
+    type Output = u32;
+    type Error = std::convert::Infallible;
+
+    fn part2(&self) -> Result<Self::Output, Self::Error> {
+        Ok(best_release(&self.cave, 2, MINUTES_PART2))
     }
 }
 
@@ -58,58 +68,183 @@ struct Valve {
 
 type Cave = HashMap<ValveId, Valve>;
 
-#[derive(Debug, Clone)]
-struct Strategy {
-    current: ValveId,
-    flow: u32,
-    flow_rate: u32,
-    time: u32,
+/// The all-pairs shortest-distance matrix over a fully-connected cave, dense
+/// over `0..flow_rates.len()` indices (one per surviving valve, `start`
+/// included), so the hot search below never allocates or clones a `Cave`:
+/// "removing a target" is setting a bit, "current" is an index, and a
+/// distance is a table lookup.
+struct DistanceMatrix {
+    flow_rates: Vec<u32>,
+    distances: Vec<Vec<u32>>,
+    start: usize,
 }
 
-impl Strategy {
-    fn new() -> Self {
-        Self {
-            current: "AA".into(),
-            flow: 0,
-            flow_rate: 0,
-            time: 0,
-        }
+fn build_distance_matrix(cave: &Cave) -> DistanceMatrix {
+    let fc_cave = fully_connect_cave(&reduce_cave(cave.clone()));
+    let ids: Vec<ValveId> = fc_cave.keys().copied().collect();
+
+    let flow_rates = ids.iter().map(|id| fc_cave[id].flow_rate).collect();
+    let distances = ids
+        .iter()
+        .map(|id| {
+            let connections = &fc_cave[id].connections;
+            ids.iter()
+                .map(|other| {
+                    if other == id {
+                        0
+                    } else {
+                        connections.get(other).copied().unwrap_or(u32::MAX)
+                    }
+                })
+                .collect()
+        })
+        .collect();
+    let start = ids.iter().position(|&id| id == "AA".into()).unwrap();
+
+    DistanceMatrix {
+        flow_rates,
+        distances,
+        start,
     }
+}
 
-    fn traverse_fully_connected_graph(mut self, final_time: u32, fc_cave: Cave) -> Strategy {
-        let Valve {
-            flow_rate,
-            connections,
-        } = &fc_cave[&self.current];
-
-        self.flow_rate += flow_rate;
-
-        connections
-            .iter()
-            .filter(|&(_, dist)| self.time + dist < final_time)
-            .map(|(target, dist)| {
-                let mut new_strat = self.clone();
-                new_strat.time += dist;
-                new_strat.flow += dist * new_strat.flow_rate;
-                new_strat.current = *target;
-                let mut new_cave = fc_cave.clone();
-                for valve in new_cave.values_mut() {
-                    valve.connections.remove(target);
-                }
-                new_strat.traverse_fully_connected_graph(final_time, new_cave)
-            })
-            .reduce(|lhs_strat, rhs_strat| std::cmp::max_by_key(lhs_strat, rhs_strat, |s| s.flow))
-            .unwrap_or_else(|| {
-                self.flow += (final_time - self.time) * self.flow_rate;
-                self.time = final_time;
-                self
+/// Searches every branch of the distance matrix to completion from
+/// `current` at `time` with `flow`/`flow_rate` accrued so far, recording
+/// into `best_by_mask` the highest total pressure release achievable for
+/// each bitmask of opened valves reached along the way (i.e. if no further
+/// valve were opened from that point on), so that single- and two-agent
+/// answers can both be read off the same search. `best` tracks the highest
+/// release found anywhere so far, letting a subtree whose optimistic upper
+/// bound can't beat it be skipped entirely. Returns the best release
+/// achievable from this state onward.
+fn search(
+    matrix: &DistanceMatrix,
+    current: usize,
+    time: u32,
+    final_time: u32,
+    flow: u32,
+    mut flow_rate: u32,
+    opened: u64,
+    best_by_mask: &mut HashMap<u64, u32>,
+    best: &mut u32,
+) -> u32 {
+    flow_rate += matrix.flow_rates[current];
+
+    let released_if_stopped_here = flow + (final_time - time) * flow_rate;
+    best_by_mask
+        .entry(opened)
+        .and_modify(|entry| *entry = (*entry).max(released_if_stopped_here))
+        .or_insert(released_if_stopped_here);
+    *best = (*best).max(released_if_stopped_here);
+
+    if upper_bound(matrix, time, final_time, flow, flow_rate, opened) <= *best {
+        return released_if_stopped_here;
+    }
+
+    (0..matrix.flow_rates.len())
+        .filter(|&target| target != current && opened & (1 << target) == 0)
+        .filter_map(|target| {
+            let dist = matrix.distances[current][target];
+            (dist != u32::MAX && time + dist < final_time).then(|| {
+                search(
+                    matrix,
+                    target,
+                    time + dist,
+                    final_time,
+                    flow + dist * flow_rate,
+                    flow_rate,
+                    opened | (1 << target),
+                    best_by_mask,
+                    best,
+                )
             })
+        })
+        .max()
+        .unwrap_or(released_if_stopped_here)
+}
+
+/// An optimistic upper bound on the total release reachable from this
+/// state: the release if nothing more were opened, plus the best case for
+/// every still-closed valve being opened as soon as physically possible —
+/// one every two minutes (one minute's travel, one to open), taking the
+/// highest flow rates first, regardless of whether the cave's actual layout
+/// could achieve that. Since it only ever over-promises, pruning a branch
+/// whose bound can't beat the best release found so far never discards the
+/// true optimum.
+fn upper_bound(
+    matrix: &DistanceMatrix,
+    time: u32,
+    final_time: u32,
+    flow: u32,
+    flow_rate: u32,
+    opened: u64,
+) -> u32 {
+    let mut remaining_rates: Vec<u32> = matrix
+        .flow_rates
+        .iter()
+        .enumerate()
+        .filter(|&(i, _)| opened & (1 << i) == 0)
+        .map(|(_, &rate)| rate)
+        .collect();
+    remaining_rates.sort_unstable_by(|a, b| b.cmp(a));
+
+    let mut bound = flow + (final_time - time) * flow_rate;
+    let mut t = time + 2;
+    for rate in remaining_rates {
+        if t >= final_time {
+            break;
+        }
+        bound += rate * (final_time - t);
+        t += 2;
     }
+    bound
+}
+
+/// Runs the single-agent search over `cave` to completion within
+/// `final_time` minutes, returning the best total pressure release
+/// reachable for each distinct bitmask of opened valves.
+fn best_release_by_mask(cave: &Cave, final_time: u32) -> HashMap<u64, u32> {
+    let matrix = build_distance_matrix(cave);
+    let mut best_by_mask = HashMap::new();
+    search(
+        &matrix,
+        matrix.start,
+        0,
+        final_time,
+        0,
+        0,
+        0,
+        &mut best_by_mask,
+        &mut 0,
+    );
+    best_by_mask
 }
 
-fn find_optimal_strategy(cave: &Cave) -> Strategy {
-    Strategy::new()
-        .traverse_fully_connected_graph(MINUTES, fully_connect_cave(&reduce_cave(cave.clone())))
+/// The best total release achievable by `agents` agents acting in parallel,
+/// each given `minutes` to open valves before time runs out. `agents == 1`
+/// reproduces the original single-agent answer; `agents == 2` is "teach an
+/// elephant" (each gets `minutes` turns, opening disjoint sets of valves).
+/// Computed generically from the single-agent per-mask map: one agent's best
+/// is the highest value in the map, and `k` agents' best is the highest sum
+/// over a `k`-way choice of pairwise-disjoint masks, found by recursing over
+/// the same map and excluding the bits already spoken for at each step.
+fn best_release(cave: &Cave, agents: usize, minutes: u32) -> u32 {
+    let masks: Vec<(u64, u32)> = best_release_by_mask(cave, minutes).into_iter().collect();
+    best_release_for_agents(&masks, agents, 0)
+}
+
+fn best_release_for_agents(masks: &[(u64, u32)], agents: usize, claimed: u64) -> u32 {
+    if agents == 0 {
+        return 0;
+    }
+    masks
+        .iter()
+        .filter(|&&(mask, _)| mask & claimed == 0)
+        .map(|&(mask, release)| {
+            release + best_release_for_agents(masks, agents - 1, claimed | mask)
+        })
+        .max()
+        .unwrap_or(0)
 }
 
 fn parse_input(input: &str) -> Result<Cave, ParseIntError> {
@@ -218,7 +353,15 @@ mod tests {
 
     #[test]
     fn maximum_flow_rate() {
-        assert_eq!(find_optimal_strategy(&reduced_example_cave()).flow, 1651);
+        assert_eq!(best_release(&reduced_example_cave(), 1, MINUTES), 1651);
+    }
+
+    #[test]
+    fn maximum_two_agent_flow_rate() {
+        assert_eq!(
+            best_release(&reduced_example_cave(), 2, MINUTES_PART2),
+            1707
+        );
     }
 
     #[test]
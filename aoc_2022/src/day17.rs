@@ -1,30 +1,59 @@
 use aoc_companion::prelude::*;
+use aoc_utils::geometry::Point;
 use aoc_utils::linalg::Vector;
 
 use itertools::Itertools;
 use thiserror::Error;
 
-use std::iter::Peekable;
+use std::collections::{HashMap, HashSet};
+use std::fmt::{self, Display, Formatter};
 
 pub(crate) struct Door {
     jet_pattern: Vec<Jet>,
 }
 
 impl<'input> ParseInput<'input> for Door {
+    type Error = ParseError;
+
     fn parse(input: &'input str) -> Result<Self, ParseError> {
         parse_jet_pattern(input).map(|jet_pattern| Self { jet_pattern })
     }
 }
 
 impl Part1 for Door {
-    fn part1(&self) -> isize {
-        cavern_after_dropping_rocks(2022, &self.jet_pattern).height()
+    type Output = isize;
+    type Error = std::convert::Infallible;
+
+    fn part1(&self) -> Result<isize, Self::Error> {
+        Ok(
+            cavern_after_dropping_rocks(2022, &self.jet_pattern, &CavernConfig::standard())
+                .height(),
+        )
     }
 }
 
 impl Part2 for Door {
-    fn part2(&self) -> isize {
-        determine_tower_height_with_matching(1000000000000, &self.jet_pattern)
+    type Output = isize;
+    type Error = std::convert::Infallible;
+
+    fn part2(&self) -> Result<isize, Self::Error> {
+        Ok(determine_tower_height_with_matching(
+            1000000000000,
+            &self.jet_pattern,
+            &CavernConfig::standard(),
+        ))
+    }
+}
+
+impl Door {
+    /// Answers the tower height after each of `targets` rocks, sharing a
+    /// single [`CycleAnalysis`] across all of them instead of re-running
+    /// cycle detection per target. Handy for sweeping over many rock counts,
+    /// e.g. to validate the closed form against a brute-force simulation or
+    /// to plot height as a function of rock count.
+    pub(crate) fn heights_for(&self, targets: &[usize]) -> Vec<isize> {
+        let analysis = CycleAnalysis::compute(&self.jet_pattern, &CavernConfig::standard());
+        targets.iter().map(|&n| analysis.height_after(n)).collect()
     }
 }
 
@@ -51,63 +80,210 @@ fn parse_jet_pattern(input: &str) -> Result<Vec<Jet>, ParseError> {
         .try_collect()
 }
 
+/// Parameters describing the falling-block chamber: how wide it is, how the
+/// walls are drawn, where a freshly spawned rock sits relative to the
+/// current tower top, and which rock shapes are in play. Pulling these out
+/// of `Cavern` and `drop_rock` keeps the simulation a general falling-block
+/// engine rather than one welded to the 2022 puzzle's 7-wide chamber and
+/// five tetrominoes.
 #[derive(Debug, Clone)]
+struct CavernConfig {
+    width: usize,
+    wall: u8,
+    /// Horizontal gap between the left wall and a freshly spawned rock.
+    spawn_left_margin: isize,
+    /// Vertical gap between the tower's current top and the bottom of a
+    /// freshly spawned rock.
+    spawn_bottom_margin: isize,
+    /// Each rock's cells, relative to its own bottom-left corner.
+    rocks: Vec<Vec<Vector<isize, 2>>>,
+}
+
+impl CavernConfig {
+    fn standard() -> Self {
+        Self {
+            width: 7,
+            wall: b'#',
+            spawn_left_margin: 2,
+            spawn_bottom_margin: 3,
+            rocks: ROCK_SEQUENCE.iter().map(|rock| rock.to_vec()).collect(),
+        }
+    }
+}
+
+#[derive(Debug)]
 struct Cavern {
     settled: ndarray::Array2<u8>,
+    /// The number of rows dropped so far by [`Self::prune_sealed_rows`],
+    /// added back on top of `settled`'s shrunk row count so that
+    /// [`Self::height`] stays accurate across pruning.
+    pruned_height: isize,
+    /// The number of rows `settled` started out with when freshly built by
+    /// [`Self::new`] (one floor row plus enough blank rows above it for the
+    /// tallest rock in `config.rocks` to spawn with its configured margin);
+    /// [`Self::height`] is zero for as long as `settled` stays at this size.
+    initial_rows: isize,
+    width: usize,
+    wall: u8,
 }
 
 impl Cavern {
-    fn new() -> Self {
+    fn new(config: &CavernConfig) -> Self {
+        let cols = config.width + 2;
+        let floor = vec![config.wall; cols];
+        let mut open = vec![config.wall; cols];
+        open[1..cols - 1].fill(b'.');
+
+        let max_rock_height = config
+            .rocks
+            .iter()
+            .flat_map(|rock| rock.iter().map(|coords| coords[0] + 1))
+            .max()
+            .unwrap_or(0);
+        let blank_rows = config.spawn_bottom_margin + max_rock_height;
+        let initial_rows = 1 + blank_rows;
+
+        let rows = std::iter::once(floor)
+            .chain(std::iter::repeat(open).take(blank_rows as usize))
+            .flatten()
+            .collect();
+
         Self {
-            settled: ndarray::Array2::from_shape_vec(
-                (8, 9),
-                [
-                    b"#########",
-                    b"#.......#",
-                    b"#.......#",
-                    b"#.......#",
-                    b"#.......#",
-                    b"#.......#",
-                    b"#.......#",
-                    b"#.......#",
-                ]
-                .into_iter()
-                .copied()
-                .flatten()
-                .collect(),
-            )
-            .unwrap(),
+            settled: ndarray::Array2::from_shape_vec((initial_rows as usize, cols), rows).unwrap(),
+            pruned_height: 0,
+            initial_rows,
+            width: config.width,
+            wall: config.wall,
         }
     }
 
     fn height(&self) -> isize {
-        self.settled.shape()[0] as isize - 8
+        self.settled.shape()[0] as isize - self.initial_rows + self.pruned_height
+    }
+
+    /// Translates an absolute row (as used by [`Rock`] coordinates, counted
+    /// from the very first floor row) into an index into `settled`, which
+    /// only retains rows from `pruned_height` upward.
+    fn local(&self, coords: Vector<isize, 2>) -> Vector<usize, 2> {
+        Vector([coords[0] - self.pruned_height, coords[1]])
+            .try_cast_as()
+            .unwrap()
     }
 
     fn test(&self, rock: &Rock) -> bool {
         rock.0
             .iter()
-            .all(|coords| self.settled[coords.try_cast_as().unwrap()] == b'.')
+            .all(|&coords| self.settled[self.local(coords)] == b'.')
     }
 
     fn add(&mut self, rock: &Rock) {
-        for coords in &rock.0 {
-            self.settled[coords.try_cast_as().unwrap()] = b'#';
+        for &coords in &rock.0 {
+            let local = self.local(coords);
+            self.settled[local] = b'#';
         }
 
         let new_height = rock.0.iter().map(|coords| coords[0]).max().unwrap();
         while self.height() < new_height {
+            let mut open_row = vec![self.wall; self.width + 2];
+            open_row[1..=self.width].fill(b'.');
             self.settled
-                .push_row(ndarray::ArrayView::from(b"#.......#"))
+                .push_row(ndarray::ArrayView::from(&open_row))
                 .unwrap();
         }
+
+        self.prune_sealed_rows();
     }
 
-    fn matches(&self, reference: &Cavern, check_amount: isize) -> bool {
-        use ndarray::s;
-        let reference_top = reference.settled.slice(s![-check_amount.., ..]);
-        let self_top = self.settled.slice(s![-check_amount.., ..]);
-        self_top == reference_top
+    /// Flood-fills from the open cells of the current top row down through
+    /// `.` cells (four-connected, blocked by `#`) to find the lowest row a
+    /// falling rock could still reach, then permanently drops every row
+    /// below that one (except the row directly underneath it, which is kept
+    /// around as the new floor so a rock can never be tested against a row
+    /// that's already been pruned away): no future rock can ever settle any
+    /// deeper, so there's no point keeping that history around and letting
+    /// `settled` grow without bound over an arbitrarily long drop.
+    fn prune_sealed_rows(&mut self) {
+        let (rows, cols) = self.settled.dim();
+        let top = rows as isize - 1;
+
+        let is_open = |p: Vector<isize, 2>| {
+            (0..rows as isize).contains(&p[0])
+                && (0..cols as isize).contains(&p[1])
+                && self.settled[p.try_cast_as::<usize>().unwrap()] == b'.'
+        };
+
+        let mut visited = HashSet::new();
+        let mut stack: Vec<_> = (0..cols as isize)
+            .map(|col| Vector([top, col]))
+            .filter(|&p| is_open(p))
+            .collect();
+        visited.extend(stack.iter().copied());
+
+        let mut lowest_reachable = top;
+        while let Some(p) = stack.pop() {
+            lowest_reachable = lowest_reachable.min(p[0]);
+            for n in p.nearest_neighbors().filter(|&n| is_open(n)) {
+                if visited.insert(n) {
+                    stack.push(n);
+                }
+            }
+        }
+
+        // `lowest_reachable` itself still has open cells (that's how the
+        // flood fill found it), so it can't double as the chamber's floor;
+        // keep the row below it too so a rock can never be tested against a
+        // row that's already been pruned away.
+        let cutoff = lowest_reachable.saturating_sub(1);
+        if cutoff > 0 {
+            self.settled = self.settled.slice(ndarray::s![cutoff.., ..]).to_owned();
+            self.pruned_height += cutoff;
+        }
+    }
+
+    /// Renders the chamber like [`Display`], but overlays `rock`'s cells as
+    /// `@`, for step-by-step traces where the falling rock hasn't settled
+    /// into `self.settled` yet. Rows above the chamber's current top (where
+    /// the rock is still falling through open air above anything ever
+    /// settled there) are synthesized as open rows rather than read from
+    /// `self.settled`. [`Display::fmt`] is just this with an empty rock, so
+    /// the two renderings can't drift apart.
+    fn render_with_rock(&self, rock: &Rock) -> String {
+        let local_rows = self.settled.dim().0 as isize;
+        let top = local_rows - 1 + self.pruned_height;
+        let highest = rock.0.iter().map(|p| p[0]).max().unwrap_or(top).max(top);
+
+        let mut frame = String::new();
+        for row in (self.pruned_height..=highest).rev() {
+            if self.pruned_height == 0 && row == 0 {
+                frame += &format!("+{}+\n", "-".repeat(self.width));
+                continue;
+            }
+            frame.push('|');
+            let local_row = row - self.pruned_height;
+            for col in 1..=self.width as isize {
+                let ch = if rock.0.contains(&Vector([row, col])) {
+                    '@'
+                } else if local_row < local_rows {
+                    self.settled[[local_row as usize, col as usize]] as char
+                } else {
+                    '.'
+                };
+                frame.push(ch);
+            }
+            frame.push_str("|\n");
+        }
+        frame
+    }
+}
+
+impl Display for Cavern {
+    /// Renders the currently retained rows (see [`Self::prune_sealed_rows`])
+    /// top-to-bottom as the puzzle's own `|..#..|` chamber. The true floor
+    /// only shows up as `+-----+` while row zero is still retained, i.e.
+    /// before the very first round of pruning; once it's pruned away, the
+    /// bottom displayed row is just whatever's been kept as the new barrier.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.render_with_rock(&Rock(Vec::new())))
     }
 }
 
@@ -123,11 +299,34 @@ impl Rock {
 }
 
 fn drop_rock(
+    cavern: &mut Cavern,
+    rock: Rock,
+    jet_iter: &mut dyn Iterator<Item = (usize, Jet)>,
+    config: &CavernConfig,
+) {
+    drop_rock_traced(cavern, rock, jet_iter, config, &mut |_, _| {});
+}
+
+/// Like [`drop_rock`], but calls `on_frame` with the cavern and the rock's
+/// current position after every jet push and every downward step, turning
+/// the simulation from a black box that only returns a final height into
+/// something a caller can render (and animate, e.g. by sleeping between
+/// frames via `println!("{}", cavern.render_with_rock(rock))`) one step at a
+/// time. The frame after the last downward step of a drop shows the rock
+/// having just moved into the colliding position that stops it falling
+/// further, mirroring the check the simulation itself makes; it's reverted
+/// again before the rock is settled into the cavern.
+fn drop_rock_traced(
     cavern: &mut Cavern,
     mut rock: Rock,
     jet_iter: &mut dyn Iterator<Item = (usize, Jet)>,
+    config: &CavernConfig,
+    on_frame: &mut dyn FnMut(&Cavern, &Rock),
 ) {
-    rock.translate(Vector([cavern.height(), 0]));
+    rock.translate(Vector([
+        cavern.height() + config.spawn_bottom_margin + 1,
+        1 + config.spawn_left_margin,
+    ]));
     while cavern.test(&rock) {
         let (_, jet) = jet_iter.next().unwrap();
         match jet {
@@ -140,133 +339,186 @@ fn drop_rock(
                 Jet::Right => rock.translate(Vector([0, -1])),
             }
         }
+        on_frame(cavern, &rock);
+
         rock.translate(Vector([-1, 0]));
+        on_frame(cavern, &rock);
     }
 
     rock.translate(Vector([1, 0]));
     cavern.add(&rock);
 }
 
-fn drop_rocks_commensurate_to_jets<R, J>(
-    cavern: &mut Cavern,
-    rocks: R,
-    jet_iter: &mut Peekable<J>,
-) -> usize
-where
-    R: Iterator<Item = Rock> + Clone,
-    J: Iterator<Item = (usize, Jet)>,
-{
-    let mut n = 0;
-    let first_jet_idx = jet_iter.peek().unwrap().0;
-    loop {
-        for rock in rocks.clone() {
-            drop_rock(cavern, rock, jet_iter);
-            n += 1;
-        }
-        if jet_iter.peek().unwrap().0 == first_jet_idx {
-            break;
-        }
-    }
-    n
-}
-
-fn rock_factory() -> impl Iterator<Item = Rock> + Clone {
-    ROCK_SEQUENCE.iter().map(|rock| Rock(rock.to_vec()))
+fn rock_factory(config: &CavernConfig) -> impl Iterator<Item = Rock> + Clone + '_ {
+    config.rocks.iter().map(|rock| Rock(rock.clone()))
 }
 
 fn drop_multiple_rocks(
     cavern: &mut Cavern,
     n: usize,
     jet_iter: &mut dyn Iterator<Item = (usize, Jet)>,
+    config: &CavernConfig,
 ) {
-    for rock in rock_factory().cycle().take(n) {
-        drop_rock(cavern, rock, jet_iter);
+    for rock in rock_factory(config).cycle().take(n) {
+        drop_rock(cavern, rock, jet_iter, config);
     }
 }
 
-fn cavern_after_dropping_rocks(n: usize, jets: &[Jet]) -> Cavern {
-    let mut cavern = Cavern::new();
+fn cavern_after_dropping_rocks(n: usize, jets: &[Jet], config: &CavernConfig) -> Cavern {
+    let mut cavern = Cavern::new(config);
     drop_multiple_rocks(
         &mut cavern,
         n,
         &mut jets.iter().copied().enumerate().cycle(),
+        config,
     );
     cavern
 }
 
-fn determine_tower_height_with_matching(n: usize, jets: &[Jet]) -> isize {
-    const SEED_N: usize = 25;
-
-    let mut jet_iter = jets.iter().copied().enumerate().cycle().peekable();
-    let mut cavern = Cavern::new();
-
-    // Initially, fill the cavern with some amount of rocks to rule out transient effects from the straight floor.
-    let mut initial_rocks = SEED_N * ROCK_SEQUENCE.len();
-    drop_multiple_rocks(&mut cavern, initial_rocks, &mut jet_iter);
-    // Then drop a number of rocks commensurate to the jet pattern to ensure that we have sufficient reference data.
-    initial_rocks += drop_rocks_commensurate_to_jets(&mut cavern, rock_factory(), &mut jet_iter);
-    let initial_cavern = cavern.clone();
-
-    // Again drop a commensurate amount to determine how many rows to look at when matching.
-    let mut rocks_until_repeat =
-        drop_rocks_commensurate_to_jets(&mut cavern, rock_factory(), &mut jet_iter);
-    let number_of_rows_to_match = cavern.height() - initial_cavern.height();
-
-    // Repeat this until a match has been found. The segment of the tower by which it grew
-    // since `initial_cavern` is bound to repeat over and over.
-    while !cavern.matches(&initial_cavern, number_of_rows_to_match) {
-        rocks_until_repeat +=
-            drop_rocks_commensurate_to_jets(&mut cavern, rock_factory(), &mut jet_iter);
-    }
-    let matching_cavern = cavern.clone();
-    let repeating_segment_height = matching_cavern.height() - initial_cavern.height();
-
-    // We pretend as though we repeated this segment until just shy of the target amount of
-    // rocks had been placed. The remaining blocks will correspond to a partial segment and
-    // we determine the addition height through those by placing them on our tower (which in
-    // reality contains the repeating segment just twice).
-    let number_of_repeats = (n - initial_rocks) / rocks_until_repeat;
-    let remaining_rocks = (n - initial_rocks) % rocks_until_repeat;
-    drop_multiple_rocks(&mut cavern, remaining_rocks, &mut jet_iter);
-    let remaining_height = cavern.height() - matching_cavern.height();
-
-    initial_cavern.height()
-        + number_of_repeats as isize * repeating_segment_height
-        + remaining_height
+/// For each of the chamber's interior columns, the vertical distance from
+/// the global top of the tower down to the highest settled rock in that
+/// column, saturating to however much of the tower is currently retained
+/// (see [`Cavern::prune_sealed_rows`]) if no rock is found at all. Two
+/// states with the same profile (and the same rock/jet position, see
+/// [`determine_tower_height_with_matching`]) expose the same silhouette to
+/// whatever falls next, so they're bound to play out identically forever
+/// after.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct SurfaceProfile(Vec<usize>);
+
+impl SurfaceProfile {
+    fn of(cavern: &Cavern) -> Self {
+        let rows = cavern.settled.dim().0;
+        let top = rows - 1;
+        SurfaceProfile(
+            (1..=cavern.width)
+                .map(|col| {
+                    (0..rows)
+                        .find(|&depth| cavern.settled[[top - depth, col]] == b'#')
+                        .unwrap_or(top)
+                })
+                .collect(),
+        )
+    }
+}
+
+/// The result of running cycle detection once: a simulated prefix (heights
+/// after 0, 1, ..., `prefix_rocks` rocks, indexed directly by
+/// [`Self::height_after`]) followed by one full detected cycle's worth of
+/// heights, which repeats forever after. Computing this once and querying it
+/// for several targets is far cheaper than re-running
+/// [`determine_tower_height_with_matching`]'s simulation per target, since
+/// the expensive part — simulating rocks up through the first repeated
+/// state — is shared.
+struct CycleAnalysis {
+    /// `prefix_heights[i]` is the tower height after `i` rocks, for `i` in
+    /// `0..prefix_heights.len()`.
+    prefix_heights: Vec<isize>,
+    /// `cycle_heights[i]` is the tower height after
+    /// `prefix_heights.len() - 1 + i + 1` rocks, for `i` in `0..cycle_rocks`:
+    /// one full pass through the detected cycle, starting right after the
+    /// prefix.
+    cycle_heights: Vec<isize>,
+    cycle_rocks: usize,
+    cycle_height: isize,
+}
+
+impl CycleAnalysis {
+    /// Simulates rocks falling until the same `(rock_index mod
+    /// config.rocks.len(), jet_index mod jet_len, surface_profile)` state
+    /// recurs, recording the height after every rock along the way. Once a
+    /// state repeats, the rocks dropped and height gained between the two
+    /// occurrences are guaranteed to repeat for as long as the simulation
+    /// continues, so that span becomes the reusable cycle.
+    fn compute(jets: &[Jet], config: &CavernConfig) -> Self {
+        let mut cavern = Cavern::new(config);
+        let mut jet_iter = jets.iter().copied().enumerate().cycle().peekable();
+        let mut rocks = rock_factory(config).cycle().enumerate();
+        let mut seen: HashMap<(usize, usize, SurfaceProfile), usize> = HashMap::new();
+        let mut heights = vec![0];
+
+        loop {
+            let (rock_index, rock) = rocks.next().unwrap();
+            let jet_index = jet_iter.peek().unwrap().0;
+            drop_rock(&mut cavern, rock, &mut jet_iter, config);
+            heights.push(cavern.height());
+
+            let key = (
+                rock_index % config.rocks.len(),
+                jet_index % jets.len(),
+                SurfaceProfile::of(&cavern),
+            );
+            if let Some(&prefix_rocks) = seen.get(&key) {
+                let cycle_rocks = heights.len() - 1 - prefix_rocks;
+                let cycle_height = heights[heights.len() - 1] - heights[prefix_rocks];
+                let cycle_heights = heights[prefix_rocks + 1..].to_vec();
+                heights.truncate(prefix_rocks + 1);
+                return Self {
+                    prefix_heights: heights,
+                    cycle_heights,
+                    cycle_rocks,
+                    cycle_height,
+                };
+            }
+            seen.insert(key, heights.len() - 1);
+        }
+    }
+
+    /// Answers the tower height after `n` rocks in O(1), using the prefix
+    /// directly and the cycle's closed form beyond it.
+    fn height_after(&self, n: usize) -> isize {
+        if n < self.prefix_heights.len() {
+            return self.prefix_heights[n];
+        }
+        let rocks_since_prefix = n - (self.prefix_heights.len() - 1);
+        let full_cycles = (rocks_since_prefix - 1) / self.cycle_rocks;
+        let offset_in_cycle = (rocks_since_prefix - 1) % self.cycle_rocks;
+        self.cycle_heights[offset_in_cycle] + full_cycles as isize * self.cycle_height
+    }
 }
 
+/// Finds the tower's height after `n` rocks by detecting when the
+/// simulation enters a cycle, then fast-forwarding the remainder in closed
+/// form instead of simulating one rock at a time. See [`CycleAnalysis`].
+fn determine_tower_height_with_matching(n: usize, jets: &[Jet], config: &CavernConfig) -> isize {
+    CycleAnalysis::compute(jets, config).height_after(n)
+}
+
+/// The puzzle's five tetrominoes, each given relative to its own
+/// bottom-left corner; [`drop_rock`] translates a copy to the chamber's
+/// configured spawn point before dropping it.
 const ROCK_SEQUENCE: [&[Vector<isize, 2>]; 5] = [
     &[
-        Vector([4, 3]),
-        Vector([4, 4]),
-        Vector([4, 5]),
-        Vector([4, 6]),
+        Vector([0, 0]),
+        Vector([0, 1]),
+        Vector([0, 2]),
+        Vector([0, 3]),
     ],
     &[
-        Vector([5, 3]),
-        Vector([5, 4]),
-        Vector([4, 4]),
-        Vector([6, 4]),
-        Vector([5, 5]),
+        Vector([1, 0]),
+        Vector([1, 1]),
+        Vector([0, 1]),
+        Vector([2, 1]),
+        Vector([1, 2]),
     ],
     &[
-        Vector([4, 3]),
-        Vector([4, 4]),
-        Vector([4, 5]),
-        Vector([5, 5]),
-        Vector([6, 5]),
+        Vector([0, 0]),
+        Vector([0, 1]),
+        Vector([0, 2]),
+        Vector([1, 2]),
+        Vector([2, 2]),
     ],
     &[
-        Vector([4, 3]),
-        Vector([5, 3]),
-        Vector([6, 3]),
-        Vector([7, 3]),
+        Vector([0, 0]),
+        Vector([1, 0]),
+        Vector([2, 0]),
+        Vector([3, 0]),
     ],
     &[
-        Vector([4, 3]),
-        Vector([4, 4]),
-        Vector([5, 3]),
-        Vector([5, 4]),
+        Vector([0, 0]),
+        Vector([0, 1]),
+        Vector([1, 0]),
+        Vector([1, 1]),
     ],
 ];
 
@@ -284,29 +536,39 @@ mod tests {
 
     #[test]
     fn empty_cavern_has_height_zero() {
-        assert_eq!(Cavern::new().height(), 0);
+        assert_eq!(Cavern::new(&CavernConfig::standard()).height(), 0);
     }
 
     #[test]
     fn each_of_the_rocks_can_be_placed_in_an_empty_cavern() {
-        let cavern = Cavern::new();
-        assert!(ROCK_SEQUENCE
-            .map(|rock| Rock(rock.to_vec()))
-            .iter()
-            .all(|rock| cavern.test(rock)));
+        let config = CavernConfig::standard();
+        let cavern = Cavern::new(&config);
+        assert!(rock_factory(&config).all(|mut rock| {
+            rock.translate(Vector([
+                cavern.height() + config.spawn_bottom_margin + 1,
+                1 + config.spawn_left_margin,
+            ]));
+            cavern.test(&rock)
+        }));
     }
 
     #[test]
     fn after_adding_a_rock_to_the_cavern_the_height_increases() {
-        let mut cavern = Cavern::new();
-        cavern.add(&Rock(ROCK_SEQUENCE[2].to_vec()));
+        let config = CavernConfig::standard();
+        let mut cavern = Cavern::new(&config);
+        let mut rock = Rock(config.rocks[2].clone());
+        rock.translate(Vector([
+            cavern.height() + config.spawn_bottom_margin + 1,
+            1 + config.spawn_left_margin,
+        ]));
+        cavern.add(&rock);
         assert_eq!(cavern.height(), 6);
     }
 
     #[test]
     fn after_dropping_the_first_rock_the_cavern_height_is_one() {
         assert_eq!(
-            cavern_after_dropping_rocks(1, EXAMPLE_JET_PATTERN).height(),
+            cavern_after_dropping_rocks(1, EXAMPLE_JET_PATTERN, &CavernConfig::standard()).height(),
             1
         );
     }
@@ -314,30 +576,139 @@ mod tests {
     #[test]
     fn after_dropping_2022_rocks_the_example_cavern_height_is_reached() {
         assert_eq!(
-            cavern_after_dropping_rocks(2022, EXAMPLE_JET_PATTERN).height(),
+            cavern_after_dropping_rocks(2022, EXAMPLE_JET_PATTERN, &CavernConfig::standard())
+                .height(),
             3068
         );
     }
 
+    #[test]
+    fn a_wider_chamber_with_custom_rocks_also_settles_correctly() {
+        let config = CavernConfig {
+            width: 10,
+            wall: b'#',
+            spawn_left_margin: 3,
+            spawn_bottom_margin: 3,
+            rocks: vec![
+                vec![Vector([0, 0])],
+                vec![
+                    Vector([0, 0]),
+                    Vector([0, 1]),
+                    Vector([1, 0]),
+                    Vector([1, 1]),
+                ],
+            ],
+        };
+        let jets = parse_jet_pattern(EXAMPLE_INPUT).unwrap();
+        let simulated = cavern_after_dropping_rocks(500, &jets, &config).height();
+        let matched = determine_tower_height_with_matching(500, &jets, &config);
+        assert_eq!(matched, simulated);
+    }
+
     #[test]
     fn example_cavern_height_is_found_through_matching() {
+        let config = CavernConfig::standard();
         assert_eq!(
-            determine_tower_height_with_matching(2022, EXAMPLE_JET_PATTERN),
+            determine_tower_height_with_matching(2022, EXAMPLE_JET_PATTERN, &config),
             3068
         );
         assert_eq!(
-            determine_tower_height_with_matching(1000000000000, EXAMPLE_JET_PATTERN),
+            determine_tower_height_with_matching(1000000000000, EXAMPLE_JET_PATTERN, &config),
             1514285714288
         );
         assert_eq!(
             determine_tower_height_with_matching(
                 1000000000000,
-                parse_jet_pattern(REAL_INPUT).unwrap().as_slice()
+                parse_jet_pattern(REAL_INPUT).unwrap().as_slice(),
+                &config
             ),
             1542941176480
         );
     }
 
+    #[test]
+    fn heights_for_matches_brute_force_simulation_for_each_target() {
+        let door = Door::parse(EXAMPLE_INPUT).unwrap();
+        let targets = [1, 5, 50, 2022, 5000];
+        let expected: Vec<_> = targets
+            .iter()
+            .map(|&n| {
+                cavern_after_dropping_rocks(n, &door.jet_pattern, &CavernConfig::standard())
+                    .height()
+            })
+            .collect();
+        assert_eq!(door.heights_for(&targets), expected);
+    }
+
+    #[test]
+    fn heights_for_agrees_with_the_single_target_cycle_matcher() {
+        let door = Door::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(
+            door.heights_for(&[2022, 1000000000000]),
+            vec![3068, 1514285714288]
+        );
+    }
+
+    #[test]
+    fn empty_cavern_is_rendered_as_an_open_chamber_on_a_floor() {
+        let config = CavernConfig::standard();
+        let rendered = Cavern::new(&config).to_string();
+        assert_eq!(rendered.lines().last().unwrap(), "+-------+");
+        assert!(rendered
+            .lines()
+            .rev()
+            .skip(1)
+            .all(|line| line == "|.......|"));
+    }
+
+    #[test]
+    fn settled_rock_shows_up_as_hashes_in_the_rendering() {
+        let config = CavernConfig::standard();
+        let cavern = cavern_after_dropping_rocks(1, EXAMPLE_JET_PATTERN, &config);
+        let rendered = cavern.to_string();
+        assert!(rendered.lines().any(|line| line.contains('#')));
+    }
+
+    #[test]
+    fn falling_rock_is_overlaid_as_at_signs() {
+        let config = CavernConfig::standard();
+        let cavern = Cavern::new(&config);
+        let mut rock = Rock(config.rocks[0].clone());
+        rock.translate(Vector([
+            cavern.height() + config.spawn_bottom_margin + 1,
+            1 + config.spawn_left_margin,
+        ]));
+        let frame = cavern.render_with_rock(&rock);
+        assert!(frame.lines().any(|line| line.contains("@@@@")));
+    }
+
+    #[test]
+    fn traced_drop_reaches_the_same_outcome_as_an_untraced_drop_and_visits_every_frame() {
+        let config = CavernConfig::standard();
+        let jets: Vec<_> = EXAMPLE_JET_PATTERN.to_vec();
+
+        let mut untraced_cavern = Cavern::new(&config);
+        drop_multiple_rocks(
+            &mut untraced_cavern,
+            1,
+            &mut jets.iter().copied().enumerate().cycle(),
+            &config,
+        );
+
+        let mut traced_cavern = Cavern::new(&config);
+        let mut frame_count = 0;
+        drop_rock_traced(
+            &mut traced_cavern,
+            Rock(config.rocks[0].clone()),
+            &mut jets.iter().copied().enumerate().cycle(),
+            &config,
+            &mut |_, _| frame_count += 1,
+        );
+
+        assert_eq!(traced_cavern.height(), untraced_cavern.height());
+        assert!(frame_count > 0);
+    }
+
     const EXAMPLE_INPUT: &str = ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>";
 
     const EXAMPLE_JET_PATTERN: &[Jet] = &[
@@ -14,20 +14,28 @@ pub(crate) struct Door {
 }
 
 impl<'input> ParseInput<'input> for Door {
+    type Error = ParseVectorError<ParseIntError>;
+
     fn parse(input: &'input str) -> Result<Self, ParseVectorError<ParseIntError>> {
         parse_input(input).map(|voxels| Door { voxels })
     }
 }
 
 impl Part1 for Door {
-    fn part1(&self) -> usize {
-        total_surface_area(&self.voxels)
+    type Output = usize;
+    type Error = std::convert::Infallible;
+
+    fn part1(&self) -> Result<usize, Self::Error> {
+        Ok(total_surface_area(&self.voxels))
     }
 }
 
 impl Part2 for Door {
-    fn part2(&self) -> usize {
-        exterior_surface_area(&self.voxels)
+    type Output = usize;
+    type Error = std::convert::Infallible;
+
+    fn part2(&self) -> Result<usize, Self::Error> {
+        Ok(exterior_surface_area(&self.voxels))
     }
 }
 
@@ -2,7 +2,9 @@ use aoc_companion::prelude::*;
 
 use enum_map::{enum_map, Enum, EnumMap};
 use itertools::Itertools;
+use rayon::prelude::*;
 
+use std::collections::HashMap;
 use std::num::ParseIntError;
 
 pub struct Door {
@@ -33,11 +35,10 @@ impl Part2 for Door {
     fn part2(&self) -> Result<Self::Output, Self::Error> {
         Ok(self
             .blueprints
-            .iter()
+            .par_iter()
             .take(3)
             .map(|blueprint| maximum_geode_yield(32, blueprint))
-            .reduce(std::ops::Mul::mul)
-            .unwrap())
+            .reduce(|| 1, std::ops::Mul::mul))
     }
 }
 
@@ -49,12 +50,6 @@ enum Resource {
     Geode,
 }
 
-impl Resource {
-    fn iter() -> impl Iterator<Item = Self> {
-        (0..Resource::LENGTH).map(Resource::from_usize)
-    }
-}
-
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Action {
     NoOp,
@@ -110,7 +105,7 @@ fn parse_blueprints(input: &str) -> Result<Vec<Blueprint>, ParseIntError> {
         .try_collect()
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 struct Inventory(EnumMap<Resource, u32>);
 
 impl Inventory {
@@ -125,13 +120,68 @@ impl Inventory {
             self.0[res] -= cost;
         }
     }
+
+    /// Whether `self` is at least as large in every component as `other`,
+    /// i.e. any state `other` could ever reach, `self` could reach too.
+    fn dominates(&self, other: &Inventory) -> bool {
+        self.0.iter().all(|(res, count)| *count >= other.0[res])
+    }
+
+    /// Caps every count at `factor` times the most the blueprint could ever
+    /// demand of it per minute, since stockpiling beyond that can never
+    /// help: pass the remaining minutes to clamp a resource stockpile, or 1
+    /// to clamp a robot count. Geodes are left untouched: they're the
+    /// score, not an input, so there's no such thing as "more than we'll
+    /// ever need" of them.
+    fn clamped_to_demand(&self, blueprint: &Blueprint, factor: u32) -> Inventory {
+        let mut clamped = self.clone();
+        for resource in [Resource::Ore, Resource::Clay, Resource::Obsidian] {
+            clamped.0[resource] = clamped.0[resource].min(blueprint.demand(resource) * factor);
+        }
+        clamped
+    }
 }
 
 #[derive(Debug, Clone)]
 struct Strategy {
     resource_inventory: Inventory,
     robot_inventory: Inventory,
-    time: u32,
+    /// Robots we could have afforded at the last decision point but chose
+    /// not to build. Building one of them now would never beat having built
+    /// it then, so [`feasible_actions`] excludes them until we actually
+    /// build something and the set is reset.
+    ///
+    /// [`feasible_actions`]: Strategy::feasible_actions
+    skipped: EnumMap<Resource, bool>,
+}
+
+/// Memoizes the best geode yield reachable from a canonical (resources,
+/// robots, time remaining) state, so equivalent states reached via
+/// different action orderings are only searched once.
+#[derive(Debug, Default)]
+struct SearchCache {
+    best_from: HashMap<(Inventory, Inventory, u32), u32>,
+}
+
+impl SearchCache {
+    /// Returns the memoized yield for this exact state, or for any
+    /// previously seen state that dominates it (at least as much time left,
+    /// and component-wise `>=` resources and robots) — such a state can
+    /// never do worse than this one, so its answer is a safe upper bound.
+    fn lookup(&self, resources: &Inventory, robots: &Inventory, remaining: u32) -> Option<u32> {
+        self.best_from
+            .iter()
+            .find(|((seen_resources, seen_robots, seen_remaining), _)| {
+                *seen_remaining >= remaining
+                    && seen_resources.dominates(resources)
+                    && seen_robots.dominates(robots)
+            })
+            .map(|(_, &best)| best)
+    }
+
+    fn record(&mut self, resources: Inventory, robots: Inventory, remaining: u32, best: u32) {
+        self.best_from.insert((resources, robots, remaining), best);
+    }
 }
 
 impl Strategy {
@@ -142,12 +192,11 @@ impl Strategy {
                 Resource::Ore => 1,
                 _ => 0,
             }),
-            time: 0,
+            skipped: EnumMap::default(),
         }
     }
 
     fn produce(&mut self) {
-        self.time += 1;
         for (robot_res, count) in &self.robot_inventory.0 {
             self.resource_inventory.0[robot_res] += count;
         }
@@ -159,116 +208,105 @@ impl Strategy {
             .can_afford_robot(Resource::Geode, blueprint)
         {
             // If we can afford it, buying a geode robot will be our only course of action
-            vec![Action::SpendOnRobot(Resource::Geode)]
-        } else {
-            Resource::iter()
-                .take(3)
-                .filter(|&robot| self.resource_inventory.can_afford_robot(robot, blueprint))
-                .filter(|&resource| self.robot_inventory.0[resource] < blueprint.demand(resource))
-                .map(|robot| Action::SpendOnRobot(robot))
-                .chain(std::iter::once(Action::NoOp))
-                .collect()
+            return vec![Action::SpendOnRobot(Resource::Geode)];
+        }
+
+        // Try the robots most likely to pay off first, so a good `best` is
+        // found early and starts pruning sibling branches sooner.
+        [Resource::Obsidian, Resource::Clay, Resource::Ore]
+            .into_iter()
+            .filter(|&robot| !self.skipped[robot])
+            .filter(|&robot| self.resource_inventory.can_afford_robot(robot, blueprint))
+            .filter(|&robot| self.robot_inventory.0[robot] < blueprint.demand(robot))
+            .map(Action::SpendOnRobot)
+            .chain(std::iter::once(Action::NoOp))
+            .collect()
+    }
+
+    /// Marks every robot affordable right now as skipped, since we just
+    /// passed up the chance to build it.
+    fn skip_affordable_robots(&mut self, blueprint: &Blueprint) {
+        for robot in [Resource::Ore, Resource::Clay, Resource::Obsidian] {
+            if self.resource_inventory.can_afford_robot(robot, blueprint) {
+                self.skipped[robot] = true;
+            }
         }
     }
 
     fn spend_on_robot(&mut self, robot: Resource, blueprint: &Blueprint) {
         self.resource_inventory.spend(robot, blueprint);
         self.robot_inventory.0[robot] += 1;
+        self.skipped = EnumMap::default();
     }
 
     fn geode_yield(&self) -> u32 {
         self.resource_inventory.0[Resource::Geode]
     }
 
-    fn evolve_reduce<I, F, R>(
-        self,
-        final_time: u32,
-        blueprint: &Blueprint,
-        init_fn: &I,
-        reduction_fn: &F,
-    ) -> R
-    where
-        I: Fn(Strategy) -> R,
-        F: Fn(R, R) -> R,
-    {
-        self.feasible_actions(blueprint)
-            .into_iter()
-            .map(|action| {
-                let mut new_strat = self.clone();
-                new_strat.produce();
-                match action {
-                    Action::NoOp => {}
-                    Action::SpendOnRobot(robot) => new_strat.spend_on_robot(robot, blueprint),
-                }
-
-                if new_strat.time == final_time {
-                    init_fn(new_strat)
-                } else {
-                    new_strat.evolve_reduce(final_time, blueprint, init_fn, reduction_fn)
-                }
-            })
-            .reduce(reduction_fn)
-            .unwrap()
+    /// An optimistic upper bound on the geode yield reachable from this
+    /// strategy with `remaining` minutes left: the geodes already banked,
+    /// plus what the current geode robots alone will produce, plus the most
+    /// we could ever get if we magically built one additional geode robot
+    /// every remaining minute (`1 + 2 + ... + remaining`).
+    fn upper_bound(&self, remaining: u32) -> u32 {
+        self.geode_yield()
+            + self.robot_inventory.0[Resource::Geode] * remaining
+            + remaining * remaining.saturating_sub(1) / 2
     }
 
-    fn evolve_top_n(self, n: usize, final_time: u32, blueprint: &Blueprint) -> Vec<Strategy> {
-        self.evolve_reduce(final_time, blueprint, &|s| vec![s], &|lhs, rhs| {
-            lhs.into_iter()
-                .merge_by(rhs.into_iter(), |l, r| l.geode_yield() > r.geode_yield())
-                .take(n)
-                .collect()
-        })
-    }
+    /// Depth-first branch-and-bound search for the maximum geode yield
+    /// reachable with `remaining` minutes left, pruning any child whose
+    /// [`upper_bound`] can't beat the best sibling found so far. Canonical
+    /// states (after clamping away resource/robot surplus the blueprint
+    /// could never use) are memoized in `cache` so the same state is never
+    /// fully explored twice.
+    fn maximize_geode_yield(
+        &self,
+        remaining: u32,
+        blueprint: &Blueprint,
+        cache: &mut SearchCache,
+    ) -> u32 {
+        if remaining == 0 {
+            return self.geode_yield();
+        }
 
-    fn maximize_geode_yield(self, final_time: u32, blueprint: &Blueprint) -> Strategy {
-        self.evolve_reduce(final_time, blueprint, &|s| s, &|lhs, rhs| {
-            std::cmp::max_by_key(lhs, rhs, Strategy::geode_yield)
-        })
-    }
+        let canonical_resources = self
+            .resource_inventory
+            .clamped_to_demand(blueprint, remaining);
+        let canonical_robots = self.robot_inventory.clamped_to_demand(blueprint, 1);
+        if let Some(best) = cache.lookup(&canonical_resources, &canonical_robots, remaining) {
+            return best;
+        }
 
-    fn top_n(self, n: usize, final_time: u32, blueprint: &Blueprint) -> Vec<Strategy> {
-        let start_time = final_time.min(final_time.min(time_to_first_geode(blueprint)) + 4);
-        (start_time..=final_time).fold(vec![self], |state, time| {
-            state
-                .into_iter()
-                .map(|strat| {
-                    strat
-                        .evolve_top_n(n, time, blueprint)
-                        .into_iter()
-                        .filter(|s| s.geode_yield() > 0)
-                })
-                .kmerge_by(|l, r| l.geode_yield() > r.geode_yield())
-                .take(n)
-                .collect()
-        })
-    }
-}
+        let mut best = 0;
+        for action in self.feasible_actions(blueprint) {
+            let mut next = self.clone();
+            if action == Action::NoOp {
+                next.skip_affordable_robots(blueprint);
+            }
+            next.produce();
+            if let Action::SpendOnRobot(robot) = action {
+                next.spend_on_robot(robot, blueprint);
+            }
+            if next.upper_bound(remaining - 1) > best {
+                best = best.max(next.maximize_geode_yield(remaining - 1, blueprint, cache));
+            }
+        }
 
-fn time_to_first_geode(blueprint: &Blueprint) -> u32 {
-    (10..)
-        .find(|&time| {
-            Strategy::new()
-                .maximize_geode_yield(time, blueprint)
-                .geode_yield()
-                > 0
-        })
-        .unwrap()
+        cache.record(canonical_resources, canonical_robots, remaining, best);
+        best
+    }
 }
 
 fn maximum_geode_yield(final_time: u32, blueprint: &Blueprint) -> u32 {
-    Strategy::new()
-        .top_n(100000, final_time, blueprint)
-        .first()
-        .map(Strategy::geode_yield)
-        .unwrap_or(0)
+    Strategy::new().maximize_geode_yield(final_time, blueprint, &mut SearchCache::default())
 }
 
 fn total_quality_level(final_time: u32, blueprints: &[Blueprint]) -> u32 {
     blueprints
-        .iter()
-        .map(|blueprint| maximum_geode_yield(final_time, blueprint))
+        .par_iter()
         .enumerate()
-        .map(|(index, quality)| (index as u32 + 1) * quality)
+        .map(|(index, blueprint)| (index as u32 + 1) * maximum_geode_yield(final_time, blueprint))
         .sum()
 }
 
@@ -285,13 +323,6 @@ mod tests {
     }
 
     #[test]
-    fn find_time_to_first_geode() {
-        assert_eq!(time_to_first_geode(&example_blueprints()[0]), 19);
-        assert_eq!(time_to_first_geode(&example_blueprints()[1]), 19);
-    }
-
-    #[test]
-    #[ignore = "slow"]
     fn find_max_geode_yield_after_24_mins() {
         assert_eq!(maximum_geode_yield(24, &example_blueprints()[0]), 9);
         assert_eq!(maximum_geode_yield(24, &example_blueprints()[1]), 12);
@@ -305,7 +336,6 @@ mod tests {
     }
 
     #[test]
-    #[ignore = "slow"]
     fn find_total_quality_level() {
         assert_eq!(total_quality_level(24, &example_blueprints()), 33);
     }
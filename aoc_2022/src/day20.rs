@@ -43,36 +43,173 @@ fn parse_input(input: &str) -> Result<Vec<isize>, ParseIntError> {
     input.lines().map(str::trim).map(str::parse).try_collect()
 }
 
-fn decrypted_sequence(numbers: &[isize], times: usize) -> Vec<isize> {
-    use std::collections::VecDeque;
+/// A node in the implicit-key treap used by [`decrypted_sequence`] to mix
+/// the list in O(n log n): `left`/`right` encode `value`'s position among
+/// its neighbors in the circular order, `size` is the usual subtree-size
+/// annotation needed to split/merge by rank, and `parent` lets a node find
+/// its own current rank by walking up to the root.
+struct Node {
+    value: isize,
+    priority: u64,
+    size: usize,
+    left: Option<usize>,
+    right: Option<usize>,
+    parent: Option<usize>,
+}
 
-    let n = numbers.len() as isize - 1;
-    let mut numbers = VecDeque::from_iter(numbers.iter().copied());
-    let mut indices = Vec::from_iter(0..numbers.len());
+/// An order-statistics treap over `numbers.len()` nodes, one per original
+/// element, addressed by original index for the lifetime of the mixing
+/// process. Splitting and rejoining by size implements "move element at
+/// rank p to rank q" in O(log n), replacing the O(n) per-move
+/// `VecDeque::remove`/`insert` plus index fix-up.
+struct Treap {
+    nodes: Vec<Node>,
+    root: Option<usize>,
+}
 
-    for i in (0..numbers.len()).cycle().take(numbers.len() * times) {
-        let j = indices[i];
-        let x = numbers.remove(j).unwrap();
-        let to_move = (x % n + n) % n;
-        let k = (j + to_move as usize) % numbers.len();
-
-        numbers.insert(k, x);
-
-        if j < k {
-            indices
-                .iter_mut()
-                .filter(|l| (j + 1..=k).contains(l))
-                .for_each(|l| *l -= 1);
+impl Treap {
+    fn new(numbers: &[isize]) -> Self {
+        let mut nodes: Vec<Node> = numbers
+            .iter()
+            .enumerate()
+            .map(|(i, &value)| Node {
+                value,
+                priority: splitmix64(i as u64),
+                size: 1,
+                left: None,
+                right: None,
+                parent: None,
+            })
+            .collect();
+
+        let root =
+            (0..nodes.len()).fold(None, |root, i| Self::merge_roots(&mut nodes, root, Some(i)));
+
+        Treap { nodes, root }
+    }
+
+    fn size_of(nodes: &[Node], x: Option<usize>) -> usize {
+        x.map_or(0, |x| nodes[x].size)
+    }
+
+    fn update(nodes: &mut [Node], x: usize) {
+        let (left, right) = (nodes[x].left, nodes[x].right);
+        if let Some(l) = left {
+            nodes[l].parent = Some(x);
+        }
+        if let Some(r) = right {
+            nodes[r].parent = Some(x);
+        }
+        nodes[x].size = 1 + Self::size_of(nodes, left) + Self::size_of(nodes, right);
+    }
+
+    fn merge_roots(nodes: &mut Vec<Node>, a: Option<usize>, b: Option<usize>) -> Option<usize> {
+        match (a, b) {
+            (None, other) | (other, None) => other,
+            (Some(a), Some(b)) => {
+                if nodes[a].priority >= nodes[b].priority {
+                    let right = Self::merge_roots(nodes, nodes[a].right, Some(b));
+                    nodes[a].right = right;
+                    Self::update(nodes, a);
+                    Some(a)
+                } else {
+                    let left = Self::merge_roots(nodes, Some(a), nodes[b].left);
+                    nodes[b].left = left;
+                    Self::update(nodes, b);
+                    Some(b)
+                }
+            }
+        }
+    }
+
+    /// Splits `t` into its first `k` elements (in in-order position) and
+    /// the rest.
+    fn split(nodes: &mut Vec<Node>, t: Option<usize>, k: usize) -> (Option<usize>, Option<usize>) {
+        let Some(x) = t else {
+            return (None, None);
+        };
+
+        let left_size = Self::size_of(nodes, nodes[x].left);
+        if left_size < k {
+            let (l, r) = Self::split(nodes, nodes[x].right, k - left_size - 1);
+            nodes[x].right = l;
+            Self::update(nodes, x);
+            (Some(x), r)
         } else {
-            indices
-                .iter_mut()
-                .filter(|l| (k..j).contains(l))
-                .for_each(|l| *l += 1);
+            let (l, r) = Self::split(nodes, nodes[x].left, k);
+            nodes[x].left = r;
+            Self::update(nodes, x);
+            (l, Some(x))
+        }
+    }
+
+    /// The current in-order rank of node `x`, found by walking up to the
+    /// root and, at every right-child step, adding the size of the
+    /// sibling's left subtree plus the parent itself.
+    fn position_of(&self, mut x: usize) -> usize {
+        let mut pos = Self::size_of(&self.nodes, self.nodes[x].left);
+        while let Some(p) = self.nodes[x].parent {
+            if self.nodes[p].right == Some(x) {
+                pos += Self::size_of(&self.nodes, self.nodes[p].left) + 1;
+            }
+            x = p;
         }
-        indices[i] = k;
+        pos
+    }
+
+    /// Finds node `x`'s current position `p`, removes it from the
+    /// `modulus`-node cycle that's left, and reinserts it at rank
+    /// `(p + value).rem_euclid(modulus)`.
+    fn move_by_own_value(&mut self, x: usize, modulus: usize) {
+        let root = self.root.take().expect("treap must be non-empty");
+        let p = self.position_of(x);
+
+        let (before, at_and_after) = Self::split(&mut self.nodes, Some(root), p);
+        let (singleton, after) = Self::split(&mut self.nodes, at_and_after, 1);
+        debug_assert_eq!(singleton, Some(x));
+
+        let remaining = Self::merge_roots(&mut self.nodes, before, after);
+        let target = (p as isize + self.nodes[x].value).rem_euclid(modulus as isize) as usize;
+
+        let (left, right) = Self::split(&mut self.nodes, remaining, target);
+        let with_x = Self::merge_roots(&mut self.nodes, left, Some(x));
+        self.root = Self::merge_roots(&mut self.nodes, with_x, right);
+    }
+
+    fn into_values(self) -> Vec<isize> {
+        let mut values = Vec::with_capacity(self.nodes.len());
+        self.collect_in_order(self.root, &mut values);
+        values
+    }
+
+    fn collect_in_order(&self, x: Option<usize>, out: &mut Vec<isize>) {
+        let Some(x) = x else { return };
+        self.collect_in_order(self.nodes[x].left, out);
+        out.push(self.nodes[x].value);
+        self.collect_in_order(self.nodes[x].right, out);
+    }
+}
+
+/// A fixed, well-spread (not cryptographically random) 64-bit hash of `x`,
+/// used to assign treap priorities deterministically so a mix is
+/// reproducible from run to run.
+fn splitmix64(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9e3779b97f4a7c15);
+    let mut z = x;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
+fn decrypted_sequence(numbers: &[isize], times: usize) -> Vec<isize> {
+    let modulus = numbers.len() - 1;
+    let mut treap = Treap::new(numbers);
+
+    for i in (0..numbers.len()).cycle().take(numbers.len() * times) {
+        treap.move_by_own_value(i, modulus);
     }
 
-    numbers.into()
+    treap.into_values()
 }
 
 fn apply_key(numbers: &[isize]) -> Vec<isize> {
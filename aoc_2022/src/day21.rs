@@ -21,8 +21,7 @@ impl<'input> Solution<'input> for Door {
     }
 
     fn part2(&self) -> Result<isize, RuntimeError> {
-        let transformed = transform_for_part2(&self.monkeys)?;
-        solve_for("humn", &transformed)
+        solve_for_humn(&self.monkeys)
     }
 }
 
@@ -42,6 +41,94 @@ pub(crate) enum ParseError {
 pub(crate) enum RuntimeError {
     #[error("Could not find an equation involving {0:?}")]
     CouldNotFindAnEquationInvolvingVariable(String),
+    #[error("{0:?}'s equation multiplies or divides two expressions that both depend on humn")]
+    NonLinearEquation(String),
+    #[error("root's two sides carry the same coefficient for humn, so there's no unique solution")]
+    NoUniqueSolutionForHumn,
+    #[error("{0:?}'s value isn't an integer")]
+    NonIntegralResult(String),
+    #[error("dependency cycle: {}", .0.join(" -> "))]
+    CyclicDependency(Vec<String>),
+}
+
+/// An exact fraction in lowest terms, with a positive denominator. This is
+/// the value type monkeys actually compute with: `isize` division would
+/// silently truncate on an inexact quotient, which can land `solve_for` on a
+/// wrong answer whenever such a quotient feeds into a later operation
+/// instead of being the puzzle's final result.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Rational {
+    num: isize,
+    den: isize,
+}
+
+impl Rational {
+    fn new(num: isize, den: isize) -> Self {
+        let sign = if den < 0 { -1 } else { 1 };
+        let g = gcd(num.abs(), den.abs()).max(1);
+        Rational {
+            num: sign * num / g,
+            den: sign * den / g,
+        }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.num == 0
+    }
+
+    /// Converts back to an exact `isize`, or `None` if the fraction isn't
+    /// integral.
+    fn to_isize(self) -> Option<isize> {
+        (self.num % self.den == 0).then_some(self.num / self.den)
+    }
+}
+
+fn gcd(a: isize, b: isize) -> isize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+impl From<isize> for Rational {
+    fn from(n: isize) -> Self {
+        Rational { num: n, den: 1 }
+    }
+}
+
+impl std::ops::Add for Rational {
+    type Output = Rational;
+    fn add(self, other: Self) -> Self {
+        Rational::new(
+            self.num * other.den + other.num * self.den,
+            self.den * other.den,
+        )
+    }
+}
+
+impl std::ops::Sub for Rational {
+    type Output = Rational;
+    fn sub(self, other: Self) -> Self {
+        Rational::new(
+            self.num * other.den - other.num * self.den,
+            self.den * other.den,
+        )
+    }
+}
+
+impl std::ops::Mul for Rational {
+    type Output = Rational;
+    fn mul(self, other: Self) -> Self {
+        Rational::new(self.num * other.num, self.den * other.den)
+    }
+}
+
+impl std::ops::Div for Rational {
+    type Output = Rational;
+    fn div(self, other: Self) -> Self {
+        Rational::new(self.num * other.den, self.den * other.num)
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -50,62 +137,78 @@ enum Operator {
     Sub,
     Mul,
     Div,
-    SubInv,
-    DivInv,
 }
 
 impl Operator {
-    fn apply(&self, lhs: isize, rhs: isize) -> isize {
+    fn apply(&self, lhs: Rational, rhs: Rational) -> Rational {
         match self {
             Operator::Add => lhs + rhs,
             Operator::Sub => lhs - rhs,
             Operator::Mul => lhs * rhs,
             Operator::Div => lhs / rhs,
-            Operator::SubInv => rhs - lhs,
-            Operator::DivInv => rhs / lhs,
         }
     }
+}
 
-    fn inv_lhs(&self) -> Self {
-        // t = x + a => x = t - a
-        // t = x - a => x = t + a
-        // t = x * a => x = t / a
-        // t = x / a => x = t * a
-        // t = a - x => x = a - t
-        // t = a / x => x = a / t
-        use Operator::*;
-        match *self {
-            Add => Sub,
-            Sub => Add,
-            Mul => Div,
-            Div => Mul,
-            SubInv => SubInv,
-            DivInv => DivInv,
+/// `humn`'s symbolic linear form `a*humn + b`, carried up the expression tree
+/// in place of a concrete value so that [`solve_for_humn`] can solve for
+/// `humn` algebraically however many times (or however deeply nested) it
+/// occurs, rather than requiring the single invertible chain the old
+/// `transform_for_part2`/`Operator::inv_lhs`/`inv_rhs` scheme assumed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Linear {
+    a: Rational,
+    b: Rational,
+}
+
+impl Linear {
+    fn constant(n: Rational) -> Self {
+        Linear {
+            a: Rational::from(0),
+            b: n,
         }
     }
 
-    fn inv_rhs(&self) -> Self {
-        // t = a + x => x = t - a
-        // t = a - x => x = a - t
-        // t = a * x => x = t / a
-        // t = a / x => x = a / t
-        // t = x - a => x = t + a
-        // t = x / a => x = t * a
-        use Operator::*;
-        match *self {
-            Add => Sub,
-            Sub => SubInv,
-            Mul => Div,
-            Div => DivInv,
-            SubInv => Add,
-            DivInv => Mul,
+    fn humn() -> Self {
+        Linear {
+            a: Rational::from(1),
+            b: Rational::from(0),
+        }
+    }
+
+    /// Combines two linear forms across `op`. `Add`/`Sub` are always linear;
+    /// `Mul`/`Div` only stay linear when at least one side's `a` (the `humn`
+    /// in the equation) is known not to depend on `humn`.
+    fn combine(self, op: Operator, other: Self, name: &str) -> Result<Self, RuntimeError> {
+        match op {
+            Operator::Add => Ok(Linear {
+                a: self.a + other.a,
+                b: self.b + other.b,
+            }),
+            Operator::Sub => Ok(Linear {
+                a: self.a - other.a,
+                b: self.b - other.b,
+            }),
+            Operator::Mul if self.a.is_zero() => Ok(Linear {
+                a: other.a * self.b,
+                b: other.b * self.b,
+            }),
+            Operator::Mul if other.a.is_zero() => Ok(Linear {
+                a: self.a * other.b,
+                b: self.b * other.b,
+            }),
+            Operator::Div if other.a.is_zero() => Ok(Linear {
+                a: self.a / other.b,
+                b: self.b / other.b,
+            }),
+            Operator::Mul | Operator::Div => Err(RuntimeError::NonLinearEquation(name.to_owned())),
         }
     }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 enum Monkey {
-    Constant(isize),
+    Constant(Rational),
     Operation {
         lhs: String,
         rhs: String,
@@ -113,25 +216,6 @@ enum Monkey {
     },
 }
 
-enum Resolution {
-    Ready(isize),
-    Dependent(Vec<String>),
-}
-
-impl Monkey {
-    fn resolve(&self, data: &HashMap<String, isize>) -> Resolution {
-        match self {
-            Monkey::Constant(num) => Resolution::Ready(*num),
-            Monkey::Operation { lhs, rhs, ops } => match (data.get(lhs), data.get(rhs)) {
-                (Some(_), None) => Resolution::Dependent(vec![rhs.clone()]),
-                (None, Some(_)) => Resolution::Dependent(vec![lhs.clone()]),
-                (None, None) => Resolution::Dependent(vec![lhs.clone(), rhs.clone()]),
-                (Some(&lhs), Some(&rhs)) => Resolution::Ready(ops.apply(lhs, rhs)),
-            },
-        }
-    }
-}
-
 impl FromStr for Monkey {
     type Err = ParseError;
 
@@ -154,7 +238,7 @@ impl FromStr for Monkey {
                 ops,
             })
         } else {
-            Ok(Monkey::Constant(s.parse()?))
+            Ok(Monkey::Constant(Rational::from(s.parse::<isize>()?)))
         }
     }
 }
@@ -171,79 +255,127 @@ fn parse_input(input: &str) -> Result<HashMap<String, Monkey>, ParseError> {
         .try_collect()
 }
 
-fn solve_for(target: &str, monkeys: &HashMap<String, Monkey>) -> Result<isize, RuntimeError> {
-    let mut data = HashMap::new();
-    let mut dependencies = vec![target.to_owned()];
-    while let Some(dependency) = dependencies.pop() {
-        match monkeys
-            .get(&dependency)
-            .ok_or_else(|| {
-                RuntimeError::CouldNotFindAnEquationInvolvingVariable(dependency.clone())
-            })?
-            .resolve(&data)
-        {
-            Resolution::Ready(num) => {
-                data.insert(dependency.to_owned(), num);
-            }
-            Resolution::Dependent(subdependencies) => {
-                dependencies.push(dependency);
-                dependencies.extend(subdependencies);
-            }
+/// A node's DFS visitation state while topologically sorting `monkeys`'
+/// dependency graph in [`topological_order`]: white is unvisited, gray is an
+/// ancestor still on the current path (visiting it again means a cycle), and
+/// black is finished and safe to skip.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Color {
+    White,
+    Gray,
+    Black,
+}
+
+/// Depth-first-visits `name` and its `lhs`/`rhs` dependencies, appending each
+/// node to `order` only once both its dependencies are done (so `order` ends
+/// up dependencies-before-dependents). Returns [`RuntimeError::CyclicDependency`]
+/// with the offending path if a gray (in-progress) node is reached again.
+fn visit(
+    name: &str,
+    monkeys: &HashMap<String, Monkey>,
+    colors: &mut HashMap<String, Color>,
+    path: &mut Vec<String>,
+    order: &mut Vec<String>,
+) -> Result<(), RuntimeError> {
+    match colors.get(name) {
+        Some(Color::Black) => return Ok(()),
+        Some(Color::Gray) => {
+            let cycle_start = path.iter().position(|n| n == name).unwrap();
+            let mut cycle = path[cycle_start..].to_vec();
+            cycle.push(name.to_owned());
+            return Err(RuntimeError::CyclicDependency(cycle));
         }
+        Some(Color::White) | None => {}
     }
 
-    Ok(data[target])
+    colors.insert(name.to_owned(), Color::Gray);
+    path.push(name.to_owned());
+
+    if let Monkey::Operation { lhs, rhs, .. } = monkeys
+        .get(name)
+        .ok_or_else(|| RuntimeError::CouldNotFindAnEquationInvolvingVariable(name.to_owned()))?
+    {
+        visit(lhs, monkeys, colors, path, order)?;
+        visit(rhs, monkeys, colors, path, order)?;
+    }
+
+    path.pop();
+    colors.insert(name.to_owned(), Color::Black);
+    order.push(name.to_owned());
+    Ok(())
 }
 
-fn transform_for_part2(
+/// Topologically sorts the monkeys reachable from `target`, dependencies
+/// before dependents, via a three-color DFS.
+fn topological_order(
+    target: &str,
     monkeys: &HashMap<String, Monkey>,
-) -> Result<HashMap<String, Monkey>, RuntimeError> {
-    let mut current = "humn".to_string();
-    let mut new_monkeys = monkeys.clone();
-    let other = loop {
-        let (name, other, ops) = monkeys
-            .iter()
-            .find_map(|(name, monkey)| match monkey {
-                Monkey::Operation { lhs, rhs, ops } if lhs == &current => {
-                    Some((name, rhs, ops.inv_lhs()))
-                }
-                Monkey::Operation { lhs, rhs, ops } if rhs == &current => {
-                    Some((name, lhs, ops.inv_rhs()))
-                }
-                _ => None,
-            })
-            .ok_or_else(|| {
-                RuntimeError::CouldNotFindAnEquationInvolvingVariable(current.clone())
-            })?;
-
-        let new = Monkey::Operation {
-            lhs: name.clone(),
-            rhs: other.clone(),
-            ops,
+) -> Result<Vec<String>, RuntimeError> {
+    let mut colors = HashMap::new();
+    let mut path = Vec::new();
+    let mut order = Vec::new();
+    visit(target, monkeys, &mut colors, &mut path, &mut order)?;
+    Ok(order)
+}
+
+fn solve_for(target: &str, monkeys: &HashMap<String, Monkey>) -> Result<isize, RuntimeError> {
+    let mut data: HashMap<String, Rational> = HashMap::new();
+    for name in topological_order(target, monkeys)? {
+        let value = match &monkeys[&name] {
+            Monkey::Constant(num) => *num,
+            Monkey::Operation { lhs, rhs, ops } => ops.apply(data[lhs], data[rhs]),
         };
-        let next = name.clone();
-        new_monkeys.remove(&next);
+        data.insert(name, value);
+    }
+
+    data[target]
+        .to_isize()
+        .ok_or_else(|| RuntimeError::NonIntegralResult(target.to_owned()))
+}
 
-        if next == "root" {
-            break other;
+/// Evaluates `name`'s monkey into a [`Linear`] form of `humn`, recursing into
+/// `lhs`/`rhs` for an `Operation` and treating `humn` itself as the variable
+/// rather than looking it up in `monkeys`.
+fn linear_form(name: &str, monkeys: &HashMap<String, Monkey>) -> Result<Linear, RuntimeError> {
+    if name == "humn" {
+        return Ok(Linear::humn());
+    }
+
+    match monkeys
+        .get(name)
+        .ok_or_else(|| RuntimeError::CouldNotFindAnEquationInvolvingVariable(name.to_owned()))?
+    {
+        Monkey::Constant(num) => Ok(Linear::constant(*num)),
+        Monkey::Operation { lhs, rhs, ops } => {
+            let lhs = linear_form(lhs, monkeys)?;
+            let rhs = linear_form(rhs, monkeys)?;
+            lhs.combine(*ops, rhs, name)
         }
+    }
+}
 
-        new_monkeys.insert(current, new);
-        current = next;
+/// Solves `root`'s equation for `humn`: the two children's [`Linear`] forms
+/// `a_l*humn + b_l = a_r*humn + b_r` give `humn = (b_r - b_l) / (a_l - a_r)`.
+fn solve_for_humn(monkeys: &HashMap<String, Monkey>) -> Result<isize, RuntimeError> {
+    let Monkey::Operation { lhs, rhs, .. } = monkeys
+        .get("root")
+        .ok_or_else(|| RuntimeError::CouldNotFindAnEquationInvolvingVariable("root".to_owned()))?
+    else {
+        return Err(RuntimeError::CouldNotFindAnEquationInvolvingVariable(
+            "root".to_owned(),
+        ));
     };
 
-    if let Some(name) = new_monkeys.values_mut().find_map(|monkey| match monkey {
-        Monkey::Operation { lhs, .. } if lhs == &current => Some(lhs),
-        Monkey::Operation { rhs, .. } if rhs == &current => Some(rhs),
-        _ => None,
-    }) {
-        *name = other.clone();
-        Ok(new_monkeys)
-    } else {
-        Err(RuntimeError::CouldNotFindAnEquationInvolvingVariable(
-            current,
-        ))
+    let lhs = linear_form(lhs, monkeys)?;
+    let rhs = linear_form(rhs, monkeys)?;
+    let slope = lhs.a - rhs.a;
+    if slope.is_zero() {
+        return Err(RuntimeError::NoUniqueSolutionForHumn);
     }
+
+    let humn = (rhs.b - lhs.b) / slope;
+    humn.to_isize()
+        .ok_or_else(|| RuntimeError::NonIntegralResult("humn".to_owned()))
 }
 
 #[cfg(test)]
@@ -261,19 +393,34 @@ mod tests {
     }
 
     #[test]
-    fn transformed_equations_can_be_solved_for_humn() {
-        assert_eq!(
-            solve_for("humn", &parse_input(TRANSFORMED_EXAMPLE).unwrap()).unwrap(),
-            301
-        );
+    fn cyclic_dependency_is_reported() {
+        let monkeys = HashMap::from([
+            (
+                "a".to_owned(),
+                Monkey::Operation {
+                    lhs: "b".to_owned(),
+                    rhs: "b".to_owned(),
+                    ops: Operator::Add,
+                },
+            ),
+            (
+                "b".to_owned(),
+                Monkey::Operation {
+                    lhs: "a".to_owned(),
+                    rhs: "a".to_owned(),
+                    ops: Operator::Add,
+                },
+            ),
+        ]);
+        assert!(matches!(
+            solve_for("a", &monkeys),
+            Err(RuntimeError::CyclicDependency(_))
+        ));
     }
 
     #[test]
-    fn transform_example_for_part2() {
-        assert_eq!(
-            transform_for_part2(&example_monkeys()).unwrap(),
-            parse_input(TRANSFORMED_EXAMPLE).unwrap()
-        );
+    fn humn_is_solved_for_via_linear_form() {
+        assert_eq!(solve_for_humn(&example_monkeys()).unwrap(), 301);
     }
 
     const EXAMPLE_INPUT: &str = "\
@@ -293,22 +440,6 @@ lgvd: ljgn * ptdq
 drzm: hmdt - zczc
 hmdt: 32";
 
-    const TRANSFORMED_EXAMPLE: &str = "\
-dbpl: 5
-zczc: 2
-dvpt: 3
-lfqf: 4
-humn: 5
-ljgn: 2
-sjmn: drzm * dbpl
-sllz: 4
-drzm: hmdt - zczc
-hmdt: 32
-humn: ptdq + dvpt
-ptdq: lgvd / ljgn
-lgvd: cczh - sllz
-cczh: sjmn * lfqf";
-
     fn example_monkeys() -> HashMap<String, Monkey> {
         HashMap::from([
             (
@@ -319,7 +450,7 @@ cczh: sjmn * lfqf";
                     ops: Operator::Add,
                 },
             ),
-            ("dbpl".to_owned(), Monkey::Constant(5)),
+            ("dbpl".to_owned(), Monkey::Constant(Rational::from(5))),
             (
                 "cczh".to_owned(),
                 Monkey::Operation {
@@ -328,7 +459,7 @@ cczh: sjmn * lfqf";
                     ops: Operator::Add,
                 },
             ),
-            ("zczc".to_owned(), Monkey::Constant(2)),
+            ("zczc".to_owned(), Monkey::Constant(Rational::from(2))),
             (
                 "ptdq".to_owned(),
                 Monkey::Operation {
@@ -337,10 +468,10 @@ cczh: sjmn * lfqf";
                     ops: Operator::Sub,
                 },
             ),
-            ("dvpt".to_owned(), Monkey::Constant(3)),
-            ("lfqf".to_owned(), Monkey::Constant(4)),
-            ("humn".to_owned(), Monkey::Constant(5)),
-            ("ljgn".to_owned(), Monkey::Constant(2)),
+            ("dvpt".to_owned(), Monkey::Constant(Rational::from(3))),
+            ("lfqf".to_owned(), Monkey::Constant(Rational::from(4))),
+            ("humn".to_owned(), Monkey::Constant(Rational::from(5))),
+            ("ljgn".to_owned(), Monkey::Constant(Rational::from(2))),
             (
                 "sjmn".to_owned(),
                 Monkey::Operation {
@@ -349,7 +480,7 @@ cczh: sjmn * lfqf";
                     ops: Operator::Mul,
                 },
             ),
-            ("sllz".to_owned(), Monkey::Constant(4)),
+            ("sllz".to_owned(), Monkey::Constant(Rational::from(4))),
             (
                 "pppw".to_owned(),
                 Monkey::Operation {
@@ -374,7 +505,7 @@ cczh: sjmn * lfqf";
                     ops: Operator::Sub,
                 },
             ),
-            ("hmdt".to_owned(), Monkey::Constant(32)),
+            ("hmdt".to_owned(), Monkey::Constant(Rational::from(32))),
         ])
     }
 }
@@ -1,11 +1,11 @@
 use aoc_companion::prelude::*;
-use aoc_utils::geometry::Point;
+use aoc_utils::geometry::{CharGrid, Point};
 use aoc_utils::linalg::Vector;
 
 use itertools::Itertools;
 use thiserror::Error;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::num::ParseIntError;
 use std::str::FromStr;
 
@@ -49,7 +49,7 @@ impl Part2 for Door {
         Ok(self
             .map
             .player_start()
-            .end::<CubicWrapping<50>>(&self.instructions, &self.map)
+            .end::<CubicWrapping>(&self.instructions, &self.map)
             .password())
     }
 }
@@ -102,7 +102,7 @@ impl From<u8> for Tile {
 }
 
 struct Map {
-    data: ndarray::Array2<Tile>,
+    data: CharGrid<Tile>,
 }
 
 impl Map {
@@ -122,25 +122,9 @@ impl FromStr for Map {
     type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let shape = (
-            s.lines().count(),
-            s.lines().map(str::len).max().unwrap_or(0),
-        );
-
-        let data = s
-            .lines()
-            .flat_map(|line| {
-                line.as_bytes()
-                    .iter()
-                    .map(|&b| b.into())
-                    .chain(std::iter::repeat(Tile::Nothing))
-                    .take(shape.1)
-            })
-            .collect();
-
-        let data = ndarray::Array2::from_shape_vec(shape, data).unwrap();
-
-        Ok(Map { data })
+        Ok(Map {
+            data: s.parse().unwrap(),
+        })
     }
 }
 
@@ -196,6 +180,15 @@ impl Direction {
             Direction::Up => Vector([-1, 0]),
         }
     }
+
+    fn glyph(&self) -> char {
+        match self {
+            Direction::Right => '>',
+            Direction::Down => 'v',
+            Direction::Left => '<',
+            Direction::Up => '^',
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -260,79 +253,223 @@ impl Wrapping for PlainWrapping {
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct ChunkCoord(Vector<usize, 2>);
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-enum Side {
-    A,
-    B,
-    C,
-    D,
-    E,
-    F,
+/// A cube face's orientation in 3D space, tracked while folding the 2D net:
+/// `normal` points away from the cube's center through the face, and
+/// `right`/`down` are unit vectors spanning the face in the directions of
+/// increasing column/row. All three are mutually orthogonal unit vectors
+/// along the cube's axes, so every corner of the face sits at
+/// `normal ± right ± down`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Face3D {
+    normal: Vector<isize, 3>,
+    right: Vector<isize, 3>,
+    down: Vector<isize, 3>,
+}
+
+impl Face3D {
+    /// The orientation of the chunk reached by stepping in 2D direction
+    /// `dir`, obtained by folding the cube 90° about the edge shared with
+    /// that neighbor.
+    fn step(&self, dir: Direction) -> Face3D {
+        match dir {
+            Direction::Right => Face3D {
+                normal: self.right,
+                right: self.normal * -1,
+                down: self.down,
+            },
+            Direction::Left => Face3D {
+                normal: self.right * -1,
+                right: self.normal,
+                down: self.down,
+            },
+            Direction::Down => Face3D {
+                normal: self.down,
+                right: self.right,
+                down: self.normal * -1,
+            },
+            Direction::Up => Face3D {
+                normal: self.down * -1,
+                right: self.right,
+                down: self.normal,
+            },
+        }
+    }
+
+    fn corners(&self) -> [Vector<isize, 3>; 4] {
+        [
+            self.normal + self.right + self.down,
+            self.normal + self.right - self.down,
+            self.normal - self.right + self.down,
+            self.normal - self.right - self.down,
+        ]
+    }
+
+    /// The two 3D corners bounding the edge this face would be left through
+    /// when walking off it in direction `dir`, ordered so that the first
+    /// corner corresponds to the start of that edge's local row/column (0)
+    /// and the second to its end (n - 1).
+    fn edge_corners(&self, dir: Direction) -> (Vector<isize, 3>, Vector<isize, 3>) {
+        match dir {
+            Direction::Right => (
+                self.normal + self.right - self.down,
+                self.normal + self.right + self.down,
+            ),
+            Direction::Left => (
+                self.normal - self.right - self.down,
+                self.normal - self.right + self.down,
+            ),
+            Direction::Down => (
+                self.normal - self.right + self.down,
+                self.normal + self.right + self.down,
+            ),
+            Direction::Up => (
+                self.normal - self.right - self.down,
+                self.normal + self.right - self.down,
+            ),
+        }
+    }
+}
+
+/// Folds the 2D net of chunks into 3D space via BFS, seeding the first chunk
+/// face-up and propagating orientation by rotating across shared edges.
+fn fold_chunks(chunk_coords: &[ChunkCoord]) -> HashMap<ChunkCoord, Face3D> {
+    let mut faces = HashMap::from([(
+        chunk_coords[0],
+        Face3D {
+            normal: Vector([0, 0, 1]),
+            right: Vector([1, 0, 0]),
+            down: Vector([0, 1, 0]),
+        },
+    )]);
+
+    let mut queue = VecDeque::from([chunk_coords[0]]);
+    while let Some(coord) = queue.pop_front() {
+        let face = faces[&coord];
+        let Ok(icoord) = coord.0.try_cast_as::<isize>() else {
+            continue;
+        };
+        for dir in [
+            Direction::Right,
+            Direction::Down,
+            Direction::Left,
+            Direction::Up,
+        ] {
+            let Ok(neighbor) = (icoord + dir.unit_vector()).try_cast_as::<usize>() else {
+                continue;
+            };
+            let neighbor = ChunkCoord(neighbor);
+            if !chunk_coords.contains(&neighbor) || faces.contains_key(&neighbor) {
+                continue;
+            }
+            faces.insert(neighbor, face.step(dir));
+            queue.push_back(neighbor);
+        }
+    }
+
+    faces
 }
 
-struct CubicWrapping<const N: usize> {
-    chunks: HashMap<ChunkCoord, (Side, Direction)>,
-    sides: HashMap<Side, (ChunkCoord, Direction)>,
+struct CubicWrapping {
+    n: usize,
+    faces: HashMap<ChunkCoord, Face3D>,
 }
 
-impl<const N: usize> Wrapping for CubicWrapping<N> {
+impl Wrapping for CubicWrapping {
     fn from_map(map: &Map) -> Self {
-        let sides = designate_chunks(&chunk_coords(map, N));
-        let chunks = invert_side_mapping(&sides);
-        Self { chunks, sides }
+        let n = cube_edge_length(map);
+        let faces = fold_chunks(&chunk_coords(map, n));
+        Self { n, faces }
     }
 
     fn advance(&self, mut player: Player) -> Player {
+        let n = self.n;
+        let current_chunk = ChunkCoord(player.pos / n);
         let new_pos = player.pos.try_cast_as::<isize>().unwrap() + player.facing.unit_vector();
-        let current_chunk = ChunkCoord(player.pos / N);
-        if new_pos.try_cast_as::<usize>().map(|p| ChunkCoord(p / N)) == Ok(current_chunk) {
+        if new_pos.try_cast_as::<usize>().map(|p| ChunkCoord(p / n)) == Ok(current_chunk) {
             // new position is within the same side of the cube
             player.pos = new_pos.try_cast_as::<usize>().unwrap();
             player
         } else {
-            // jumping between sides of the cube
-            let (current_side, current_chunk_orientation) = self.chunks[&current_chunk];
-
-            let mut new_coords_in_chunk = ((player.pos + Vector([N, N]))
-                .try_cast_as::<isize>()
-                .unwrap()
-                + player.facing.unit_vector())
-            .try_cast_as::<usize>()
-            .unwrap();
-            new_coords_in_chunk[0] %= N;
-            new_coords_in_chunk[1] %= N;
-
-            let leaving_chunk_in_direction =
-                player.facing.rotate_by(current_chunk_orientation.inv());
-
-            let (next_side, relative_orientation) = side_neighbors(current_side)
-                [match leaving_chunk_in_direction {
-                    Direction::Right => 1,
-                    Direction::Down => 0,
-                    Direction::Left => 3,
-                    Direction::Up => 2,
-                }];
-
-            let (next_chunk, next_chunk_orientation) = self.sides[&next_side];
-
-            let next_facing = leaving_chunk_in_direction
-                .rotate_by(next_chunk_orientation)
-                .rotate_by(relative_orientation.inv());
-
-            new_coords_in_chunk =
-                transform_coords_in_chunk(new_coords_in_chunk, player.facing.inv(), N);
-            new_coords_in_chunk = transform_coords_in_chunk(new_coords_in_chunk, next_facing, N);
+            // jumping between sides of the cube: find the edge of 3D space
+            // being crossed, then the other face sharing that same edge.
+            let face = self.faces[&current_chunk];
+            let local = Vector([player.pos[0] % n, player.pos[1] % n]);
+            let t = match player.facing {
+                Direction::Right | Direction::Left => local[0],
+                Direction::Down | Direction::Up => local[1],
+            };
+            let (c1, c2) = face.edge_corners(player.facing);
+
+            let (&next_chunk, next_face) = self
+                .faces
+                .iter()
+                .find(|&(&chunk, f)| {
+                    chunk != current_chunk && {
+                        let corners = f.corners();
+                        corners.contains(&c1) && corners.contains(&c2)
+                    }
+                })
+                .expect("every edge of a valid cube net borders exactly one other face");
+
+            let (dir, flipped) = [
+                Direction::Right,
+                Direction::Down,
+                Direction::Left,
+                Direction::Up,
+            ]
+            .into_iter()
+            .find_map(|dir| match next_face.edge_corners(dir) {
+                (d1, d2) if d1 == c1 && d2 == c2 => Some((dir, false)),
+                (d1, d2) if d1 == c2 && d2 == c1 => Some((dir, true)),
+                _ => None,
+            })
+            .expect("the shared edge must appear among the destination face's four edges");
 
-            player.pos = new_coords_in_chunk + next_chunk.0 * N;
-            player.facing = next_facing;
+            let t = if flipped { n - 1 - t } else { t };
+            player.pos = match dir {
+                Direction::Right => Vector([t, n - 1]),
+                Direction::Left => Vector([t, 0]),
+                Direction::Down => Vector([n - 1, t]),
+                Direction::Up => Vector([0, t]),
+            } + next_chunk.0 * n;
+            player.facing = dir.inv();
 
             player
         }
     }
 }
 
+/// Derives the cube's edge length from the parsed `Map` instead of having
+/// callers hard-code it: a valid cube net's populated tiles number exactly
+/// `6·n²`, so `n` falls out of the non-`Nothing` tile count. Cross-checked
+/// by confirming that this `n` yields exactly six chunks, each of which is
+/// fully populated (not just its corner, which is all [`chunk_coords`]
+/// itself checks).
+fn cube_edge_length(map: &Map) -> usize {
+    let populated_tiles = map.data.iter().filter(|&&tile| tile != Tile::Nothing).count();
+    let n = (populated_tiles / 6).isqrt();
+    assert_eq!(
+        n * n * 6,
+        populated_tiles,
+        "populated area of the map should be exactly six n×n faces"
+    );
+
+    let chunks = chunk_coords(map, n);
+    assert_eq!(chunks.len(), 6, "cube net should be made up of six faces");
+    assert!(
+        chunks.iter().all(|&ChunkCoord(origin)| {
+            (0..n)
+                .cartesian_product(0..n)
+                .all(|(dy, dx)| map.data[origin * n + Vector([dy, dx])] != Tile::Nothing)
+        }),
+        "every tile of each detected face should be populated"
+    );
+
+    n
+}
+
 fn chunk_coords(map: &Map, n: usize) -> Vec<ChunkCoord> {
-    let &[height, width] = map.data.shape() else { panic!() };
+    let Vector([height, width]) = map.data.shape();
     (0..height / n)
         .cartesian_product(0..width / n)
         .map(|(y, x)| ChunkCoord(Vector([y, x])))
@@ -340,76 +477,14 @@ fn chunk_coords(map: &Map, n: usize) -> Vec<ChunkCoord> {
         .collect()
 }
 
-fn designate_chunks(chunk_coords: &[ChunkCoord]) -> HashMap<Side, (ChunkCoord, Direction)> {
-    let mut raw_sides = vec![(Side::A, (chunk_coords[0], Direction::Up))];
-    let mut sides = HashMap::from_iter(raw_sides.iter().cloned());
-    while let Some((side, (coords, orientation))) = raw_sides.pop() {
-        let icoords = coords.0.try_cast_as::<i64>().unwrap();
-
-        let skip_amount = match orientation {
-            Direction::Right => 1,
-            Direction::Down => 2,
-            Direction::Left => 3,
-            Direction::Up => 0,
-        };
-
-        for (neighbor_chunk, (side, neighbor_orientation)) in icoords
-            .nearest_neighbors()
-            .zip(
-                side_neighbors(side)
-                    .into_iter()
-                    .cycle()
-                    .skip(skip_amount)
-                    .map(|(s, o)| (s, o.rotate_by(orientation))),
-            )
-            .filter_map(|(ic, n)| ic.try_cast_as::<usize>().ok().map(|v| (ChunkCoord(v), n)))
-            .filter(|(c, _)| chunk_coords.contains(c))
-        {
-            if !sides.contains_key(side) {
-                sides.insert(*side, (neighbor_chunk, neighbor_orientation));
-                raw_sides.push((*side, (neighbor_chunk, neighbor_orientation)));
-            }
-        }
-    }
-
-    sides
-}
-
-fn side_neighbors(side: Side) -> &'static [(Side, Direction)] {
-    use Direction::*;
-    use Side::*;
-    match side {
-        A => &[(F, Up), (B, Up), (E, Up), (D, Up)],
-        B => &[(F, Left), (C, Up), (E, Right), (A, Up)],
-        C => &[(F, Down), (D, Up), (E, Down), (B, Up)],
-        D => &[(F, Right), (A, Up), (E, Left), (C, Up)],
-        E => &[(A, Up), (B, Left), (C, Down), (D, Right)],
-        F => &[(C, Down), (B, Right), (A, Up), (D, Left)],
-    }
-}
-
-fn transform_coords_in_chunk(
-    Vector([y, x]): Vector<usize, 2>,
-    dir: Direction,
-    size: usize,
-) -> Vector<usize, 2> {
-    match dir {
-        Direction::Right => Vector([x, size - 1 - y]),
-        Direction::Down => Vector([size - 1 - y, size - 1 - x]),
-        Direction::Left => Vector([size - 1 - x, y]),
-        Direction::Up => Vector([y, x]),
-    }
-}
-
-fn invert_side_mapping(
-    sides: &HashMap<Side, (ChunkCoord, Direction)>,
-) -> HashMap<ChunkCoord, (Side, Direction)> {
-    sides.iter().map(|(&s, &(c, o))| (c, (s, o))).collect()
-}
-
 impl Player {
-    fn execute<W>(&mut self, instruction: Instruction, map: &Map, wrapping: &W)
-    where
+    fn execute<W>(
+        &mut self,
+        instruction: Instruction,
+        map: &Map,
+        wrapping: &W,
+        trace: &mut HashMap<Vector<usize, 2>, Direction>,
+    ) where
         W: Wrapping,
     {
         match instruction {
@@ -420,6 +495,7 @@ impl Player {
                         break;
                     } else {
                         *self = wrapped;
+                        trace.insert(self.pos, self.facing);
                     }
                 }
             }
@@ -427,12 +503,26 @@ impl Player {
         }
     }
 
-    fn end<W: Wrapping>(mut self, instructions: &[Instruction], map: &Map) -> Self {
+    fn end<W: Wrapping>(self, instructions: &[Instruction], map: &Map) -> Self {
+        self.end_with_trace::<W>(instructions, map).0
+    }
+
+    /// Like [`Self::end`], but also returns every open tile the player
+    /// stepped onto along the way, paired with the facing at the moment of
+    /// that visit. Lets [`render_path`] retrace and display a walk whose
+    /// wrapping logic (in particular [`CubicWrapping`]'s folding) would
+    /// otherwise be opaque to eyeball.
+    fn end_with_trace<W: Wrapping>(
+        mut self,
+        instructions: &[Instruction],
+        map: &Map,
+    ) -> (Self, HashMap<Vector<usize, 2>, Direction>) {
         let wrapping = W::from_map(map);
+        let mut trace = HashMap::from([(self.pos, self.facing)]);
         for instruction in instructions {
-            self.execute(*instruction, map, &wrapping);
+            self.execute(*instruction, map, &wrapping, &mut trace);
         }
-        self
+        (self, trace)
     }
 
     fn password(&self) -> usize {
@@ -447,6 +537,34 @@ impl Player {
     }
 }
 
+/// Prints `map` with every tile the walk passed through (per `trace`, as
+/// collected by [`Player::end_with_trace`]) replaced by an arrow showing the
+/// facing at that moment, and the final tile marked `X`. Walls and open
+/// tiles never visited render as in the input; padding renders as a space.
+fn render_path(
+    map: &Map,
+    trace: &HashMap<Vector<usize, 2>, Direction>,
+    final_pos: Vector<usize, 2>,
+) -> String {
+    let Vector([height, width]) = map.data.shape();
+    (0..height)
+        .map(|y| {
+            (0..width)
+                .map(|x| {
+                    let pos = Vector([y, x]);
+                    match map.data[pos] {
+                        Tile::Nothing => ' ',
+                        Tile::Wall => '#',
+                        Tile::Open if pos == final_pos => 'X',
+                        Tile::Open => trace.get(&pos).map_or('.', Direction::glyph),
+                    }
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Instruction {
     Move(usize),
@@ -485,7 +603,7 @@ mod tests {
     #[test]
     fn map_is_parsed() {
         let Door { map, .. } = Door::parse(EXAMPLE_INPUT).unwrap();
-        assert_eq!(map.data.shape(), [12, 16]);
+        assert_eq!(map.data.shape(), Vector([12, 16]));
 
         assert_eq!(map.data[Vector([0, 0])], Tile::Nothing);
         assert_eq!(map.data[Vector([0, 7])], Tile::Nothing);
@@ -556,26 +674,24 @@ mod tests {
     }
 
     #[test]
-    fn sides_are_designed_to_chunks() {
-        assert_eq!(
-            designate_chunks(&EXAMPLE_CHUNK_COORDS),
-            HashMap::from(EXAMPLE_SIDES)
-        );
-    }
-
-    #[test]
-    fn side_mapping_can_be_inverted() {
-        assert_eq!(
-            invert_side_mapping(&HashMap::from(EXAMPLE_SIDES)),
-            HashMap::from(EXAMPLE_CHUNKS)
-        );
+    fn chunks_are_folded_into_distinct_cube_faces() {
+        let faces = fold_chunks(&EXAMPLE_CHUNK_COORDS);
+        assert_eq!(faces.len(), 6);
+
+        let normals: std::collections::HashSet<_> = faces.values().map(|f| f.normal).collect();
+        assert_eq!(normals.len(), 6, "every face should point a different way");
+        for face in faces.values() {
+            assert_eq!(face.normal.0.iter().filter(|&&c| c != 0).count(), 1);
+            assert_eq!(face.right.0.iter().filter(|&&c| c != 0).count(), 1);
+            assert_eq!(face.down.0.iter().filter(|&&c| c != 0).count(), 1);
+        }
     }
 
     #[test]
     fn player_moves_within_the_same_side_of_the_cube() {
-        let wrapping = CubicWrapping::<4> {
-            chunks: HashMap::from(EXAMPLE_CHUNKS),
-            sides: HashMap::from(EXAMPLE_SIDES),
+        let wrapping = CubicWrapping {
+            n: 4,
+            faces: fold_chunks(&EXAMPLE_CHUNK_COORDS),
         };
         assert_eq!(
             wrapping.advance(Player {
@@ -601,9 +717,9 @@ mod tests {
 
     #[test]
     fn player_moves_between_different_sides_of_the_cube() {
-        let wrapping = CubicWrapping::<4> {
-            chunks: HashMap::from(EXAMPLE_CHUNKS),
-            sides: HashMap::from(EXAMPLE_SIDES),
+        let wrapping = CubicWrapping {
+            n: 4,
+            faces: fold_chunks(&EXAMPLE_CHUNK_COORDS),
         };
         assert_eq!(
             wrapping.advance(Player {
@@ -666,10 +782,33 @@ mod tests {
         let Door { map, .. } = Door::parse(EXAMPLE_INPUT).unwrap();
         let player = map
             .player_start()
-            .end::<CubicWrapping<4>>(EXAMPLE_INSTRUCTIONS, &map);
+            .end::<CubicWrapping>(EXAMPLE_INSTRUCTIONS, &map);
         assert_eq!(player.password(), 5031);
     }
 
+    #[test]
+    fn cube_edge_length_is_derived_from_the_map() {
+        let Door { map, .. } = Door::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(cube_edge_length(&map), 4);
+    }
+
+    #[test]
+    fn path_is_rendered_with_facing_arrows_and_final_position_marked() {
+        let Door { map, instructions } = Door::parse(EXAMPLE_INPUT).unwrap();
+        let (player, trace) = map
+            .player_start()
+            .end_with_trace::<PlainWrapping>(&instructions, &map);
+        let rendered = render_path(&map, &trace, player.pos);
+
+        assert_eq!(rendered.lines().count(), map.data.shape()[0]);
+        assert!(rendered.contains('X'), "final position should be marked");
+        assert!(
+            rendered.chars().any(|c| ">v<^".contains(c)),
+            "at least one visited tile should show a facing arrow"
+        );
+        assert!(rendered.contains(' '), "padding should render as blank");
+    }
+
     const EXAMPLE_INPUT: &str = r"        ...#
         .#..
         #...
@@ -715,21 +854,4 @@ mod tests {
         ChunkCoord(Vector([2, 3])),
     ];
 
-    const EXAMPLE_CHUNKS: [(ChunkCoord, (Side, Direction)); 6] = [
-        (ChunkCoord(Vector([0, 2])), (Side::A, Direction::Up)),
-        (ChunkCoord(Vector([2, 3])), (Side::B, Direction::Down)),
-        (ChunkCoord(Vector([2, 2])), (Side::C, Direction::Down)),
-        (ChunkCoord(Vector([1, 1])), (Side::D, Direction::Left)),
-        (ChunkCoord(Vector([1, 0])), (Side::E, Direction::Down)),
-        (ChunkCoord(Vector([1, 2])), (Side::F, Direction::Up)),
-    ];
-
-    const EXAMPLE_SIDES: [(Side, (ChunkCoord, Direction)); 6] = [
-        (Side::A, (ChunkCoord(Vector([0, 2])), Direction::Up)),
-        (Side::B, (ChunkCoord(Vector([2, 3])), Direction::Down)),
-        (Side::C, (ChunkCoord(Vector([2, 2])), Direction::Down)),
-        (Side::D, (ChunkCoord(Vector([1, 1])), Direction::Left)),
-        (Side::E, (ChunkCoord(Vector([1, 0])), Direction::Down)),
-        (Side::F, (ChunkCoord(Vector([1, 2])), Direction::Up)),
-    ];
 }
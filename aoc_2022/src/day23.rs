@@ -12,22 +12,32 @@ pub(crate) struct Door {
 }
 
 impl<'input> ParseInput<'input> for Door {
-    fn parse(input: &'input str) -> Self {
-        Self {
+    type Error = std::convert::Infallible;
+
+    fn parse(input: &'input str) -> Result<Self, Self::Error> {
+        Ok(Self {
             elves: parse_input(input),
-        }
+        })
     }
 }
 
 impl Part1 for Door {
-    fn part1(&self) -> usize {
-        open_spaces_in_bounding_rect(&execute_many_rounds(self.elves.clone(), 10).0)
+    type Output = usize;
+    type Error = std::convert::Infallible;
+
+    fn part1(&self) -> Result<usize, Self::Error> {
+        Ok(open_spaces_in_bounding_rect(
+            &execute_many_rounds(self.elves.clone(), 10).0,
+        ))
     }
 }
 
 impl Part2 for Door {
-    fn part2(&self) -> usize {
-        execute_many_rounds(self.elves.clone(), usize::MAX).1
+    type Output = usize;
+    type Error = std::convert::Infallible;
+
+    fn part2(&self) -> Result<usize, Self::Error> {
+        Ok(execute_many_rounds(self.elves.clone(), usize::MAX).1)
     }
 }
 
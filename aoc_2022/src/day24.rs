@@ -4,7 +4,7 @@ use aoc_utils::linalg::Vector;
 
 use thiserror::Error;
 
-use std::collections::{BTreeSet, BinaryHeap, HashSet};
+use std::collections::{HashSet, VecDeque};
 
 pub(crate) struct Door {
     blizzards: Blizzards,
@@ -18,17 +18,17 @@ impl<'input> Solution<'input> for Door {
     }
 
     fn part1(&self) -> Result<u32, RuntimeError> {
-        shortest_time_to_exit(&self.blizzards, self.shape, 500)
+        shortest_time_to_exit(&self.blizzards, self.shape)
     }
 
     fn part2(&self) -> Result<u32, RuntimeError> {
-        shortest_time_for_snack_recovery(&self.blizzards, self.shape, 500)
+        shortest_time_for_snack_recovery(&self.blizzards, self.shape)
     }
 }
 
 #[derive(Debug, Error, PartialEq, Eq)]
 pub(crate) enum RuntimeError {
-    #[error("Could not find a path to the exit within the specified time box")]
+    #[error("Could not find a path to the exit")]
     NoPathFoundInTime,
 }
 
@@ -92,114 +92,112 @@ struct SpaceTime {
     point: Vector<i32, 2>,
 }
 
-impl Ord for SpaceTime {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        Ord::cmp(&other.time, &self.time).then_with(|| Ord::cmp(&self.point, &other.point))
-    }
-}
+/// The blizzard field repeats with period `lcm(rows, cols)`: each row's
+/// horizontal blizzards cycle every `cols` steps, and each column's vertical
+/// blizzards cycle every `rows` steps. Precomputing the occupied cells for
+/// every `t` in `0..period` turns each step's occupancy check into an O(1)
+/// set lookup instead of scanning every blizzard.
+fn occupancy_by_time(blizzards: &Blizzards, shape: (usize, usize)) -> Vec<HashSet<Vector<i32, 2>>> {
+    use num_integer::Integer as _;
 
-impl PartialOrd for SpaceTime {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
-    }
+    let period = shape.0.lcm(&shape.1) as u32;
+    (0..period)
+        .map(|t| blizzards.iter().map(|b| b.pos_at_time(t, shape)).collect())
+        .collect()
 }
 
+const START_POINT: Vector<i32, 2> = Vector([-1, 0]);
+const NEXT_TO_START_POINT: Vector<i32, 2> = Vector([0, 0]);
+
+/// Finds the shortest time to walk from `start` to `end`, waiting out
+/// blizzards where needed. Every state is a `(point, time mod period)` pair,
+/// of which there are only `rows * cols * period`; breadth-first search over
+/// this state space is therefore guaranteed to terminate, either by reaching
+/// `end` or by exhausting every reachable state.
 fn shortest_time_for_path(
     start: SpaceTime,
     end: Vector<i32, 2>,
-    blizzards: &Blizzards,
+    occupied_by_time: &[HashSet<Vector<i32, 2>>],
     shape: (usize, usize),
-    time_box: u32,
 ) -> Result<u32, RuntimeError> {
-    let final_time = start.time + time_box;
-    let mut flows = ndarray::Array2::from_elem(shape, BTreeSet::new());
-    let mut queue = BinaryHeap::from([start.clone()]);
+    let period = occupied_by_time.len() as u32;
+
+    let mut visited = HashSet::from([(start.point, start.time % period)]);
+    let mut queue = VecDeque::from([start]);
 
     while let Some(SpaceTime {
         time,
         point: current,
-    }) = queue.pop()
+    }) = queue.pop_front()
     {
-        let mut neighbors: HashSet<_> = current
+        if current == end {
+            return Ok(time);
+        }
+
+        let next_time = time + 1;
+        let occupied = &occupied_by_time[(next_time % period) as usize];
+
+        let candidates = current
             .nearest_neighbors()
-            .filter_map(|n| n.try_cast_as::<usize>().ok())
-            .filter(|&Vector([y, x])| y < shape.0 && x < shape.1)
-            .collect();
-
-        for time in (time + 1..=final_time).take_while(|&t| {
-            !blizzards
-                .iter()
-                .any(|b| b.pos_at_time(t - 1, shape) == current)
-        }) {
-            for n in neighbors.clone().iter() {
-                let n_int = n.try_cast_as::<i32>().unwrap();
-                if !blizzards
-                    .iter()
-                    .any(|b| b.pos_at_time(time, shape) == n_int)
-                {
-                    if current != start.point {
-                        neighbors.remove(n);
-                    }
-                    if flows[*n].insert(time) {
-                        queue.push(SpaceTime { time, point: n_int });
-                    }
-                }
+            .filter(|&n| {
+                n == end
+                    || n.try_cast_as::<usize>()
+                        .is_ok_and(|Vector([y, x])| y < shape.0 && x < shape.1)
+            })
+            .chain(std::iter::once(current));
+
+        for next in candidates {
+            if !occupied.contains(&next) && visited.insert((next, next_time % period)) {
+                queue.push_back(SpaceTime {
+                    time: next_time,
+                    point: next,
+                });
             }
         }
     }
 
-    flows[end.try_cast_as::<usize>().unwrap()]
-        .first()
-        .copied()
-        .ok_or(RuntimeError::NoPathFoundInTime)
-        .map(|t| t + 1)
+    Err(RuntimeError::NoPathFoundInTime)
 }
 
-const START_POINT: Vector<i32, 2> = Vector([-1, 0]);
-const NEXT_TO_START_POINT: Vector<i32, 2> = Vector([0, 0]);
-
 fn shortest_time_to_exit(
     blizzards: &Blizzards,
     shape: (usize, usize),
-    time_box: u32,
 ) -> Result<u32, RuntimeError> {
-    let next_to_exit = Vector([shape.0 - 1, shape.1 - 1]);
+    let occupied_by_time = occupancy_by_time(blizzards, shape);
+    let exit = Vector([shape.0, shape.1 - 1]).try_cast_as().unwrap();
     shortest_time_for_path(
         SpaceTime {
             time: 0,
             point: START_POINT,
         },
-        next_to_exit.try_cast_as().unwrap(),
-        blizzards,
+        exit,
+        &occupied_by_time,
         shape,
-        time_box,
     )
 }
 
 fn shortest_time_for_snack_recovery(
     blizzards: &Blizzards,
     shape: (usize, usize),
-    time_box_per_leg: u32,
 ) -> Result<u32, RuntimeError> {
-    let next_to_exit = Vector([shape.0 - 1, shape.1 - 1]).try_cast_as().unwrap();
-    let exit = Vector([shape.0, shape.1 - 1]).try_cast_as().unwrap();
+    let occupied_by_time = occupancy_by_time(blizzards, shape);
+    let exit: Vector<i32, 2> = Vector([shape.0, shape.1 - 1]).try_cast_as().unwrap();
+
     shortest_time_for_path(
         SpaceTime {
             time: 0,
             point: START_POINT,
         },
-        next_to_exit,
-        blizzards,
+        exit,
+        &occupied_by_time,
         shape,
-        time_box_per_leg,
     )
     .and_then(|time| {
         shortest_time_for_path(
             SpaceTime { time, point: exit },
             NEXT_TO_START_POINT,
-            blizzards,
+            &occupied_by_time,
             shape,
-            time_box_per_leg,
         )
     })
     .and_then(|time| {
@@ -208,10 +206,9 @@ fn shortest_time_for_snack_recovery(
                 time,
                 point: START_POINT,
             },
-            next_to_exit,
-            blizzards,
+            exit,
+            &occupied_by_time,
             shape,
-            time_box_per_leg,
         )
     })
 }
@@ -284,7 +281,7 @@ mod tests {
     #[test]
     fn shortest_time_to_exit_is_found() {
         assert_eq!(
-            shortest_time_to_exit(&HashSet::from(EXAMPLE_BLIZZARDS), EXAMPLE_SHAPE, 25),
+            shortest_time_to_exit(&HashSet::from(EXAMPLE_BLIZZARDS), EXAMPLE_SHAPE),
             Ok(18)
         );
     }
@@ -292,7 +289,7 @@ mod tests {
     #[test]
     fn shortest_time_for_snack_recovery_is_found() {
         assert_eq!(
-            shortest_time_for_snack_recovery(&HashSet::from(EXAMPLE_BLIZZARDS), EXAMPLE_SHAPE, 50),
+            shortest_time_for_snack_recovery(&HashSet::from(EXAMPLE_BLIZZARDS), EXAMPLE_SHAPE),
             Ok(54)
         );
     }
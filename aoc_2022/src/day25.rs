@@ -3,6 +3,8 @@ use aoc_companion::prelude::*;
 use itertools::Itertools;
 use thiserror::Error;
 
+use std::fmt::Write as _;
+use std::marker::PhantomData;
 use std::str::FromStr;
 
 pub struct Door {
@@ -26,85 +28,194 @@ impl Part1 for Door {
     type Error = std::convert::Infallible;
 
     fn part1(&self) -> Result<Self::Output, Self::Error> {
-        Ok(sum_snafu_numbers(&self.fuel))
+        Ok(sum_balanced_radix(&self.fuel))
+    }
+}
+
+// Day 25 traditionally has no second part.
+impl Part2 for Door {
+    type Output = std::convert::Infallible;
+    type Error = DoorError;
+
+    fn part2(&self) -> Result<Self::Output, Self::Error> {
+        Err(DoorError::SolutionNotImplemented)
     }
 }
 
 #[derive(Debug, Error, PartialEq, Eq)]
 pub enum ParseError {
-    #[error("Encountered invalid SNAFU digit")]
-    InvalidSnafuDigit,
+    #[error("Encountered invalid digit")]
+    InvalidDigit,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Snafu(Vec<i64>);
+/// The glyphs of a balanced, odd-`BASE` numeral system: `ALPHABET[i]` is the
+/// digit standing for the balanced value `i as i64 - (BASE - 1) / 2`, so the
+/// middle glyph always denotes zero. Pluggable the way base64's
+/// `CharacterSet` is, so a [`BalancedRadix`] can be given its own glyphs
+/// without touching the conversion/summation logic.
+pub trait DigitAlphabet<const BASE: usize> {
+    const ALPHABET: [char; BASE];
+}
+
+/// The conventional glyphs for balanced base 5 (SNAFU's `=-012`) and
+/// balanced base 3 (`-0+`).
+pub struct StandardAlphabet;
+
+impl DigitAlphabet<5> for StandardAlphabet {
+    const ALPHABET: [char; 5] = ['=', '-', '0', '1', '2'];
+}
+
+impl DigitAlphabet<3> for StandardAlphabet {
+    const ALPHABET: [char; 3] = ['-', '0', '+'];
+}
+
+/// A balanced, odd-radix numeral: each digit is one of `BASE` symmetric
+/// values centered on zero (`-(BASE-1)/2 ..= (BASE-1)/2`), stored least
+/// significant first. SNAFU (AoC 2022 day 25) is `BalancedRadix<5>`.
+pub struct BalancedRadix<const BASE: usize, A: DigitAlphabet<BASE> = StandardAlphabet> {
+    digits: Vec<i64>,
+    alphabet: PhantomData<A>,
+}
+
+pub type Snafu = BalancedRadix<5>;
+
+impl<const BASE: usize, A: DigitAlphabet<BASE>> Submissible for BalancedRadix<BASE, A> {}
+
+impl<const BASE: usize, A: DigitAlphabet<BASE>> BalancedRadix<BASE, A> {
+    fn from_digits(digits: Vec<i64>) -> Self {
+        Self {
+            digits,
+            alphabet: PhantomData,
+        }
+    }
+}
+
+impl<const BASE: usize, A: DigitAlphabet<BASE>> Clone for BalancedRadix<BASE, A> {
+    fn clone(&self) -> Self {
+        Self::from_digits(self.digits.clone())
+    }
+}
+
+impl<const BASE: usize, A: DigitAlphabet<BASE>> PartialEq for BalancedRadix<BASE, A> {
+    fn eq(&self, other: &Self) -> bool {
+        self.digits == other.digits
+    }
+}
+
+impl<const BASE: usize, A: DigitAlphabet<BASE>> Eq for BalancedRadix<BASE, A> {}
+
+impl<const BASE: usize, A: DigitAlphabet<BASE>> std::fmt::Debug for BalancedRadix<BASE, A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("BalancedRadix").field(&self.digits).finish()
+    }
+}
 
-impl FromStr for Snafu {
+impl<const BASE: usize, A: DigitAlphabet<BASE>> FromStr for BalancedRadix<BASE, A> {
     type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        s.as_bytes()
-            .iter()
+        s.chars()
             .rev()
-            .map(|d| match d {
-                b'=' => Ok(-2),
-                b'-' => Ok(-1),
-                b'0' => Ok(0),
-                b'1' => Ok(1),
-                b'2' => Ok(2),
-                _ => Err(ParseError::InvalidSnafuDigit),
+            .map(|c| {
+                A::ALPHABET
+                    .iter()
+                    .position(|&glyph| glyph == c)
+                    .map(|i| i as i64 - (BASE as i64 - 1) / 2)
+                    .ok_or(ParseError::InvalidDigit)
             })
             .try_collect()
-            .map(|digits| Self(digits))
+            .map(Self::from_digits)
     }
 }
 
-impl std::fmt::Display for Snafu {
+impl<const BASE: usize, A: DigitAlphabet<BASE>> std::fmt::Display for BalancedRadix<BASE, A> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        self.0.iter().rev().fold(Ok(()), |res, digit| {
-            res.and_then(|_| {
-                f.write_str(match digit {
-                    -2 => "=",
-                    -1 => "-",
-                    0 => "0",
-                    1 => "1",
-                    2 => "2",
-                    _ => "#",
-                })
-            })
+        self.digits.iter().rev().try_for_each(|digit| {
+            let index = (digit + (BASE as i64 - 1) / 2) as usize;
+            f.write_char(A::ALPHABET[index])
         })
     }
 }
 
-impl From<Snafu> for i64 {
-    fn from(value: Snafu) -> Self {
+impl<const BASE: usize, A: DigitAlphabet<BASE>> From<BalancedRadix<BASE, A>> for i64 {
+    fn from(value: BalancedRadix<BASE, A>) -> Self {
         value
-            .0
+            .digits
             .into_iter()
-            .fold((1, 0), |(base, sum), digit| (base * 5, sum + base * digit))
+            .fold((1, 0), |(base, sum), digit| {
+                (base * BASE as i64, sum + base * digit)
+            })
             .1
     }
 }
 
-impl From<i64> for Snafu {
+impl<const BASE: usize, A: DigitAlphabet<BASE>> From<i64> for BalancedRadix<BASE, A> {
     fn from(mut value: i64) -> Self {
+        let carry_threshold = (BASE as i64 + 1) / 2;
         let mut digits = Vec::new();
         let mut carry = 0;
         while value > 0 || carry > 0 {
-            let mut x = value % 5 + std::mem::replace(&mut carry, 0);
-            value /= 5;
-            while x >= 3 {
-                x -= 5;
+            let mut x = value % BASE as i64 + std::mem::replace(&mut carry, 0);
+            value /= BASE as i64;
+            while x >= carry_threshold {
+                x -= BASE as i64;
+                carry += 1;
+            }
+            digits.push(x);
+        }
+        Self::from_digits(digits)
+    }
+}
+
+/// Adds digit-by-digit with carry propagation, the same way
+/// [`From<i64>`](BalancedRadix#impl-From<i64>-for-BalancedRadix<BASE,+A>)
+/// derives each digit, except the digit vector grows to fit the result
+/// instead of ever being collapsed into a fixed-width integer. This is what
+/// lets [`sum_balanced_radix`] add arbitrarily many arbitrarily long numbers
+/// without an overflow ceiling.
+impl<const BASE: usize, A: DigitAlphabet<BASE>> std::ops::Add for BalancedRadix<BASE, A> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        let half = (BASE as i64 - 1) / 2;
+        let len = self.digits.len().max(rhs.digits.len());
+        let mut digits = Vec::with_capacity(len + 1);
+        let mut carry = 0;
+        for i in 0..len {
+            let mut x = self.digits.get(i).copied().unwrap_or(0)
+                + rhs.digits.get(i).copied().unwrap_or(0)
+                + carry;
+            carry = 0;
+            while x > half {
+                x -= BASE as i64;
                 carry += 1;
             }
-            digits.push(x as i64);
+            while x < -half {
+                x += BASE as i64;
+                carry -= 1;
+            }
+            digits.push(x);
+        }
+        if carry != 0 {
+            digits.push(carry);
+        }
+        while digits.last() == Some(&0) {
+            digits.pop();
         }
-        Self(digits)
+        Self::from_digits(digits)
     }
 }
 
-fn sum_snafu_numbers(numbers: &[Snafu]) -> Snafu {
-    numbers.iter().cloned().map(i64::from).sum::<i64>().into()
+impl<const BASE: usize, A: DigitAlphabet<BASE>> std::iter::Sum for BalancedRadix<BASE, A> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::from_digits(Vec::new()), |acc, n| acc + n)
+    }
+}
+
+fn sum_balanced_radix<const BASE: usize, A: DigitAlphabet<BASE>>(
+    numbers: &[BalancedRadix<BASE, A>],
+) -> BalancedRadix<BASE, A> {
+    numbers.iter().cloned().sum()
 }
 
 #[cfg(test)]
@@ -135,18 +246,23 @@ mod tests {
     fn snafu_numbers_can_be_parsed() {
         assert_eq!(
             "1121-1110-1=0".parse::<Snafu>(),
-            Ok(Snafu(vec![0, -2, 1, -1, 0, 1, 1, 1, -1, 1, 2, 1, 1]))
+            Ok(Snafu::from_digits(vec![
+                0, -2, 1, -1, 0, 1, 1, 1, -1, 1, 2, 1, 1
+            ]))
         );
         assert_eq!(
             "1121-1x10-1=0".parse::<Snafu>(),
-            Err(ParseError::InvalidSnafuDigit)
+            Err(ParseError::InvalidDigit)
         );
     }
 
     #[test]
     fn snafu_numbers_can_be_formatted() {
         assert_eq!(
-            &format!("{}", Snafu(vec![0, -2, 1, -1, 0, 1, 1, 1, -1, 1, 2, 1, 1])),
+            &format!(
+                "{}",
+                Snafu::from_digits(vec![0, -2, 1, -1, 0, 1, 1, 1, -1, 1, 2, 1, 1])
+            ),
             "1121-1110-1=0"
         );
     }
@@ -164,8 +280,34 @@ mod tests {
     #[test]
     fn snafu_numbers_can_be_summed_up() {
         assert_eq!(
-            sum_snafu_numbers(&snafu_numbers()),
+            sum_balanced_radix(&snafu_numbers()),
             "2=-1=0".parse().unwrap()
         );
     }
+
+    #[test]
+    fn summing_many_large_values_does_not_overflow_i64() {
+        let value = i64::MAX / 3;
+        let count = 10;
+        let numbers: Vec<Snafu> = std::iter::repeat(Snafu::from(value)).take(count).collect();
+
+        let total = sum_balanced_radix(&numbers);
+
+        let decoded: i128 = total
+            .digits
+            .iter()
+            .rev()
+            .fold(0i128, |acc, &digit| acc * 5 + digit as i128);
+        assert_eq!(decoded, value as i128 * count as i128);
+    }
+
+    #[test]
+    fn balanced_ternary_reuses_the_same_conversion_logic() {
+        type BalancedTernary = BalancedRadix<3>;
+
+        let ternary: BalancedTernary = "+-0".parse().unwrap();
+        assert_eq!(i64::from(ternary.clone()), 1 * 9 + -1 * 3 + 0);
+        assert_eq!(BalancedTernary::from(6), "+0-".parse().unwrap());
+        assert_eq!(&format!("{ternary}"), "+-0");
+    }
 }
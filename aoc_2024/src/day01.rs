@@ -1,7 +1,5 @@
-use std::num::ParseIntError;
-
 use aoc_companion::prelude::*;
-use aoc_utils::iter::IterUtils;
+use aoc_utils::parse::{parse_columns, ColumnsError};
 use itertools::Itertools;
 
 #[derive(Debug, PartialEq, Eq)]
@@ -10,28 +8,9 @@ pub(crate) struct Door {
     right: Vec<usize>,
 }
 
-#[derive(Debug, thiserror::Error)]
-pub(crate) enum ParseError {
-    #[error("expected two whitespace-separated tokens, got {token_count} tokens")]
-    WhitespaceError { token_count: usize },
-    #[error(transparent)]
-    ParseIntError(#[from] ParseIntError),
-}
-
 impl<'input> Solution<'input> for Door {
-    fn parse(input: &'input str) -> Result<Self, ParseError> {
-        let (left, right) = input
-            .lines()
-            .map(|line| -> Result<(usize, usize), ParseError> {
-                line.split_ascii_whitespace()
-                    .collect_tuple()
-                    .ok_or_else(|| ParseError::WhitespaceError {
-                        token_count: line.split_ascii_whitespace().count(),
-                    })
-                    .and_then(|(lhs, rhs)| Ok((lhs.parse()?, rhs.parse()?)))
-            })
-            .try_unzip()?;
-
+    fn parse(input: &'input str) -> Result<Self, ColumnsError> {
+        let [left, right] = parse_columns(input)?;
         Ok(Door { left, right })
     }
 
@@ -73,25 +52,29 @@ mod tests {
 
     #[test]
     fn parse_fails_for_token_mismatch() {
+        use aoc_utils::parse::ColumnsErrorKind::TokenCount;
+
         assert_matches!(
             Door::parse("1"),
-            Err(ParseError::WhitespaceError { token_count: 1 })
+            Err(ColumnsError { kind: TokenCount { expected: 2, found: 1 }, .. })
         );
         assert_matches!(
             Door::parse("1 2 3"),
-            Err(ParseError::WhitespaceError { token_count: 3 })
+            Err(ColumnsError { kind: TokenCount { expected: 2, found: 3 }, .. })
         );
         assert_matches!(
             Door::parse("one two three"),
-            Err(ParseError::WhitespaceError { token_count: 3 })
+            Err(ColumnsError { kind: TokenCount { expected: 2, found: 3 }, .. })
         );
     }
 
     #[test]
     fn parse_fails_for_non_numbers() {
-        assert_matches!(Door::parse("one two"), Err(ParseError::ParseIntError(_)));
-        assert_matches!(Door::parse("one 2"), Err(ParseError::ParseIntError(_)));
-        assert_matches!(Door::parse("1 two"), Err(ParseError::ParseIntError(_)));
+        use aoc_utils::parse::ColumnsErrorKind::Value;
+
+        assert_matches!(Door::parse("one two"), Err(ColumnsError { kind: Value, .. }));
+        assert_matches!(Door::parse("one 2"), Err(ColumnsError { kind: Value, .. }));
+        assert_matches!(Door::parse("1 two"), Err(ColumnsError { kind: Value, .. }));
     }
 
     #[test]
@@ -1,4 +1,8 @@
-use std::{cmp::Ordering, collections::HashSet, num::ParseIntError, str::FromStr};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    num::ParseIntError,
+    str::FromStr,
+};
 
 use aoc_companion::prelude::*;
 use itertools::Itertools;
@@ -49,38 +53,62 @@ impl<'input> Solution<'input> for Door {
 }
 
 impl Door {
-    fn rules_cmp(&self) -> impl Fn(&u32, &u32) -> Ordering + Copy + use<'_> {
-        |&lhs, &rhs| {
-            if lhs == rhs {
-                Ordering::Equal
-            } else if self.rules.contains(&(lhs, rhs)) {
-                Ordering::Less
-            } else if self.rules.contains(&(rhs, lhs)) {
-                Ordering::Greater
-            } else {
-                panic!(
-                    "Rules don't impose total ordering: unknown relation between {lhs} and {rhs}"
-                );
+    fn is_correctly_ordered(&self, update: &[u32]) -> bool {
+        update
+            .windows(2)
+            .all(|pair| !self.rules.contains(&(pair[1], pair[0])))
+    }
+
+    // Kahn's algorithm restricted to the rules relating pages that actually
+    // occur together in `update`, since the full rule set need not impose a
+    // total order over all pages.
+    fn topo_sorted(&self, update: &[u32]) -> Vec<u32> {
+        let mut in_degree: HashMap<u32, usize> = update.iter().map(|&page| (page, 0)).collect();
+        let mut successors: HashMap<u32, Vec<u32>> = HashMap::new();
+        for (i, &page) in update.iter().enumerate() {
+            for &other in &update[i + 1..] {
+                let (before, after) = if self.rules.contains(&(page, other)) {
+                    (page, other)
+                } else if self.rules.contains(&(other, page)) {
+                    (other, page)
+                } else {
+                    continue;
+                };
+                successors.entry(before).or_default().push(after);
+                *in_degree.get_mut(&after).unwrap() += 1;
+            }
+        }
+
+        let mut ready: VecDeque<u32> = update
+            .iter()
+            .copied()
+            .filter(|page| in_degree[page] == 0)
+            .collect();
+        let mut sorted = Vec::with_capacity(update.len());
+        while let Some(page) = ready.pop_front() {
+            sorted.push(page);
+            for &next in successors.get(&page).into_iter().flatten() {
+                let degree = in_degree.get_mut(&next).unwrap();
+                *degree -= 1;
+                if *degree == 0 {
+                    ready.push_back(next);
+                }
             }
         }
+        sorted
     }
 
-    fn sorted_updates(&self) -> impl Iterator<Item = impl AsRef<[u32]> + use<'_>> {
-        let cmp = self.rules_cmp();
+    fn sorted_updates(&self) -> impl Iterator<Item = &Vec<u32>> {
         self.updates
             .iter()
-            .filter(move |update| update.is_sorted_by(move |lhs, rhs| cmp(lhs, rhs).is_le()))
+            .filter(|update| self.is_correctly_ordered(update))
     }
 
-    fn restored_updates(&self) -> impl Iterator<Item = impl AsRef<[u32]> + use<'_>> {
-        self.updates.iter().filter_map(|update| {
-            let sorted = update
-                .iter()
-                .copied()
-                .sorted_by(self.rules_cmp())
-                .collect_vec();
-            (&sorted != update).then_some(sorted)
-        })
+    fn restored_updates(&self) -> impl Iterator<Item = Vec<u32>> + '_ {
+        self.updates
+            .iter()
+            .filter(|update| !self.is_correctly_ordered(update))
+            .map(|update| self.topo_sorted(update))
     }
 }
 
@@ -111,18 +111,23 @@ impl Door {
     }
 
     fn count_incursions_resulting_in_loop(&self) -> usize {
-        let (col_bounds, row_bounds) = self.map.bounds.clone();
-        Itertools::cartesian_product(col_bounds, row_bounds)
-            .par_bridge()
-            .map(|(col, row)| Vector([col, row]))
-            .filter(|p| self.starting_guard.pos != *p)
-            .filter(|p| !self.map.obstacles.contains(p))
+        // An obstacle off the guard's original route can never alter the
+        // path, so only the cells actually visited are worth trying.
+        let candidates: Vec<_> = self
+            .path()
+            .map(|g| g.pos)
+            .unique()
+            .filter(|&p| p != self.starting_guard.pos)
+            .collect();
+
+        candidates
+            .into_par_iter()
             .map(|p| {
-                self.clone().tap_mut(|clone| {
-                    clone.map.obstacles.insert(p);
+                self.map.clone().tap_mut(|map| {
+                    map.obstacles.insert(p);
                 })
             })
-            .filter(|door| is_loop(door.path()))
+            .filter(|map| is_loop(self.starting_guard, map))
             .count()
     }
 }
@@ -131,8 +136,30 @@ fn count_unique_positions(path: impl Iterator<Item = Guard>) -> usize {
     path.map(|g| g.pos).unique().count()
 }
 
-fn is_loop(path: impl Iterator<Item = Guard>) -> bool {
-    path.duplicates().next().is_some()
+/// Detects a loop in the guard's patrol via Floyd's tortoise-and-hare, in
+/// constant extra memory: a `slow` guard advances one step per iteration, a
+/// `fast` guard two, and they've found a loop iff the two ever coincide in
+/// both position and heading before `fast` walks off the map.
+fn is_loop(start: Guard, map: &Map) -> bool {
+    let mut slow = start;
+    let mut fast = start;
+
+    loop {
+        slow = step(slow, map);
+
+        fast = step(fast, map);
+        if !map.is_in_bounds(fast.pos) {
+            return false;
+        }
+        fast = step(fast, map);
+        if !map.is_in_bounds(fast.pos) {
+            return false;
+        }
+
+        if slow == fast {
+            return true;
+        }
+    }
 }
 
 #[cfg(test)]
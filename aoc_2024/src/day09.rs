@@ -1,4 +1,4 @@
-use std::ops::Range;
+use std::{collections::BTreeSet, ops::Range};
 
 use anyhow::anyhow;
 use aoc_companion::prelude::*;
@@ -62,25 +62,70 @@ fn compact(disk: &mut [Option<usize>]) {
     }
 }
 
+/// Tracks free gaps by size (a chunk's input digit caps it at 9) so that the
+/// leftmost gap fitting a file of a given length can be found in
+/// `O(log n)` instead of rescanning the disk for every file.
+struct FreeSpaceIndex {
+    /// `starts_by_size[size - 1]` holds the start offsets of every gap of
+    /// exactly `size`, in ascending order.
+    starts_by_size: [BTreeSet<usize>; 9],
+}
+
+impl FreeSpaceIndex {
+    fn from_disk(disk: &[Option<usize>]) -> Self {
+        let mut index = Self {
+            starts_by_size: std::array::from_fn(|_| BTreeSet::new()),
+        };
+        let mut gap_start = None;
+        for (i, slot) in disk.iter().enumerate() {
+            match (gap_start, slot.is_none()) {
+                (None, true) => gap_start = Some(i),
+                (Some(start), false) => {
+                    index.insert(start, i - start);
+                    gap_start = None;
+                }
+                _ => {}
+            }
+        }
+        if let Some(start) = gap_start {
+            index.insert(start, disk.len() - start);
+        }
+        index
+    }
+
+    fn insert(&mut self, start: usize, size: usize) {
+        if size > 0 {
+            self.starts_by_size[size - 1].insert(start);
+        }
+    }
+
+    /// Removes and returns the start offset and size of the leftmost gap of
+    /// at least `min_size`, among those starting before `limit`.
+    fn take_leftmost(&mut self, min_size: usize, limit: usize) -> Option<(usize, usize)> {
+        let (size, start) = (min_size..=9)
+            .filter_map(|size| Some((size, *self.starts_by_size[size - 1].first()?)))
+            .filter(|&(_, start)| start < limit)
+            .min_by_key(|&(_, start)| start)?;
+        self.starts_by_size[size - 1].remove(&start);
+        Some((start, size))
+    }
+}
+
 fn defragment(disk: &mut [Option<usize>]) {
     let Some(max_id) = disk.iter().rev().find_map(|x| *x) else {
         return;
     };
 
+    let mut free_space = FreeSpaceIndex::from_disk(disk);
+
     for id in (0..=max_id).rev() {
         let file_span = find_span(Some(id), disk).unwrap();
+        let len = file_span.len();
 
-        let (before, after) = disk.split_at_mut(file_span.start);
-
-        let mut free_chunks = before
-            .chunk_by_mut(|lhs, rhs| lhs.is_some() == rhs.is_some())
-            .skip(1)
-            .step_by(2);
-
-        if let Some(free_chunk) = free_chunks.find(|chunk| chunk.len() >= file_span.len()) {
-            let free_chunk = &mut free_chunk[..file_span.len()];
-            let file_chunk = &mut after[..file_span.len()];
-            free_chunk.swap_with_slice(file_chunk);
+        if let Some((start, gap_size)) = free_space.take_leftmost(len, file_span.start) {
+            disk.copy_within(file_span.clone(), start);
+            disk[file_span].fill(None);
+            free_space.insert(start + len, gap_size - len);
         }
     }
 }
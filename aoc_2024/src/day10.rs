@@ -1,8 +1,6 @@
-use std::collections::HashSet;
-
 use aoc_companion::prelude::*;
 use aoc_utils::{
-    geometry::{map_bounds, Point},
+    geometry::{bfs_reachable, count_distinct_paths, grid_neighbors, map_bounds},
     linalg::Vector,
 };
 use itertools::Itertools;
@@ -36,18 +34,13 @@ fn parse_map(input: &str) -> Result<ndarray::Array2<u8>, ndarray::ShapeError> {
     ndarray::Array2::from_shape_vec(bounds.map(|b| b.end as usize), data)
 }
 
-fn reachable_niners<Coll>(start: Vector<usize, 2>, map: &ndarray::Array2<u8>) -> Coll
-where
-    Coll: FromIterator<Vector<usize, 2>>,
-    for<'a> &'a Coll: IntoIterator<Item = &'a Vector<usize, 2>>,
-{
-    ((map[start] + 1)..=9).fold(std::iter::once(start).collect(), |points, level| {
-        points
-            .into_iter()
-            .flat_map(|p| p.nearest_neighbors())
-            .filter(|p| map.get(*p) == Some(&level))
-            .collect()
-    })
+/// A single uphill step from `p`: the neighbors exactly one height higher,
+/// the only moves a trail is allowed to make.
+fn climb(map: &ndarray::Array2<u8>, p: Vector<usize, 2>) -> Vec<Vector<usize, 2>> {
+    let next_height = map[p] + 1;
+    grid_neighbors(p, map)
+        .filter(move |&n| map[n] == next_height)
+        .collect()
 }
 
 fn trailhead_iter(map: &ndarray::Array2<u8>) -> impl Iterator<Item = Vector<usize, 2>> + use<'_> {
@@ -57,11 +50,16 @@ fn trailhead_iter(map: &ndarray::Array2<u8>) -> impl Iterator<Item = Vector<usiz
 }
 
 fn trailhead_scores(map: &ndarray::Array2<u8>) -> impl Iterator<Item = usize> + use<'_> {
-    trailhead_iter(map).map(|v| reachable_niners::<HashSet<Vector<usize, 2>>>(v, map).len())
+    trailhead_iter(map).map(|start| {
+        bfs_reachable(start, |p| climb(map, p), |_| true)
+            .into_iter()
+            .filter(|&p| map[p] == 9)
+            .count()
+    })
 }
 
 fn trailhead_ratings(map: &ndarray::Array2<u8>) -> impl Iterator<Item = usize> + use<'_> {
-    trailhead_iter(map).map(|v| reachable_niners::<Vec<Vector<usize, 2>>>(v, map).len())
+    trailhead_iter(map).map(|start| count_distinct_paths(start, |p| climb(map, p), |p| map[p] == 9))
 }
 
 #[cfg(test)]
@@ -1,6 +1,6 @@
-use anyhow::bail;
 use aoc_companion::prelude::*;
 use aoc_utils::linalg::Vector;
+use aoc_utils::parse::{finish, vector};
 use itertools::Itertools;
 
 const OFFSET: i64 = 10000000000000;
@@ -45,48 +45,14 @@ impl std::str::FromStr for Machine {
 
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
         let mut lines = s.trim().lines();
-        let Some(a_line) = lines.next() else {
-            bail!("missing line describing button A");
-        };
-        let Some(b_line) = lines.next() else {
-            bail!("missing line describing button B");
-        };
-        let Some(prize_line) = lines.next() else {
-            bail!("missing line describing prize");
-        };
-        let Some(a_line) = a_line.strip_prefix("Button A: ") else {
-            bail!("missing prefix for button A");
-        };
-        let Some(b_line) = b_line.strip_prefix("Button B: ") else {
-            bail!("missing prefix for button B");
-        };
-        let Some(prize_line) = prize_line.strip_prefix("Prize: ") else {
-            bail!("missing prefix for prize");
-        };
-
-        let parse_vector = |str: &str, introducer: char| -> Result<Vector<i64, 2>> {
-            let Some((lhs, rhs)) = str.split_once(", ") else {
-                bail!("missing comma delimiting coordinates");
-            };
-            let Some(lhs) = lhs.strip_prefix('X') else {
-                bail!("missing coordinate name 'X'");
-            };
-            let Some(rhs) = rhs.strip_prefix('Y') else {
-                bail!("missing coordinate name 'Y'");
-            };
-            let Some(lhs) = lhs.strip_prefix(introducer) else {
-                bail!("missing coordinate introducer {introducer:?}");
-            };
-            let Some(rhs) = rhs.strip_prefix(introducer) else {
-                bail!("missing coordinate introducer {introducer:?}");
-            };
-            Ok(Vector([lhs.parse()?, rhs.parse()?]))
-        };
+        let a_line = lines.next().unwrap_or_default();
+        let b_line = lines.next().unwrap_or_default();
+        let prize_line = lines.next().unwrap_or_default();
 
         Ok(Machine {
-            a: parse_vector(a_line, '+')?,
-            b: parse_vector(b_line, '+')?,
-            prize: parse_vector(prize_line, '=')?,
+            a: finish(a_line, vector(["Button A: X+", ", Y+"]))?,
+            b: finish(b_line, vector(["Button B: X+", ", Y+"]))?,
+            prize: finish(prize_line, vector(["Prize: X=", ", Y="]))?,
         })
     }
 }
@@ -1,8 +1,11 @@
 use std::collections::HashSet;
 
-use anyhow::bail;
 use aoc_companion::prelude::*;
-use aoc_utils::{geometry::Point, linalg::Vector};
+use aoc_utils::{
+    geometry::{Point, dump_frames, render_points},
+    linalg::Vector,
+    parse::{finish, tag, vector},
+};
 use itertools::Itertools;
 
 pub(crate) struct Door {
@@ -40,22 +43,12 @@ impl std::str::FromStr for Robot {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self> {
-        let Some((pos_str, vel_str)) = s.trim().split_once(' ') else {
-            bail!("Missing space separating position and velocity");
-        };
-
-        let Some(pos_str) = pos_str.strip_prefix("p=") else {
-            bail!("Missing position introducer");
-        };
-
-        let Some(vel_str) = vel_str.strip_prefix("v=") else {
-            bail!("Missing velocity introducer");
-        };
-
-        Ok(Robot {
-            p: pos_str.parse()?,
-            v: vel_str.parse()?,
-        })
+        Ok(finish(s.trim(), |input| {
+            let (rest, p) = vector(["p=", ","])(input)?;
+            let (rest, ()) = tag(" ")(rest)?;
+            let (rest, v) = vector(["v=", ","])(rest)?;
+            Ok((rest, Robot { p, v }))
+        })?)
     }
 }
 
@@ -122,6 +115,29 @@ fn steps_to_large_cluster(robots: &[Robot], bounds: Vector<i16, 2>, critical_mas
         + 1
 }
 
+/// Successive robot generations starting from `robots`, one step apart,
+/// suitable for dumping to disk with [`dump_frames_to`] to eyeball what
+/// `steps_to_large_cluster`'s heuristic detected.
+fn frames_from(robots: &[Robot], bounds: Vector<i16, 2>) -> impl Iterator<Item = Vec<Robot>> {
+    std::iter::successors(Some(robots.to_vec()), move |prev| {
+        let mut next = prev.clone();
+        step_n(&mut next, 1, bounds);
+        Some(next)
+    })
+}
+
+fn dump_frames_to(
+    robots: &[Robot],
+    bounds: Vector<i16, 2>,
+    count: usize,
+    dir: &std::path::Path,
+) -> std::io::Result<usize> {
+    dump_frames(frames_from(robots, bounds).take(count), dir, |robots| {
+        let points: HashSet<_> = robots.iter().map(|r| r.p).collect();
+        render_points(&points, bounds, '#', '.')
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use itertools::assert_equal;
@@ -210,4 +226,27 @@ p=9,5 v=-3,-3";
         step_n(&mut robots, 100, EXAMPLE_BOUNDS);
         assert_eq!(safety_factor(&robots, EXAMPLE_BOUNDS), 12);
     }
+
+    #[test]
+    fn dump_frames_to_writes_one_file_per_step() {
+        let dir = std::env::temp_dir().join("aoc-day14-dump-frames-to-test");
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let written = dump_frames_to(EXAMPLE_ROBOTS, EXAMPLE_BOUNDS, 3, &dir).unwrap();
+
+        assert_eq!(written, 3);
+        let first_frame = std::fs::read_to_string(dir.join("0000.txt")).unwrap();
+        assert_eq!(
+            first_frame,
+            render_points(
+                &EXAMPLE_ROBOTS.iter().map(|r| r.p).collect(),
+                EXAMPLE_BOUNDS,
+                '#',
+                '.'
+            )
+        );
+        assert!(dir.join("0002.txt").exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
 }
@@ -1,9 +1,10 @@
-use std::collections::{HashMap, HashSet};
+use std::cmp::Ordering;
+use std::collections::HashSet;
 
 use aoc_companion::prelude::*;
 use aoc_utils::{
-    array,
-    geometry::{map_bounds, Point},
+    geometry::{map_bounds, normalize_grid_input},
+    graph::{self, ContractedGraph},
     linalg::Vector,
 };
 
@@ -32,12 +33,13 @@ impl<'input> Solution<'input> for Door {
     }
 
     fn part2(&self) -> usize {
-        find_seats(&self.graph, self.start, self.end).seats.len() + 1
+        find_seats(&self.graph, self.start, self.end).len() + 1
     }
 }
 
 fn parse_map(input: &str) -> Result<Map> {
-    let bounds = map_bounds(input).map(|r| r.end);
+    let input = normalize_grid_input(input)?;
+    let bounds = map_bounds(&input).map(|r| r.end);
     let data = input.lines().flat_map(str::as_bytes).copied().collect();
     Ok(Map::from_shape_vec(bounds, data)?)
 }
@@ -47,157 +49,84 @@ fn find_in_map(map: &Map, target: u8) -> Option<Vector<usize, 2>> {
         .find_map(|((row, col), b)| (*b == target).then_some(Vector([row, col])))
 }
 
-type Graph = HashMap<Vertex, Node>;
-
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-struct Vertex {
-    position: Vector<usize, 2>,
-    facing: usize,
+type Vertex = graph::Vertex<Vector<usize, 2>>;
+type Graph = ContractedGraph<Vector<usize, 2>, 4>;
+
+/// Cost of turning from one facing to another, on top of the unconditional
+/// unit cost of a step: no penalty for continuing straight, 1000 for
+/// turning (matching the reindeer's movement rules).
+fn turn_cost(prev_facing: usize, next_facing: usize) -> usize {
+    if prev_facing == next_facing {
+        0
+    } else {
+        1000
+    }
 }
 
-type Node = [Option<Edge>; 4];
-
-#[derive(Debug, Clone, PartialEq, Eq)]
-struct Edge {
-    target: Vertex,
-    distance: usize,
-    seats: HashSet<Vector<usize, 2>>,
+fn graph(map: &Map, start: Vector<usize, 2>, end: Vector<usize, 2>) -> Graph {
+    let all_points = map.indexed_iter().map(|((row, col), _)| Vector([row, col]));
+    graph::contract_grid(
+        all_points,
+        |p: Vector<usize, 2>| map[p] != b'#',
+        |p| p == start || p == end,
+        turn_cost,
+    )
 }
 
-fn graph(map: &Map, start: Vector<usize, 2>, end: Vector<usize, 2>) -> Graph {
-    let mut junctions = HashSet::from([start, end]);
-    junctions.extend(map.indexed_iter().filter_map(|((row, col), b)| {
-        (*b == b'.')
-            .then_some(Vector([row, col]))
-            .filter(|p| p.nearest_neighbors().filter(|n| map[*n] != b'#').count() > 2)
-    }));
-
-    junctions
-        .iter()
-        .flat_map(|p| {
-            (0..4).map(|facing_from| {
-                (
-                    Vertex {
-                        position: *p,
-                        facing: facing_from,
-                    },
-                    array::from_iter_exact(p.nearest_neighbors().enumerate().map(
-                        |(facing_to, n)| {
-                            if facing_from == facing_to {
-                                (map[n] != b'#')
-                                    .then(|| {
-                                        std::iter::successors(
-                                            Some((facing_from, n, *p)),
-                                            |(_, current, prev)| {
-                                                current
-                                                    .nearest_neighbors()
-                                                    .enumerate()
-                                                    .filter(|(_, nn)| map[*nn] != b'#')
-                                                    .find(|(_, nn)| nn != prev)
-                                                    .map(|(dir, nn)| (dir, nn, *current))
-                                            },
-                                        )
-                                        .scan(
-                                            (facing_from, 0usize, HashSet::new()),
-                                            |(dd, dist, seats), (dir, point, _)| {
-                                                *dist += if *dd != dir { 1001 } else { 1 };
-                                                *dd = dir;
-                                                seats.insert(point);
-                                                Some(Edge {
-                                                    target: Vertex {
-                                                        position: point,
-                                                        facing: dir,
-                                                    },
-                                                    distance: *dist,
-                                                    seats: seats.clone(),
-                                                })
-                                            },
-                                        )
-                                        .find(|edge| junctions.contains(&edge.target.position))
-                                    })
-                                    .flatten()
-                            } else {
-                                (map[n] != b'#').then(|| Edge {
-                                    target: Vertex {
-                                        position: *p,
-                                        facing: facing_to,
-                                    },
-                                    distance: 1000,
-                                    seats: HashSet::new(),
-                                })
-                            }
-                        },
-                    ))
-                    .unwrap(),
-                )
-            })
-        })
-        .collect()
+fn start_vertex(position: Vector<usize, 2>) -> Vertex {
+    Vertex {
+        position,
+        facing: 1,
+    }
 }
 
 fn find_shortest_path(graph: &Graph, start: Vector<usize, 2>, end: Vector<usize, 2>) -> usize {
-    find_seats(graph, start, end).distance
+    graph::shortest_path(graph, start_vertex(start), &end).unwrap()
 }
 
-#[derive(Debug, Clone, Default)]
-struct SeatState {
-    distance: usize,
-    seats: HashSet<Vector<usize, 2>>,
+/// A lower bound on the remaining distance from `vertex` to `end`: the
+/// Manhattan distance (every remaining step costs at least 1) plus a
+/// mandatory turn penalty of 1000 if `vertex`'s facing isn't already the one
+/// straight-line direction that could reach `end` without turning again —
+/// which is always the case once both the row and column offsets to `end`
+/// are nonzero, since no single facing points straight at the target.
+fn heuristic(vertex: &Vertex, end: Vector<usize, 2>) -> usize {
+    let [row, col] = vertex.position.0;
+    let [end_row, end_col] = end.0;
+    let manhattan = row.abs_diff(end_row) + col.abs_diff(end_col);
+
+    let turn_needed = match (end_row.cmp(&row), end_col.cmp(&col)) {
+        (Ordering::Equal, Ordering::Equal) => false,
+        (Ordering::Equal, Ordering::Greater) => vertex.facing != 1,
+        (Ordering::Equal, Ordering::Less) => vertex.facing != 3,
+        (Ordering::Greater, Ordering::Equal) => vertex.facing != 0,
+        (Ordering::Less, Ordering::Equal) => vertex.facing != 2,
+        _ => true,
+    };
+
+    manhattan + if turn_needed { 1000 } else { 0 }
 }
 
-impl SeatState {
-    fn update(mut self, old: Option<&SeatState>) -> Option<SeatState> {
-        if let Some(old) = old {
-            match self.distance.cmp(&old.distance) {
-                std::cmp::Ordering::Less => Some(self),
-                std::cmp::Ordering::Equal if self.seats.is_subset(&old.seats) => None,
-                std::cmp::Ordering::Equal => {
-                    self.seats.extend(old.seats.clone());
-                    Some(self)
-                }
-                std::cmp::Ordering::Greater => None,
-            }
-        } else {
-            Some(self)
-        }
-    }
+/// Like [`find_shortest_path`], but explores the contracted graph with
+/// [`graph::shortest_path_a_star`] guided by [`heuristic`] instead of plain
+/// Dijkstra, pruning corridors that can't possibly lead to a shorter route.
+fn find_shortest_path_a_star(
+    graph: &Graph,
+    start: Vector<usize, 2>,
+    end: Vector<usize, 2>,
+) -> usize {
+    graph::shortest_path_a_star(graph, start_vertex(start), &end, |vertex| {
+        heuristic(vertex, end)
+    })
+    .unwrap()
 }
 
-fn find_seats(graph: &Graph, start: Vector<usize, 2>, end: Vector<usize, 2>) -> SeatState {
-    let mut distances = HashMap::from([(
-        Vertex {
-            position: start,
-            facing: 1,
-        },
-        SeatState::default(),
-    )]);
-
-    let mut todo = HashSet::from([Vertex {
-        position: start,
-        facing: 1,
-    }]);
-    while let Some(current) = todo.iter().next().cloned() {
-        todo.remove(&current);
-        for edge in graph.get(&current).unwrap().iter().flatten() {
-            let mut target_state = distances.get(&current).unwrap().clone();
-            target_state.distance += edge.distance;
-            target_state.seats.extend(edge.seats.iter().cloned());
-
-            if let Some(updated) = target_state.update(distances.get(&edge.target)) {
-                distances.insert(edge.target.clone(), updated);
-                todo.insert(edge.target.clone());
-            }
-        }
-    }
-
-    (0..4)
-        .map(|i| Vertex {
-            position: end,
-            facing: i,
-        })
-        .filter_map(|end_vertex| distances.get(&end_vertex).cloned())
-        .fold(None, |lhs, rhs| rhs.clone().update(lhs.as_ref()).or(lhs))
-        .unwrap()
+fn find_seats(
+    graph: &Graph,
+    start: Vector<usize, 2>,
+    end: Vector<usize, 2>,
+) -> HashSet<Vector<usize, 2>> {
+    graph::optimal_path_nodes(graph, start_vertex(start), &end)
 }
 
 #[cfg(test)]
@@ -251,6 +180,22 @@ mod tests {
         assert_eq!(find_in_map(&map, b'E'), Some(SMALL_EXAMPLE_END));
     }
 
+    #[test]
+    fn parses_crlf_terminated_input() {
+        let crlf_input = SMALL_EXAMPLE_INPUT.replace('\n', "\r\n");
+        let map = parse_map(&crlf_input).unwrap();
+        assert_eq!(find_in_map(&map, b'S'), Some(SMALL_EXAMPLE_START));
+        assert_eq!(find_in_map(&map, b'E'), Some(SMALL_EXAMPLE_END));
+    }
+
+    #[test]
+    fn parses_input_with_a_trailing_blank_line() {
+        let input = format!("{SMALL_EXAMPLE_INPUT}\n\n");
+        let map = parse_map(&input).unwrap();
+        assert_eq!(find_in_map(&map, b'S'), Some(SMALL_EXAMPLE_START));
+        assert_eq!(find_in_map(&map, b'E'), Some(SMALL_EXAMPLE_END));
+    }
+
     #[test]
     fn shortest_path_for_small_example() {
         assert_eq!(
@@ -263,6 +208,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn a_star_agrees_with_dijkstra_for_small_example() {
+        assert_eq!(
+            find_shortest_path_a_star(
+                &small_example_graph(),
+                SMALL_EXAMPLE_START,
+                SMALL_EXAMPLE_END
+            ),
+            7036
+        );
+    }
+
+    #[test]
+    fn a_star_agrees_with_dijkstra_for_larger_example() {
+        assert_eq!(
+            find_shortest_path_a_star(
+                &larger_example_graph(),
+                LARGER_EXAMPLE_START,
+                LARGER_EXAMPLE_END
+            ),
+            11048
+        );
+    }
+
     #[test]
     fn short_way_up() {
         assert_eq!(
@@ -291,7 +260,6 @@ mod tests {
                 SMALL_EXAMPLE_START,
                 SMALL_EXAMPLE_END
             )
-            .seats
             .len()
                 + 1,
             45
@@ -306,7 +274,6 @@ mod tests {
                 LARGER_EXAMPLE_START,
                 LARGER_EXAMPLE_END
             )
-            .seats
             .len()
                 + 1,
             64
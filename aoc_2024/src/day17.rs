@@ -1,7 +1,9 @@
+use std::collections::HashMap;
+
 use anyhow::{anyhow, bail};
 use aoc_companion::prelude::*;
 use aoc_utils::array;
-use itertools::{Itertools, iterate};
+use itertools::Itertools;
 
 pub(crate) struct Door {
     initial_registers: [u64; 3],
@@ -87,37 +89,9 @@ impl Iterator for Computer<'_> {
 
     fn next(&mut self) -> Option<Self::Item> {
         loop {
-            let Some(&[op, arg]) = self.program.get(self.ip..self.ip + 2) else {
-                return None;
-            };
-
-            self.ip += 2;
-
-            match op {
-                ADV => {
-                    self.registers[0] >>= self.combo(arg);
-                }
-                BXL => {
-                    self.registers[1] ^= arg as u64;
-                }
-                BST => {
-                    self.registers[1] = self.combo(arg) % 8;
-                }
-                JNZ if self.registers[0] == 0 => {}
-                JNZ => {
-                    self.ip = arg as usize;
-                }
-                BXC => {
-                    self.registers[1] ^= self.registers[2];
-                }
-                OUT => return Some(self.combo(arg) % 8),
-                BDV => {
-                    self.registers[1] = self.registers[0] >> self.combo(arg);
-                }
-                CDV => {
-                    self.registers[2] = self.registers[0] >> self.combo(arg);
-                }
-                8.. => unreachable!("invalid opcode"),
+            let (.., output) = self.step()?;
+            if let Some(value) = output {
+                return Some(value);
             }
         }
     }
@@ -131,6 +105,224 @@ impl Computer<'_> {
             7.. => unreachable!("invalid combo operand"),
         }
     }
+
+    /// Executes exactly one instruction and reports the instruction pointer
+    /// and registers afterwards, plus the value emitted if this was an
+    /// `out`. Returns `None` once the instruction pointer runs past the end
+    /// of the program, the same halt condition the `Iterator` impl honors.
+    /// `next()` is just this, looped until an `out` produces a value.
+    fn step(&mut self) -> Option<(usize, [u64; 3], Option<u64>)> {
+        let &[op, arg] = self.program.get(self.ip..self.ip + 2)?;
+        self.ip += 2;
+
+        let output = match op {
+            ADV => {
+                self.registers[0] >>= self.combo(arg);
+                None
+            }
+            BXL => {
+                self.registers[1] ^= arg as u64;
+                None
+            }
+            BST => {
+                self.registers[1] = self.combo(arg) % 8;
+                None
+            }
+            JNZ if self.registers[0] == 0 => None,
+            JNZ => {
+                self.ip = arg as usize;
+                None
+            }
+            BXC => {
+                self.registers[1] ^= self.registers[2];
+                None
+            }
+            OUT => Some(self.combo(arg) % 8),
+            BDV => {
+                self.registers[1] = self.registers[0] >> self.combo(arg);
+                None
+            }
+            CDV => {
+                self.registers[2] = self.registers[0] >> self.combo(arg);
+                None
+            }
+            8.. => unreachable!("invalid opcode"),
+        };
+
+        Some((self.ip, self.registers, output))
+    }
+
+    /// Single-steps to completion, yielding a snapshot after every
+    /// instruction (jumps and silent register ops included, not just
+    /// `out`s), for inspecting register evolution or cross-checking a
+    /// reverse-engineered code path instruction by instruction.
+    fn trace(&mut self) -> impl Iterator<Item = (usize, [u64; 3], Option<u64>)> + '_ {
+        std::iter::from_fn(move || self.step())
+    }
+}
+
+/// Renders `program` as one mnemonic per opcode/operand pair (`adv`, `bxl`,
+/// `bst`, `jnz`, `bxc`, `out`, `bdv`, `cdv`), with combo operands spelled out
+/// symbolically (`A`, `B`, `C`) rather than as the raw `4`/`5`/`6` literals
+/// the combo encoding uses.
+fn disassemble(program: &[u8]) -> Vec<String> {
+    fn combo(arg: u8) -> String {
+        match arg {
+            0..4 => arg.to_string(),
+            4 => "A".to_string(),
+            5 => "B".to_string(),
+            6 => "C".to_string(),
+            7.. => format!("<reserved {arg}>"),
+        }
+    }
+
+    program
+        .chunks(2)
+        .map(|chunk| match *chunk {
+            [ADV, arg] => format!("adv {}", combo(arg)),
+            [BXL, arg] => format!("bxl {arg}"),
+            [BST, arg] => format!("bst {}", combo(arg)),
+            [JNZ, arg] => format!("jnz {arg}"),
+            [BXC, arg] => format!("bxc {arg}"),
+            [OUT, arg] => format!("out {}", combo(arg)),
+            [BDV, arg] => format!("bdv {}", combo(arg)),
+            [CDV, arg] => format!("cdv {}", combo(arg)),
+            [op, arg] => format!("<invalid opcode {op}> {arg}"),
+            [op] => format!("<truncated opcode {op}>"),
+            [] => unreachable!("chunks(2) never yields an empty chunk"),
+        })
+        .collect()
+}
+
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum AssembleError {
+    #[error("line {line}: unknown mnemonic {mnemonic:?}")]
+    UnknownMnemonic { line: usize, mnemonic: String },
+    #[error("line {line}: missing operand")]
+    MissingOperand { line: usize },
+    #[error("line {line}: operand {operand:?} is neither a number, a combo register (A/B/C), nor a label")]
+    InvalidOperand { line: usize, operand: String },
+    #[error("line {line}: literal operand {value} does not fit in 3 bits (must be 0..=7)")]
+    LiteralOutOfRange { line: usize, value: u32 },
+    #[error("line {line}: combo operand {value} is out of range (must be 0..=6)")]
+    ComboOutOfRange { line: usize, value: u32 },
+    #[error("line {line}: jump target {value} does not fit in a single byte")]
+    JumpTargetOutOfRange { line: usize, value: usize },
+    #[error("line {line}: undefined label {label:?}")]
+    UndefinedLabel { line: usize, label: String },
+}
+
+fn parse_literal(operand: &str, line: usize) -> Result<u8, AssembleError> {
+    let value: u32 = operand.parse().map_err(|_| AssembleError::InvalidOperand {
+        line,
+        operand: operand.to_string(),
+    })?;
+    if value > 7 {
+        return Err(AssembleError::LiteralOutOfRange { line, value });
+    }
+    Ok(value as u8)
+}
+
+fn parse_combo(operand: &str, line: usize) -> Result<u8, AssembleError> {
+    match operand {
+        "A" => Ok(4),
+        "B" => Ok(5),
+        "C" => Ok(6),
+        _ => {
+            let value: u32 = operand.parse().map_err(|_| AssembleError::InvalidOperand {
+                line,
+                operand: operand.to_string(),
+            })?;
+            if value > 6 {
+                return Err(AssembleError::ComboOutOfRange { line, value });
+            }
+            Ok(value as u8)
+        }
+    }
+}
+
+fn resolve_jnz_target(
+    operand: &str,
+    line: usize,
+    labels: &HashMap<String, usize>,
+) -> Result<u8, AssembleError> {
+    let target = match operand.parse::<usize>() {
+        Ok(value) => value,
+        Err(_) => *labels
+            .get(operand)
+            .ok_or_else(|| AssembleError::UndefinedLabel {
+                line,
+                label: operand.to_string(),
+            })?,
+    };
+    u8::try_from(target).map_err(|_| AssembleError::JumpTargetOutOfRange {
+        line,
+        value: target,
+    })
+}
+
+/// Parses mnemonic source, one `opcode operand` instruction per line (as
+/// emitted by [`disassemble`]), into the `Vec<u8>` program [`Computer`]
+/// executes. `#` introduces a line comment, blank lines are ignored, and any
+/// line may be prefixed with `label:` to make its instruction's byte offset
+/// available to `jnz` elsewhere in the source, so hand-written programs don't
+/// need their jump targets computed by hand. Combo operands accept `A`/`B`/`C`
+/// as well as the raw `0..=6` literal [`disassemble`] would print; `jnz`
+/// targets are either a raw byte offset or a label, and are otherwise
+/// unrestricted since they address bytes, not combo/literal operands.
+fn assemble(source: &str) -> Result<Vec<u8>, AssembleError> {
+    let lines: Vec<(usize, &str)> = source
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.split('#').next().unwrap().trim()))
+        .filter(|(_, line)| !line.is_empty())
+        .collect();
+
+    let mut labels = HashMap::new();
+    let mut instrs: Vec<(usize, &str, &str)> = Vec::new();
+    for (line_no, line) in lines {
+        let code = match line.split_once(':') {
+            Some((label, rest)) => {
+                labels.insert(label.trim().to_string(), instrs.len() * 2);
+                rest.trim()
+            }
+            None => line,
+        };
+        if code.is_empty() {
+            continue;
+        }
+
+        let mut tokens = code.split_whitespace();
+        let mnemonic = tokens.next().unwrap();
+        let operand = tokens
+            .next()
+            .ok_or(AssembleError::MissingOperand { line: line_no })?;
+        instrs.push((line_no, mnemonic, operand));
+    }
+
+    let mut program = Vec::with_capacity(instrs.len() * 2);
+    for (line_no, mnemonic, operand) in instrs {
+        let (op, arg) = match mnemonic {
+            "adv" => (ADV, parse_combo(operand, line_no)?),
+            "bxl" => (BXL, parse_literal(operand, line_no)?),
+            "bst" => (BST, parse_combo(operand, line_no)?),
+            "jnz" => (JNZ, resolve_jnz_target(operand, line_no, &labels)?),
+            "bxc" => (BXC, parse_literal(operand, line_no)?),
+            "out" => (OUT, parse_combo(operand, line_no)?),
+            "bdv" => (BDV, parse_combo(operand, line_no)?),
+            "cdv" => (CDV, parse_combo(operand, line_no)?),
+            _ => {
+                return Err(AssembleError::UnknownMnemonic {
+                    line: line_no,
+                    mnemonic: mnemonic.to_string(),
+                })
+            }
+        };
+        program.push(op);
+        program.push(arg);
+    }
+
+    Ok(program)
 }
 
 #[allow(dead_code)]
@@ -147,36 +339,32 @@ fn brute_force_self_replication_value(program: &[u8]) -> u64 {
         .unwrap()
 }
 
-#[allow(dead_code)]
-fn run_reverse_engineered_program(initial_registers: [u64; 3]) -> impl Iterator<Item = u64> {
-    iterate(initial_registers, |&[mut a, _, _]| {
-        let mut b = a % 8; // bst a
-        b ^= 3; // bxl 3
-        let c = a >> b; // cdv b
-        b ^= c; // bxc _
-        a >>= 3; // adv 3
-        b ^= 5; // bxl 5
-        [a, b, c]
-    })
-    .skip(1)
-    .take_while_inclusive(|&[a, _, _]| a != 0) // jnz 0
-    .map(|[_, b, _]| b % 8) // out b
-}
-
+/// Reconstructs the lowest register `A` for which running `program` against
+/// it reproduces `program` itself, by driving the real [`Computer`] instead
+/// of replaying one input's hand-derived bit algebra. Works for any program
+/// whose major loop divides `A` by 8 (i.e. `adv 3`) once per iteration: since
+/// each iteration only ever looks at the low 10 bits of `A` or so, the three
+/// bits that get shifted out next iteration can be determined from the
+/// output they produce, so `A` can be built up three bits at a time,
+/// most-significant group first, by searching backwards from the program's
+/// last output byte to its first.
 fn reversed_self_replication_value(program: &[u8]) -> u64 {
     program
         .iter()
         .rev()
-        .fold(vec![0], |possible_a, b4| {
-            possible_a
+        .fold(vec![0], |candidates, &target| {
+            candidates
                 .iter()
-                .flat_map(|a| {
-                    let b3 = *b4 as u64 ^ 5;
-                    (0..8).filter_map(move |b2| {
-                        let b1 = b2 ^ 3;
-                        let a2 = (a << 3) | b1;
-                        let c = a2 >> b2;
-                        (b3 == b2 ^ (c % 8)).then_some(a2)
+                .flat_map(|&a| {
+                    (0..8).filter_map(move |b| {
+                        let a2 = (a << 3) | b;
+                        let first_output = Computer {
+                            ip: 0,
+                            registers: [a2, 0, 0],
+                            program,
+                        }
+                        .next();
+                        (first_output == Some(target as u64)).then_some(a2)
                     })
                 })
                 .collect()
@@ -189,7 +377,6 @@ fn reversed_self_replication_value(program: &[u8]) -> u64 {
 #[cfg(test)]
 mod tests {
     use itertools::assert_equal;
-    use proptest::proptest;
 
     use super::*;
 
@@ -233,33 +420,105 @@ Program: 0,1,5,4,3,0";
     }
 
     #[test]
-    fn find_lowest_self_replication_value() {
+    fn trace_reports_a_snapshot_per_instruction_including_jumps() {
+        let mut computer = Computer {
+            ip: 0,
+            registers: EXAMPLE_INIT_REG,
+            program: EXAMPLE_PROGRAM,
+        };
+        let snapshots = computer.trace().collect_vec();
+
+        // adv 1, out A, jnz 0: three instructions per loop, looping once per
+        // output value produced before A finally hits zero.
+        assert_eq!(snapshots.len(), 3 * 10);
         assert_eq!(
-            brute_force_self_replication_value(SELF_REPL_PROGRAM),
-            117440
+            snapshots
+                .iter()
+                .filter_map(|&(_, _, out)| out)
+                .collect_vec(),
+            [4, 6, 3, 5, 6, 3, 5, 2, 1, 0]
         );
+        // The jnz back-edge shows up as an ip snapshot of 0 with no output.
+        assert_eq!(snapshots[2], (0, [364, 0, 0], None));
     }
 
-    proptest! {
+    #[test]
+    fn disassembly_renders_mnemonics_with_symbolic_combo_operands() {
+        assert_equal(
+            disassemble(INPUT_PROGRAM),
+            [
+                "bst A", "bxl 3", "cdv B", "bxc 2", "adv 3", "bxl 5", "out B", "jnz 0",
+            ],
+        );
+    }
 
-        #[test]
-        fn reverse_engineered_function_produces_same_results(a in 0..=u64::MAX) {
-            let control_computer = Computer {
-                ip: 0,
-                registers: [a, 0, 0],
-                program: INPUT_PROGRAM,
-            };
-            assert_equal(run_reverse_engineered_program([a, 0, 0]), control_computer);
+    #[test]
+    fn assemble_round_trips_through_disassemble() {
+        for program in [EXAMPLE_PROGRAM, SELF_REPL_PROGRAM, INPUT_PROGRAM] {
+            let source = disassemble(program).join("\n");
+            assert_eq!(assemble(&source).unwrap(), program);
         }
+    }
 
+    #[test]
+    fn assemble_resolves_labels_to_instruction_offsets() {
+        let source = "\
+start: bst A
+bxl 3
+jnz start";
+        assert_eq!(assemble(source).unwrap(), vec![BST, 4, BXL, 3, JNZ, 0]);
+    }
+
+    #[test]
+    fn assemble_ignores_comments_and_blank_lines() {
+        let source = "\
+# set up register B
+bst A  # combo operand A
+
+jnz 0  # loop forever";
+        assert_eq!(assemble(source).unwrap(), vec![BST, 4, JNZ, 0]);
+    }
+
+    #[test]
+    fn assemble_rejects_combo_operand_over_six() {
+        assert!(matches!(
+            assemble("adv 7"),
+            Err(AssembleError::ComboOutOfRange { value: 7, .. })
+        ));
+    }
+
+    #[test]
+    fn assemble_rejects_literal_operand_over_seven() {
+        assert!(matches!(
+            assemble("bxl 8"),
+            Err(AssembleError::LiteralOutOfRange { value: 8, .. })
+        ));
+    }
+
+    #[test]
+    fn assemble_rejects_undefined_labels() {
+        assert!(matches!(
+            assemble("jnz nowhere"),
+            Err(AssembleError::UndefinedLabel { label, .. }) if label == "nowhere"
+        ));
+    }
+
+    #[test]
+    fn find_lowest_self_replication_value() {
+        assert_eq!(
+            brute_force_self_replication_value(SELF_REPL_PROGRAM),
+            117440
+        );
     }
 
     #[test]
     fn reverse_engineered_self_replication_value_replicates_program() {
         let a = reversed_self_replication_value(INPUT_PROGRAM);
-        assert_equal(
-            run_reverse_engineered_program([a, 0, 0]),
-            INPUT_PROGRAM.iter().map(|p| *p as u64),
-        );
+        let computer = Computer {
+            ip: 0,
+            registers: [a, 0, 0],
+            program: INPUT_PROGRAM,
+        };
+        assert_equal(computer, INPUT_PROGRAM.iter().map(|p| *p as u64));
     }
 }
@@ -1,11 +1,10 @@
-use std::{
-    collections::{HashMap, HashSet},
-    num::ParseIntError,
-};
+use std::{collections::HashSet, num::ParseIntError};
 
 use aoc_companion::prelude::*;
 use aoc_utils::{
+    disjoint_set::DisjointSet,
     geometry::Point,
+    graph,
     linalg::{ParseVectorError, Vector},
 };
 use itertools::Itertools;
@@ -37,46 +36,81 @@ fn parse_bytes(input: &str) -> Result<Vec<Vector<usize, 2>>, ParseVectorError<Pa
     input.lines().map(str::parse).try_collect()
 }
 
+/// Shortest path from the top-left corner to the bottom-right, via A* with
+/// a Manhattan-distance heuristic to the exit so the search stays focused
+/// instead of exploring the whole grid outward from the start like a plain
+/// BFS would.
 fn find_shortest_path_with_fallen_bytes(bytes: &[Vector<usize, 2>], shape: [usize; 2]) -> usize {
     let fallen_bytes: HashSet<Vector<usize, 2>> = bytes.iter().cloned().collect();
-    let mut distances = ndarray::Array2::from_elem(shape, usize::MAX);
-    distances[Vector::default()] = 0;
-
-    let mut todo = HashSet::from([Vector::default()]);
-    while let Some(current) = todo.iter().next().cloned() {
-        todo.remove(&current);
-        let current_dist = distances[current] + 1;
-        todo.extend(
-            current
-                .nearest_neighbors()
-                .filter(|n| !fallen_bytes.contains(n))
-                .filter(|n| {
-                    let better = distances.get(*n).is_some_and(|d| current_dist < *d);
-                    if better {
-                        distances[*n] = current_dist;
-                    }
-                    better
-                }),
-        );
-    }
+    let exit = Vector(shape.map(|c| c - 1));
 
-    distances[shape.map(|c| c - 1)]
+    graph::a_star(
+        Vector::default(),
+        exit,
+        |p| {
+            p.nearest_neighbors()
+                .filter(|n| n[0] < shape[0] && n[1] < shape[1])
+                .filter(|n| !fallen_bytes.contains(n))
+                .map(|n| (n, 1))
+        },
+        |p| p[0].abs_diff(exit[0]) + p[1].abs_diff(exit[1]),
+    )
+    .unwrap_or(usize::MAX)
 }
 
+/// Finds the first byte (in fall order) whose landing disconnects `start`
+/// from `exit`, in a single linear sweep instead of [`find_shortest_path_with_fallen_bytes`]'s
+/// repeated BFS passes. Starts from the grid with every byte fallen and
+/// processes bytes in reverse, un-blocking one cell at a time and `union`ing
+/// it with its already-open orthogonal neighbors; the first reverse step
+/// after which `start` and `exit` become connected is un-blocking exactly
+/// the byte that, falling forward, had cut them off.
 fn first_byte_to_cut_off_exit(
     bytes: &[Vector<usize, 2>],
     shape: [usize; 2],
 ) -> Option<Vector<usize, 2>> {
-    let mut cache = HashMap::new();
-    let ends: Vec<_> = (0..bytes.len()).collect();
+    fn cell_index(p: Vector<usize, 2>, shape: [usize; 2]) -> usize {
+        p[0] * shape[1] + p[1]
+    }
+
+    fn open_cell(
+        p: Vector<usize, 2>,
+        shape: [usize; 2],
+        open: &mut ndarray::Array2<bool>,
+        dsu: &mut DisjointSet,
+    ) {
+        open[p] = true;
+        for n in p
+            .nearest_neighbors()
+            .filter(|n| open.get(*n).copied().unwrap_or(false))
+        {
+            dsu.union(cell_index(p, shape), cell_index(n, shape));
+        }
+    }
+
+    let start = Vector::default();
+    let exit = Vector(shape.map(|c| c - 1));
+    let blocked: HashSet<_> = bytes.iter().cloned().collect();
+    let mut open = ndarray::Array2::from_elem(shape, false);
+    let mut dsu = DisjointSet::new(shape[0] * shape[1]);
+
+    for row in 0..shape[0] {
+        for col in 0..shape[1] {
+            let p = Vector([row, col]);
+            if !blocked.contains(&p) {
+                open_cell(p, shape, &mut open, &mut dsu);
+            }
+        }
+    }
 
-    ends.binary_search_by_key(&false, |&end| {
-        *cache.entry(end).or_insert_with(|| {
-            find_shortest_path_with_fallen_bytes(&bytes[..end], shape) == usize::MAX
+    bytes
+        .iter()
+        .rev()
+        .find(|&&byte| {
+            open_cell(byte, shape, &mut open, &mut dsu);
+            dsu.connected(cell_index(start, shape), cell_index(exit, shape))
         })
-    })
-    .ok()
-    .map(|end| bytes[end])
+        .copied()
 }
 
 #[cfg(test)]
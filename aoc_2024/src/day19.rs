@@ -39,27 +39,24 @@ impl<'input> Door<'input> {
 
     fn number_of_matches(&self) -> usize {
         let towels = self.towels.iter().map(|s| s.to_string()).collect_vec();
-        let mut cache = Cache::<&str, usize>::new(move |pattern, cache| {
+        let cache = Cache::<&str, usize>::new(move |pattern, cache| {
             number_of_matches_for_pattern(pattern, &towels, cache)
         });
-        self.patterns
-            .iter()
-            .map(|pattern| *cache.view().get_or_calc(pattern))
-            .sum()
+        cache.view().par_compute_all(self.patterns.iter().copied())
     }
 }
 
 fn number_of_matches_for_pattern<'input>(
     pattern: &'input str,
     towels: &[String],
-    cache: &mut CacheView<&'input str, usize>,
+    cache: &CacheView<'input, &'input str, usize>,
 ) -> usize {
     towels
         .iter()
         .map(|towel| match pattern.strip_prefix(towel) {
             None => 0,
             Some("") => 1,
-            Some(rest) => *cache.get_or_calc(rest),
+            Some(rest) => cache.get_or_calc(rest),
         })
         .sum()
 }
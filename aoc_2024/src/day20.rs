@@ -2,7 +2,7 @@ use std::collections::HashMap;
 
 use aoc_companion::prelude::*;
 use aoc_utils::{
-    geometry::{Point, map_bounds},
+    geometry::{bfs, map_bounds},
     linalg::Vector,
 };
 use ndarray::ShapeError;
@@ -39,24 +39,22 @@ fn parse_map(input: &str) -> Result<Map, ShapeError> {
     Map::from_shape_vec(shape, data)
 }
 
-fn race_track(map: &Map) -> impl Iterator<Item = Vector<usize, 2>> + use<'_> {
-    let start = map
+fn race_track(map: &Map) -> HashMap<Vector<usize, 2>, usize> {
+    let Some(start) = map
         .indexed_iter()
         .find(|(_, elem)| **elem == b'S')
-        .map(|((x, y), _)| Vector([x, y]));
+        .map(|((x, y), _)| Vector([x, y]))
+    else {
+        return HashMap::new();
+    };
 
-    std::iter::successors(start.map(|s| (None, s)), |(prev, current)| {
-        current
-            .nearest_neighbors()
-            .filter(|n| &Some(*n) != prev)
-            .find(|n| map.get(*n).is_some_and(|q| *q != b'#'))
-            .map(|next| (Some(*current), next))
+    bfs(start, |_| false, |p| {
+        map.get(p).is_some_and(|q| *q != b'#')
     })
-    .map(|(_, p)| p)
 }
 
 fn cheats(duration: usize, map: &Map) -> impl Iterator<Item = usize> + use<'_> {
-    let race_track: HashMap<_, _> = race_track(map).enumerate().map(|(d, p)| (p, d)).collect();
+    let race_track = race_track(map);
     let rc_race_track = std::rc::Rc::new(race_track.clone());
 
     race_track.into_iter().flat_map(move |(start, start_dist)| {
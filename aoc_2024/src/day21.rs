@@ -90,6 +90,25 @@ impl NumericButton {
     }
 }
 
+/// The coordinates of the one missing key on each keypad, matching the
+/// coordinate system [`NumericButton::coords`]/[`DirectionalButton::coords`]
+/// use (column, row; row increasing upward): below `N7` for the numeric
+/// keypad, above `Left` for the directional one.
+const NUMERIC_GAP: Vector<i8, 2> = Vector([0, 0]);
+const DIRECTIONAL_GAP: Vector<i8, 2> = Vector([0, 1]);
+
+impl DirectionalButton {
+    fn coords(&self) -> Vector<i8, 2> {
+        match self {
+            DirectionalButton::Left => Vector([0, 0]),
+            DirectionalButton::Down => Vector([1, 0]),
+            DirectionalButton::Right => Vector([2, 0]),
+            DirectionalButton::Up => Vector([1, 1]),
+            DirectionalButton::A => Vector([2, 1]),
+        }
+    }
+}
+
 impl std::fmt::Display for NumericButton {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_char(match self {
@@ -120,16 +139,25 @@ impl std::fmt::Display for DirectionalButton {
     }
 }
 
+/// Enumerates the (at most two) shortest ways to move a keypad arm from
+/// `from` to `to` and press it: straight to `to`'s column then `to`'s row,
+/// or vice versa, each followed by `A`. An ordering is dropped if it would
+/// cross `gap`, the one position neither keypad has a button on — which,
+/// since a real `from`/`to` is never itself on the gap, only the endpoint
+/// sharing the gap's row (for the horizontal-first ordering) or column (for
+/// the vertical-first one) can line up with. A purely horizontal or
+/// vertical move only needs one ordering, since both would be identical.
 fn shortest_moves(
     from: Vector<i8, 2>,
     to: Vector<i8, 2>,
+    gap: Vector<i8, 2>,
 ) -> impl IntoIterator<
     IntoIter = impl Iterator<Item = impl Iterator<Item = DirectionalButton> + Clone> + Clone,
     Item = impl Iterator<Item = DirectionalButton> + Clone,
 > + Clone {
     let offset = to - from;
     [
-        (from[1] != 0 || to[0] != 0).then_some(
+        (from[1] != gap[1] || to[0] != gap[0]).then_some(
             Iterator::chain(
                 repeat_n(
                     if offset[0] < 0 {
@@ -150,7 +178,7 @@ fn shortest_moves(
             )
             .chain(std::iter::once(DirectionalButton::A)),
         ),
-        ((from[0] != 0 || to[1] != 0) && from[0] != to[0] && from[1] != to[1]).then_some(
+        ((from[0] != gap[0] || to[1] != gap[1]) && from[0] != to[0] && from[1] != to[1]).then_some(
             Iterator::chain(
                 repeat_n(
                     if offset[1] < 0 {
@@ -176,78 +204,59 @@ fn shortest_moves(
     .flatten()
 }
 
-fn numeric_keypad_moves(
-    desired_code: &[NumericButton],
-) -> impl Iterator<Item = impl IntoIterator<Item = DirectionalButton> + Clone> {
-    std::iter::once(NumericButton::A)
-        .chain(desired_code.iter().copied())
-        .tuple_windows()
-        .map(|(from, to)| shortest_moves(from.coords(), to.coords()))
-        .multi_cartesian_product()
-        .map(|s| s.into_iter().flatten().collect_vec())
-}
-
-fn shortest_directional_moves(
-    from: DirectionalButton,
-    to: DirectionalButton,
-) -> &'static [DirectionalButton] {
-    use DirectionalButton::*;
-    match (from, to) {
-        (Up, Up) => &[A],
-        (Up, A) => &[Right, A],
-        (Up, Left) => &[Down, Left, A],
-        (Up, Down) => &[Down, A],
-        (Up, Right) => &[Down, Right, A],
-        (A, Up) => &[Left, A],
-        (A, A) => &[A],
-        (A, Left) => &[Down, Left, Left, A],
-        (A, Down) => &[Left, Down, A],
-        (A, Right) => &[Down, A],
-        (Left, Up) => &[Right, Up, A],
-        (Left, A) => &[Right, Right, Up, A],
-        (Left, Left) => &[A],
-        (Left, Down) => &[Right, A],
-        (Left, Right) => &[Right, Right, A],
-        (Down, Up) => &[Up, A],
-        (Down, A) => &[Up, Right, A],
-        (Down, Left) => &[Left, A],
-        (Down, Down) => &[A],
-        (Down, Right) => &[Right, A],
-        (Right, Up) => &[Left, Up, A],
-        (Right, A) => &[Up, A],
-        (Right, Left) => &[Left, Left, A],
-        (Right, Down) => &[Left, A],
-        (Right, Right) => &[A],
-    }
-}
-
-fn directional_keypad_moves(desired_buttons: Vec<DirectionalButton>) -> Vec<DirectionalButton> {
-    std::iter::once(DirectionalButton::A)
-        .chain(desired_buttons)
-        .tuple_windows()
-        .flat_map(|(from, to)| shortest_directional_moves(from, to).iter().copied())
-        .collect()
+/// The cost of moving a directional-keypad arm from `from` to `to` and
+/// pressing it, `depth` robot layers removed from the one a human operates
+/// directly. `depth == 0` is the human's own arm: pressing a key always
+/// costs exactly one button press. For `depth > 0`, the arm one layer up
+/// must itself move between (at most two) candidate directional sequences —
+/// the same horizontal-then-vertical/vertical-then-horizontal orderings
+/// [`shortest_moves`] already enumerates for the numeric keypad, gap check
+/// included, now reused for the directional keypad via [`DirectionalButton::coords`] —
+/// and the cost of typing one of those candidates is the sum of the
+/// recursive cost of each of its consecutive button pairs, starting from
+/// `A`. Memoized on `(from, to, depth)` since the same pair recurs
+/// throughout a code's expansion.
+fn directional_cost(depth: usize) -> impl FnMut(DirectionalButton, DirectionalButton) -> usize {
+    let mut cost = cached(move |(from, to, depth), recurse| -> usize {
+        if depth == 0 {
+            return 1;
+        }
+        shortest_moves(from, to, DIRECTIONAL_GAP)
+            .into_iter()
+            .map(|candidate| {
+                std::iter::once(DirectionalButton::A)
+                    .chain(candidate)
+                    .tuple_windows()
+                    .map(|(p, q): (DirectionalButton, DirectionalButton)| {
+                        recurse((p.coords(), q.coords(), depth - 1))
+                    })
+                    .sum()
+            })
+            .min()
+            .unwrap()
+    });
+    move |from, to| cost((from.coords(), to.coords(), depth))
 }
 
 fn shortest_seq<const N: usize>(desired_code: &[NumericButton]) -> usize {
-    let mut cached_subseq_len = cached(|subseq, _| subseq_len(subseq, N / 2));
-    numeric_keypad_moves(desired_code)
-        .map(|moves| -> usize {
-            let moves = moves.into_iter().collect_vec();
-            (0..(N - N / 2))
-                .fold(moves, |v, _| directional_keypad_moves(v))
-                .split_inclusive(|&e| e == DirectionalButton::A)
-                .map(|subseq| cached_subseq_len(subseq.to_vec()))
-                .sum()
+    let mut cost = directional_cost(N);
+    std::iter::once(NumericButton::A)
+        .chain(desired_code.iter().copied())
+        .tuple_windows()
+        .map(|(from, to)| {
+            shortest_moves(from.coords(), to.coords(), NUMERIC_GAP)
+                .into_iter()
+                .map(|candidate| {
+                    std::iter::once(DirectionalButton::A)
+                        .chain(candidate)
+                        .tuple_windows()
+                        .map(|(p, q)| cost(p, q))
+                        .sum()
+                })
+                .min()
+                .unwrap()
         })
-        .min()
-        .unwrap()
-}
-
-fn subseq_len(subseq: Vec<DirectionalButton>, m: usize) -> usize {
-    (0..m)
-        .fold(subseq, |v, _| directional_keypad_moves(v))
-        .len()
+        .sum()
 }
 
 fn complexity<const N: usize>(desired_code: &[NumericButton]) -> usize {
@@ -266,25 +275,29 @@ mod tests {
     use super::*;
 
     #[test]
-    fn directional_moves_for_numeric_code() {
-        use NumericButton::*;
+    fn shortest_moves_avoids_the_gap_on_the_directional_keypad() {
+        use DirectionalButton::*;
+        // A -> Left is the one pair where only the vertical-then-horizontal
+        // ordering is valid: going horizontal-then-vertical from A would
+        // pass over the gap above Left.
         assert_eq!(
-            numeric_keypad_moves(&[N0, N2, N9, A])
-                .map(|i| i.into_iter().map(|b| b.to_string()).join(""))
+            shortest_moves(A.coords(), Left.coords(), DIRECTIONAL_GAP)
+                .into_iter()
+                .map(|m| m.map(|b| b.to_string()).join(""))
                 .collect_vec(),
-            ["<A^A>^^AvvvA", "<A^A^^>AvvvA"]
+            ["v<<A"],
         );
     }
 
     #[test]
-    fn directional_moves_for_directional_code() {
+    fn shortest_moves_offers_both_orderings_when_neither_crosses_the_gap() {
         use DirectionalButton::*;
         assert_eq!(
-            directional_keypad_moves(vec![Left, A, Up, A, Right, Up, Up, A, Down, Down, Down, A])
+            shortest_moves(Down.coords(), A.coords(), DIRECTIONAL_GAP)
                 .into_iter()
-                .map(|b| b.to_string())
-                .join(""),
-            "v<<A>>^A<A>AvA<^AA>A<vAAA^>A"
+                .map(|m| m.map(|b| b.to_string()).join(""))
+                .collect_vec(),
+            [">^A", "^>A"],
         );
     }
 
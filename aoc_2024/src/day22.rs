@@ -2,7 +2,6 @@ use std::num::ParseIntError;
 
 use aoc_companion::prelude::*;
 use itertools::{iterate, Itertools};
-use rayon::iter::{ParallelBridge, ParallelIterator};
 
 pub(crate) struct Door {
     initial_numbers: Vec<u64>,
@@ -45,6 +44,7 @@ fn prices(seed: u64) -> impl Iterator<Item = u64> {
     prng(seed).map(|p| p % 10)
 }
 
+#[allow(dead_code)]
 fn profit(seed: u64, trigger: [i8; 4]) -> u64 {
     prices(seed)
         .zip(
@@ -60,20 +60,47 @@ fn profit(seed: u64, trigger: [i8; 4]) -> u64 {
         .unwrap_or(0)
 }
 
-fn trigger_combos() -> impl Iterator<Item = [i8; 4]> {
-    (-9..=9)
-        .cartesian_product(-9..=9)
-        .cartesian_product(-9..=9)
-        .cartesian_product(-9..=9)
-        .map(|(((d1, d2), d3), d4)| [d1, d2, d3, d4])
+/// `19^4`: every possible run of four consecutive deltas, each in `-9..=9`.
+const KEY_SPACE: usize = 19 * 19 * 19 * 19;
+
+/// Packs four deltas into a single `0..KEY_SPACE` index by shifting each
+/// `-9..=9` delta into `0..19` and treating the result as a base-19 number.
+fn window_key(deltas: [i8; 4]) -> usize {
+    deltas.iter().fold(0, |key, &d| key * 19 + (d + 9) as usize)
+}
+
+/// Every (key, price) pair this seed's 2000-price sequence produces, keyed by
+/// the four-delta window ending at that price. Mirrors [`profit`]'s quirk of
+/// treating the price before the first as `0`, so the very first window's
+/// leading delta is really the first price itself rather than a genuine
+/// change.
+fn window_prices(seed: u64) -> impl Iterator<Item = (usize, u64)> {
+    let deltas = std::iter::once(0)
+        .chain(prices(seed))
+        .tuple_windows()
+        .map(|(lhs, rhs)| rhs as i8 - lhs as i8);
+    prices(seed)
+        .zip(deltas)
+        .take(2000)
+        .tuple_windows()
+        .map(|((_, d1), (_, d2), (_, d3), (price, d4))| (window_key([d1, d2, d3, d4]), price))
 }
 
 fn most_profit(seeds: &[u64]) -> u64 {
-    trigger_combos()
-        .par_bridge()
-        .map(|trigger| seeds.iter().map(|&seed| profit(seed, trigger)).sum())
-        .max()
-        .unwrap()
+    let mut totals = vec![0u64; KEY_SPACE];
+    let mut seen = vec![false; KEY_SPACE];
+
+    for &seed in seeds {
+        seen.fill(false);
+        for (key, price) in window_prices(seed) {
+            if !seen[key] {
+                seen[key] = true;
+                totals[key] += price;
+            }
+        }
+    }
+
+    totals.into_iter().max().unwrap()
 }
 
 #[cfg(test)]
@@ -2,6 +2,7 @@ use std::collections::{BTreeSet, HashMap, HashSet};
 
 use anyhow::anyhow;
 use aoc_companion::prelude::*;
+use aoc_utils::graph;
 use itertools::Itertools;
 use tap::Tap;
 
@@ -77,46 +78,21 @@ fn subnets_in_question(network: &Network) -> impl Iterator<Item = [&str; 3]> {
 }
 
 fn max_subnet(network: &Network) -> BTreeSet<&str> {
-    max_subnet_recursive(
-        network.keys().map(|s| s.as_str()).collect(),
-        BTreeSet::new(),
-        network,
-    )
-}
-
-fn max_subnet_recursive<'n>(
-    nodes: HashSet<&'n str>,
-    cluster: BTreeSet<&'n str>,
-    network: &'n Network,
-) -> BTreeSet<&'n str> {
-    let mut visited: HashSet<&str> = HashSet::new();
-    nodes
+    let adjacency: HashMap<&str, HashSet<&str>> = network
         .iter()
-        .flat_map(|&node| {
-            if visited.contains(node) {
-                return None;
-            }
-            let connections = network.get(node).unwrap();
-            let mut new_cluster = cluster.clone();
-            new_cluster.insert(node);
-            let new_nodes = nodes
-                .iter()
-                .cloned()
-                .filter(|&n| connections.contains(n))
-                .filter(|&n| !cluster.contains(n))
-                .collect::<HashSet<_>>();
-
-            Some(
-                if new_nodes.is_empty() {
-                    new_cluster
-                } else {
-                    max_subnet_recursive(new_nodes, new_cluster, network)
-                }
-                .tap(|v| visited.extend(v)),
+        .map(|(node, connections)| {
+            (
+                node.as_str(),
+                connections.iter().map(String::as_str).collect(),
             )
         })
-        .max_by_key(|n| n.len())
+        .collect();
+    graph::maximal_cliques(&adjacency)
+        .into_iter()
+        .max_by_key(HashSet::len)
         .unwrap()
+        .into_iter()
+        .collect()
 }
 
 #[cfg(test)]
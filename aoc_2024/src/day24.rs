@@ -75,13 +75,40 @@ impl<'input> Solution<'input> for Door {
         read_numeric_output(&outputs)
     }
 
-    fn part2(&self) -> String {
-        let mut crossed_wires = ripple_carry_defects(&self.gates).into_keys().collect_vec();
+    fn part2(&self) -> Result<String, AdderError> {
+        let mut crossed_wires = ripple_carry_defects(&self.gates)?
+            .into_keys()
+            .collect_vec();
         crossed_wires.sort();
-        crossed_wires.join(",")
+        Ok(crossed_wires.join(","))
     }
 }
 
+/// The gate graph didn't match the expected ripple-carry adder structure
+/// closely enough for [`ripple_carry_defects`] to keep tracing through it;
+/// surfaced as a part-level error rather than a panic, since a malformed or
+/// unexpectedly-wired input is exactly the kind of thing a part should be
+/// able to report gracefully instead of aborting the whole run.
+#[derive(Debug, thiserror::Error)]
+enum AdderError {
+    #[error("expected {a} and {b} to be input for a {gate:?} gate, but no such gate exists")]
+    MissingGate { a: String, b: String, gate: Gate },
+    #[error(
+        "swapping a={a} and d={d} didn't help: gate exists, but still produces {actual_z} \
+         instead of {expected_z}"
+    )]
+    SwapDidNotFix {
+        a: String,
+        d: String,
+        expected_z: String,
+        actual_z: String,
+    },
+    #[error("swapping a={a} and d={d} didn't help: no XOR gate between d={d} and c={carry_in} either")]
+    NoAlternateGate { a: String, d: String, carry_in: String },
+    #[error("expected the final carry-out to feed z45, but it fed {0}")]
+    UnexpectedFinalCarry(String),
+}
+
 impl Gate {
     fn exec(&self, lhs: bool, rhs: bool) -> bool {
         match self {
@@ -119,21 +146,23 @@ fn read_numeric_output(outputs: &HashMap<String, bool>) -> u64 {
 
 type DefectsMap = HashMap<String, String>;
 
-fn ripple_carry_defects(gates: &GatesMap) -> DefectsMap {
-    let (final_carry, defects) = (1..=44)
-        .fold(half_adder_defects(gates), |(carry_in, defects), i| {
-            full_adder_defects(i, &carry_in, gates, defects)
-        });
-    assert_eq!(final_carry, "z45");
-    defects
+fn ripple_carry_defects(gates: &GatesMap) -> Result<DefectsMap, AdderError> {
+    let (mut carry_in, mut defects) = half_adder_defects(gates)?;
+    for i in 1..=44 {
+        (carry_in, defects) = full_adder_defects(i, &carry_in, gates, defects)?;
+    }
+    if carry_in != "z45" {
+        return Err(AdderError::UnexpectedFinalCarry(carry_in));
+    }
+    Ok(defects)
 }
 
-fn half_adder_defects(gates: &GatesMap) -> (String, DefectsMap) {
+fn half_adder_defects(gates: &GatesMap) -> Result<(String, DefectsMap), AdderError> {
     let mut defects = DefectsMap::new();
-    let expected_z0 = gate_out("x00", "y00", Gate::Xor, gates);
+    let expected_z0 = gate_out("x00", "y00", Gate::Xor, gates)?;
     expect_eq(&expected_z0, "z00", &mut defects);
 
-    (gate_out("x00", "y00", Gate::And, gates), defects)
+    Ok((gate_out("x00", "y00", Gate::And, gates)?, defects))
 }
 
 fn full_adder_defects(
@@ -141,13 +170,13 @@ fn full_adder_defects(
     carry_in: &str,
     gates: &GatesMap,
     mut defects: DefectsMap,
-) -> (String, DefectsMap) {
+) -> Result<(String, DefectsMap), AdderError> {
     let x = format!("x{i:02}");
     let y = format!("y{i:02}");
     let z = format!("z{i:02}");
 
-    let a = gate_out(&x, &y, Gate::Xor, gates);
-    let d = gate_out(&x, &y, Gate::And, gates);
+    let a = gate_out(&x, &y, Gate::Xor, gates)?;
+    let d = gate_out(&x, &y, Gate::And, gates)?;
 
     match checked_gate_out(&a, carry_in, Gate::Xor, gates) {
         Some(expected_z) => {
@@ -159,21 +188,33 @@ fn full_adder_defects(
                     defects.insert(a.clone(), d.clone());
                     defects.insert(d.clone(), a.clone());
                 } else {
-                    panic!("swapping a={a:?} and d={d:?} didn't help: gate exists, but still doesn't produce {z:?}; instead produced {expected_z:?}");
+                    return Err(AdderError::SwapDidNotFix {
+                        a,
+                        d,
+                        expected_z: z,
+                        actual_z: expected_z,
+                    });
                 }
             } else {
-                panic!("swapping a={a:?} and d={d:?} didn't help: no XOR gate between d={d:?} and c={carry_in:?} either");
+                return Err(AdderError::NoAlternateGate {
+                    a,
+                    d,
+                    carry_in: carry_in.to_string(),
+                });
             }
         }
     }
 
     let b = fixed(
-        gate_out(&fixed(a, &defects), carry_in, Gate::And, gates),
+        gate_out(&fixed(a, &defects), carry_in, Gate::And, gates)?,
+        &defects,
+    );
+    let carry_out = fixed(
+        gate_out(&b, &fixed(d, &defects), Gate::Or, gates)?,
         &defects,
     );
-    let carry_out = fixed(gate_out(&b, &fixed(d, &defects), Gate::Or, gates), &defects);
 
-    (carry_out, defects)
+    Ok((carry_out, defects))
 }
 
 fn expect_eq(lhs: &str, rhs: &str, defects: &mut DefectsMap) {
@@ -195,9 +236,12 @@ fn checked_gate_out(a: &str, b: &str, gate: Gate, gates: &GatesMap) -> Option<St
         .cloned()
 }
 
-fn gate_out(a: &str, b: &str, gate: Gate, gates: &GatesMap) -> String {
-    checked_gate_out(a, b, gate, gates)
-        .unwrap_or_else(|| panic!("expected {a:?} and {b:?} to be input for {gate:?} gate"))
+fn gate_out(a: &str, b: &str, gate: Gate, gates: &GatesMap) -> Result<String, AdderError> {
+    checked_gate_out(a, b, gate, gates).ok_or_else(|| AdderError::MissingGate {
+        a: a.to_string(),
+        b: b.to_string(),
+        gate,
+    })
 }
 
 #[cfg(test)]
@@ -1,7 +1,10 @@
-use anyhow::Context as _;
 use aoc_companion::prelude::*;
+use aoc_utils::combinatorics::max_value_subsequence;
+use aoc_utils::parse::digits;
 use itertools::Itertools as _;
 
+const RADIX: u64 = 10;
+
 pub(crate) struct Door {
     banks: Vec<Vec<u32>>,
 }
@@ -11,36 +14,26 @@ impl<'input> Solution<'input> for Door {
         Ok(Door {
             banks: input
                 .lines()
-                .map(|line| {
-                    line.chars()
-                        .map(|c| {
-                            c.to_digit(10)
-                                .with_context(|| anyhow::anyhow!("{c:?} is not a digit"))
-                        })
-                        .try_collect()
-                })
+                .map(|line| digits(line, RADIX as u32))
                 .try_collect()?,
         })
     }
 
     fn part1(&self) -> u64 {
-        self.banks.iter().map(|b| max_joltage(b, 2)).sum()
+        self.banks.iter().map(|b| max_joltage(b, 2, RADIX)).sum()
     }
 
     fn part2(&self) -> u64 {
-        self.banks.iter().map(|b| max_joltage(b, 12)).sum()
+        self.banks.iter().map(|b| max_joltage(b, 12, RADIX)).sum()
     }
 }
 
-fn max_joltage(bank: &[u32], n_battery: usize) -> u64 {
-    let (res, _) = (0..n_battery).rev().fold((0, bank), |(acc, available), n| {
-        let rev_pos = available.iter().rev().skip(n).position_max().unwrap() + n;
-        let max_idx = available.len() - rev_pos - 1;
-        let (max, rest) = available[max_idx..].split_first().unwrap();
-        (acc * 10 + *max as u64, rest)
-    });
-
-    res
+/// The largest number obtainable by picking `n_battery` digits out of
+/// `bank`, keeping their relative order, read in `radix`.
+fn max_joltage(bank: &[u32], n_battery: usize, radix: u64) -> u64 {
+    max_value_subsequence(bank, n_battery)
+        .into_iter()
+        .fold(0, |acc, digit| acc * radix + digit as u64)
 }
 
 #[cfg(test)]
@@ -68,19 +61,27 @@ mod tests {
 
     #[test]
     fn max_joltage_for_multiple_repeats() {
-        assert_eq!(max_joltage(&[9, 9, 1], 2), 99);
+        assert_eq!(max_joltage(&[9, 9, 1], 2, RADIX), 99);
     }
 
     #[test]
     fn example_max_joltages_of_2_batteries() {
-        itertools::assert_equal(EXAMPLE_BANKS.map(|b| max_joltage(&b, 2)), [98, 89, 78, 92]);
+        itertools::assert_equal(
+            EXAMPLE_BANKS.map(|b| max_joltage(&b, 2, RADIX)),
+            [98, 89, 78, 92],
+        );
     }
 
     #[test]
     fn example_max_joltages_of_12_batteries() {
         itertools::assert_equal(
-            EXAMPLE_BANKS.map(|b| max_joltage(&b, 12)),
+            EXAMPLE_BANKS.map(|b| max_joltage(&b, 12, RADIX)),
             [987654321111, 811111111119, 434234234278, 888911112111],
         );
     }
+
+    #[test]
+    fn max_joltage_works_in_other_radixes() {
+        assert_eq!(max_joltage(&[15, 15, 1], 2, 16), 255);
+    }
 }
@@ -1,9 +1,9 @@
 use std::collections::HashSet;
 
 use aoc_companion::prelude::*;
+use aoc_utils::automaton::{CellularAutomaton, Neighborhood};
 use aoc_utils::geometry::{ParseMapError, Point};
 use aoc_utils::linalg::Vector;
-use itertools::Itertools;
 
 pub(crate) struct Door {
     rolls: HashSet<Vector<usize, 2>>,
@@ -56,22 +56,26 @@ fn accessible_roll_locations(
         .copied()
 }
 
-fn with_accessible_rolls_removed(rolls: &HashSet<Vector<usize, 2>>) -> HashSet<Vector<usize, 2>> {
+/// A roll survives a generation once it's no longer accessible, i.e. once
+/// it has 4 live neighbors; since rolls are never re-created,
+/// `without_expansion` pins the grid to the initial occupied region.
+fn non_removable_roll_locations(rolls: HashSet<Vector<usize, 2>>) -> HashSet<Vector<usize, 2>> {
+    let automaton = CellularAutomaton::from_cells(
+        rolls
+            .iter()
+            .map(|&Vector([x, y])| (Vector([x as i32, y as i32]), true)),
+        false,
+        Neighborhood::Moore,
+    )
+    .without_expansion()
+    .run_to_fixed_point(|&alive, neighbors| alive && neighbors.iter().filter(|&&n| n).count() >= 4);
+
     rolls
-        .difference(&accessible_roll_locations(rolls).collect())
-        .copied()
+        .into_iter()
+        .filter(|&Vector([x, y])| *automaton.get(Vector([x as i32, y as i32])))
         .collect()
 }
 
-fn non_removable_roll_locations(rolls: HashSet<Vector<usize, 2>>) -> HashSet<Vector<usize, 2>> {
-    std::iter::successors(Some(rolls), |prev| {
-        Some(with_accessible_rolls_removed(prev))
-    })
-    .tuple_windows()
-    .find_map(|(lhs, rhs)| (lhs == rhs).then_some(lhs))
-    .unwrap()
-}
-
 #[cfg(test)]
 mod tests {
     use itertools::Itertools;
@@ -1,4 +1,4 @@
-use std::{collections::HashSet, ops::RangeInclusive};
+use std::ops::RangeInclusive;
 
 use anyhow::Context;
 use aoc_companion::prelude::*;
@@ -58,23 +58,16 @@ where
 
 fn disjoint_ranges<'a>(
     ranges: impl IntoIterator<Item = &'a RangeInclusive<u64>>,
-) -> HashSet<RangeInclusive<u64>> {
-    ranges.into_iter().fold(
-        HashSet::<RangeInclusive<u64>>::new(),
-        |mut disjoint_ranges, r| {
-            let overlapping = disjoint_ranges.extract_if(|d| d.try_union(r).is_some());
-            let union = overlapping
-                .map(|o| o.try_union(r).unwrap())
-                .reduce(|lhs, rhs| lhs.try_union(&rhs).unwrap())
-                .unwrap_or(r.clone());
-            disjoint_ranges.insert(union);
-            disjoint_ranges
-        },
-    )
+) -> RangeSet<u64> {
+    ranges.into_iter().cloned().collect()
 }
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashSet;
+
+    use aoc_utils::range::IntervalOps;
+
     use super::*;
 
     const EXAMPLE_INPUT: &str = "\
@@ -131,6 +124,7 @@ mod tests {
     fn disjoint_ranges_are_mutually_disjoint() {
         assert_eq!(
             disjoint_ranges(EXAMPLE_FRESH_RANGES)
+                .ranges()
                 .iter()
                 .tuple_combinations()
                 .find(|(lhs, rhs)| lhs.intersection(rhs).is_some()),
@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
 use aoc_companion::prelude::*;
-use aoc_utils::iter::AtMostTwo;
+use aoc_utils::iter::{AtMostTwo, IterUtils};
 use itertools::Itertools;
 
 pub(crate) struct Door {
@@ -76,10 +76,8 @@ fn split_beam(splitters: &[impl AsRef<[i32]>]) -> State {
                         AtMostTwo::one((b, c))
                     }
                 })
-                .into_group_map()
-                .into_iter()
-                .map(|(b, cs)| (b, cs.into_iter().sum()))
-                .collect();
+                .grouping_map()
+                .sum();
 
             State { beams, splits }
         },
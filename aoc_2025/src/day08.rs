@@ -1,9 +1,19 @@
+use std::collections::HashMap;
 use std::num::ParseIntError;
 
 use aoc_companion::prelude::*;
+use aoc_utils::clustering::{Stop, cluster, minimum_spanning_tree};
+use aoc_utils::kdtree::KdTree;
 use aoc_utils::linalg::{ParseVectorError, Vector};
 use itertools::Itertools as _;
 
+/// How many of each box's nearest neighbors are considered as candidate
+/// edges. Comfortably more than the handful of unions either caller ends up
+/// making use of, so the short edges they need are overwhelmingly likely to
+/// show up in *someone's* neighbor list, while still being a small constant
+/// rather than every other box.
+const NEIGHBOR_CANDIDATES: usize = 20;
+
 pub(crate) struct Door {
     boxes: Vec<Vector<i64, 3>>,
 }
@@ -34,62 +44,41 @@ impl<'input> Solution<'input> for Door {
     }
 }
 
-fn networks(boxes: &[Vector<i64, 3>], n_connect: usize) -> Vec<usize> {
-    boxes
-        .iter()
-        .enumerate()
-        .tuple_combinations()
-        .sorted_unstable_by_key(|((_, pi), (_, pj))| (**pi - **pj).norm_l2_sq())
-        .take(n_connect)
-        .fold(
-            (0..boxes.len()).collect_vec(),
-            |mut assoc, ((i, _), (j, _))| {
-                update_network_associations(&mut assoc, i, j);
-                assoc
-            },
-        )
-}
+/// Builds a short-edge candidate set via a [`KdTree`] rather than sorting
+/// every `O(n²)` pair: each box contributes its
+/// [`NEIGHBOR_CANDIDATES`] nearest neighbors, deduplicated (a pair can be
+/// found from either endpoint) and paired with their squared distance for
+/// [`cluster`] to sort by.
+fn candidate_edges(boxes: &[Vector<i64, 3>]) -> Vec<(usize, usize, i64)> {
+    let tree = KdTree::new(boxes.iter().copied());
+    let k = NEIGHBOR_CANDIDATES.min(boxes.len().saturating_sub(1));
 
-fn final_connection(boxes: &[Vector<i64, 3>]) -> (Vector<i64, 3>, Vector<i64, 3>) {
-    boxes
-        .iter()
-        .enumerate()
-        .tuple_combinations()
-        .sorted_unstable_by_key(|((_, pi), (_, pj))| (**pi - **pj).norm_l2_sq())
-        .scan(
-            ((0..boxes.len()).collect_vec(), boxes.len()),
-            |(assoc, n_grp), ((i, pi), (j, pj))| {
-                if update_network_associations(assoc, i, j) == UpdateOutcome::NetworksConnected {
-                    *n_grp -= 1;
-                }
-                Some(((*pi, *pj), *n_grp))
-            },
-        )
-        .find(|(_, n_grp)| *n_grp == 1)
-        .expect("at least 2 junction boxes should be present")
-        .0
-}
+    let mut edges = HashMap::new();
+    for (i, &point) in boxes.iter().enumerate() {
+        for (j, dist_sq) in tree.k_nearest(point, k + 1) {
+            if j != i {
+                edges.entry((i.min(j), i.max(j))).or_insert(dist_sq);
+            }
+        }
+    }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum UpdateOutcome {
-    AlreadyConnected,
-    NetworksConnected,
+    edges
+        .into_iter()
+        .sorted_unstable_by_key(|&(edge, dist_sq)| (dist_sq, edge))
+        .map(|((i, j), dist_sq)| (i, j, dist_sq))
+        .collect()
 }
 
-fn update_network_associations(assoc: &mut [usize], i: usize, j: usize) -> UpdateOutcome {
-    let lhs = assoc[i];
-    let rhs = assoc[j];
-
-    if lhs != rhs {
-        assoc
-            .iter_mut()
-            .filter(|x| **x == rhs)
-            .for_each(|x| *x = lhs);
+fn networks(boxes: &[Vector<i64, 3>], n_connect: usize) -> Vec<usize> {
+    cluster(boxes.len(), candidate_edges(boxes), Stop::AfterEdges(n_connect)).groups
+}
 
-        UpdateOutcome::NetworksConnected
-    } else {
-        UpdateOutcome::AlreadyConnected
-    }
+fn final_connection(boxes: &[Vector<i64, 3>]) -> (Vector<i64, 3>, Vector<i64, 3>) {
+    let (i, j) = minimum_spanning_tree(boxes.len(), candidate_edges(boxes))
+        .into_iter()
+        .next_back()
+        .expect("at least 2 junction boxes should be present");
+    (boxes[i], boxes[j])
 }
 
 #[cfg(test)]
@@ -2,6 +2,7 @@ use std::ops::BitXor;
 
 use anyhow::{Context, anyhow, bail};
 use aoc_companion::prelude::*;
+use aoc_utils::linalg::BitMatrix;
 use itertools::Itertools as _;
 use rayon::iter::{ParallelBridge as _, ParallelIterator as _};
 
@@ -122,10 +123,18 @@ fn button_presses_for_lights(
 }
 
 impl Machine {
+    /// The indicator-light problem is a minimum-Hamming-weight solution to
+    /// a linear system over GF(2): each button is a column vector (its
+    /// wiring mask), and we want the fewest-buttons selection that XORs to
+    /// `desired_indicator_lights`. Solving this way is polynomial in the
+    /// number of buttons, unlike [`button_presses_for_lights`]'s brute-force
+    /// enumeration of every subset (still used by the joltage search below,
+    /// which needs every combination reaching a given light pattern rather
+    /// than just the lightest one).
     fn fewest_button_presses_for_lights(&self) -> Option<usize> {
-        button_presses_for_lights(&self.buttons, self.desired_indicator_lights)
-            .map(|combo| combo.len())
-            .next()
+        BitMatrix::new(self.buttons.clone())
+            .solve_min_weight(self.desired_indicator_lights)
+            .map(|combo| combo.count_ones() as usize)
     }
 
     fn fewest_button_presses_for_joltage(&self) -> Option<usize> {
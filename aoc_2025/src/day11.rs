@@ -45,6 +45,13 @@ impl std::fmt::Debug for Device {
     }
 }
 
+impl std::fmt::Display for Device {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = str::from_utf8(&self.0).expect("device names should be valid UTF-8");
+        f.write_str(s)
+    }
+}
+
 impl Device {
     fn new(name: &str) -> Result<Self> {
         if name.len() != 3 {
@@ -130,6 +137,24 @@ fn number_of_problematic_paths(
     })(start)
 }
 
+/// Dumps `connections` as Graphviz DOT, with the puzzle's named endpoints
+/// highlighted, so a path count that looks wrong can be debugged by eye
+/// instead of squinting at `{connections:?}`. Not called from `Door` itself;
+/// invoke it ad hoc (e.g. from a `#[test]` or a scratch `main`) and render
+/// the result with `dot -Tsvg`.
+#[cfg(debug_assertions)]
+#[allow(dead_code)]
+fn dump_dot(connections: &HashMap<Device, HashSet<Device>>) -> String {
+    let highlight = HashMap::from([
+        (OUT, "style=filled,fillcolor=lightgray"),
+        (YOU, "style=filled,fillcolor=lightblue"),
+        (SVR, "style=filled,fillcolor=lightblue"),
+        (DAC, "style=filled,fillcolor=lightgreen"),
+        (FFT, "style=filled,fillcolor=lightgreen"),
+    ]);
+    aoc_utils::graph::to_dot(connections, &highlight)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,11 +1,17 @@
-use std::{collections::HashSet, num::ParseIntError};
+use std::num::ParseIntError;
 
-use anyhow::{Context, anyhow, bail};
+use anyhow::{anyhow, bail, Context};
 use aoc_companion::prelude::*;
+use aoc_utils::linalg::Vector;
 use itertools::Itertools as _;
 
+/// The up-to-eight distinct orientations (four rotations, each optionally
+/// mirrored) of one present shape, each a set of `(dx, dy)` cell offsets
+/// normalized to touch the origin on both axes.
+type Orientations = Vec<Vec<Vector<i32, 2>>>;
+
 pub(crate) struct Door {
-    areas: [usize; 6],
+    shapes: [Orientations; 6],
     problems: Vec<Problem>,
 }
 
@@ -14,7 +20,7 @@ impl<'input> Solution<'input> for Door {
         let Some((shapes, problems)) = input.rsplit_once("\n\n") else {
             bail!("could not find empty line delimiting shapes and problems");
         };
-        let areas = aoc_utils::array::try_from_iter_exact(shapes.split("\n\n").map(|shape| {
+        let shapes = aoc_utils::array::try_from_iter_exact(shapes.split("\n\n").map(|shape| {
             let Some((_, shape)) = shape.split_once(":\n") else {
                 bail!("missing shape introducer line ending in colon");
             };
@@ -27,34 +33,30 @@ impl<'input> Solution<'input> for Door {
                     shape.dim().1
                 );
             }
-            Ok(shape.iter().filter(|b| **b == b'#').count())
+            let cells = shape
+                .indexed_iter()
+                .filter(|(_, &cell)| cell == b'#')
+                .map(|((row, col), _)| Vector([col as i32, row as i32]))
+                .collect();
+            Ok(orientations(cells))
         }))?
         .map_err(|v| anyhow!("expected exactly 6 present shapes, got {}", v.len()))?;
         let problems = problems.lines().map(str::parse).try_collect()?;
-        Ok(Door { areas, problems })
+        Ok(Door { shapes, problems })
     }
 
-    fn part1(&self) -> Result<usize> {
-        let (conclusive, inconclusive): (Vec<bool>, HashSet<&Problem>) = self
-            .problems
+    fn part1(&self) -> usize {
+        self.problems
             .iter()
-            .map(|problem| {
-                rule_out_due_to_insufficient_area(problem, &self.areas)
-                    .or_else(|| verify_with_trivial_packing(problem))
-                    .ok_or(problem)
-            })
-            .partition_result();
-
-        if !inconclusive.is_empty() {
-            bail!(
-                "{}/{} problems were inconclusive, e.g.: {}",
-                inconclusive.len(),
-                self.problems.len(),
-                inconclusive.iter().next().unwrap()
-            );
-        }
+            .filter(|problem| can_pack(problem, &self.shapes))
+            .count()
+    }
 
-        Ok(conclusive.into_iter().filter(|&b| b).count())
+    fn part2(&self) -> usize {
+        self.problems
+            .iter()
+            .map(|problem| max_packed(problem, &self.shapes))
+            .sum()
     }
 }
 
@@ -108,23 +110,260 @@ impl Problem {
     }
 }
 
-fn rule_out_due_to_insufficient_area(problem: &Problem, areas: &[usize; 6]) -> Option<bool> {
+/// Normalizes a set of cell offsets so its minimum coordinate on each axis
+/// is zero, then sorts it, giving two shapes the same representation iff
+/// they cover the same cells relative to one another.
+fn normalize(mut cells: Vec<Vector<i32, 2>>) -> Vec<Vector<i32, 2>> {
+    let min_x = cells.iter().map(|p| p[0]).min().unwrap_or(0);
+    let min_y = cells.iter().map(|p| p[1]).min().unwrap_or(0);
+    for p in &mut cells {
+        *p = Vector([p[0] - min_x, p[1] - min_y]);
+    }
+    cells.sort();
+    cells
+}
+
+/// The up-to-eight distinct orientations of `cells` reachable by rotating
+/// it (via [`Vector::rotate_right`]) and optionally mirroring it first,
+/// deduplicated since a shape's symmetries can make some orientations
+/// coincide.
+fn orientations(cells: Vec<Vector<i32, 2>>) -> Orientations {
+    let mirrored = cells.iter().map(|p| Vector([-p[0], p[1]])).collect();
+    let mut seen = std::collections::HashSet::new();
+    for mut oriented in [cells, mirrored] {
+        for _ in 0..4 {
+            seen.insert(normalize(oriented.clone()));
+            oriented = oriented.iter().map(|p| p.rotate_right()).collect();
+        }
+    }
+    seen.into_iter().collect()
+}
+
+/// The number of cells one present of shape `shape_idx` covers, regardless
+/// of orientation.
+fn shape_area(shapes: &[Orientations; 6], shape_idx: usize) -> usize {
+    shapes[shape_idx].first().map_or(0, Vec::len)
+}
+
+/// Every anchor position (as `(anchor index, absolute cell indices)`,
+/// anchor index increasing) at which `orientation` fits entirely within a
+/// `width x height` grid, regardless of occupancy. Shared by [`pack`] and
+/// [`branch_and_bound`] so both search over the exact same placements.
+fn placements(
+    orientation: &[Vector<i32, 2>],
+    width: usize,
+    height: usize,
+) -> Vec<(usize, Vec<usize>)> {
+    let shape_width = orientation.iter().map(|p| p[0]).max().unwrap_or(0) as usize + 1;
+    let shape_height = orientation.iter().map(|p| p[1]).max().unwrap_or(0) as usize + 1;
+    if shape_width > width || shape_height > height {
+        return Vec::new();
+    }
+
+    (0..=height - shape_height)
+        .flat_map(|y| (0..=width - shape_width).map(move |x| (y, x)))
+        .map(|(y, x)| {
+            let cells = orientation
+                .iter()
+                .map(|p| (y + p[1] as usize) * width + (x + p[0] as usize))
+                .collect();
+            (y * width + x, cells)
+        })
+        .collect()
+}
+
+/// Decides whether every present required by `problem` can be packed,
+/// without overlap, into its `W×H` region, by backtracking over every
+/// orientation and anchor position of the shapes that still have presents
+/// left to place.
+fn can_pack(problem: &Problem, shapes: &[Orientations; 6]) -> bool {
     let required_area: usize = problem
         .presents
         .iter()
-        .zip(areas)
-        .map(|(count, area)| count * area)
+        .enumerate()
+        .map(|(i, &count)| count * shape_area(shapes, i))
         .sum();
+    if required_area > problem.area() {
+        return false;
+    }
 
-    (required_area > problem.area()).then_some(false)
+    let [width, height] = problem.dimensions;
+    let mut occupied = vec![false; width * height];
+    pack(
+        &mut occupied,
+        width,
+        height,
+        &mut problem.presents.clone(),
+        shapes,
+        [0; 6],
+    )
 }
 
-fn verify_with_trivial_packing(problem: &Problem) -> Option<bool> {
-    let available_cells = (problem.dimensions[0] / 3) * (problem.dimensions[1] / 3);
+/// Backtracking search underlying [`can_pack`]. Always places presents of
+/// the lowest-indexed shape type with remaining `counts` next, which is
+/// enough to find a packing if one exists (the order types are interleaved
+/// in doesn't matter) while avoiding needless branching over which type to
+/// place next. Within a type, a piece is only placed at an anchor index
+/// greater than or equal to the last one used for that type, breaking the
+/// symmetry between otherwise-identical pieces being placed in either
+/// order. `occupied` and `counts` are restored to their prior state on
+/// backtrack.
+fn pack(
+    occupied: &mut [bool],
+    width: usize,
+    height: usize,
+    counts: &mut [usize; 6],
+    shapes: &[Orientations; 6],
+    mut last_anchor: [usize; 6],
+) -> bool {
+    let Some(shape_idx) = (0..6).find(|&i| counts[i] > 0) else {
+        return true;
+    };
+
+    let free_cells = occupied.iter().filter(|&&cell| !cell).count();
+    let remaining_area: usize = counts
+        .iter()
+        .enumerate()
+        .map(|(i, &count)| count * shape_area(shapes, i))
+        .sum();
+    if remaining_area > free_cells {
+        return false;
+    }
+
+    for orientation in &shapes[shape_idx] {
+        for (anchor, cells) in placements(orientation, width, height) {
+            if anchor < last_anchor[shape_idx] {
+                continue;
+            }
+            if cells.iter().any(|&cell| occupied[cell]) {
+                continue;
+            }
 
-    let total_presents: usize = problem.presents.iter().sum();
+            for &cell in &cells {
+                occupied[cell] = true;
+            }
+            counts[shape_idx] -= 1;
+            let prior_anchor = last_anchor[shape_idx];
+            last_anchor[shape_idx] = anchor;
 
-    (total_presents <= available_cells).then_some(true)
+            if pack(occupied, width, height, counts, shapes, last_anchor) {
+                return true;
+            }
+
+            last_anchor[shape_idx] = prior_anchor;
+            counts[shape_idx] += 1;
+            for &cell in &cells {
+                occupied[cell] = false;
+            }
+        }
+    }
+
+    false
+}
+
+/// The largest number of presents from `problem`'s multiset that fit into
+/// its region without overlap, found by branch-and-bound in the spirit of
+/// a 0/1-knapsack solver: pieces are considered largest-area first (so a
+/// good `best` turns up early and prunes harder), and at each one the
+/// search either places it (trying every orientation/anchor, as in
+/// [`pack`]) or discards it.
+fn max_packed(problem: &Problem, shapes: &[Orientations; 6]) -> usize {
+    let mut pieces: Vec<usize> = problem
+        .presents
+        .iter()
+        .enumerate()
+        .flat_map(|(shape_idx, &count)| std::iter::repeat(shape_idx).take(count))
+        .collect();
+    pieces.sort_by_key(|&shape_idx| std::cmp::Reverse(shape_area(shapes, shape_idx)));
+
+    let [width, height] = problem.dimensions;
+    let mut occupied = vec![false; width * height];
+    let mut best = 0;
+    branch_and_bound(
+        &mut occupied,
+        width,
+        height,
+        shapes,
+        &pieces,
+        0,
+        0,
+        &mut best,
+    );
+    best
+}
+
+/// Backtracking search underlying [`max_packed`]; `pieces[..index]` have
+/// already been decided (placed or discarded), `placed` of them were
+/// actually placed, and `best` holds the most any branch explored so far
+/// managed to place. Prunes a node once an optimistic upper bound on what
+/// it could still achieve — every remaining piece placed, capped by how
+/// many pieces of the smallest remaining area could possibly fit in the
+/// free cells — can no longer beat `best`.
+fn branch_and_bound(
+    occupied: &mut [bool],
+    width: usize,
+    height: usize,
+    shapes: &[Orientations; 6],
+    pieces: &[usize],
+    index: usize,
+    placed: usize,
+    best: &mut usize,
+) {
+    *best = (*best).max(placed);
+
+    let remaining = &pieces[index..];
+    let Some(&shape_idx) = remaining.first() else {
+        return;
+    };
+
+    let free_cells = occupied.iter().filter(|&&cell| !cell).count();
+    let smallest_remaining_area = remaining
+        .iter()
+        .map(|&shape_idx| shape_area(shapes, shape_idx))
+        .min()
+        .unwrap_or(1)
+        .max(1);
+    let upper_bound = placed + remaining.len().min(free_cells / smallest_remaining_area);
+    if upper_bound <= *best {
+        return;
+    }
+
+    for orientation in &shapes[shape_idx] {
+        for (_, cells) in placements(orientation, width, height) {
+            if cells.iter().any(|&cell| occupied[cell]) {
+                continue;
+            }
+
+            for &cell in &cells {
+                occupied[cell] = true;
+            }
+            branch_and_bound(
+                occupied,
+                width,
+                height,
+                shapes,
+                pieces,
+                index + 1,
+                placed + 1,
+                best,
+            );
+            for &cell in &cells {
+                occupied[cell] = false;
+            }
+        }
+    }
+
+    // Discard this piece without placing it.
+    branch_and_bound(
+        occupied,
+        width,
+        height,
+        shapes,
+        pieces,
+        index + 1,
+        placed,
+        best,
+    );
 }
 
 #[cfg(test)]
@@ -166,7 +405,6 @@ mod tests {
 12x5: 1 0 1 0 2 2
 12x5: 1 0 1 0 3 2";
 
-    const EXAMPLE_AREAS: [usize; 6] = [7, 7, 7, 7, 7, 7];
     const EXAMPLE_PROBLEMS: &[Problem] = &[
         Problem {
             dimensions: [4, 4],
@@ -184,26 +422,79 @@ mod tests {
 
     #[test]
     fn parse_example_input() {
-        let Door { areas, problems } = Door::parse(EXAMPLE_INPUT).unwrap();
-        assert_eq!(areas, EXAMPLE_AREAS);
+        let Door { shapes, problems } = Door::parse(EXAMPLE_INPUT).unwrap();
+        itertools::assert_equal((0..6).map(|i| shape_area(&shapes, i)), [7; 6]);
         itertools::assert_equal(&problems, EXAMPLE_PROBLEMS);
     }
 
     #[test]
-    fn none_of_the_example_problems_can_be_ruled_out_due_to_insufficient_area() {
-        itertools::assert_equal(
-            EXAMPLE_PROBLEMS
-                .iter()
-                .map(|problem| rule_out_due_to_insufficient_area(problem, &EXAMPLE_AREAS)),
-            [None, None, None],
+    fn every_shape_has_seven_cells_regardless_of_orientation() {
+        let Door { shapes, .. } = Door::parse(EXAMPLE_INPUT).unwrap();
+        for orientations in &shapes {
+            assert!(orientations.iter().all(|o| o.len() == 7));
+        }
+    }
+
+    #[test]
+    fn orientations_are_normalized_and_deduplicated() {
+        let plus = orientations(vec![
+            Vector([1, 0]),
+            Vector([0, 1]),
+            Vector([1, 1]),
+            Vector([2, 1]),
+            Vector([1, 2]),
+        ]);
+        // A plus shape is identical under every rotation and mirroring.
+        assert_eq!(plus.len(), 1);
+        assert_eq!(
+            plus[0],
+            vec![
+                Vector([0, 1]),
+                Vector([1, 0]),
+                Vector([1, 1]),
+                Vector([1, 2]),
+                Vector([2, 1]),
+            ]
         );
     }
 
     #[test]
-    fn none_of_the_example_problems_can_be_verified_with_trivial_packing() {
+    fn can_pack_decides_every_example_problem_exactly() {
+        let Door { shapes, problems } = Door::parse(EXAMPLE_INPUT).unwrap();
         itertools::assert_equal(
-            EXAMPLE_PROBLEMS.iter().map(verify_with_trivial_packing),
-            [None, None, None],
+            problems.iter().map(|problem| can_pack(problem, &shapes)),
+            [true, true, false],
+        );
+    }
+
+    #[test]
+    fn part1_counts_the_packable_example_problems() {
+        let door = Door::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(door.part1(), 2);
+    }
+
+    #[test]
+    fn max_packed_matches_every_present_when_the_problem_fully_packs() {
+        let Door { shapes, problems } = Door::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(
+            max_packed(&problems[0], &shapes),
+            problems[0].presents.iter().sum()
+        );
+        assert_eq!(
+            max_packed(&problems[1], &shapes),
+            problems[1].presents.iter().sum()
         );
     }
+
+    #[test]
+    fn max_packed_falls_short_when_not_every_present_fits() {
+        let Door { shapes, problems } = Door::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(max_packed(&problems[2], &shapes), 6);
+    }
+
+    #[test]
+    fn part2_sums_the_maximum_packed_per_problem() {
+        let door = Door::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(door.part2(), 14);
+    }
 }
@@ -0,0 +1,49 @@
+use super::AoCClient;
+use crate::api::{AnswerResponse, DayResponse};
+use crate::door::{DoorDate, Part};
+
+use anyhow::Result;
+
+/// The synchronous counterpart to [`AoCClient`], for callers that don't want
+/// to pull in an async runtime of their own. Anything that implements
+/// [`AoCClient`] gets this for free, so `FilesystemCache`/`Cache`/
+/// `CachingClient` stay written once against the async surface and work
+/// under either one.
+pub trait BlockingAoCClient {
+    fn get_input(&self, date: &DoorDate) -> Result<String>;
+    fn get_day(&self, date: &DoorDate) -> Result<DayResponse>;
+    fn post_answer(&self, date: &DoorDate, part: Part, guess: &str) -> Result<AnswerResponse>;
+    fn get_example(&self, date: &DoorDate) -> Result<Option<String>>;
+}
+
+impl<T: AoCClient> BlockingAoCClient for T {
+    fn get_input(&self, date: &DoorDate) -> Result<String> {
+        block_on(AoCClient::get_input(self, date))
+    }
+
+    fn get_day(&self, date: &DoorDate) -> Result<DayResponse> {
+        block_on(AoCClient::get_day(self, date))
+    }
+
+    fn post_answer(&self, date: &DoorDate, part: Part, guess: &str) -> Result<AnswerResponse> {
+        block_on(AoCClient::post_answer(self, date, part, guess))
+    }
+
+    fn get_example(&self, date: &DoorDate) -> Result<Option<String>> {
+        block_on(AoCClient::get_example(self, date))
+    }
+}
+
+/// Unifies the async and blocking client surfaces so a caller can pick
+/// either one without changing the caching/fs layers underneath.
+pub trait Client: AoCClient + BlockingAoCClient {}
+
+impl<T: AoCClient + BlockingAoCClient> Client for T {}
+
+fn block_on<F: std::future::Future>(fut: F) -> F::Output {
+    tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("failed to start a blocking runtime")
+        .block_on(fut)
+}
@@ -3,14 +3,66 @@ use crate::door::{DoorDate, Part};
 
 use anyhow::Result;
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use std::io;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 #[async_trait]
 pub trait Cache {
-    async fn cache(&mut self, key: &str, value: &str);
-    async fn recall(&self, key: &str) -> Option<String>;
-    async fn dirty(&mut self, key: &str);
+    async fn cache(&mut self, key: &str, value: &str) -> io::Result<()>;
+    async fn recall(&self, key: &str) -> io::Result<Option<String>>;
+    async fn dirty(&mut self, key: &str) -> io::Result<()>;
+
+    /// Like [`Self::cache`], but hints that `value` need not be kept past
+    /// `ttl`. Backends that have no notion of expiry (the default) just
+    /// cache it unconditionally, which matches how inputs, which never go
+    /// stale, are already cached today; the actual TTL enforcement for
+    /// entries that do expire happens in [`CachingClient`] regardless,
+    /// since it's the one that knows how old a cached value is.
+    async fn cache_with_ttl(&mut self, key: &str, value: &str, _ttl: Duration) -> io::Result<()> {
+        self.cache(key, value).await
+    }
+}
+
+/// How long a cached [`DayResponse`] is trusted before [`CachingClient`]
+/// re-fetches it, so a second star unlocked by a correct guess made
+/// elsewhere (or corrected puzzle text) eventually shows up without
+/// needing an explicit [`Cache::dirty`].
+const DAY_RESPONSE_TTL: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// A cached value together with the instant it was cached, so
+/// [`CachingClient`] can tell a stale entry from a fresh one without the
+/// underlying [`Cache`] needing to know anything about expiry itself.
+#[derive(Serialize, Deserialize)]
+struct Timestamped<T> {
+    cached_at_unix_secs: u64,
+    value: T,
+}
+
+impl<T> Timestamped<T> {
+    fn now(value: T) -> Self {
+        Self {
+            cached_at_unix_secs: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .expect("system clock should be after the Unix epoch")
+                .as_secs(),
+            value,
+        }
+    }
+
+    fn cached_at(&self) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(self.cached_at_unix_secs)
+    }
 }
 
+/// Wraps any [`AoCClient`] and persists [`AoCClient::get_input`]/
+/// [`AoCClient::get_day`] results to `cache`, keyed by [`DoorDate`] and
+/// stored separately (`{year}/{day}/input.txt` vs. `{year}/{day}/day.json`
+/// for [`super::DiskCache`]), so repeated requests for the same door hit the
+/// network at most once. [`AoCClient::post_answer`] always passes through
+/// uncached. See [`Self::on_disk`] for the common case of caching to the
+/// user's on-disk cache directory.
 pub struct CachingClient<U, C>
 where
     U: AoCClient + Send + Sync,
@@ -33,11 +85,52 @@ where
     }
 
     fn input_key(&self, DoorDate { day, year }: &DoorDate) -> String {
-        format!("input_{year}_{day}")
+        format!("{year}/{day}/input.txt")
     }
 
     fn day_key(&self, DoorDate { day, year }: &DoorDate) -> String {
-        format!("day_{year}_{day}")
+        format!("{year}/{day}/day.json")
+    }
+
+    fn example_key(&self, DoorDate { day, year }: &DoorDate) -> String {
+        format!("{year}/{day}/example.txt")
+    }
+
+    /// A cached [`DayResponse`] is fresh if it's within [`DAY_RESPONSE_TTL`]
+    /// and was cached no earlier than the puzzle's own unlock moment, so a
+    /// page scraped before the puzzle (or this part of it) unlocked can't
+    /// linger in the cache forever.
+    fn day_response_is_fresh(&self, entry: &Timestamped<DayResponse>, date: &DoorDate) -> bool {
+        let cached_at = entry.cached_at();
+        let is_within_ttl = SystemTime::now()
+            .duration_since(cached_at)
+            .is_ok_and(|age| age < DAY_RESPONSE_TTL);
+        is_within_ttl && cached_at >= date.unlock_time()
+    }
+}
+
+impl<U> CachingClient<U, super::DiskCache>
+where
+    U: AoCClient + Send + Sync,
+{
+    /// `CachingClient::new(underlying_client, DiskCache::user_cache_dir()?)`,
+    /// for the common case of caching `underlying_client` (typically a
+    /// [`super::WebClient`]) to the user's on-disk cache directory rather
+    /// than a custom [`Cache`] backend.
+    pub fn on_disk(underlying_client: U) -> Result<Self> {
+        Ok(Self::new(
+            underlying_client,
+            super::DiskCache::user_cache_dir()?,
+        ))
+    }
+
+    /// Like [`Self::on_disk`], but wipes any existing on-disk cache first,
+    /// as an opt-out for a user who wants a guaranteed-fresh run.
+    pub fn on_disk_refreshed(underlying_client: U) -> Result<Self> {
+        Ok(Self::new(
+            underlying_client,
+            super::DiskCache::clean_user_cache_dir()?,
+        ))
     }
 }
 
@@ -49,13 +142,13 @@ where
 {
     async fn get_input(&self, date: &DoorDate) -> Result<String> {
         let key = self.input_key(date);
-        let cache_result = self.cache.read().await.recall(&key).await;
+        let cache_result = self.cache.read().await.recall(&key).await?;
         match cache_result {
             Some(cached) => Ok(cached),
             None => {
                 let result = self.underlying_client.get_input(date).await;
                 if let Ok(value) = &result {
-                    self.cache.write().await.cache(&key, value).await;
+                    self.cache.write().await.cache(&key, value).await?;
                 }
                 result
             }
@@ -69,18 +162,23 @@ where
             .read()
             .await
             .recall(&key)
-            .await
-            .and_then(|s| serde_json::from_str(&s).ok());
+            .await?
+            .and_then(|s| serde_json::from_str::<Timestamped<DayResponse>>(&s).ok())
+            .filter(|entry| self.day_response_is_fresh(entry, date));
         match cache_result {
-            Some(cached) => Ok(cached),
+            Some(entry) => Ok(entry.value),
             None => {
                 let result = self.underlying_client.get_day(date).await;
                 if let Some(value) = result
                     .as_ref()
                     .ok()
-                    .and_then(|resp| serde_json::to_string(resp).ok())
+                    .and_then(|resp| serde_json::to_string(&Timestamped::now(resp.clone())).ok())
                 {
-                    self.cache.write().await.cache(&key, &value).await;
+                    self.cache
+                        .write()
+                        .await
+                        .cache_with_ttl(&key, &value, DAY_RESPONSE_TTL)
+                        .await?;
                 }
                 result
             }
@@ -95,10 +193,31 @@ where
     ) -> Result<AnswerResponse> {
         let result = self.underlying_client.post_answer(date, part, guess).await;
         if let Ok(AnswerResponse::Correct) = &result {
-            self.cache.write().await.dirty(&self.day_key(date)).await;
+            self.cache.write().await.dirty(&self.day_key(date)).await?;
         }
         result
     }
+
+    async fn get_example(&self, date: &DoorDate) -> Result<Option<String>> {
+        // An empty cached value stands for "scraped, but no example was
+        // found", as distinct from "not yet cached" (a cache miss).
+        let key = self.example_key(date);
+        let cache_result = self.cache.read().await.recall(&key).await?;
+        match cache_result {
+            Some(cached) => Ok((!cached.is_empty()).then_some(cached)),
+            None => {
+                let result = self.underlying_client.get_example(date).await;
+                if let Ok(value) = &result {
+                    self.cache
+                        .write()
+                        .await
+                        .cache(&key, value.as_deref().unwrap_or(""))
+                        .await?;
+                }
+                result
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -112,16 +231,18 @@ mod tests {
 
     #[async_trait]
     impl Cache for HashMap<String, String> {
-        async fn cache(&mut self, key: &str, value: &str) {
+        async fn cache(&mut self, key: &str, value: &str) -> io::Result<()> {
             self.insert(key.to_owned(), value.to_owned());
+            Ok(())
         }
 
-        async fn recall(&self, key: &str) -> Option<String> {
-            self.get(key).cloned()
+        async fn recall(&self, key: &str) -> io::Result<Option<String>> {
+            Ok(self.get(key).cloned())
         }
 
-        async fn dirty(&mut self, key: &str) {
+        async fn dirty(&mut self, key: &str) -> io::Result<()> {
             self.remove(key);
+            Ok(())
         }
     }
 
@@ -129,6 +250,7 @@ mod tests {
         inputs_queried: Mutex<[bool; 25]>,
         days_queried: Mutex<[bool; 25]>,
         day_data: Mutex<[DayResponse; 25]>,
+        examples_queried: Mutex<[bool; 25]>,
     }
 
     impl FakeUnderlyingClient {
@@ -137,6 +259,7 @@ mod tests {
                 inputs_queried: Mutex::new([false; 25]),
                 days_queried: Mutex::new([false; 25]),
                 day_data: Mutex::new(<[DayResponse; 25]>::default()),
+                examples_queried: Mutex::new([false; 25]),
             }
         }
     }
@@ -147,7 +270,7 @@ mod tests {
             match date {
                 DoorDate {
                     day: 1..=25,
-                    year: 2042,
+                    year: 2015,
                 } => {
                     let mut inputs = self.inputs_queried.lock().unwrap();
                     assert!(
@@ -157,7 +280,7 @@ mod tests {
                     Ok(format!("input {}", date.day))
                 }
                 _ => Err(anyhow!(
-                    "Date not supported by fake; need to be in advent of 2042."
+                    "Date not supported by fake; need to be in advent of 2015."
                 )),
             }
         }
@@ -166,7 +289,7 @@ mod tests {
             match date {
                 DoorDate {
                     day: 1..=25,
-                    year: 2042,
+                    year: 2015,
                 } => {
                     let mut days = self.days_queried.lock().unwrap();
                     let index = (date.day - 1) as usize;
@@ -177,7 +300,7 @@ mod tests {
                     Ok(self.day_data.lock().unwrap()[index].clone())
                 }
                 _ => Err(anyhow!(
-                    "Date not supported by fake; need to be in advent of 2042."
+                    "Date not supported by fake; need to be in advent of 2015."
                 )),
             }
         }
@@ -191,7 +314,7 @@ mod tests {
             match date {
                 DoorDate {
                     day: 1..=25,
-                    year: 2042,
+                    year: 2015,
                 } => {
                     use AnswerResponse::*;
                     match (part, guess) {
@@ -207,11 +330,31 @@ mod tests {
                         }
                         _ => Ok(IncorrectTooManyGuesses {
                             guess: guess.to_owned(),
+                            wait: Duration::from_secs(60),
                         }),
                     }
                 }
                 _ => Err(anyhow!(
-                    "Date not supported by fake; need to be in advent of 2042."
+                    "Date not supported by fake; need to be in advent of 2015."
+                )),
+            }
+        }
+
+        async fn get_example(&self, date: &DoorDate) -> Result<Option<String>> {
+            match date {
+                DoorDate {
+                    day: 1..=25,
+                    year: 2015,
+                } => {
+                    let mut examples = self.examples_queried.lock().unwrap();
+                    assert!(
+                        !std::mem::replace(&mut examples[(date.day - 1) as usize], true),
+                        "Accessing example for {date:?} for the second time!"
+                    );
+                    Ok((date.day % 2 == 0).then(|| format!("example {}", date.day)))
+                }
+                _ => Err(anyhow!(
+                    "Date not supported by fake; need to be in advent of 2015."
                 )),
             }
         }
@@ -219,11 +362,15 @@ mod tests {
 
     const TEST_DATE_1: &DoorDate = &DoorDate {
         day: 17,
-        year: 2042,
+        year: 2015,
     };
     const TEST_DATE_2: &DoorDate = &DoorDate {
         day: 21,
-        year: 2042,
+        year: 2015,
+    };
+    const TEST_DATE_WITH_EXAMPLE: &DoorDate = &DoorDate {
+        day: 20,
+        year: 2015,
     };
 
     #[tokio::test]
@@ -292,6 +439,25 @@ mod tests {
         assert_eq!(before, after);
     }
 
+    #[tokio::test]
+    async fn a_day_response_older_than_the_ttl_is_refetched() {
+        let mut cache = HashMap::new();
+        let stale_entry = Timestamped {
+            cached_at_unix_secs: 0,
+            value: DayResponse::default(),
+        };
+        cache.insert(
+            format!("{}/{}/day.json", TEST_DATE_1.year, TEST_DATE_1.day),
+            serde_json::to_string(&stale_entry).unwrap(),
+        );
+        let client = CachingClient::new(FakeUnderlyingClient::new(), cache);
+
+        // The fake underlying client asserts it's queried only once per day
+        // unless dirtied; it being queried here at all proves the stale
+        // entry above wasn't trusted.
+        client.get_day(TEST_DATE_1).await.unwrap();
+    }
+
     #[tokio::test]
     async fn repeatedly_getting_day_response_for_the_same_day_uses_the_underlying_client_again_if_the_correct_answer_has_been_posted(
     ) {
@@ -301,7 +467,8 @@ mod tests {
             before,
             DayResponse {
                 first_half: None,
-                second_half: None
+                second_half: None,
+                ..
             }
         );
         client
@@ -313,7 +480,8 @@ mod tests {
             after,
             DayResponse {
                 first_half: Some(_),
-                second_half: None
+                second_half: None,
+                ..
             }
         );
     }
@@ -330,4 +498,31 @@ mod tests {
         let after = client.get_day(TEST_DATE_1).await.unwrap();
         assert_eq!(before, after);
     }
+
+    #[tokio::test]
+    async fn underlying_client_gets_example_when_not_in_cache() {
+        let client = CachingClient::new(FakeUnderlyingClient::new(), HashMap::new());
+        assert_eq!(client.get_example(TEST_DATE_1).await.unwrap(), None);
+        assert_eq!(
+            client.get_example(TEST_DATE_WITH_EXAMPLE).await.unwrap(),
+            Some("example 20".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn repeatedly_getting_example_for_the_same_day_only_uses_underlying_client_once() {
+        let client = CachingClient::new(FakeUnderlyingClient::new(), HashMap::new());
+        let before = client.get_example(TEST_DATE_WITH_EXAMPLE).await.unwrap();
+        let after = client.get_example(TEST_DATE_WITH_EXAMPLE).await.unwrap();
+        assert_eq!(before, after);
+    }
+
+    #[tokio::test]
+    async fn a_day_with_no_example_is_also_cached_and_not_mistaken_for_a_cache_miss() {
+        let client = CachingClient::new(FakeUnderlyingClient::new(), HashMap::new());
+        let before = client.get_example(TEST_DATE_1).await.unwrap();
+        let after = client.get_example(TEST_DATE_1).await.unwrap();
+        assert_eq!(before, None);
+        assert_eq!(after, None);
+    }
 }
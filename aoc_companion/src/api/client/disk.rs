@@ -0,0 +1,61 @@
+use super::{Cache, FilesystemCache};
+
+use async_trait::async_trait;
+
+use std::io;
+use std::path::PathBuf;
+
+/// A [`Cache`] that persists entries under a stable, user-level data
+/// directory rather than [`FilesystemCache::tmp`]'s OS temp directory, which
+/// platforms are free to wipe on reboot. Puzzle inputs never change once
+/// issued, so they're worth keeping around indefinitely; day pages are
+/// already re-fetched on demand via [`Cache::dirty`], so the same storage
+/// serves both without extra bookkeeping here.
+pub struct DiskCache(FilesystemCache);
+
+impl DiskCache {
+    pub fn new(dir: PathBuf) -> Self {
+        Self(FilesystemCache::at(dir))
+    }
+
+    /// Resolves to `$XDG_CACHE_HOME/aoc`, falling back to `~/.cache/aoc`
+    /// where `XDG_CACHE_HOME` isn't set.
+    pub fn user_cache_dir() -> io::Result<Self> {
+        Ok(Self::new(Self::resolve_dir()?))
+    }
+
+    /// Like [`Self::user_cache_dir`], but wipes any existing entries first.
+    pub fn clean_user_cache_dir() -> io::Result<Self> {
+        let dir = Self::resolve_dir()?;
+        std::fs::remove_dir_all(&dir)?;
+        Ok(Self::new(dir))
+    }
+
+    fn resolve_dir() -> io::Result<PathBuf> {
+        std::env::var_os("XDG_CACHE_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
+            .map(|base| base.join("aoc"))
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    "neither XDG_CACHE_HOME nor HOME is set",
+                )
+            })
+    }
+}
+
+#[async_trait]
+impl Cache for DiskCache {
+    async fn cache(&mut self, key: &str, value: &str) -> io::Result<()> {
+        self.0.cache(key, value).await
+    }
+
+    async fn recall(&self, key: &str) -> io::Result<Option<String>> {
+        self.0.recall(key).await
+    }
+
+    async fn dirty(&mut self, key: &str) -> io::Result<()> {
+        self.0.dirty(key).await
+    }
+}
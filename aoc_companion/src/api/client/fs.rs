@@ -1,5 +1,8 @@
 use super::Cache;
 
+use async_trait::async_trait;
+
+use std::io;
 use std::path::PathBuf;
 
 pub struct FilesystemCache {
@@ -7,32 +10,44 @@ pub struct FilesystemCache {
 }
 
 impl FilesystemCache {
+    pub(crate) fn at(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
     pub fn tmp() -> Self {
-        Self {
-            dir: std::env::temp_dir().join("aoc-cache"),
-        }
+        Self::at(std::env::temp_dir().join("aoc-cache"))
     }
 
-    pub fn clean_tmp() -> std::io::Result<Self> {
+    pub fn clean_tmp() -> io::Result<Self> {
         let cache = Self::tmp();
         std::fs::remove_dir_all(&cache.dir)?;
         Ok(cache)
     }
 }
 
+#[async_trait]
 impl Cache for FilesystemCache {
-    async fn cache(&mut self, key: &str, value: &str) {
-        let _ = std::fs::create_dir(&self.dir);
-        tokio::fs::write(self.dir.join(key), value)
-            .await
-            .expect("Failed to write to cache");
+    async fn cache(&mut self, key: &str, value: &str) -> io::Result<()> {
+        let path = self.dir.join(key);
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(path, value).await
     }
 
-    async fn recall(&self, key: &str) -> Option<String> {
-        tokio::fs::read_to_string(self.dir.join(key)).await.ok()
+    async fn recall(&self, key: &str) -> io::Result<Option<String>> {
+        match tokio::fs::read_to_string(self.dir.join(key)).await {
+            Ok(contents) => Ok(Some(contents)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
     }
 
-    async fn dirty(&mut self, key: &str) {
-        let _ = tokio::fs::remove_file(self.dir.join(key)).await;
+    async fn dirty(&mut self, key: &str) -> io::Result<()> {
+        match tokio::fs::remove_file(self.dir.join(key)).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e),
+        }
     }
 }
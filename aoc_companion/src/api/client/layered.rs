@@ -0,0 +1,110 @@
+use super::Cache;
+
+use async_trait::async_trait;
+
+use std::io;
+
+/// A [`Cache`] that checks `front` before falling back to `back`, the same
+/// way [`CachingClient`](super::CachingClient) wraps a fast cache lookup
+/// around a slower underlying client: a hit in `back` is written through to
+/// `front` so it's served straight from the fast path next time. `front` is
+/// kept behind its own lock for the same reason `CachingClient` keeps its
+/// cache behind one: `recall` only borrows `&self`, but a `back` hit still
+/// needs to write `front`.
+pub struct LayeredCache<A, B> {
+    front: tokio::sync::RwLock<A>,
+    back: B,
+}
+
+impl<A, B> LayeredCache<A, B> {
+    pub fn new(front: A, back: B) -> Self {
+        Self {
+            front: tokio::sync::RwLock::new(front),
+            back,
+        }
+    }
+}
+
+#[async_trait]
+impl<A, B> Cache for LayeredCache<A, B>
+where
+    A: Cache + Send + Sync,
+    B: Cache + Send + Sync,
+{
+    async fn cache(&mut self, key: &str, value: &str) -> io::Result<()> {
+        self.front.write().await.cache(key, value).await?;
+        self.back.cache(key, value).await
+    }
+
+    async fn recall(&self, key: &str) -> io::Result<Option<String>> {
+        if let Some(value) = self.front.read().await.recall(key).await? {
+            return Ok(Some(value));
+        }
+
+        match self.back.recall(key).await? {
+            Some(value) => {
+                self.front.write().await.cache(key, &value).await?;
+                Ok(Some(value))
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn dirty(&mut self, key: &str) -> io::Result<()> {
+        self.front.write().await.dirty(key).await?;
+        self.back.dirty(key).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::InMemoryCache;
+    use super::*;
+
+    #[tokio::test]
+    async fn a_value_only_in_back_is_found_and_written_through_to_front() {
+        let mut back = InMemoryCache::new();
+        back.cache("key", "value").await.unwrap();
+        let mut layered = LayeredCache::new(InMemoryCache::new(), back);
+
+        assert_eq!(
+            layered.recall("key").await.unwrap(),
+            Some("value".to_owned())
+        );
+        assert_eq!(
+            layered.front.read().await.recall("key").await.unwrap(),
+            Some("value".to_owned())
+        );
+    }
+
+    #[tokio::test]
+    async fn a_value_absent_from_both_layers_is_a_miss() {
+        let layered = LayeredCache::new(InMemoryCache::new(), InMemoryCache::new());
+        assert_eq!(layered.recall("missing").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn caching_writes_through_both_layers() {
+        let mut layered = LayeredCache::new(InMemoryCache::new(), InMemoryCache::new());
+        layered.cache("key", "value").await.unwrap();
+
+        assert_eq!(
+            layered.front.read().await.recall("key").await.unwrap(),
+            Some("value".to_owned())
+        );
+        assert_eq!(
+            layered.back.recall("key").await.unwrap(),
+            Some("value".to_owned())
+        );
+    }
+
+    #[tokio::test]
+    async fn dirtying_removes_from_both_layers() {
+        let mut layered = LayeredCache::new(InMemoryCache::new(), InMemoryCache::new());
+        layered.cache("key", "value").await.unwrap();
+        layered.dirty("key").await.unwrap();
+
+        assert_eq!(layered.recall("key").await.unwrap(), None);
+        assert_eq!(layered.back.recall("key").await.unwrap(), None);
+    }
+}
@@ -0,0 +1,68 @@
+use super::Cache;
+
+use async_trait::async_trait;
+
+use std::collections::HashMap;
+use std::io;
+use std::sync::Mutex;
+
+/// A [`Cache`] backed by an in-process `HashMap`, behind a [`Mutex`] so a
+/// single instance can be shared (e.g. via `Arc`) beyond the lifetime of
+/// whichever `CachingClient` it's plugged into. Never touches disk, so it's
+/// the natural backend for tests and benches.
+#[derive(Default)]
+pub struct InMemoryCache {
+    entries: Mutex<HashMap<String, String>>,
+}
+
+impl InMemoryCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl Cache for InMemoryCache {
+    async fn cache(&mut self, key: &str, value: &str) -> io::Result<()> {
+        self.entries
+            .lock()
+            .unwrap()
+            .insert(key.to_owned(), value.to_owned());
+        Ok(())
+    }
+
+    async fn recall(&self, key: &str) -> io::Result<Option<String>> {
+        Ok(self.entries.lock().unwrap().get(key).cloned())
+    }
+
+    async fn dirty(&mut self, key: &str) -> io::Result<()> {
+        self.entries.lock().unwrap().remove(key);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn a_cached_value_can_be_recalled() {
+        let mut cache = InMemoryCache::new();
+        cache.cache("key", "value").await.unwrap();
+        assert_eq!(cache.recall("key").await.unwrap(), Some("value".to_owned()));
+    }
+
+    #[tokio::test]
+    async fn recalling_an_absent_key_is_a_miss_not_an_error() {
+        let cache = InMemoryCache::new();
+        assert_eq!(cache.recall("missing").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn a_dirtied_value_is_no_longer_recalled() {
+        let mut cache = InMemoryCache::new();
+        cache.cache("key", "value").await.unwrap();
+        cache.dirty("key").await.unwrap();
+        assert_eq!(cache.recall("key").await.unwrap(), None);
+    }
+}
@@ -1,19 +1,93 @@
+mod blocking;
 mod cache;
+mod disk;
 mod fs;
+mod layered;
+mod memory;
+mod retry;
+mod sled;
 mod web;
 
-use crate::api::{AnswerResponse, DayResponse};
+use crate::api::{jittered, AnswerResponse, DayResponse};
 use crate::door::{DoorDate, Part};
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
+
+use std::sync::Arc;
 
 pub trait AoCClient {
     async fn get_input(&self, date: &DoorDate) -> Result<String>;
     async fn get_day(&self, date: &DoorDate) -> Result<DayResponse>;
     async fn post_answer(&self, date: &DoorDate, part: Part, guess: &str)
         -> Result<AnswerResponse>;
+
+    /// Scrapes the puzzle page's canonical sample input, for running a
+    /// `Solution::parse` against the example rather than the real input.
+    /// `None` means the page carries no recognizable example block, not
+    /// that the request failed.
+    async fn get_example(&self, date: &DoorDate) -> Result<Option<String>>;
+
+    /// Submits `guess` for `part` of `date`, resubmitting automatically
+    /// while AoC reports the guess as too recent: sleeps out the server's
+    /// own reported cooldown, [`jittered`] so many callers backing off at
+    /// once don't all retry in the same instant, up to `max_attempts` tries
+    /// in total. Short-circuits immediately on a definitive
+    /// "correct"/"wrong answer" result; "you don't seem to be solving the
+    /// right level" already surfaces as a distinct error out of
+    /// [`AnswerResponse::parse`], so it propagates here rather than being
+    /// retried.
+    async fn send_and_confirm_answer(
+        &self,
+        date: &DoorDate,
+        part: Part,
+        guess: &str,
+        max_attempts: u32,
+    ) -> Result<AnswerResponse> {
+        for _ in 0..max_attempts.max(1) {
+            match self.post_answer(date, part, guess).await? {
+                AnswerResponse::GuessedTooRecently { wait } => {
+                    tokio::time::sleep(jittered(wait)).await;
+                }
+                response => return Ok(response),
+            }
+        }
+        Err(anyhow!(
+            "gave up waiting out AoC's submission cooldown for part {part} of day {}, {} after {max_attempts} attempts",
+            date.day,
+            date.year
+        ))
+    }
+
+    /// Fires off [`send_and_confirm_answer`](Self::send_and_confirm_answer)
+    /// on the current `LocalSet` and returns immediately with a handle to
+    /// it, for callers that want to keep making progress (e.g. solving the
+    /// next door) while this submission's cooldown retries play out in the
+    /// background rather than blocking on them. Mirrors the
+    /// `Arc<impl AoCClient>` sharing already used to fan a client out across
+    /// concurrent door tasks in [`crate::runtime`].
+    fn submit_in_background(
+        self: Arc<Self>,
+        date: DoorDate,
+        part: Part,
+        guess: String,
+        max_attempts: u32,
+    ) -> tokio::task::JoinHandle<Result<AnswerResponse>>
+    where
+        Self: Sized + 'static,
+    {
+        tokio::task::spawn_local(async move {
+            self.send_and_confirm_answer(&date, part, &guess, max_attempts)
+                .await
+        })
+    }
 }
 
+pub use blocking::{BlockingAoCClient, Client};
 pub use cache::{Cache, CachingClient};
+pub use disk::DiskCache;
 pub use fs::FilesystemCache;
+pub use layered::LayeredCache;
+pub use memory::InMemoryCache;
+pub use retry::{RetryConfig, RetryingClient};
+pub use sled::SledCache;
 pub use web::WebClient;
@@ -0,0 +1,273 @@
+use super::web::SessionError;
+use super::AoCClient;
+use crate::api::{jittered, AnswerResponse, DayResponse};
+use crate::door::{DoorDate, Part};
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+
+use std::time::Duration;
+
+/// Exponential backoff parameters for [`RetryingClient`]: attempt `n`
+/// (0-indexed) waits `min(max_delay, base_delay * 2^n)`, [`jittered`] so
+/// many callers backing off from the same outage don't all retry in the
+/// same instant.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryConfig {
+    pub max_retries: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: 4,
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+}
+
+impl RetryConfig {
+    fn delay(&self, attempt: u32) -> Duration {
+        let backoff = self
+            .base_delay
+            .checked_mul(1u32.checked_shl(attempt).unwrap_or(u32::MAX))
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay);
+        jittered(backoff)
+    }
+}
+
+/// Wraps any [`AoCClient`] and retries a failed request with exponential
+/// backoff, so a 5xx or a dropped connection under load doesn't fail a
+/// whole door outright. Only conditions worth retrying are retried: a
+/// network-level failure, HTTP 429, or a 5xx; in particular
+/// [`SessionError::AuthenticationInvalidOrExpired`] is never retried, since
+/// waiting won't fix an expired session token.
+pub struct RetryingClient<C> {
+    underlying_client: C,
+    config: RetryConfig,
+}
+
+impl<C: AoCClient> RetryingClient<C> {
+    pub fn new(underlying_client: C, config: RetryConfig) -> Self {
+        Self {
+            underlying_client,
+            config,
+        }
+    }
+}
+
+/// Whether `err` describes a condition worth retrying rather than a
+/// definitive failure. Walks the whole cause chain, since `WebClient`
+/// surfaces the underlying [`reqwest::Error`]/[`SessionError`] as the root
+/// cause of an [`anyhow::Error`] built up with `.context()`.
+fn is_transient(err: &anyhow::Error) -> bool {
+    if err
+        .chain()
+        .any(|cause| cause.downcast_ref::<SessionError>().is_some())
+    {
+        return false;
+    }
+    err.chain().any(|cause| {
+        cause.downcast_ref::<reqwest::Error>().is_some_and(|e| {
+            e.is_connect()
+                || e.is_timeout()
+                || e.status().is_some_and(|status| {
+                    status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+                })
+        })
+    })
+}
+
+/// Calls `attempt` until it succeeds, it fails with a non-transient error,
+/// or [`RetryConfig::max_retries`] is exhausted, sleeping out the backoff
+/// delay between tries.
+async fn retry<T, Fut>(config: &RetryConfig, mut attempt: impl FnMut() -> Fut) -> Result<T>
+where
+    Fut: std::future::Future<Output = Result<T>>,
+{
+    let mut n = 0;
+    loop {
+        match attempt().await {
+            Ok(value) => return Ok(value),
+            Err(err) if n < config.max_retries && is_transient(&err) => {
+                tokio::time::sleep(config.delay(n)).await;
+                n += 1;
+            }
+            Err(err) => {
+                return Err(err).with_context(|| format!("gave up after {} attempt(s)", n + 1));
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<C: AoCClient + Send + Sync> AoCClient for RetryingClient<C> {
+    async fn get_input(&self, date: &DoorDate) -> Result<String> {
+        retry(&self.config, || self.underlying_client.get_input(date)).await
+    }
+
+    async fn get_day(&self, date: &DoorDate) -> Result<DayResponse> {
+        retry(&self.config, || self.underlying_client.get_day(date)).await
+    }
+
+    async fn post_answer(
+        &self,
+        date: &DoorDate,
+        part: Part,
+        guess: &str,
+    ) -> Result<AnswerResponse> {
+        retry(&self.config, || {
+            self.underlying_client.post_answer(date, part, guess)
+        })
+        .await
+    }
+
+    async fn get_example(&self, date: &DoorDate) -> Result<Option<String>> {
+        retry(&self.config, || self.underlying_client.get_example(date)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use std::sync::Mutex;
+
+    use super::*;
+
+    const EXAMPLE_DATE: DoorDate = DoorDate { day: 1, year: 2015 };
+
+    /// A client whose `get_input` fails with a genuine transient
+    /// [`reqwest::Error`] (nothing listens on this loopback port, so it's a
+    /// real connection failure, not a simulated one) the first
+    /// `failures_before_success` times it's called, then succeeds.
+    struct FlakyClient {
+        failures_before_success: Mutex<u32>,
+        attempts: Mutex<u32>,
+    }
+
+    impl FlakyClient {
+        fn new(failures_before_success: u32) -> Self {
+            Self {
+                failures_before_success: Mutex::new(failures_before_success),
+                attempts: Mutex::new(0),
+            }
+        }
+
+        fn should_fail_this_attempt(&self) -> bool {
+            let mut remaining = self.failures_before_success.lock().unwrap();
+            if *remaining > 0 {
+                *remaining -= 1;
+                true
+            } else {
+                false
+            }
+        }
+    }
+
+    #[async_trait]
+    impl AoCClient for FlakyClient {
+        async fn get_input(&self, _date: &DoorDate) -> Result<String> {
+            *self.attempts.lock().unwrap() += 1;
+            if self.should_fail_this_attempt() {
+                let err = reqwest::Client::new()
+                    .get("http://127.0.0.1:1")
+                    .send()
+                    .await
+                    .unwrap_err();
+                Err(err.into())
+            } else {
+                Ok("input".to_owned())
+            }
+        }
+
+        async fn get_day(&self, _date: &DoorDate) -> Result<DayResponse> {
+            unimplemented!("unused by these tests")
+        }
+
+        async fn post_answer(
+            &self,
+            _date: &DoorDate,
+            _part: Part,
+            _guess: &str,
+        ) -> Result<AnswerResponse> {
+            unimplemented!("unused by these tests")
+        }
+
+        async fn get_example(&self, _date: &DoorDate) -> Result<Option<String>> {
+            unimplemented!("unused by these tests")
+        }
+    }
+
+    /// A client that always fails authentication, to confirm
+    /// [`RetryingClient`] never retries it.
+    struct NeverAuthenticatedClient {
+        attempts: Mutex<u32>,
+    }
+
+    #[async_trait]
+    impl AoCClient for NeverAuthenticatedClient {
+        async fn get_input(&self, _date: &DoorDate) -> Result<String> {
+            *self.attempts.lock().unwrap() += 1;
+            Err(SessionError::AuthenticationInvalidOrExpired.into())
+        }
+
+        async fn get_day(&self, _date: &DoorDate) -> Result<DayResponse> {
+            unimplemented!("unused by these tests")
+        }
+
+        async fn post_answer(
+            &self,
+            _date: &DoorDate,
+            _part: Part,
+            _guess: &str,
+        ) -> Result<AnswerResponse> {
+            unimplemented!("unused by these tests")
+        }
+
+        async fn get_example(&self, _date: &DoorDate) -> Result<Option<String>> {
+            unimplemented!("unused by these tests")
+        }
+    }
+
+    fn tiny_config(max_retries: u32) -> RetryConfig {
+        RetryConfig {
+            max_retries,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(1),
+        }
+    }
+
+    #[tokio::test]
+    async fn succeeds_without_retrying_when_the_first_attempt_succeeds() {
+        let client = RetryingClient::new(FlakyClient::new(0), tiny_config(4));
+        assert_eq!(client.get_input(&EXAMPLE_DATE).await.unwrap(), "input");
+        assert_eq!(*client.underlying_client.attempts.lock().unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn retries_transient_failures_until_success() {
+        let client = RetryingClient::new(FlakyClient::new(2), tiny_config(3));
+        assert_eq!(client.get_input(&EXAMPLE_DATE).await.unwrap(), "input");
+        assert_eq!(*client.underlying_client.attempts.lock().unwrap(), 3);
+    }
+
+    #[tokio::test]
+    async fn never_retries_a_session_error() {
+        let client = RetryingClient::new(
+            NeverAuthenticatedClient {
+                attempts: Mutex::new(0),
+            },
+            tiny_config(3),
+        );
+        let err = client.get_input(&EXAMPLE_DATE).await.unwrap_err();
+        assert_matches!(
+            err.downcast_ref::<SessionError>(),
+            Some(SessionError::AuthenticationInvalidOrExpired)
+        );
+        assert_eq!(*client.underlying_client.attempts.lock().unwrap(), 1);
+    }
+}
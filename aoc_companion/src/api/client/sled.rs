@@ -0,0 +1,82 @@
+use super::Cache;
+
+use async_trait::async_trait;
+
+use std::io;
+use std::path::PathBuf;
+
+/// A [`Cache`] backed by an embedded [`sled`] database rather than loose
+/// files, so a single durable store can be shared across concurrent solver
+/// runs (and, since `sled`'s on-disk format doesn't care which process wrote
+/// it, across machines via a synced directory) without the "half-written
+/// file" races a plain [`super::FilesystemCache`] would be exposed to if two
+/// runs raced to cache the same key. Every write goes through a `sled`
+/// transaction, which is `sled`'s unit of atomicity: the entry is either
+/// fully visible or not there at all, even if the process is killed
+/// mid-write.
+pub struct SledCache(sled::Db);
+
+impl SledCache {
+    pub fn open(dir: PathBuf) -> sled::Result<Self> {
+        Ok(Self(sled::open(dir)?))
+    }
+}
+
+fn sled_error(e: sled::Error) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, e)
+}
+
+fn transaction_error(e: sled::transaction::TransactionError<sled::Error>) -> io::Error {
+    match e {
+        sled::transaction::TransactionError::Abort(inner) => sled_error(inner),
+        sled::transaction::TransactionError::Storage(inner) => sled_error(inner),
+    }
+}
+
+fn join_error(e: tokio::task::JoinError) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, e)
+}
+
+#[async_trait]
+impl Cache for SledCache {
+    async fn cache(&mut self, key: &str, value: &str) -> io::Result<()> {
+        let db = self.0.clone();
+        let (key, value) = (key.to_owned(), value.to_owned());
+        tokio::task::spawn_blocking(move || {
+            db.transaction(|tx| {
+                tx.insert(key.as_bytes(), value.as_bytes())?;
+                Ok(())
+            })
+            .map_err(transaction_error)
+        })
+        .await
+        .map_err(join_error)?
+    }
+
+    async fn recall(&self, key: &str) -> io::Result<Option<String>> {
+        let db = self.0.clone();
+        let key = key.to_owned();
+        tokio::task::spawn_blocking(move || {
+            Ok(db
+                .get(key.as_bytes())
+                .map_err(sled_error)?
+                .map(|bytes| String::from_utf8_lossy(&bytes).into_owned()))
+        })
+        .await
+        .map_err(join_error)?
+    }
+
+    async fn dirty(&mut self, key: &str) -> io::Result<()> {
+        let db = self.0.clone();
+        let key = key.to_owned();
+        tokio::task::spawn_blocking(move || {
+            db.transaction(|tx| {
+                tx.remove(key.as_bytes())?;
+                Ok(())
+            })
+            .map_err(transaction_error)
+        })
+        .await
+        .map_err(join_error)?
+    }
+}
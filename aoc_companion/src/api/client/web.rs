@@ -1,27 +1,86 @@
-use crate::api::{AnswerResponse, AoCClient, DayResponse};
+use crate::api::{extract_example, AnswerResponse, AoCClient, DayResponse};
 use crate::door::{DoorDate, Part};
 
 use anyhow::{anyhow, Context, Result};
 use thiserror::Error;
 
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
+
+/// Sends `request`, retrying with exponential backoff (1s, 2s, 4s, capped)
+/// on a connection/timeout error or an HTTP 5xx response — the AoC server
+/// occasionally hiccups under load, and neither of those means the request
+/// itself was bad. Any other outcome, success or failure, is returned as-is
+/// for the caller to interpret.
+async fn send_with_backoff(request: reqwest::RequestBuilder) -> reqwest::Result<reqwest::Response> {
+    const MAX_ATTEMPTS: u32 = 4;
+    const MAX_BACKOFF: Duration = Duration::from_secs(4);
+
+    let mut backoff = Duration::from_secs(1);
+    for _ in 1..MAX_ATTEMPTS {
+        let result = request
+            .try_clone()
+            .expect("request body must be clonable to retry it")
+            .send()
+            .await;
+        let should_retry = match &result {
+            Err(err) => err.is_connect() || err.is_timeout(),
+            Ok(resp) => resp.status().is_server_error(),
+        };
+        if !should_retry {
+            return result;
+        }
+        tokio::time::sleep(backoff).await;
+        backoff = (backoff * 2).min(MAX_BACKOFF);
+    }
+    request.send().await
+}
 
 #[derive(Debug, Error)]
 pub enum SessionError {
-    #[error("Failed to retrieve SESSION environment variable")]
-    CannotAccessSessionEnvVar,
+    #[error("Failed to retrieve the session token from the AOC_SESSION (or legacy SESSION) environment variable, and no session file was found at {config_path}")]
+    CannotAccessSessionEnvVar { config_path: String },
     #[error("The session token is invalid; it may have expired. Log into https://adventofcode.com/ and update the session token.")]
     AuthenticationInvalidOrExpired,
 }
 
+/// `$XDG_CONFIG_HOME/aoc/session`, falling back to `~/.config/aoc/session`
+/// where `XDG_CONFIG_HOME` isn't set; mirrors [`super::DiskCache`]'s own
+/// resolution of its XDG cache directory.
+fn session_file_path() -> Option<PathBuf> {
+    std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .map(|base| base.join("aoc").join("session"))
+}
+
+/// Reads the session token from the `AOC_SESSION` env var, falling back to
+/// the legacy `SESSION` one and then to a session file on disk, so a token
+/// set up once doesn't have to be re-exported into every shell.
+fn read_session() -> Result<String, SessionError> {
+    std::env::var("AOC_SESSION")
+        .or_else(|_| std::env::var("SESSION"))
+        .ok()
+        .or_else(|| std::fs::read_to_string(session_file_path()?).ok())
+        .map(|session| session.trim().to_owned())
+        .ok_or_else(|| SessionError::CannotAccessSessionEnvVar {
+            config_path: session_file_path()
+                .map(|p| p.display().to_string())
+                .unwrap_or_else(|| {
+                    "<unresolvable: neither XDG_CONFIG_HOME nor HOME is set>".to_string()
+                }),
+        })
+}
+
 pub struct WebClient {
     http: reqwest::Client,
 }
 
 impl WebClient {
     pub fn new() -> Result<Self> {
-        let session = std::env::var("SESSION").context(SessionError::CannotAccessSessionEnvVar)?;
+        let session = read_session()?;
 
         let jar = reqwest::cookie::Jar::default();
         jar.add_cookie_str(
@@ -40,11 +99,11 @@ impl WebClient {
 
 impl AoCClient for WebClient {
     async fn get_input(&self, &DoorDate { year, day }: &DoorDate) -> Result<String> {
-        let resp = self
-            .http
-            .get(format!("https://adventofcode.com/{year}/day/{day}/input"))
-            .send()
-            .await?;
+        let resp = send_with_backoff(
+            self.http
+                .get(format!("https://adventofcode.com/{year}/day/{day}/input")),
+        )
+        .await?;
 
         use reqwest::StatusCode;
         match resp.status() {
@@ -60,17 +119,29 @@ impl AoCClient for WebClient {
     }
 
     async fn get_day(&self, &DoorDate { year, day }: &DoorDate) -> Result<DayResponse> {
-        let resp = self
-            .http
-            .get(format!("https://adventofcode.com/{year}/day/{day}"))
-            .send()
-            .await?
-            .error_for_status()?
-            .text()
-            .await?;
+        let resp = send_with_backoff(
+            self.http
+                .get(format!("https://adventofcode.com/{year}/day/{day}")),
+        )
+        .await?
+        .error_for_status()?
+        .text()
+        .await?;
         Ok(DayResponse::parse(&resp))
     }
 
+    async fn get_example(&self, &DoorDate { year, day }: &DoorDate) -> Result<Option<String>> {
+        let resp = send_with_backoff(
+            self.http
+                .get(format!("https://adventofcode.com/{year}/day/{day}")),
+        )
+        .await?
+        .error_for_status()?
+        .text()
+        .await?;
+        Ok(extract_example(&resp))
+    }
+
     async fn post_answer(
         &self,
         &DoorDate { year, day }: &DoorDate,
@@ -79,12 +150,12 @@ impl AoCClient for WebClient {
     ) -> Result<AnswerResponse> {
         let part_string = part.to_string();
         let form = HashMap::from([("level", part_string.as_str()), ("answer", guess)]);
-        let resp = self
-            .http
-            .post(format!("https://adventofcode.com/{year}/day/{day}/answer"))
-            .form(&form)
-            .send()
-            .await?;
+        let resp = send_with_backoff(
+            self.http
+                .post(format!("https://adventofcode.com/{year}/day/{day}/answer"))
+                .form(&form),
+        )
+        .await?;
 
         if resp.status() == reqwest::StatusCode::from_u16(302).unwrap() {
             // Redirect in case of authentication failure
@@ -108,3 +179,21 @@ impl AoCClient for WebClient {
         })
     }
 }
+
+impl WebClient {
+    /// Submits `guess`, automatically sleeping out and resubmitting past
+    /// [`AnswerResponse::GuessedTooRecently`] cooldowns rather than leaving
+    /// that foot-gun to the caller. A thin, `WebClient`-specific name for
+    /// [`AoCClient::send_and_confirm_answer`], which does the actual
+    /// waiting and retrying.
+    pub async fn post_answer_waiting(
+        &self,
+        date: &DoorDate,
+        part: Part,
+        guess: &str,
+        max_attempts: u32,
+    ) -> Result<AnswerResponse> {
+        self.send_and_confirm_answer(date, part, guess, max_attempts)
+            .await
+    }
+}
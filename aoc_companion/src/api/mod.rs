@@ -2,7 +2,7 @@ mod client;
 mod response;
 
 pub(crate) use client::AoCClient;
-pub(crate) use response::{AnswerResponse, DayResponse};
+pub(crate) use response::{extract_example, jittered, AnswerResponse, DayResponse};
 
 #[allow(dead_code)]
 pub fn plain_client() -> anyhow::Result<impl AoCClient> {
@@ -11,12 +11,10 @@ pub fn plain_client() -> anyhow::Result<impl AoCClient> {
 
 #[allow(dead_code)]
 pub fn caching_client(empty_cache: bool) -> anyhow::Result<impl AoCClient> {
-    Ok(client::CachingClient::new(
-        client::WebClient::new()?,
-        if empty_cache {
-            client::FilesystemCache::clean_tmp()?
-        } else {
-            client::FilesystemCache::tmp()
-        },
-    ))
+    let underlying_client = client::WebClient::new()?;
+    if empty_cache {
+        client::CachingClient::on_disk_refreshed(underlying_client)
+    } else {
+        client::CachingClient::on_disk(underlying_client)
+    }
 }
@@ -1,22 +1,47 @@
 use itertools::Itertools;
-use scraper::{ElementRef, Html, Selector};
+use scraper::{ElementRef, Html, Node, Selector};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+use std::time::Duration;
+
 #[derive(Debug, Error)]
 pub enum ResponseParsingError {
     #[error("Could not find DOM element for the selector {selector:?}")]
     SelectorDoesNotApply { selector: &'static str },
     #[error("Unexpected response received: {msg}")]
     UnexpectedResponse { msg: String },
+    #[error("Submitted an answer for a level you don't seem to be solving yet")]
+    WrongLevel,
 }
 
 #[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
 pub struct DayResponse {
+    pub day: Option<u32>,
+    pub title: Option<String>,
+    /// The puzzle prose of each unlocked part, rendered as Markdown from its
+    /// `<article>`; see [`render_article_as_markdown`]. One entry per
+    /// unlocked part, so an unsolved part-2-locked day has just one.
+    pub prose: Vec<String>,
     pub first_half: Option<String>,
     pub second_half: Option<String>,
 }
 
+/// Pulls the day number and title out of the puzzle page's
+/// `--- Day N: Title ---` header, which precedes both halves' `<article>`.
+/// `None` when the page carries no such header (e.g. an unauthenticated
+/// response) or it doesn't parse as expected.
+fn day_and_title(document: &Html) -> Option<(u32, String)> {
+    let h2_selector = Selector::parse("article h2").unwrap();
+    let h2 = document.select(&h2_selector).next()?;
+
+    let text: String = h2.text().collect();
+    let (day_part, title_part) = text.trim().trim_matches('-').trim().split_once(':')?;
+    let day = day_part.trim().strip_prefix("Day ")?.parse().ok()?;
+    let title = title_part.trim().to_string();
+    Some((day, title))
+}
+
 fn solution_after_article(article: ElementRef) -> Option<String> {
     let code_selector = Selector::parse("code").unwrap();
     let after_article = article
@@ -34,31 +59,145 @@ fn solution_after_article(article: ElementRef) -> Option<String> {
     }
 }
 
+/// Renders an inline node's text, translating `<code>` to backtick spans and
+/// `<em>` to `*emphasis*`; any other element just contributes its own
+/// rendered children, so e.g. an `<a>` collapses to its link text.
+fn render_inline(el: ElementRef) -> String {
+    let mut rendered = String::new();
+    for child in el.children() {
+        match child.value() {
+            Node::Text(text) => rendered.push_str(text),
+            Node::Element(_) => {
+                let Some(child) = ElementRef::wrap(child) else {
+                    continue;
+                };
+                match child.value().name() {
+                    "code" => rendered.push_str(&format!("`{}`", render_inline(child))),
+                    "em" => rendered.push_str(&format!("*{}*", render_inline(child))),
+                    _ => rendered.push_str(&render_inline(child)),
+                }
+            }
+            _ => {}
+        }
+    }
+    rendered
+}
+
+/// Renders one `<article>`'s puzzle prose as Markdown: `<h2>` becomes a `##`
+/// heading, `<p>` a paragraph, `<pre>` a fenced code block (its contents
+/// taken verbatim, not run through [`render_inline`], since AoC's `<pre>`
+/// blocks are already plain text), and `<ul>`/`<li>` a bullet list. Blocks
+/// are separated by a blank line, matching how Markdown sources are usually
+/// hand-written.
+fn render_article_as_markdown(article: ElementRef) -> String {
+    article
+        .children()
+        .filter_map(ElementRef::wrap)
+        .filter_map(|el| match el.value().name() {
+            "h2" => Some(format!("## {}", render_inline(el).trim())),
+            "p" => Some(render_inline(el).trim().to_string()),
+            "pre" => Some(format!(
+                "```\n{}\n```",
+                el.text().collect::<String>().trim_end()
+            )),
+            "ul" => Some(
+                el.children()
+                    .filter_map(ElementRef::wrap)
+                    .filter(|li| li.value().name() == "li")
+                    .map(|li| format!("- {}", render_inline(li).trim()))
+                    .join("\n"),
+            ),
+            _ => None,
+        })
+        .join("\n\n")
+}
+
 impl DayResponse {
     pub fn parse(response: &str) -> Self {
         let document = Html::parse_document(response);
         let article_selector = Selector::parse("article").unwrap();
-        let articles = document.select(&article_selector);
 
-        let (first_half, second_half) = articles
+        let prose = document
+            .select(&article_selector)
+            .map(render_article_as_markdown)
+            .collect();
+        let (first_half, second_half) = document
+            .select(&article_selector)
             .map(solution_after_article)
             .chain(std::iter::repeat(None))
             .next_tuple()
             .unwrap();
+        let (day, title) = day_and_title(&document).unzip();
 
         Self {
+            day,
+            title,
+            prose,
             first_half,
             second_half,
         }
     }
+
+    /// How many of this day's two parts are already solved, according to
+    /// the scraped puzzle page: `0`, `1`, or `2`. Drives `--skip-solved`'s
+    /// `parts_solved` count, so a part correctly submitted on a previous
+    /// run is never recomputed (let alone resubmitted) on this one.
+    pub fn solved_count(&self) -> usize {
+        self.first_half.is_some() as usize + self.second_half.is_some() as usize
+    }
 }
 
+/// Scrapes the canonical sample input out of a puzzle page: the contents of
+/// the first `<pre><code>` block following a paragraph that introduces an
+/// example (e.g. "For example, ..."). Returns `None` if no such block is
+/// found, which callers should treat as "no example available", not an
+/// error.
+pub fn extract_example(response: &str) -> Option<String> {
+    let document = Html::parse_document(response);
+    let p_selector = Selector::parse("p").unwrap();
+    let code_selector = Selector::parse("code").unwrap();
+
+    document
+        .select(&p_selector)
+        .find(|p| p.text().any(|text| text.contains("For example")))?
+        .next_siblings()
+        .filter_map(ElementRef::wrap)
+        .find(|el| el.value().name() == "pre")?
+        .select(&code_selector)
+        .next()
+        .map(|code| code.inner_html())
+}
+
+/// AoC's standard one-minute submission cooldown: the fixed wait attached to
+/// every incorrect guess ("please wait one minute before trying again",
+/// never reported with finer precision), and the fallback for
+/// [`GuessedTooRecently`] on the rare response whose "You have ... left to
+/// wait" notice doesn't parse.
+///
+/// [`GuessedTooRecently`]: AnswerResponse::GuessedTooRecently
+const DEFAULT_COOLDOWN: Duration = Duration::from_secs(60);
+
 #[derive(Debug, PartialEq)]
 pub enum AnswerResponse {
-    IncorrectTooLow { guess: String },
-    IncorrectTooHigh { guess: String },
-    IncorrectTooManyGuesses { guess: String },
-    GuessedTooRecently,
+    IncorrectTooLow {
+        guess: String,
+        wait: Duration,
+    },
+    IncorrectTooHigh {
+        guess: String,
+        wait: Duration,
+    },
+    IncorrectTooManyGuesses {
+        guess: String,
+        wait: Duration,
+    },
+    /// AoC's rate limit on submissions: "You gave an answer too recently;
+    /// you have to wait ... before trying again." `wait` is the remaining
+    /// cooldown parsed out of that notice by [`parse_wait_duration`], or
+    /// [`DEFAULT_COOLDOWN`] if it doesn't parse.
+    GuessedTooRecently {
+        wait: Duration,
+    },
     Correct,
 }
 
@@ -80,7 +219,11 @@ impl AnswerResponse {
         let contains_text = |needle: &str| paragraph.text().any(|text| text.contains(needle));
 
         if contains_text("You gave an answer too recently") {
-            Ok(Self::GuessedTooRecently)
+            let wait = parse_wait_duration(&paragraph.text().collect::<String>())
+                .unwrap_or(DEFAULT_COOLDOWN);
+            Ok(Self::GuessedTooRecently { wait })
+        } else if contains_text("solving the right level") {
+            Err(ResponseParsingError::WrongLevel)
         } else {
             if contains_text("not the right answer") {
                 let guess = paragraph
@@ -88,13 +231,14 @@ impl AnswerResponse {
                     .next()
                     .ok_or(ResponseParsingError::SelectorDoesNotApply { selector: "code" })?
                     .inner_html();
+                let wait = DEFAULT_COOLDOWN;
 
                 if contains_text("too low") {
-                    Ok(Self::IncorrectTooLow { guess })
+                    Ok(Self::IncorrectTooLow { guess, wait })
                 } else if contains_text("too high") {
-                    Ok(Self::IncorrectTooHigh { guess })
+                    Ok(Self::IncorrectTooHigh { guess, wait })
                 } else {
-                    Ok(Self::IncorrectTooManyGuesses { guess })
+                    Ok(Self::IncorrectTooManyGuesses { guess, wait })
                 }
             } else if contains_text("That's the right answer") {
                 Ok(Self::Correct)
@@ -107,6 +251,40 @@ impl AnswerResponse {
     }
 }
 
+/// Pads a wait duration with up to 10% pseudo-random jitter, derived from
+/// the current time rather than pulling in a `rand` dependency this crate
+/// doesn't otherwise need, so that many callers backing off from the same
+/// cooldown don't all retry in the same instant. The backoff policy for
+/// [`AnswerResponse::GuessedTooRecently`]'s retry loop; see
+/// [`crate::api::client::AoCClient::send_and_confirm_answer`].
+pub fn jittered(wait: Duration) -> Duration {
+    let subsec_nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|since_epoch| since_epoch.subsec_nanos())
+        .unwrap_or(0);
+    let jitter_fraction = subsec_nanos as f64 / u32::MAX as f64 * 0.1;
+    wait + wait.mul_f64(jitter_fraction)
+}
+
+/// Parses AoC's "You have 7m 30s left to wait." cooldown notice out of the
+/// paragraph text, if present. Either component may be absent (e.g. just
+/// "30s"), so each is defaulted to zero rather than required.
+fn parse_wait_duration(text: &str) -> Option<Duration> {
+    let after = text.split("You have ").nth(1)?;
+    let before = after.split(" left to wait").next()?;
+
+    let mut minutes = 0;
+    let mut seconds = 0;
+    for part in before.split_whitespace() {
+        if let Some(digits) = part.strip_suffix('m') {
+            minutes = digits.parse().ok()?;
+        } else if let Some(digits) = part.strip_suffix('s') {
+            seconds = digits.parse().ok()?;
+        }
+    }
+    Some(Duration::from_secs(minutes * 60 + seconds))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -130,6 +308,7 @@ mod tests {
             DayResponse {
                 first_half: None,
                 second_half: None,
+                ..
             }
         );
         assert_matches!(
@@ -137,6 +316,7 @@ mod tests {
             DayResponse {
                 first_half: None,
                 second_half: None,
+                ..
             }
         );
         assert_matches!(
@@ -144,6 +324,7 @@ mod tests {
             DayResponse {
                 first_half: Some(_),
                 second_half: None,
+                ..
             }
         );
         assert_matches!(
@@ -151,6 +332,7 @@ mod tests {
             DayResponse {
                 first_half: Some(_),
                 second_half: Some(_),
+                ..
             }
         );
     }
@@ -162,6 +344,7 @@ mod tests {
             DayResponse {
                 first_half: None,
                 second_half: None,
+                ..
             }
         );
         assert_matches!(
@@ -169,6 +352,7 @@ mod tests {
             DayResponse {
                 first_half: Some(_),
                 second_half: None,
+                ..
             }
         );
         assert_matches!(
@@ -176,46 +360,93 @@ mod tests {
             DayResponse {
                 first_half: Some(_),
                 second_half: Some(_),
+                ..
             }
         );
     }
 
+    #[test]
+    fn solved_count_reflects_which_halves_are_present() {
+        assert_eq!(DayResponse::parse(UNSOLVED_DAY).solved_count(), 0);
+        assert_eq!(DayResponse::parse(PARTIALLY_SOLVED_DAY).solved_count(), 1);
+        assert_eq!(DayResponse::parse(FULLY_SOLVED_DAY).solved_count(), 2);
+    }
+
     #[test]
     fn day_response_determines_solutions() {
         assert_eq!(
-            DayResponse::parse(PARTIALLY_SOLVED_DAY),
-            DayResponse {
-                first_half: Some("893700".to_string()),
-                second_half: None,
-            }
-        );
-        assert_eq!(
-            DayResponse::parse(FULLY_SOLVED_DAY),
-            DayResponse {
-                first_half: Some("392043".to_string()),
-                second_half: Some("1605968119".to_string()),
-            }
+            DayResponse::parse(PARTIALLY_SOLVED_DAY).first_half,
+            Some("893700".to_string())
         );
+        assert_eq!(DayResponse::parse(PARTIALLY_SOLVED_DAY).second_half, None);
+
+        let fully_solved = DayResponse::parse(FULLY_SOLVED_DAY);
+        assert_eq!(fully_solved.first_half, Some("392043".to_string()));
+        assert_eq!(fully_solved.second_half, Some("1605968119".to_string()));
     }
 
     #[test]
     fn day_response_determines_solutions_for_days_with_oneline_input() {
+        let partially_solved = DayResponse::parse(ONELINE_INPUT_PARTIALLY_SOLVED_DAY);
+        assert_eq!(partially_solved.first_half, Some("438".to_string()));
+        assert_eq!(partially_solved.second_half, None);
+
+        let fully_solved = DayResponse::parse(ONELINE_INPUT_FULLY_SOLVED_DAY);
+        assert_eq!(fully_solved.first_half, Some("438".to_string()));
+        assert_eq!(fully_solved.second_half, Some("266330".to_string()));
+    }
+
+    const DAY_WITH_EXAMPLE: &str = include_str!("data/day/with_example.html");
+
+    #[test]
+    fn extracts_example_block_following_for_example_paragraph() {
         assert_eq!(
-            DayResponse::parse(ONELINE_INPUT_PARTIALLY_SOLVED_DAY),
-            DayResponse {
-                first_half: Some("438".to_string()),
-                second_half: None,
-            }
+            extract_example(DAY_WITH_EXAMPLE),
+            Some("1\n2\n3\n".to_string())
         );
+    }
+
+    #[test]
+    fn day_response_determines_day_number_and_title() {
+        let response = DayResponse::parse(DAY_WITH_EXAMPLE);
+        assert_eq!(response.day, Some(1));
+        assert_eq!(response.title, Some("Example Puzzle".to_string()));
+    }
+
+    #[test]
+    fn day_response_renders_each_articles_prose_as_markdown() {
+        let prose = DayResponse::parse(DAY_WITH_EXAMPLE).prose;
         assert_eq!(
-            DayResponse::parse(ONELINE_INPUT_FULLY_SOLVED_DAY),
-            DayResponse {
-                first_half: Some("438".to_string()),
-                second_half: Some("266330".to_string()),
-            }
+            prose,
+            vec!["\
+## --- Day 1: Example Puzzle ---
+
+This is some flavor text describing the puzzle.
+
+For example, suppose you have the following input:
+
+```
+1
+2
+3
+```
+
+Then the answer would be computed some way."
+                .to_string()]
         );
     }
 
+    #[test]
+    fn day_response_has_no_day_or_title_without_a_header() {
+        assert_eq!(DayResponse::parse(UNAUTHENTICATED_DAY).day, None);
+        assert_eq!(DayResponse::parse(UNAUTHENTICATED_DAY).title, None);
+    }
+
+    #[test]
+    fn extracting_example_yields_none_when_no_example_paragraph_is_present() {
+        assert_eq!(extract_example(UNSOLVED_DAY), None);
+    }
+
     const ANSWER_CORRECT_PART_1: &str = include_str!("data/answer/correct_part1.html");
     const ANSWER_CORRECT_PART_2: &str = include_str!("data/answer/correct_part2.html");
     const ANSWER_TOO_LOW: &str = include_str!("data/answer/incorrect_too_low.html");
@@ -266,7 +497,34 @@ mod tests {
         );
         assert_matches!(
             AnswerResponse::parse(&ANSWER_GUESSED_TOO_RECENTLY),
-            Ok(AnswerResponse::GuessedTooRecently)
+            Ok(AnswerResponse::GuessedTooRecently { .. })
+        );
+    }
+
+    #[test]
+    fn answer_response_surfaces_wrong_level_as_an_error() {
+        let response = "\
+<article><p>You don't seem to be solving the right level.  Did you
+already complete it? <a href=\"/2042/day/17\">[Return to Day 17]</a></p></article>";
+        assert_matches!(
+            AnswerResponse::parse(response),
+            Err(ResponseParsingError::WrongLevel)
+        );
+    }
+
+    #[test]
+    fn parses_minutes_and_seconds_out_of_the_cooldown_notice() {
+        assert_eq!(
+            parse_wait_duration("You have 7m 30s left to wait."),
+            Some(std::time::Duration::from_secs(450))
+        );
+        assert_eq!(
+            parse_wait_duration("You have 30s left to wait."),
+            Some(std::time::Duration::from_secs(30))
+        );
+        assert_eq!(
+            parse_wait_duration("You gave an answer too recently."),
+            None
         );
     }
 
@@ -275,20 +533,44 @@ mod tests {
         assert_eq!(
             AnswerResponse::parse(&ANSWER_TOO_LOW).unwrap(),
             AnswerResponse::IncorrectTooLow {
-                guess: "234234".to_string()
+                guess: "234234".to_string(),
+                wait: DEFAULT_COOLDOWN,
             }
         );
         assert_eq!(
             AnswerResponse::parse(&ANSWER_TOO_HIGH).unwrap(),
             AnswerResponse::IncorrectTooHigh {
-                guess: "985639847539754389578395".to_string()
+                guess: "985639847539754389578395".to_string(),
+                wait: DEFAULT_COOLDOWN,
             }
         );
         assert_eq!(
             AnswerResponse::parse(&ANSWER_INCORRECT_AFTER_MANY_GUESSES).unwrap(),
             AnswerResponse::IncorrectTooManyGuesses {
-                guess: "435".to_string()
+                guess: "435".to_string(),
+                wait: DEFAULT_COOLDOWN,
             }
         );
     }
+
+    #[test]
+    fn guessed_too_recently_falls_back_to_the_default_cooldown_when_unparsed() {
+        let response = "\
+<article><p>You gave an answer too recently; you have to wait after giving
+an answer before trying again.</p></article>";
+        assert_eq!(
+            AnswerResponse::parse(response).unwrap(),
+            AnswerResponse::GuessedTooRecently {
+                wait: DEFAULT_COOLDOWN
+            }
+        );
+    }
+
+    #[test]
+    fn jittered_pads_a_duration_by_up_to_ten_percent() {
+        let wait = Duration::from_secs(60);
+        let padded = jittered(wait);
+        assert!(padded >= wait);
+        assert!(padded <= wait + wait.mul_f64(0.1));
+    }
 }
@@ -0,0 +1,56 @@
+use crate::api::DayResponse;
+
+use serde::Serialize;
+
+use std::fmt::{Display, Formatter};
+
+/// A year-at-a-glance summary of which halves are solved, built from one
+/// [`DayResponse`] per day. Unlike [`crate::output::Report`], which only
+/// covers doors a run has just finished solving, this reflects the AoC
+/// server's own bookkeeping (via `AoCClient::get_day`) for every day of the
+/// year, including ones this crate has no door for yet.
+#[derive(Debug, Clone, Serialize)]
+pub struct Calendar {
+    pub year: u32,
+    pub days: Vec<(u32, DayResponse)>,
+}
+
+fn glyph(half: &Option<String>) -> char {
+    if half.is_some() {
+        '⭐'
+    } else {
+        '·'
+    }
+}
+
+impl Display for Calendar {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Advent of Code {}", self.year)?;
+        writeln!(
+            f,
+            "{:<8}{:<32}{:<8}{:<8}",
+            "Day", "Title", "Part 1", "Part 2"
+        )?;
+
+        let (mut solved1, mut solved2) = (0, 0);
+        for (day, response) in &self.days {
+            let day_label = format!("Dec {day}");
+            let title = response.title.as_deref().unwrap_or("???");
+            let glyph1 = glyph(&response.first_half);
+            let glyph2 = glyph(&response.second_half);
+            solved1 += response.first_half.is_some() as usize;
+            solved2 += response.second_half.is_some() as usize;
+            writeln!(f, "{day_label:<8}{title:<32}{glyph1:<8}{glyph2:<8}")?;
+        }
+
+        writeln!(f, "{:-<56}", "")?;
+        writeln!(
+            f,
+            "{:<40}{solved1}/{len}  {solved2}/{len}",
+            "total:",
+            len = self.days.len()
+        )?;
+
+        Ok(())
+    }
+}
@@ -1,10 +1,82 @@
 use clap::value_parser;
 pub(crate) use clap::Parser;
 
+use crate::door::{Part, SolveMode};
+use crate::output::OutputMode;
 use crate::validation::ValidationMode;
 
+/// Which way to render progress while solving. `Auto` (the default) picks
+/// [`OutputMode::Live`] on a TTY and [`OutputMode::Lines`] otherwise, so
+/// piping to a file or CI log doesn't need an explicit flag to avoid a
+/// stream of clear-screen escapes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub(crate) enum OutputFormat {
+    #[default]
+    Auto,
+    Live,
+    Lines,
+}
+
+/// A day to `download`, `scaffold`, or `read`, bypassing the usual
+/// validate-and-submit pipeline entirely; see [`crate::command`]. Unlike the
+/// rest of `Options`, these don't need `--day`/`--year` to disambiguate a
+/// door, since a day one of these is invoked for may not have a door yet.
+#[derive(Debug, Clone, clap::Subcommand)]
+pub(crate) enum Command {
+    #[command(about = "Download and cache a day's puzzle input without solving it")]
+    Download {
+        #[arg(value_parser = value_parser!(u32).range(1..=25))]
+        day: u32,
+    },
+    #[command(
+        about = "Generate a new day module stub, wired to its scraped example, and register it"
+    )]
+    Scaffold {
+        #[arg(value_parser = value_parser!(u32).range(1..=25))]
+        day: u32,
+    },
+    #[command(about = "Print a day's cached (or freshly downloaded) puzzle input")]
+    Read {
+        #[arg(value_parser = value_parser!(u32).range(1..=25))]
+        day: u32,
+    },
+    #[command(
+        about = "Benchmark every registered door against a committed timing baseline",
+        long_about = "Time parse/part1/part2 for every registered door and compare each \
+                       against a committed baseline (bench_baseline.json in the crate's working \
+                       directory): a measurement more than --ratchet-noise-percent slower than \
+                       its baseline fails the run, while one meaningfully faster ratchets the \
+                       baseline down. A door with no prior baseline entry just records one. This \
+                       is a regression gate for solution rewrites, not a precise profiler; use \
+                       --bench for that."
+    )]
+    Bench {
+        #[arg(
+            long,
+            default_value_t = 10.0,
+            help = "Allowed slowdown, in percent, before a door is reported as regressed"
+        )]
+        ratchet_noise_percent: f64,
+    },
+    #[command(about = "Show a year-at-a-glance calendar of which puzzle halves are solved")]
+    Calendar {
+        #[arg(long, help = "Print the calendar as JSON instead of a table")]
+        json: bool,
+    },
+}
+
 #[derive(Debug, Default, Clone, Parser)]
 pub(crate) struct Options {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+    #[arg(
+        long,
+        help = "Verify every registered door's examples instead of solving real input",
+        long_help = "Verify every registered door's examples (see `Solution::examples`) instead \
+                      of solving real input, printing a pass/fail per example and exiting with \
+                      an error if any failed. Ignores --day/--year/--part."
+    )]
+    pub check: bool,
     #[arg(
         short,
         long,
@@ -16,8 +88,44 @@ pub(crate) struct Options {
     pub skip_solved: bool,
     #[arg(short, long, value_parser = value_parser!(u32).range(1..=25), help="Only solve problems for the specified day")]
     pub day: Option<u32>,
+    #[arg(short, long, help = "Only solve problems for the specified year")]
+    pub year: Option<u32>,
+    #[arg(short, long, value_parser = value_parser!(u8).range(1..=2), help = "Run only the specified part of the selected door")]
+    pub part: Option<u8>,
+    #[arg(
+        short = 'x',
+        long,
+        help = "Run the selected door against the scraped example input instead of the real one",
+        long_help = "Run the selected door against the scraped example input instead of the \
+                      real one. Requires --day (and --year, if ambiguous) to select exactly one \
+                      door; neither validates nor submits the computed answer."
+    )]
+    pub example: bool,
     #[arg(short = 'n', long, help = "Do not submit new answers to AoC server")]
     dry_run: bool,
+    #[arg(
+        short,
+        long,
+        help = "Benchmark the selected door with repeated samples instead of solving it once",
+        long_help = "Benchmark the selected door with repeated samples instead of solving it \
+                      once, printing min/mean/median/std-dev once the time budget or sample cap \
+                      is reached. Requires --day (and --year, if ambiguous) to select exactly \
+                      one door; neither validates nor submits the computed answer."
+    )]
+    pub bench: bool,
+    #[arg(
+        long,
+        default_value_t = 3.0,
+        help = "Wall-clock time budget in seconds for --bench's adaptive sampling"
+    )]
+    pub bench_seconds: f64,
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = OutputFormat::Auto,
+        help = "How to render solving progress: a live redrawn table, append-only JSON Lines, or Auto to pick based on whether stdout is a TTY"
+    )]
+    pub output: OutputFormat,
 }
 
 impl Options {
@@ -27,4 +135,42 @@ impl Options {
             Options { .. } => ValidationMode::default(),
         }
     }
+
+    pub(crate) fn part_filter(&self) -> Option<Part> {
+        match self.part {
+            Some(1) => Some(Part::Part1),
+            Some(2) => Some(Part::Part2),
+            _ => None,
+        }
+    }
+
+    /// Whether the CLI requested a single door/part run outside the usual
+    /// multi-door validate-and-submit pipeline.
+    pub(crate) fn quick_run_requested(&self) -> bool {
+        self.example || self.part.is_some() || self.bench
+    }
+
+    /// Resolves `--output`, picking [`OutputMode::Live`]/[`OutputMode::Lines`]
+    /// based on whether stdout is a TTY when `Auto` (the default) was
+    /// requested rather than one of the modes explicitly.
+    pub(crate) fn output_mode(&self) -> OutputMode {
+        use std::io::IsTerminal;
+        match self.output {
+            OutputFormat::Live => OutputMode::Live,
+            OutputFormat::Lines => OutputMode::Lines,
+            OutputFormat::Auto if std::io::stdout().is_terminal() => OutputMode::Live,
+            OutputFormat::Auto => OutputMode::Lines,
+        }
+    }
+
+    pub(crate) fn solve_mode(&self) -> SolveMode {
+        if self.bench {
+            SolveMode::Benchmarked {
+                budget: time::Duration::seconds_f64(self.bench_seconds),
+                max_samples: 1000,
+            }
+        } else {
+            SolveMode::Normal
+        }
+    }
 }
@@ -0,0 +1,387 @@
+use crate::api::AoCClient;
+use crate::calendar::Calendar;
+use crate::cli::Command;
+use crate::door::{DoorDate, DoorEntry, DoorPartResult, SolveMode};
+
+use anyhow::{anyhow, bail, Context, Result};
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Reads the puzzle year a [`Command`] applies to from the `AOC_YEAR` env
+/// var. Unlike a door's year (baked into its `door!` registration), a day
+/// passed to `download`/`scaffold`/`read` may not have a door yet, so there's
+/// nowhere else to get it from.
+fn resolve_year() -> Result<u32> {
+    std::env::var("AOC_YEAR")
+        .context("AOC_YEAR must be set to the puzzle year to download, scaffold, or read a day")?
+        .parse()
+        .context("AOC_YEAR must be a valid year")
+}
+
+/// A new day module implementing the `Solution` trait stub: `parse` just
+/// hands back `Door`, `part1`/`part2` are left as `todo!()` for the
+/// contributor to fill in. `{example}` is substituted with a `mod tests`
+/// block wired to the scraped example, or left empty if none was found.
+const TEMPLATE: &str = "\
+use aoc_companion::prelude::*;
+
+pub(crate) struct Door;
+
+impl<'input> Solution<'input> for Door {
+    fn parse(input: &'input str) -> impl IntoParseResult<Self> {
+        let _ = input;
+        Ok(Door)
+    }
+
+    fn part1(&self) -> impl IntoResult {
+        todo!()
+    }
+
+    fn part2(&self) -> impl IntoResult {
+        todo!()
+    }
+}
+{example}";
+
+pub(crate) async fn run(
+    command: &Command,
+    client: impl AoCClient,
+    doors: &'static [DoorEntry],
+) -> Result<()> {
+    match command {
+        Command::Download { day } => download(client, *day).await,
+        Command::Scaffold { day } => scaffold(client, *day).await,
+        Command::Read { day } => read(client, *day).await,
+        Command::Bench {
+            ratchet_noise_percent,
+        } => bench(client, doors, *ratchet_noise_percent).await,
+        Command::Calendar { json } => calendar(client, *json).await,
+    }
+}
+
+async fn download(client: impl AoCClient, day: u32) -> Result<()> {
+    let date = DoorDate {
+        day,
+        year: resolve_year()?,
+    };
+    // `get_input` on a caching client already refuses to re-download a day
+    // that's cached, so there's nothing more to do here than call it.
+    client.get_input(&date).await?;
+    println!("Cached input for day {day}, {}", date.year);
+    Ok(())
+}
+
+async fn read(client: impl AoCClient, day: u32) -> Result<()> {
+    let date = DoorDate {
+        day,
+        year: resolve_year()?,
+    };
+    print!("{}", client.get_input(&date).await?);
+    Ok(())
+}
+
+/// Renders a string as a Rust string literal suitable for splicing into the
+/// scaffolded `EXAMPLE_INPUT` constant: a plain `r"..."` raw string, falling
+/// back to the `r#"..."#` form on the rare example that itself contains a
+/// `"`, the way hand-written doors already do (e.g. `aoc_2022`'s day 4).
+fn raw_string_literal(s: &str) -> String {
+    if s.contains('"') {
+        format!("r#\"{s}\"#")
+    } else {
+        format!("r\"{s}\"")
+    }
+}
+
+/// The `#[cfg(test)] mod tests` block to append to a scaffolded day,
+/// wiring up the scraped example as `EXAMPLE_INPUT` for the contributor to
+/// build their first test against. Left empty when the puzzle page carried
+/// no recognizable example.
+fn example_test_module(example: Option<&str>) -> String {
+    let Some(example) = example else {
+        return String::new();
+    };
+    format!(
+        "\n#[cfg(test)]\nmod tests {{\n    use super::*;\n\n    const EXAMPLE_INPUT: &str = {};\n}}\n",
+        raw_string_literal(example.trim_end())
+    )
+}
+
+/// Inserts `mod dayNN;` among `main.rs`'s existing `mod dayXX;` declarations
+/// (kept in day order) and turns on its `door!` registration: if the
+/// not-yet-implemented placeholder `// door!(year-12-dd ~> dayNN),` is
+/// already present (see the convention in e.g. `aoc_2024/src/main.rs`), it's
+/// uncommented in place; otherwise a fresh line is inserted among the other
+/// `door!` entries, in day order.
+fn register_door(main_rs: &str, year: u32, day: u32) -> String {
+    let mod_line = format!("mod day{day:02};");
+    let placeholder = format!("// door!({year}-12-{day:02} ~> day{day:02}),");
+    let door_line = format!("door!({year}-12-{day:02} ~> day{day:02}),");
+
+    let mut lines: Vec<String> = main_rs.lines().map(str::to_owned).collect();
+
+    if !lines.iter().any(|line| line.trim() == mod_line) {
+        let insert_at = lines
+            .iter()
+            .position(|line| {
+                line.trim()
+                    .strip_prefix("mod day")
+                    .and_then(|rest| rest.strip_suffix(';'))
+                    .and_then(|n| n.parse::<u32>().ok())
+                    .is_some_and(|n| n > day)
+            })
+            .unwrap_or_else(|| {
+                lines
+                    .iter()
+                    .rposition(|line| line.trim().starts_with("mod day"))
+                    .map_or(0, |i| i + 1)
+            });
+        lines.insert(insert_at, mod_line);
+    }
+
+    if let Some(placeholder_at) = lines.iter().position(|line| line.trim() == placeholder) {
+        let line = &lines[placeholder_at];
+        let indent = line[..line.len() - line.trim_start().len()].to_owned();
+        lines[placeholder_at] = format!("{indent}{door_line}");
+    } else if !lines.iter().any(|line| line.trim() == door_line) {
+        let insert_at = lines
+            .iter()
+            .position(|line| {
+                let trimmed = line.trim().trim_start_matches("// ");
+                trimmed
+                    .strip_prefix("door!(")
+                    .and_then(|rest| rest.split("-12-").nth(1))
+                    .and_then(|rest| rest.split(' ').next())
+                    .and_then(|n| n.parse::<u32>().ok())
+                    .is_some_and(|n| n > day)
+            })
+            .unwrap_or_else(|| {
+                lines
+                    .iter()
+                    .position(|line| line.contains("append \"doors\" here"))
+                    .unwrap_or(lines.len())
+            });
+        let indent = "        ";
+        lines.insert(insert_at, format!("{indent}{door_line}"));
+    }
+
+    let mut result = lines.join("\n");
+    result.push('\n');
+    result
+}
+
+async fn scaffold(client: impl AoCClient, day: u32) -> Result<()> {
+    let year = resolve_year()?;
+    let date = DoorDate { day, year };
+    let path = Path::new("src").join(format!("day{day:02}.rs"));
+    if path.exists() {
+        bail!("{} already exists; not overwriting it", path.display());
+    }
+
+    // Warm the cache the same way `download` does, and pull the example (if
+    // any) to pre-populate the stub's test module.
+    client.get_input(&date).await?;
+    let example = client.get_example(&date).await?;
+
+    let contents = TEMPLATE.replace("{example}", &example_test_module(example.as_deref()));
+    std::fs::write(&path, contents)
+        .with_context(|| format!("failed to write {}", path.display()))?;
+    println!("Wrote {}", path.display());
+
+    let main_rs_path = Path::new("src").join("main.rs");
+    let main_rs = std::fs::read_to_string(&main_rs_path)
+        .with_context(|| format!("failed to read {}", main_rs_path.display()))?;
+    std::fs::write(&main_rs_path, register_door(&main_rs, year, day))
+        .with_context(|| format!("failed to write {}", main_rs_path.display()))?;
+    println!(
+        "Registered mod day{day:02} and door!({year}-12-{day:02} ~> day{day:02}) in {}",
+        main_rs_path.display()
+    );
+
+    if example.is_none() {
+        println!("No example found on the puzzle page; EXAMPLE_INPUT was left for you to fill in.");
+    }
+    Ok(())
+}
+
+/// Where a crate's bench subcommand keeps its committed timing baseline;
+/// relative to the crate's own directory, the same way `scaffold` writes
+/// `src/dayXX.rs` relative to it.
+const BASELINE_PATH: &str = "bench_baseline.json";
+
+/// How long `bench` samples each part for, trading off run time against a
+/// less noisy reading; mirrors `--bench`'s own default.
+const BENCH_BUDGET: time::Duration = time::Duration::seconds(3);
+const BENCH_MAX_SAMPLES: usize = 1000;
+
+/// One phase of a door's solve, timed and ratcheted independently since a
+/// slowdown in parsing doesn't imply one in either part (and vice versa).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Phase {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl std::fmt::Display for Phase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Phase::Parse => "parse",
+            Phase::Part1 => "part1",
+            Phase::Part2 => "part2",
+        })
+    }
+}
+
+/// A committed baseline is a flat map from `"{year}-12-{day}:{phase}"` to a
+/// measured duration in seconds, rather than anything keyed on `DoorDate`
+/// directly, so the file serializes as a plain, sorted, diffable JSON
+/// object that reviewers can read a ratchet out of at a glance.
+type Baseline = BTreeMap<String, f64>;
+
+fn baseline_key(date: &DoorDate, phase: Phase) -> String {
+    format!("{}-12-{:02}:{phase}", date.year, date.day)
+}
+
+fn load_baseline() -> Result<Baseline> {
+    match std::fs::read_to_string(BASELINE_PATH) {
+        Ok(contents) => serde_json::from_str(&contents)
+            .with_context(|| format!("failed to parse {BASELINE_PATH}")),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Baseline::new()),
+        Err(err) => Err(err).with_context(|| format!("failed to read {BASELINE_PATH}")),
+    }
+}
+
+fn save_baseline(baseline: &Baseline) -> Result<()> {
+    let json = serde_json::to_string_pretty(baseline)?;
+    std::fs::write(BASELINE_PATH, format!("{json}\n"))
+        .with_context(|| format!("failed to write {BASELINE_PATH}"))
+}
+
+fn part_duration(result: &Result<DoorPartResult>) -> Option<time::Duration> {
+    match result {
+        Ok(DoorPartResult::Computed { time, .. }) => Some(*time),
+        Ok(DoorPartResult::Benchmarked { median, .. }) => Some(*median),
+        Ok(DoorPartResult::Skipped) | Err(_) => None,
+    }
+}
+
+fn format_seconds(duration: time::Duration) -> String {
+    format!("{:.2}ms", duration.as_seconds_f64() * 1000.0)
+}
+
+/// Times `parse`/`part1`/`part2` for every registered door and ratchets
+/// each against `BASELINE_PATH`: a measurement more than `noise_percent`
+/// slower than its baseline is reported as a regression (and fails the
+/// run), one meaningfully faster ratchets the baseline down, and a door
+/// with no prior entry just records one. See [`Command::Bench`].
+async fn bench(
+    client: impl AoCClient,
+    doors: &'static [DoorEntry],
+    noise_percent: f64,
+) -> Result<()> {
+    let mut baseline = load_baseline()?;
+    let mut regressions = Vec::new();
+    let mut year_totals: BTreeMap<u32, time::Duration> = BTreeMap::new();
+
+    for DoorEntry(date, door_fn, _) in doors {
+        let input = client.get_input(date).await?;
+        let result = door_fn(
+            input.trim_end(),
+            0,
+            SolveMode::Benchmarked {
+                budget: BENCH_BUDGET,
+                max_samples: BENCH_MAX_SAMPLES,
+            },
+        );
+
+        for (phase, measured) in [
+            (Phase::Parse, Some(result.parse)),
+            (Phase::Part1, part_duration(&result.part1)),
+            (Phase::Part2, part_duration(&result.part2)),
+        ] {
+            let Some(measured) = measured else { continue };
+            *year_totals.entry(date.year).or_insert(time::Duration::ZERO) += measured;
+
+            let key = baseline_key(date, phase);
+            let Some(&baseline_secs) = baseline.get(&key) else {
+                println!(
+                    "Dec {:2}, {} - {phase}: {} (no baseline yet; recorded)",
+                    date.day,
+                    date.year,
+                    format_seconds(measured)
+                );
+                baseline.insert(key, measured.as_seconds_f64());
+                continue;
+            };
+            let baseline_duration = time::Duration::seconds_f64(baseline_secs);
+            let change_percent = (measured.as_seconds_f64() / baseline_secs - 1.0) * 100.0;
+
+            if measured.as_seconds_f64() > baseline_secs * (1.0 + noise_percent / 100.0) {
+                regressions.push(format!(
+                    "Dec {:2}, {} - {phase}: {} regressed to {} ({change_percent:+.1}%)",
+                    date.day,
+                    date.year,
+                    format_seconds(baseline_duration),
+                    format_seconds(measured)
+                ));
+            } else if measured.as_seconds_f64() < baseline_secs * (1.0 - noise_percent / 100.0) {
+                println!(
+                    "Dec {:2}, {} - {phase}: ratcheted {} down to {} ({change_percent:+.1}%)",
+                    date.day,
+                    date.year,
+                    format_seconds(baseline_duration),
+                    format_seconds(measured)
+                );
+                baseline.insert(key, measured.as_seconds_f64());
+            } else {
+                println!(
+                    "Dec {:2}, {} - {phase}: {} (within noise of baseline {})",
+                    date.day,
+                    date.year,
+                    format_seconds(measured),
+                    format_seconds(baseline_duration)
+                );
+            }
+        }
+    }
+
+    save_baseline(&baseline)?;
+
+    println!();
+    for (year, total) in year_totals {
+        println!("{year} total: {}", format_seconds(total));
+    }
+
+    if regressions.is_empty() {
+        Ok(())
+    } else {
+        for line in &regressions {
+            eprintln!("{line}");
+        }
+        Err(anyhow!(
+            "{} door part(s) regressed beyond the {noise_percent}% noise tolerance",
+            regressions.len()
+        ))
+    }
+}
+
+/// Fetches every day of the year via [`AoCClient::get_day`] and prints the
+/// resulting [`Calendar`], as a table or (with `json`) the machine-readable
+/// form via its `Serialize` derive. See [`Command::Calendar`].
+async fn calendar(client: impl AoCClient, json: bool) -> Result<()> {
+    let year = resolve_year()?;
+    let mut days = Vec::with_capacity(25);
+    for day in 1..=25 {
+        let date = DoorDate { day, year };
+        days.push((day, client.get_day(&date).await?));
+    }
+    let calendar = Calendar { year, days };
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&calendar)?);
+    } else {
+        print!("{calendar}");
+    }
+    Ok(())
+}
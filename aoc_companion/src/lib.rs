@@ -1,5 +1,7 @@
 mod api;
+mod calendar;
 mod cli;
+mod command;
 pub mod door;
 mod output;
 mod runtime;
@@ -7,9 +9,16 @@ mod validation;
 
 pub mod prelude {
     pub use crate::door;
-    pub use crate::door::{DoorDate, DoorEntry, Solution};
+    pub use crate::door::{
+        DoorDate, DoorEntry, DoorError, Example, ParseInput, Part1, Part2, Puzzle, Solution,
+        Submissible,
+    };
     pub use crate::runtime::aoc_main;
     pub use anyhow::Result;
     pub use thiserror::Error;
+
+    pub use aoc_utils::linalg::Vector;
+    pub use aoc_utils::wrap::WrappingIndex;
+    pub use num_traits::{Euclid, Num, NumCast};
 }
 pub use prelude::*;
@@ -3,6 +3,7 @@ use crate::door::*;
 use crate::validation::*;
 
 use anyhow::Result;
+use serde::Serialize;
 use termion::screen::IntoAlternateScreen;
 use tokio::sync::mpsc;
 
@@ -11,6 +12,94 @@ use std::fmt::{Display, Formatter};
 use std::io::Write;
 use std::num::NonZero;
 
+/// How [`process_progress_updates`] renders the doors it's tracking: a
+/// [`Table`] redrawn from scratch on every update (fine for an interactive
+/// terminal, garbage once piped to a file or CI log), or one JSON object per
+/// event, append-only, for scripted/batch runs and machine consumption.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputMode {
+    Live,
+    Lines,
+}
+
+/// One line of [`OutputMode::Lines`] output: a single door/part's outcome,
+/// serialized with just enough of [`ValidationResult`]/[`PartValidation`] to
+/// be machine-readable, since neither carries `Serialize` itself (their
+/// `Result`/`AnswerResponse` fields don't either).
+#[derive(Debug, Serialize)]
+struct ProgressLine {
+    day: u32,
+    year: u32,
+    status: &'static str,
+    part: Option<u8>,
+    answer: Option<String>,
+    validity: Option<String>,
+    time_secs: Option<f64>,
+}
+
+fn progress_status(progress: &Progress) -> &'static str {
+    match progress {
+        Progress::Pending => "pending",
+        Progress::DownloadingInput => "downloading_input",
+        Progress::DownloadingDay => "downloading_day",
+        Progress::ComputingAnswer => "computing_answer",
+        Progress::ValidatingAnswer => "validating_answer",
+        Progress::Complete(_) => "complete",
+    }
+}
+
+fn part_answer(validation: &Result<PartValidation>) -> Option<String> {
+    match validation {
+        Ok(PartValidation {
+            guess:
+                DoorPartResult::Computed { answer, .. } | DoorPartResult::Benchmarked { answer, .. },
+            ..
+        }) => Some(answer.clone()),
+        Ok(PartValidation {
+            guess: DoorPartResult::Skipped,
+            validity: PartValidity::Skipped { correct },
+        }) => Some(correct.clone()),
+        _ => None,
+    }
+}
+
+fn part_validity_label(validation: &Result<PartValidation>) -> Option<String> {
+    match validation {
+        Ok(pv) => Some(format!("{:?}", pv.validity)),
+        Err(err) => Some(err.to_string()),
+    }
+}
+
+/// The [`ProgressLine`]s for one `DoorProgress`: a single status line while
+/// the door is still in flight, or one line per part once it's
+/// [`Progress::Complete`], each carrying that part's answer/validity/timing.
+fn progress_lines(DoorProgress(date, progress): &DoorProgress) -> Vec<ProgressLine> {
+    let DoorDate { day, year } = *date;
+    match progress {
+        Progress::Complete(result) => [(1u8, &result.part1), (2u8, &result.part2)]
+            .into_iter()
+            .map(|(part, validation)| ProgressLine {
+                day,
+                year,
+                status: "complete",
+                part: Some(part),
+                answer: part_answer(validation),
+                validity: part_validity_label(validation),
+                time_secs: Some(part_time(validation).as_seconds_f64()),
+            })
+            .collect(),
+        _ => vec![ProgressLine {
+            day,
+            year,
+            status: progress_status(progress),
+            part: None,
+            answer: None,
+            validity: None,
+            time_secs: None,
+        }],
+    }
+}
+
 #[derive(Debug)]
 pub enum Progress {
     Pending,
@@ -26,6 +115,28 @@ pub struct DoorProgress(pub DoorDate, pub Progress);
 
 pub struct Table(BTreeMap<DoorDate, Progress>);
 
+fn format_time(time: &time::Duration) -> String {
+    format!("{time:.0$}", significant_decimals(time, 3))
+}
+
+fn format_benchmark(
+    answer: &str,
+    samples: &[time::Duration],
+    mean: &time::Duration,
+    median: &time::Duration,
+    min: &time::Duration,
+    std_dev: &time::Duration,
+) -> String {
+    format!(
+        "{answer} ({} samples; mean {}, median {}, min {}, σ {})",
+        samples.len(),
+        format_time(mean),
+        format_time(median),
+        format_time(min),
+        format_time(std_dev)
+    )
+}
+
 fn write_answer(
     f: &mut Formatter,
     DoorDate { day, year }: &DoorDate,
@@ -35,42 +146,48 @@ fn write_answer(
     use AnswerResponse::*;
     use PartValidity::*;
     let (message, emoji) = match validation {
-        Ok(PartValidation {
-            guess: DoorPartResult::Computed { answer, time },
-            validity: Consistent,
-        }) => (
-            format!("{answer} ({time:.0$})", significant_decimals(time, 3)),
-            '⭐',
+        Ok(
+            pv @ PartValidation {
+                guess: DoorPartResult::Computed { answer, time },
+                validity: Consistent,
+            },
+        ) => (format!("{answer} ({})", format_time(time)), pv.validity.glyph()),
+        Ok(
+            pv @ PartValidation {
+                guess: DoorPartResult::Computed { answer, .. },
+                validity: DisparateAnswer { correct },
+            },
+        ) => (
+            format!("{answer}, but correct answer was {correct}"),
+            pv.validity.glyph(),
         ),
-        Ok(PartValidation {
-            guess: DoorPartResult::Computed { answer, .. },
-            validity: DisparateAnswer { correct },
-        }) => (format!("{answer}, but correct answer was {correct}"), '💢'),
-        Ok(PartValidation {
-            guess: DoorPartResult::Computed { answer, time },
-            validity: GuessSubmitted(Correct),
-        }) => (
-            format!("{answer} ({time:.0$})", significant_decimals(time, 3)),
-            '🌟',
-        ),
-        Ok(PartValidation {
-            guess: DoorPartResult::Computed { answer, .. },
-            validity: GuessSubmitted(GuessedTooRecently),
-        }) => (
+        Ok(
+            pv @ PartValidation {
+                guess: DoorPartResult::Computed { answer, time },
+                validity: GuessSubmitted(Correct),
+            },
+        ) => (format!("{answer} ({})", format_time(time)), pv.validity.glyph()),
+        Ok(
+            pv @ PartValidation {
+                guess: DoorPartResult::Computed { answer, .. },
+                validity: GuessSubmitted(GuessedTooRecently { .. }),
+            },
+        ) => (
             format!("{answer} (unable to submit; guessed too recently)"),
-            '🕑',
-        ),
-        Ok(PartValidation {
-            guess: DoorPartResult::Computed { answer, time },
-            validity: Unknown,
-        }) => (
-            format!("{answer} ({time:.0$})", significant_decimals(time, 3)),
-            '🤷',
+            pv.validity.glyph(),
         ),
-        Ok(PartValidation {
-            guess: DoorPartResult::Skipped,
-            validity: Skipped { correct },
-        }) => (format!("{correct} (skipped)"), '⭐'),
+        Ok(
+            pv @ PartValidation {
+                guess: DoorPartResult::Computed { answer, time },
+                validity: Unknown,
+            },
+        ) => (format!("{answer} ({})", format_time(time)), pv.validity.glyph()),
+        Ok(
+            pv @ PartValidation {
+                guess: DoorPartResult::Skipped,
+                validity: Skipped { correct },
+            },
+        ) => (format!("{correct} (skipped)"), pv.validity.glyph()),
         Ok(PartValidation {
             guess: DoorPartResult::Skipped,
             validity: _,
@@ -79,25 +196,62 @@ fn write_answer(
             guess: DoorPartResult::Computed { .. },
             validity: Skipped { .. },
         }) => panic!("Inconsistent PartValidation state"),
-        Ok(PartValidation {
-            guess: DoorPartResult::Computed { answer, .. },
-            validity: GuessSubmitted(IncorrectTooLow),
-            ..
-        }) => (format!("{answer} is too low"), '🔻'),
-        Ok(PartValidation {
-            guess: DoorPartResult::Computed { answer, .. },
-            validity: GuessSubmitted(IncorrectTooHigh),
-            ..
-        }) => (format!("{answer} is too high"), '🔺'),
-        Ok(PartValidation {
-            guess: DoorPartResult::Computed { answer, .. },
-            validity: GuessSubmitted(IncorrectTooManyGuesses),
-            ..
-        }) => (format!("{answer} is incorrect; too many guesses"), '❌'),
-        Ok(PartValidation {
-            guess: DoorPartResult::Computed { answer, .. },
-            validity: GuessSubmitted(IncorrectOther),
-        }) => (format!("{answer} is incorrect"), '❌'),
+        Ok(
+            pv @ PartValidation {
+                guess:
+                    DoorPartResult::Benchmarked {
+                        answer,
+                        samples,
+                        mean,
+                        median,
+                        min,
+                        std_dev,
+                    },
+                ..
+            },
+        ) => (
+            format_benchmark(answer, samples, mean, median, min, std_dev),
+            pv.validity.glyph(),
+        ),
+        Ok(
+            pv @ PartValidation {
+                guess: DoorPartResult::Computed { answer, .. },
+                validity: GuessSubmitted(IncorrectTooLow { .. }),
+            },
+        ) => (format!("{answer} is too low"), pv.validity.glyph()),
+        Ok(
+            pv @ PartValidation {
+                guess: DoorPartResult::Computed { answer, .. },
+                validity: GuessSubmitted(IncorrectTooHigh { .. }),
+            },
+        ) => (format!("{answer} is too high"), pv.validity.glyph()),
+        Ok(
+            pv @ PartValidation {
+                guess: DoorPartResult::Computed { answer, .. },
+                validity: GuessSubmitted(IncorrectTooManyGuesses { .. }),
+            },
+        ) => (
+            format!("{answer} is incorrect; too many guesses"),
+            pv.validity.glyph(),
+        ),
+        Ok(
+            pv @ PartValidation {
+                guess: DoorPartResult::Computed { answer, .. },
+                validity: PartValidity::Deferred,
+            },
+        ) => (format!("{answer} (submission deferred)"), pv.validity.glyph()),
+        Ok(
+            pv @ PartValidation {
+                guess: DoorPartResult::Computed { answer, .. },
+                validity: PartValidity::StillCoolingDown { next_retry_in },
+            },
+        ) => (
+            format!(
+                "{answer} (still on cooldown; next retry would be in {})",
+                format_time(&next_retry_in)
+            ),
+            pv.validity.glyph(),
+        ),
         Err(err) => (err.to_string(), '⛔'),
     };
 
@@ -130,6 +284,136 @@ impl Display for Table {
     }
 }
 
+impl Table {
+    /// Collects the `ValidationResult`s of doors that have finished, in
+    /// ascending date order, discarding any that are still in progress.
+    pub fn into_results(self) -> Vec<ValidationResult> {
+        self.0
+            .into_values()
+            .filter_map(|progress| match progress {
+                Progress::Complete(result) => Some(result),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+fn part_time(validation: &Result<PartValidation>) -> time::Duration {
+    match validation {
+        Ok(PartValidation {
+            guess: DoorPartResult::Computed { time, .. },
+            ..
+        }) => *time,
+        _ => time::Duration::ZERO,
+    }
+}
+
+fn part_glyph(validation: &Result<PartValidation>) -> char {
+    match validation {
+        Ok(pv) => pv.validity.glyph(),
+        Err(_) => '⛔',
+    }
+}
+
+/// A year-at-a-glance summary of a batch of `ValidationResult`s, rendered as
+/// a plain-text table with one row per day and a totals row for solve time.
+pub struct Report<'a>(pub &'a [ValidationResult]);
+
+impl Display for Report<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.0.is_empty() {
+            return writeln!(f, "No solutions implemented for days matching filter");
+        }
+
+        writeln!(
+            f,
+            "{:<8}{:<32}{:<16}{:<16}{:<16}",
+            "Day", "Title", "Parse", "Part 1", "Part 2"
+        )?;
+
+        let mut total_time = time::Duration::ZERO;
+        for result in self.0 {
+            let day = result.date.day;
+            let title = result.title.as_deref().unwrap_or("???");
+            let parse_time = format_time(&result.parse);
+            let glyph1 = part_glyph(&result.part1);
+            let glyph2 = part_glyph(&result.part2);
+            let time1 = format_time(&part_time(&result.part1));
+            let time2 = format_time(&part_time(&result.part2));
+            total_time += result.parse + part_time(&result.part1) + part_time(&result.part2);
+            writeln!(
+                f,
+                "Dec {day:<4}{title:<32}{parse_time:<16}{glyph1} {time1:<13}{glyph2} {time2:<13}"
+            )?;
+        }
+
+        writeln!(f, "{:-<88}", "")?;
+        writeln!(f, "{:<72}total: {}", "", format_time(&total_time))?;
+
+        Ok(())
+    }
+}
+
+/// Prints the outcome of a single door/part run requested via the CLI's
+/// `--example`/`--part` quick-run mode, bypassing the validate-and-submit
+/// pipeline entirely (there is nothing to validate a scraped example
+/// against, and a quick run never submits).
+pub(crate) fn print_quick_run(date: &DoorDate, result: &DoorResult, part_filter: Option<Part>) {
+    println!(
+        "Dec {:2}, {} - Parse: {}",
+        date.day,
+        date.year,
+        format_time(&result.parse)
+    );
+    for (part, part_result) in [(Part::Part1, &result.part1), (Part::Part2, &result.part2)] {
+        if part_filter.is_some_and(|filter| filter != part) {
+            continue;
+        }
+        let message = match part_result {
+            Ok(DoorPartResult::Computed { answer, time }) => {
+                format!("{answer} ({})", format_time(time))
+            }
+            Ok(DoorPartResult::Benchmarked {
+                answer,
+                samples,
+                mean,
+                median,
+                min,
+                std_dev,
+            }) => format_benchmark(answer, samples, mean, median, min, std_dev),
+            Ok(DoorPartResult::Skipped) => "(skipped)".to_string(),
+            Err(err) => err.to_string(),
+        };
+        println!("Dec {:2}, {} - Part {part}: {message}", date.day, date.year);
+    }
+}
+
+fn format_example_outcome(outcome: &ExampleOutcome) -> (String, char) {
+    match outcome {
+        ExampleOutcome::NotChecked => (String::new(), ' '),
+        ExampleOutcome::Passed => ("ok".to_string(), '✔'),
+        ExampleOutcome::Failed { expected, actual } => {
+            (format!("{actual}, expected {expected}"), '✘')
+        }
+        ExampleOutcome::Errored(err) => (err.clone(), '⛔'),
+    }
+}
+
+/// Prints the outcome of one `Example`'s parts as verified via `--check`,
+/// skipping parts the example carried no expected answer for.
+pub(crate) fn print_check_report(date: &DoorDate, index: usize, report: &ExampleReport) {
+    for (part, outcome) in [(Part::Part1, &report.part1), (Part::Part2, &report.part2)] {
+        if matches!(outcome, ExampleOutcome::NotChecked) {
+            continue;
+        }
+        let (message, glyph) = format_example_outcome(outcome);
+        println!(
+            "Dec {:2}, {} - Example {index} Part {part}: {message} {glyph}",
+            date.day, date.year
+        );
+    }
+}
+
 pub fn prefilled_screen() -> Result<impl std::io::Write + Send> {
     let mut screen = std::io::stdout().into_alternate_screen()?;
 
@@ -145,6 +429,7 @@ pub async fn process_progress_updates<S>(
     mut rx: mpsc::Receiver<DoorProgress>,
     mut screen: S,
     doors: impl IntoIterator<Item = &'static DoorEntry>,
+    mode: OutputMode,
 ) -> Table
 where
     S: std::io::Write + Send,
@@ -156,11 +441,21 @@ where
             .collect(),
     );
 
-    while let Some(DoorProgress(date, progress)) = rx.recv().await {
-        table.0.insert(date, progress);
-
-        write!(screen, "{}{table}", termion::clear::All).unwrap();
-        screen.flush().unwrap();
+    while let Some(update) = rx.recv().await {
+        match mode {
+            OutputMode::Live => {
+                table.0.insert(update.0, update.1);
+                write!(screen, "{}{table}", termion::clear::All).unwrap();
+                screen.flush().unwrap();
+            }
+            OutputMode::Lines => {
+                for line in progress_lines(&update) {
+                    writeln!(screen, "{}", serde_json::to_string(&line).unwrap()).unwrap();
+                }
+                screen.flush().unwrap();
+                table.0.insert(update.0, update.1);
+            }
+        }
     }
 
     table
@@ -15,18 +15,39 @@ use tokio::sync::mpsc;
 pub async fn aoc_main(doors: &'static [DoorEntry]) -> Result<()> {
     let opts = Options::parse();
 
+    if let Some(command) = &opts.command {
+        return crate::command::run(command, caching_client(opts.empty_cache)?, doors).await;
+    }
+
+    if opts.check {
+        return run_check(doors);
+    }
+
     let filter_day = opts.day;
-    let doors = doors
-        .iter()
-        .filter(move |entry| filter_day.is_none_or(|filter_day| entry.0.day == filter_day));
+    let filter_year = opts.year;
+    let doors = doors.iter().filter(move |entry| {
+        filter_day.is_none_or(|filter_day| entry.0.day == filter_day)
+            && filter_year.is_none_or(|filter_year| entry.0.year == filter_year)
+    });
 
     let client = caching_client(opts.empty_cache)?;
 
+    if opts.quick_run_requested() {
+        return run_single_door(doors, client, &opts).await;
+    }
+
+    let output_mode = opts.output_mode();
+    let screen: Box<dyn std::io::Write + Send> = match output_mode {
+        OutputMode::Live => Box::new(prefilled_screen()?),
+        OutputMode::Lines => Box::new(std::io::stdout()),
+    };
+
     let (tx, rx) = mpsc::channel(25);
     let updater_task = tokio::task::spawn(process_progress_updates(
         rx,
-        prefilled_screen()?,
+        screen,
         doors.clone(),
+        output_mode,
     ));
 
     let result = tokio::task::LocalSet::new()
@@ -35,15 +56,69 @@ pub async fn aoc_main(doors: &'static [DoorEntry]) -> Result<()> {
 
     let final_table = updater_task.await?;
     if result.is_ok() {
-        print!("{final_table}");
+        print!("{}", Report(&final_table.into_results()));
     }
     std::io::stdout().lock().flush()?;
 
     result
 }
 
+/// Verifies every registered door's `Solution::examples` (via `--check`),
+/// printing a pass/fail per example instead of solving real input. Ignores
+/// `--day`/`--year`/`--part`, since the point is to check every door at once.
+fn run_check(doors: &'static [DoorEntry]) -> Result<()> {
+    let mut any_failed = false;
+    for DoorEntry(date, _, verify_fn) in doors {
+        for (index, report) in verify_fn().iter().enumerate() {
+            print_check_report(date, index, report);
+            any_failed |= report.is_failure();
+        }
+    }
+
+    if any_failed {
+        Err(anyhow!("one or more examples failed verification"))
+    } else {
+        Ok(())
+    }
+}
+
+/// Runs exactly one door/part, selected via `--day`/`--year`/`--part`, against
+/// either the real cached input or (`--example`) the scraped sample, printing
+/// the outcome directly instead of going through the progress UI and
+/// validate-and-submit pipeline.
+async fn run_single_door(
+    mut doors: impl Iterator<Item = &'static DoorEntry>,
+    client: impl AoCClient,
+    opts: &Options,
+) -> Result<()> {
+    let DoorEntry(date, door_fn, ..) = doors
+        .next()
+        .ok_or_else(|| anyhow!("no door matches the given --day/--year filter"))?;
+    if doors.next().is_some() {
+        return Err(anyhow!(
+            "more than one door matches the given --day/--year filter; narrow it down with --day and --year"
+        ));
+    }
+
+    let input = if opts.example {
+        client.get_example(date).await?.ok_or_else(|| {
+            anyhow!(
+                "no scraped example available for day {}, {}",
+                date.day,
+                date.year
+            )
+        })?
+    } else {
+        client.get_input(date).await?
+    };
+
+    let result = door_fn(input.trim_end(), 0, opts.solve_mode());
+    print_quick_run(date, &result, opts.part_filter());
+    Ok(())
+}
+
 async fn handle_door(
-    DoorEntry(date, door_fn): &'static DoorEntry,
+    DoorEntry(date, door_fn, ..): &'static DoorEntry,
     client: Arc<impl AoCClient>,
     progress_sender: mpsc::Sender<DoorProgress>,
     opts: Options,
@@ -66,12 +141,14 @@ async fn handle_door(
     };
     let (answer_tx, answer_rx) = tokio::sync::oneshot::channel();
     rayon::spawn(move || {
-        let result =
-            std::panic::catch_unwind(|| door_fn(input.trim_end(), parts_considered_solved))
-                .unwrap_or_else(|e| DoorResult {
-                    part1: Err(panic_as_anyhow_error(e.as_ref())),
-                    part2: Err(panic_as_anyhow_error(e.as_ref())),
-                });
+        let result = std::panic::catch_unwind(|| {
+            door_fn(input.trim_end(), parts_considered_solved, SolveMode::Normal)
+        })
+        .unwrap_or_else(|e| DoorResult {
+            parse: time::Duration::ZERO,
+            part1: Err(panic_as_anyhow_error(e.as_ref())),
+            part2: Err(panic_as_anyhow_error(e.as_ref())),
+        });
         answer_tx.send(result).unwrap()
     });
     let answer = answer_rx.await?;
@@ -80,6 +157,7 @@ async fn handle_door(
         .await?;
     validate_answer(
         date,
+        None,
         answer,
         &status,
         opts.validation_mode(),
@@ -16,24 +16,97 @@ pub enum PartValidity {
     DisparateAnswer { correct: String },
     GuessSubmitted(AnswerResponse),
     Unknown,
+    /// Submission was held back; the computed answer is available from a
+    /// [`pending_submissions`] pass over the enclosing [`ValidationResult`].
+    Deferred,
+    /// Every retry attempt still found the server on cooldown. Carries the
+    /// backoff delay the next attempt would have used, as a hint for a
+    /// caller deciding whether to wait it out.
+    StillCoolingDown { next_retry_in: time::Duration },
+}
+
+impl PartValidity {
+    /// A single-character glyph summarizing this validity, for use in
+    /// compact tabular reports where a full sentence would not fit.
+    pub fn glyph(&self) -> char {
+        use AnswerResponse::*;
+        use PartValidity::*;
+        match self {
+            Consistent | Skipped { .. } => '⭐',
+            DisparateAnswer { .. } => '💢',
+            GuessSubmitted(Correct) => '🌟',
+            GuessSubmitted(GuessedTooRecently { .. }) => '🕑',
+            GuessSubmitted(IncorrectTooLow { .. }) => '🔻',
+            GuessSubmitted(IncorrectTooHigh { .. }) => '🔺',
+            GuessSubmitted(IncorrectTooManyGuesses { .. }) => '❌',
+            Unknown => '🤷',
+            Deferred => '⏸',
+            StillCoolingDown { .. } => '⏳',
+        }
+    }
 }
 
 #[derive(Debug)]
 pub struct ValidationResult {
     pub date: DoorDate,
+    pub title: Option<String>,
+    pub parse: time::Duration,
     pub part1: Result<PartValidation>,
     pub part2: Result<PartValidation>,
 }
 
+/// A computed-but-unsubmitted answer set aside by [`ValidationMode::Deferred`],
+/// for a later pass to flush once the server's cooldown has expired.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingSubmission {
+    pub date: DoorDate,
+    pub part: Part,
+    pub answer: String,
+}
+
+/// Collects the answers that were held back by [`ValidationMode::Deferred`]
+/// while validating `result`.
+pub fn pending_submissions(result: &ValidationResult) -> Vec<PendingSubmission> {
+    use Part::*;
+    [(Part1, &result.part1), (Part2, &result.part2)]
+        .into_iter()
+        .filter_map(|(part, validation)| match validation {
+            Ok(PartValidation {
+                guess: DoorPartResult::Computed { answer, .. },
+                validity: PartValidity::Deferred,
+            }) => Some(PendingSubmission {
+                date: result.date,
+                part,
+                answer: answer.clone(),
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
 #[derive(Clone, Copy, Debug, Default)]
 pub enum ValidationMode {
     #[default]
     Normal,
     DryRun,
+    /// Like [`ValidationMode::Normal`], but instead of submitting right
+    /// away, retries a rate-limited guess with an exponentially increasing
+    /// delay (capped) for up to `max_attempts` tries before giving up.
+    Retry {
+        max_attempts: u32,
+        base_delay: std::time::Duration,
+    },
+    /// Computes and records answers without submitting any of them; use
+    /// [`pending_submissions`] to retrieve the held-back guesses for a
+    /// later flush pass.
+    Deferred,
 }
 
+const MAX_RETRY_BACKOFF: std::time::Duration = std::time::Duration::from_secs(15 * 60);
+
 pub async fn validate_answer(
     date: &DoorDate,
+    title: Option<String>,
     answer: DoorResult,
     submitted: &DayResponse,
     mode: ValidationMode,
@@ -42,6 +115,8 @@ pub async fn validate_answer(
     use Part::*;
     Ok(ValidationResult {
         date: *date,
+        title,
+        parse: answer.parse,
         part1: validate_part(
             date,
             Part1,
@@ -74,16 +149,29 @@ async fn validate_part(
     use PartValidity::*;
 
     Ok(match guess {
-        Ok(DoorPartResult::Computed { ref answer, .. }) => {
+        Ok(
+            DoorPartResult::Computed { ref answer, .. }
+            | DoorPartResult::Benchmarked { ref answer, .. },
+        ) => {
             let validity = match (submitted, mode) {
                 (Some(correct), _) if correct == answer => Consistent,
                 (Some(correct), _) => DisparateAnswer {
                     correct: correct.to_owned(),
                 },
                 (None, ValidationMode::DryRun) => Unknown,
+                (None, ValidationMode::Deferred) => Deferred,
                 (None, ValidationMode::Normal) => {
                     GuessSubmitted(client.post_answer(date, part, answer).await?)
                 }
+                (
+                    None,
+                    ValidationMode::Retry {
+                        max_attempts,
+                        base_delay,
+                    },
+                ) => {
+                    submit_with_retry(date, part, answer, max_attempts, base_delay, client).await?
+                }
             };
 
             Ok(PartValidation {
@@ -101,11 +189,40 @@ async fn validate_part(
     })
 }
 
+/// Submits `answer`, retrying with exponential backoff (capped at
+/// [`MAX_RETRY_BACKOFF`]) while the server reports the guess as too recent,
+/// up to `max_attempts` tries.
+async fn submit_with_retry(
+    date: &DoorDate,
+    part: Part,
+    answer: &str,
+    max_attempts: u32,
+    base_delay: std::time::Duration,
+    client: &(impl AoCClient + Send + Sync),
+) -> Result<PartValidity> {
+    let mut delay = base_delay;
+    for attempt in 0..max_attempts.max(1) {
+        match client.post_answer(date, part, answer).await? {
+            AnswerResponse::GuessedTooRecently { .. } if attempt + 1 < max_attempts => {
+                tokio::time::sleep(delay).await;
+                delay = (delay * 2).min(MAX_RETRY_BACKOFF);
+            }
+            AnswerResponse::GuessedTooRecently { .. } => {
+                let next_retry_in = delay.try_into().expect("duration should be positive");
+                return Ok(PartValidity::StillCoolingDown { next_retry_in });
+            }
+            response => return Ok(PartValidity::GuessSubmitted(response)),
+        }
+    }
+    unreachable!("loop always returns on its final iteration")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use anyhow::anyhow;
     use assert_matches::assert_matches;
+    use itertools::assert_equal;
 
     use AnswerResponse::*;
     use PartValidity::*;
@@ -135,6 +252,10 @@ mod tests {
             panic!("operation not supported by fake")
         }
 
+        async fn get_example(&self, _: &DoorDate) -> Result<Option<String>> {
+            panic!("operation not supported by fake")
+        }
+
         async fn post_answer(
             &self,
             date: &DoorDate,
@@ -144,8 +265,10 @@ mod tests {
             use AnswerResponse::*;
             use Part::*;
 
+            let wait = std::time::Duration::from_secs(60);
+
             if self.on_cooldown {
-                return Ok(GuessedTooRecently);
+                return Ok(GuessedTooRecently { wait });
             }
 
             if date.day > 25 {
@@ -156,10 +279,19 @@ mod tests {
             let guess_num: i32 = guess.parse().unwrap();
             match (part, guess_num) {
                 (Part1, 42) => Ok(Correct),
-                (Part1, x) if x < 42 => Ok(IncorrectTooLow),
-                (Part1, _) => Ok(IncorrectTooHigh),
+                (Part1, x) if x < 42 => Ok(IncorrectTooLow {
+                    guess: guess.to_owned(),
+                    wait,
+                }),
+                (Part1, _) => Ok(IncorrectTooHigh {
+                    guess: guess.to_owned(),
+                    wait,
+                }),
                 (Part2, 123) => Ok(Correct),
-                (Part2, _) => Ok(IncorrectTooManyGuesses),
+                (Part2, _) => Ok(IncorrectTooManyGuesses {
+                    guess: guess.to_owned(),
+                    wait,
+                }),
             }
         }
     }
@@ -170,13 +302,16 @@ mod tests {
         assert_matches!(
             validate_answer(
                 &TEST_DAY,
+                None,
                 DoorResult {
+                    parse: time::Duration::ZERO,
                     part1: make_part_result("42"),
                     part2: make_part_result("123")
                 },
                 &DayResponse {
                     first_half: Some("42".to_string()),
-                    second_half: Some("123".to_string())
+                    second_half: Some("123".to_string()),
+                    ..Default::default()
                 },
                 ValidationMode::Normal,
                 &client
@@ -191,7 +326,8 @@ mod tests {
                 part2: Ok(PartValidation {
                     validity: Consistent,
                     ..
-                })
+                }),
+                ..
             })
         );
     }
@@ -202,13 +338,16 @@ mod tests {
         assert_matches!(
             validate_answer(
                 &TEST_DAY,
+                None,
                 DoorResult {
+                    parse: time::Duration::ZERO,
                     part1: make_part_result("43"),
                     part2: make_part_result("123")
                 },
                 &DayResponse {
                     first_half: Some("42".to_string()),
-                    second_half: Some("123".to_string())
+                    second_half: Some("123".to_string()),
+                    ..Default::default()
                 },
                 ValidationMode::Normal,
                 &client
@@ -217,7 +356,8 @@ mod tests {
             Ok(ValidationResult {
                 date: TEST_DAY,
                 part1: Ok(PartValidation { validity: DisparateAnswer { correct }, ..}),
-                part2: Ok(PartValidation { validity: Consistent, ..})
+                part2: Ok(PartValidation { validity: Consistent, ..}),
+                ..
             }) if &correct == "42"
         );
     }
@@ -228,13 +368,16 @@ mod tests {
         assert_matches!(
             validate_answer(
                 &TEST_DAY,
+                None,
                 DoorResult {
+                    parse: time::Duration::ZERO,
                     part1: make_part_result("43"),
                     part2: make_part_result("123")
                 },
                 &DayResponse {
                     first_half: Some("42".to_string()),
-                    second_half: Some("123".to_string())
+                    second_half: Some("123".to_string()),
+                    ..Default::default()
                 },
                 ValidationMode::DryRun,
                 &client
@@ -243,7 +386,8 @@ mod tests {
             Ok(ValidationResult {
                 date: TEST_DAY,
                 part1: Ok(PartValidation { validity: DisparateAnswer { correct }, ..}),
-                part2: Ok(PartValidation { validity: Consistent, ..})
+                part2: Ok(PartValidation { validity: Consistent, ..}),
+                ..
             }) if &correct == "42"
         );
     }
@@ -254,13 +398,16 @@ mod tests {
         assert_matches!(
             validate_answer(
                 &TEST_DAY,
+                None,
                 DoorResult {
+                    parse: time::Duration::ZERO,
                     part1: make_part_result("42"),
                     part2: make_part_result("123")
                 },
                 &DayResponse {
                     first_half: None,
-                    second_half: None
+                    second_half: None,
+                    ..Default::default()
                 },
                 ValidationMode::Normal,
                 &client
@@ -275,19 +422,23 @@ mod tests {
                 part2: Ok(PartValidation {
                     validity: GuessSubmitted(Correct),
                     ..
-                })
+                }),
+                ..
             })
         );
         assert_matches!(
             validate_answer(
                 &TEST_DAY,
+                None,
                 DoorResult {
+                    parse: time::Duration::ZERO,
                     part1: make_part_result("42"),
                     part2: make_part_result("123")
                 },
                 &DayResponse {
                     first_half: Some("42".to_string()),
-                    second_half: None
+                    second_half: None,
+                    ..Default::default()
                 },
                 ValidationMode::Normal,
                 &client
@@ -302,7 +453,8 @@ mod tests {
                 part2: Ok(PartValidation {
                     validity: GuessSubmitted(Correct),
                     ..
-                })
+                }),
+                ..
             })
         );
     }
@@ -313,13 +465,16 @@ mod tests {
         assert_matches!(
             validate_answer(
                 &TEST_DAY,
+                None,
                 DoorResult {
+                    parse: time::Duration::ZERO,
                     part1: make_part_result("42"),
                     part2: make_part_result("123")
                 },
                 &DayResponse {
                     first_half: None,
-                    second_half: None
+                    second_half: None,
+                    ..Default::default()
                 },
                 ValidationMode::DryRun,
                 &client
@@ -334,7 +489,8 @@ mod tests {
                 part2: Ok(PartValidation {
                     validity: Unknown,
                     ..
-                })
+                }),
+                ..
             })
         );
     }
@@ -345,13 +501,16 @@ mod tests {
         assert_matches!(
             validate_answer(
                 &TEST_DAY,
+                None,
                 DoorResult {
+                    parse: time::Duration::ZERO,
                     part1: make_part_result("43"),
                     part2: make_part_result("122")
                 },
                 &DayResponse {
                     first_half: None,
-                    second_half: None
+                    second_half: None,
+                    ..Default::default()
                 },
                 ValidationMode::Normal,
                 &client
@@ -366,7 +525,8 @@ mod tests {
                 part2: Ok(PartValidation {
                     validity: GuessSubmitted(IncorrectTooManyGuesses { .. }),
                     ..
-                })
+                }),
+                ..
             })
         );
     }
@@ -377,13 +537,16 @@ mod tests {
         assert_matches!(
             validate_answer(
                 &TEST_DAY,
+                None,
                 DoorResult {
+                    parse: time::Duration::ZERO,
                     part1: make_part_result("42"),
                     part2: make_part_result("123")
                 },
                 &DayResponse {
                     first_half: Some("42".to_string()),
-                    second_half: None
+                    second_half: None,
+                    ..Default::default()
                 },
                 ValidationMode::Normal,
                 &client
@@ -396,9 +559,10 @@ mod tests {
                     ..
                 }),
                 part2: Ok(PartValidation {
-                    validity: GuessSubmitted(GuessedTooRecently),
+                    validity: GuessSubmitted(GuessedTooRecently { .. }),
                     ..
-                })
+                }),
+                ..
             })
         );
     }
@@ -412,13 +576,16 @@ mod tests {
                     day: 27,
                     year: 2042
                 },
+                None,
                 DoorResult {
+                    parse: time::Duration::ZERO,
                     part1: make_part_result("42"),
                     part2: make_part_result("123")
                 },
                 &DayResponse {
                     first_half: Some("42".to_string()),
-                    second_half: None
+                    second_half: None,
+                    ..Default::default()
                 },
                 ValidationMode::Normal,
                 &client
@@ -427,4 +594,135 @@ mod tests {
             Err(_)
         );
     }
+
+    #[tokio::test]
+    async fn retry_mode_submits_immediately_when_not_on_cooldown() {
+        let client = FakeValidationClient { on_cooldown: false };
+        assert_matches!(
+            validate_answer(
+                &TEST_DAY,
+                None,
+                DoorResult {
+                    parse: time::Duration::ZERO,
+                    part1: make_part_result("42"),
+                    part2: make_part_result("123")
+                },
+                &DayResponse {
+                    first_half: None,
+                    second_half: None,
+                    ..Default::default()
+                },
+                ValidationMode::Retry {
+                    max_attempts: 3,
+                    base_delay: std::time::Duration::from_millis(1),
+                },
+                &client
+            )
+            .await,
+            Ok(ValidationResult {
+                date: TEST_DAY,
+                part1: Ok(PartValidation {
+                    validity: GuessSubmitted(Correct),
+                    ..
+                }),
+                part2: Ok(PartValidation {
+                    validity: GuessSubmitted(Correct),
+                    ..
+                }),
+                ..
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn retry_mode_gives_up_after_max_attempts_and_reports_remaining_cooldown() {
+        let client = FakeValidationClient { on_cooldown: true };
+        assert_matches!(
+            validate_answer(
+                &TEST_DAY,
+                None,
+                DoorResult {
+                    parse: time::Duration::ZERO,
+                    part1: make_part_result("42"),
+                    part2: make_part_result("123")
+                },
+                &DayResponse {
+                    first_half: None,
+                    second_half: None,
+                    ..Default::default()
+                },
+                ValidationMode::Retry {
+                    max_attempts: 3,
+                    base_delay: std::time::Duration::from_millis(1),
+                },
+                &client
+            )
+            .await,
+            Ok(ValidationResult {
+                date: TEST_DAY,
+                part1: Ok(PartValidation {
+                    validity: StillCoolingDown { .. },
+                    ..
+                }),
+                part2: Ok(PartValidation {
+                    validity: StillCoolingDown { .. },
+                    ..
+                }),
+                ..
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn deferred_mode_holds_back_submission_and_is_collected_as_pending() {
+        let client = FakeValidationClient { on_cooldown: true };
+        let result = validate_answer(
+            &TEST_DAY,
+            None,
+            DoorResult {
+                parse: time::Duration::ZERO,
+                part1: make_part_result("42"),
+                part2: make_part_result("123"),
+            },
+            &DayResponse {
+                first_half: None,
+                second_half: None,
+                ..Default::default()
+            },
+            ValidationMode::Deferred,
+            &client,
+        )
+        .await
+        .unwrap();
+
+        assert_matches!(
+            result,
+            ValidationResult {
+                part1: Ok(PartValidation {
+                    validity: Deferred,
+                    ..
+                }),
+                part2: Ok(PartValidation {
+                    validity: Deferred,
+                    ..
+                }),
+                ..
+            }
+        );
+        assert_equal(
+            pending_submissions(&result),
+            [
+                PendingSubmission {
+                    date: TEST_DAY,
+                    part: Part::Part1,
+                    answer: "42".to_string(),
+                },
+                PendingSubmission {
+                    date: TEST_DAY,
+                    part: Part::Part2,
+                    answer: "123".to_string(),
+                },
+            ],
+        );
+    }
 }
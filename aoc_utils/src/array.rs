@@ -1,134 +1,158 @@
 use std::mem::MaybeUninit;
 
+/// Owns a `[MaybeUninit<T>; N]` plus a count of how many leading slots have
+/// actually been written, so that a panic (or an early `?` return) midway
+/// through filling it still drops exactly the elements that were
+/// initialized, instead of leaking them. Callers write into the guard one
+/// element at a time via [`push`](InitGuard::push) and only
+/// [`mem::forget`](std::mem::forget) it — via [`into_array`](InitGuard::into_array)
+/// or [`into_vec`](InitGuard::into_vec) — once they're done with it.
+struct InitGuard<T, const N: usize> {
+    array: [MaybeUninit<T>; N],
+    initialized: usize,
+}
+
+impl<T, const N: usize> InitGuard<T, N> {
+    fn new() -> Self {
+        InitGuard {
+            array: std::array::from_fn(|_| MaybeUninit::uninit()),
+            initialized: 0,
+        }
+    }
+
+    /// Writes `value` into the next free slot. Panics if all `N` slots are
+    /// already initialized.
+    fn push(&mut self, value: T) {
+        self.array[self.initialized] = MaybeUninit::new(value);
+        self.initialized += 1;
+    }
+
+    /// All `N` slots must already be initialized. Hands the array to the
+    /// caller and forgets the guard, so its `Drop` impl doesn't run and
+    /// immediately drop what the caller just took ownership of.
+    fn into_array(self) -> [T; N] {
+        debug_assert_eq!(self.initialized, N);
+        let array = unsafe { std::ptr::read(&self.array) };
+        std::mem::forget(self);
+        array.map(|slot| unsafe { slot.assume_init() })
+    }
+
+    /// Hands back the first `n` initialized elements as a `Vec` (`n` may be
+    /// less than `N`), forgetting the guard so those elements aren't
+    /// dropped a second time; any slots past `n` were never initialized,
+    /// so there's nothing there to leak.
+    fn into_vec(self) -> Vec<T> {
+        let n = self.initialized;
+        let array = unsafe { std::ptr::read(&self.array) };
+        std::mem::forget(self);
+        array
+            .into_iter()
+            .take(n)
+            .map(|slot| unsafe { slot.assume_init() })
+            .collect()
+    }
+}
+
+impl<T, const N: usize> Drop for InitGuard<T, N> {
+    fn drop(&mut self) {
+        let initialized = unsafe {
+            std::slice::from_raw_parts_mut(self.array.as_mut_ptr().cast::<T>(), self.initialized)
+        };
+        unsafe { std::ptr::drop_in_place(initialized) };
+    }
+}
+
 pub fn try_from_fn<T, E, const N: usize>(
     mut f: impl FnMut(usize) -> Result<T, E>,
 ) -> Result<[T; N], E> {
-    let mut array: [_; N] = std::array::from_fn(|_| MaybeUninit::uninit());
-    for (i, elem) in array.iter_mut().enumerate() {
-        *elem = MaybeUninit::new(f(i)?);
+    let mut guard = InitGuard::new();
+    for i in 0..N {
+        guard.push(f(i)?);
     }
-    Ok(array.map(|x| unsafe { x.assume_init() }))
+    Ok(guard.into_array())
 }
 
 pub fn from_iter<T, const N: usize>(iter: impl IntoIterator<Item = T>) -> Result<[T; N], Vec<T>> {
-    let mut n = 0;
+    let mut guard = InitGuard::new();
     let mut fused = iter.into_iter().fuse();
-    let array: [_; N] = std::array::from_fn(|i| {
-        if let Some(elem) = fused.next() {
-            n = i + 1;
-            MaybeUninit::new(elem)
-        } else {
-            MaybeUninit::uninit()
-        }
-    });
+    for _ in 0..N {
+        let Some(elem) = fused.next() else { break };
+        guard.push(elem);
+    }
 
-    if n != N {
-        // received fewer elements from the iterator than N
-        Err(array
-            .into_iter()
-            .take(n)
-            .map(|x| unsafe { x.assume_init() })
-            .collect())
+    if guard.initialized == N {
+        Ok(guard.into_array())
     } else {
-        Ok(array.map(|x| unsafe { x.assume_init() }))
+        // received fewer elements from the iterator than N
+        Err(guard.into_vec())
     }
 }
 
 pub fn from_iter_exact<T, const N: usize>(
     iter: impl IntoIterator<Item = T>,
 ) -> Result<[T; N], Vec<T>> {
-    let mut n = 0;
+    let mut guard = InitGuard::new();
     let mut fused = iter.into_iter().fuse();
-    let array: [_; N] = std::array::from_fn(|i| {
-        if let Some(elem) = fused.next() {
-            n = i + 1;
-            MaybeUninit::new(elem)
-        } else {
-            MaybeUninit::uninit()
-        }
-    });
+    for _ in 0..N {
+        let Some(elem) = fused.next() else { break };
+        guard.push(elem);
+    }
 
-    if n != N {
+    if guard.initialized != N {
         // received fewer elements from the iterator than N
-        Err(array
-            .into_iter()
-            .take(n)
-            .map(|x| unsafe { x.assume_init() })
-            .collect())
+        Err(guard.into_vec())
     } else if let Some(extra) = fused.next() {
         // received at least one more element than expected
-        let mut vec: Vec<T> = array
-            .into_iter()
-            .map(|x| unsafe { x.assume_init() })
-            .collect();
+        let mut vec = guard.into_vec();
         vec.push(extra);
         vec.extend(fused);
         Err(vec)
     } else {
-        Ok(array.map(|x| unsafe { x.assume_init() }))
+        Ok(guard.into_array())
     }
 }
 
 pub fn try_from_iter<T, E, const N: usize>(
     iter: impl IntoIterator<Item = Result<T, E>>,
 ) -> Result<Result<[T; N], Vec<T>>, E> {
-    let mut n = 0;
+    let mut guard = InitGuard::new();
     let mut fused = iter.into_iter().fuse();
-    let array: [_; N] = try_from_fn(|i| {
-        if let Some(elem) = fused.next() {
-            n = i + 1;
-            Ok(MaybeUninit::new(elem?))
-        } else {
-            Ok(MaybeUninit::uninit())
-        }
-    })?;
+    for _ in 0..N {
+        let Some(elem) = fused.next() else { break };
+        guard.push(elem?);
+    }
 
-    Ok(if n != N {
-        // received fewer elements from the iterator than N
-        Err(array
-            .into_iter()
-            .take(n)
-            .map(|x| unsafe { x.assume_init() })
-            .collect())
+    Ok(if guard.initialized == N {
+        Ok(guard.into_array())
     } else {
-        Ok(array.map(|x| unsafe { x.assume_init() }))
+        // received fewer elements from the iterator than N
+        Err(guard.into_vec())
     })
 }
 
 pub fn try_from_iter_exact<T, E, const N: usize>(
     iter: impl IntoIterator<Item = Result<T, E>>,
 ) -> Result<Result<[T; N], Vec<T>>, E> {
-    let mut n = 0;
+    let mut guard = InitGuard::new();
     let mut fused = iter.into_iter().fuse();
-    let array: [_; N] = try_from_fn(|i| {
-        if let Some(elem) = fused.next() {
-            n = i + 1;
-            Ok(MaybeUninit::new(elem?))
-        } else {
-            Ok(MaybeUninit::uninit())
-        }
-    })?;
+    for _ in 0..N {
+        let Some(elem) = fused.next() else { break };
+        guard.push(elem?);
+    }
 
-    Ok(if n != N {
+    Ok(if guard.initialized != N {
         // received fewer elements from the iterator than N
-        Err(array
-            .into_iter()
-            .take(n)
-            .map(|x| unsafe { x.assume_init() })
-            .collect())
+        Err(guard.into_vec())
     } else if let Some(extra) = fused.next() {
         // received at least one more element than expected
-        let mut vec: Vec<T> = array
-            .into_iter()
-            .map(|x| unsafe { x.assume_init() })
-            .collect();
+        let mut vec = guard.into_vec();
         vec.push(extra?);
         for extra in fused {
             vec.push(extra?);
         }
         Err(vec)
     } else {
-        Ok(array.map(|x| unsafe { x.assume_init() }))
+        Ok(guard.into_array())
     })
 }
 
@@ -154,7 +178,11 @@ mod tests {
         let mut n = 0;
         std::iter::from_fn(move || {
             n += 1;
-            if n > 1 { Some(n) } else { None }
+            if n > 1 {
+                Some(n)
+            } else {
+                None
+            }
         })
     }
 
@@ -331,4 +359,31 @@ mod tests {
         })
         .unwrap_err();
     }
+
+    #[test]
+    fn guard_drops_only_the_initialized_prefix_on_early_return() {
+        use std::rc::Rc;
+
+        let drop_count = Rc::new(std::cell::Cell::new(0));
+
+        struct DropCounter(Rc<std::cell::Cell<usize>>);
+        impl Drop for DropCounter {
+            fn drop(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+
+        let mut n = 0;
+        let result: Result<[DropCounter; 4], ()> = try_from_fn(|_| {
+            n += 1;
+            if n <= 2 {
+                Ok(DropCounter(drop_count.clone()))
+            } else {
+                Err(())
+            }
+        });
+
+        assert_matches!(result, Err(()));
+        assert_eq!(drop_count.get(), 2);
+    }
 }
@@ -0,0 +1,305 @@
+use itertools::Itertools;
+
+use crate::linalg::Vector;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Dimension {
+    pub offset: i32,
+    pub size: u32,
+}
+
+impl Dimension {
+    pub fn new(size: u32) -> Self {
+        Dimension { offset: 0, size }
+    }
+
+    fn index(&self, coord: i32) -> Option<usize> {
+        let shifted = coord.checked_add(self.offset)?;
+        (shifted >= 0 && (shifted as u32) < self.size).then_some(shifted as usize)
+    }
+
+    fn extend(&mut self) {
+        self.offset += 1;
+        self.size += 2;
+    }
+}
+
+/// Which cells count as a given cell's neighbors when
+/// [`CellularAutomaton::step`] gathers neighbor states: either the full
+/// `3^D - 1` Moore neighborhood, or just the `2 * D` axis-aligned von
+/// Neumann neighbors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Neighborhood {
+    Moore,
+    VonNeumann,
+}
+
+impl Neighborhood {
+    fn offsets<const D: usize>(self) -> Vec<Vector<i32, D>> {
+        match self {
+            Neighborhood::Moore => std::iter::repeat([-1i32, 0, 1])
+                .take(D)
+                .multi_cartesian_product()
+                .filter(|offset| offset.iter().any(|&x| x != 0))
+                .map(|offset| Vector(crate::array::from_iter_exact(offset).unwrap()))
+                .collect(),
+            Neighborhood::VonNeumann => (0..D)
+                .flat_map(|axis| [-1i32, 1].into_iter().map(move |d| (axis, d)))
+                .map(|(axis, d)| {
+                    let mut offset = [0i32; D];
+                    offset[axis] = d;
+                    Vector(offset)
+                })
+                .collect(),
+        }
+    }
+}
+
+/// A cellular automaton over a dense `Vec<State>` grid, addressed through a
+/// per-axis [`Dimension`] (`offset`, `size`) descriptor, so that coordinates
+/// map to `offset + pos`; looking up a cell outside the stored box yields
+/// `default` rather than panicking. By default [`CellularAutomaton::step`]
+/// first pads every axis by one cell on each side, letting the live region
+/// grow outward generation over generation, as Conway-cube-style problems
+/// need; call [`CellularAutomaton::without_expansion`] to pin the bounds for
+/// grids (like Day 11's seating layout) that are fixed up front.
+pub struct CellularAutomaton<const D: usize, State> {
+    dimensions: [Dimension; D],
+    cells: Vec<State>,
+    default: State,
+    neighborhood: Neighborhood,
+    expand: bool,
+}
+
+impl<const D: usize, State: Clone> CellularAutomaton<D, State> {
+    pub fn new(dimensions: [Dimension; D], default: State, neighborhood: Neighborhood) -> Self {
+        let len = dimensions.iter().map(|d| d.size as usize).product();
+        CellularAutomaton {
+            dimensions,
+            cells: vec![default.clone(); len],
+            default,
+            neighborhood,
+            expand: true,
+        }
+    }
+
+    /// Builds a grid sized tightly around `cells`' bounding box, for the
+    /// common case of starting out from a sparse set of non-default
+    /// positions (e.g. the initially-live cubes of a Conway cube).
+    pub fn from_cells(
+        cells: impl IntoIterator<Item = (Vector<i32, D>, State)>,
+        default: State,
+        neighborhood: Neighborhood,
+    ) -> Self {
+        let cells: Vec<_> = cells.into_iter().collect();
+        let dimensions = std::array::from_fn(|i| {
+            let (min, max) = cells
+                .iter()
+                .map(|(p, _)| p[i])
+                .minmax()
+                .into_option()
+                .unwrap_or((0, 0));
+            Dimension {
+                offset: -min,
+                size: (max - min + 1) as u32,
+            }
+        });
+        let mut automaton = Self::new(dimensions, default, neighborhood);
+        for (p, state) in cells {
+            automaton.set(p, state);
+        }
+        automaton
+    }
+
+    /// Opts a fixed-bounds grid out of the one-cell-per-side growth
+    /// [`CellularAutomaton::step`] otherwise applies before every
+    /// generation.
+    pub fn without_expansion(mut self) -> Self {
+        self.expand = false;
+        self
+    }
+
+    pub fn set(&mut self, p: Vector<i32, D>, state: State) {
+        if let Some(idx) = Self::flat_index(&self.dimensions, p) {
+            self.cells[idx] = state;
+        }
+    }
+
+    pub fn get(&self, p: Vector<i32, D>) -> &State {
+        Self::flat_index(&self.dimensions, p)
+            .map(|idx| &self.cells[idx])
+            .unwrap_or(&self.default)
+    }
+
+    pub fn count(&self, matches: impl Fn(&State) -> bool) -> usize {
+        self.cells.iter().filter(|s| matches(s)).count()
+    }
+
+    fn flat_index(dimensions: &[Dimension; D], p: Vector<i32, D>) -> Option<usize> {
+        dimensions
+            .iter()
+            .zip(p)
+            .try_fold(0usize, |acc, (dim, coord)| {
+                Some(acc * dim.size as usize + dim.index(coord)?)
+            })
+    }
+
+    fn coord_of(dimensions: &[Dimension; D], mut flat: usize) -> Vector<i32, D> {
+        let mut coords = [0i32; D];
+        for (i, dim) in dimensions.iter().enumerate().rev() {
+            let size = dim.size as usize;
+            coords[i] = (flat % size) as i32 - dim.offset;
+            flat /= size;
+        }
+        Vector(coords)
+    }
+
+    fn next_dimensions(&self) -> [Dimension; D] {
+        let mut dimensions = self.dimensions;
+        if self.expand {
+            for dim in &mut dimensions {
+                dim.extend();
+            }
+        }
+        dimensions
+    }
+
+    /// Advances the automaton by one generation: every cell becomes
+    /// `transition(current_state, neighbor_states)`, where the neighbor
+    /// states are gathered according to `self`'s [`Neighborhood`]. If
+    /// expansion hasn't been opted out of via
+    /// [`CellularAutomaton::without_expansion`], every axis is first padded
+    /// by one cell on each side, so cells can be born at the grid's edge.
+    pub fn step(&self, transition: impl Fn(&State, &[State]) -> State) -> Self {
+        let dimensions = self.next_dimensions();
+        let offsets = self.neighborhood.offsets::<D>();
+        let len = dimensions.iter().map(|d| d.size as usize).product();
+        let cells = (0..len)
+            .map(|flat| {
+                let coord = Self::coord_of(&dimensions, flat);
+                let neighbors: Vec<State> = offsets
+                    .iter()
+                    .map(|&offset| self.get(coord + offset).clone())
+                    .collect();
+                transition(self.get(coord), &neighbors)
+            })
+            .collect();
+        CellularAutomaton {
+            dimensions,
+            cells,
+            default: self.default.clone(),
+            neighborhood: self.neighborhood,
+            expand: self.expand,
+        }
+    }
+
+    pub fn run(self, generations: usize, transition: impl Fn(&State, &[State]) -> State) -> Self {
+        (0..generations).fold(self, |automaton, _| automaton.step(&transition))
+    }
+}
+
+impl<const D: usize, State: Clone + PartialEq> CellularAutomaton<D, State> {
+    /// Repeatedly [`CellularAutomaton::step`]s until a generation exactly
+    /// reproduces the previous one (same cells and bounds).
+    pub fn run_to_fixed_point(self, transition: impl Fn(&State, &[State]) -> State) -> Self {
+        let mut current = self;
+        loop {
+            let next = current.step(&transition);
+            if next.cells == current.cells && next.dimensions == current.dimensions {
+                return next;
+            }
+            current = next;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn conway_transition(current: &bool, neighbors: &[bool]) -> bool {
+        let live = neighbors.iter().filter(|&&n| n).count();
+        if *current {
+            live == 2 || live == 3
+        } else {
+            live == 3
+        }
+    }
+
+    fn conway_automaton<const D: usize>(
+        live: impl IntoIterator<Item = Vector<i32, D>>,
+    ) -> CellularAutomaton<D, bool> {
+        CellularAutomaton::from_cells(
+            live.into_iter().map(|p| (p, true)),
+            false,
+            Neighborhood::Moore,
+        )
+    }
+
+    #[test]
+    fn isolated_cell_dies_from_underpopulation() {
+        let automaton = conway_automaton([Vector([0, 0])]).step(conway_transition);
+        assert_eq!(automaton.count(|&live| live), 0);
+    }
+
+    #[test]
+    fn blinker_oscillates_in_2d() {
+        let automaton = conway_automaton([[1, 0], [1, 1], [1, 2]].into_iter().map(Vector::from));
+
+        let automaton = automaton.step(conway_transition);
+        assert_eq!(automaton.count(|&live| live), 3);
+        assert!(*automaton.get(Vector([0, 1])));
+        assert!(*automaton.get(Vector([1, 1])));
+        assert!(*automaton.get(Vector([2, 1])));
+
+        let automaton = automaton.step(conway_transition);
+        assert_eq!(automaton.count(|&live| live), 3);
+        assert!(*automaton.get(Vector([1, 0])));
+        assert!(*automaton.get(Vector([1, 1])));
+        assert!(*automaton.get(Vector([1, 2])));
+    }
+
+    #[test]
+    fn blinker_first_step_matches_2d_when_embedded_in_3d() {
+        let automaton = conway_automaton(
+            [[1, 0, 0], [1, 1, 0], [1, 2, 0]]
+                .into_iter()
+                .map(Vector::from),
+        )
+        .step(conway_transition);
+        assert_eq!(automaton.count(|&live| live), 3);
+        assert!(*automaton.get(Vector([0, 1, 0])));
+        assert!(*automaton.get(Vector([1, 1, 0])));
+        assert!(*automaton.get(Vector([2, 1, 0])));
+    }
+
+    #[test]
+    fn von_neumann_neighborhood_ignores_diagonals() {
+        // A diagonal pair shouldn't count as neighbors under von Neumann
+        // adjacency, so both cells starve instead of surviving as a block.
+        let automaton = CellularAutomaton::from_cells(
+            [(Vector([0, 0]), true), (Vector([1, 1]), true)],
+            false,
+            Neighborhood::VonNeumann,
+        )
+        .step(conway_transition);
+        assert_eq!(automaton.count(|&live| live), 0);
+    }
+
+    #[test]
+    fn fixed_bounds_automaton_does_not_expand() {
+        let automaton =
+            CellularAutomaton::from_cells([(Vector([0, 0]), true)], false, Neighborhood::Moore)
+                .without_expansion()
+                .step(conway_transition);
+        assert_eq!(automaton.count(|_| true), 1);
+    }
+
+    #[test]
+    fn run_to_fixed_point_stops_once_stable() {
+        let automaton = conway_automaton([[1, 0], [1, 1], [1, 2]].into_iter().map(Vector::from))
+            .without_expansion()
+            .run_to_fixed_point(conway_transition);
+        assert_eq!(automaton.count(|&live| live), 0);
+    }
+}
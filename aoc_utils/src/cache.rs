@@ -1,63 +1,198 @@
-use std::{collections::HashMap, hash::Hash};
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    hash::{BuildHasher, Hash},
+    iter::Sum,
+    rc::Rc,
+    sync::Arc,
+};
 
-type CacheFunc<'f, T, R> = Box<dyn Fn(T, &mut CacheView<T, R>) -> R + 'f>;
+use dashmap::DashMap;
+use rayon::prelude::*;
 
-struct Cache<'f, T, R>
+use crate::hash::FastHasher;
+
+type CacheFunc<'f, T, R, S> = Box<dyn Fn(T, &CacheView<'f, T, R, S>) -> R + Sync + 'f>;
+
+/// Owns the memo table and the recurrence that fills it in. The table is
+/// backed by a [`DashMap`] rather than a plain `HashMap` so that
+/// [`CacheView::par_get_or_calc`]/[`CacheView::par_compute_all`] can drive the
+/// same recurrence from several rayon workers at once, sharing memoized
+/// sub-results between them instead of each thread keeping its own table.
+/// The hasher defaults to [`FastHasher`] since memo keys are derived from
+/// puzzle input, not adversarial; pass a different `S` if that ever changes.
+pub struct Cache<'f, T, R, S = FastHasher>
 where
-    T: Clone + Hash + Eq,
+    T: Clone + Hash + Eq + Send + Sync,
+    R: Clone + Send + Sync,
+    S: BuildHasher + Clone + Send + Sync,
 {
-    data: HashMap<T, R>,
-    func: CacheFunc<'f, T, R>,
+    data: Arc<DashMap<T, R, S>>,
+    func: Arc<CacheFunc<'f, T, R, S>>,
 }
 
-impl<'f, T, R> Cache<'f, T, R>
+impl<'f, T, R, S> Cache<'f, T, R, S>
 where
-    T: Clone + Hash + Eq,
+    T: Clone + Hash + Eq + Send + Sync,
+    R: Clone + Send + Sync,
+    S: BuildHasher + Clone + Default + Send + Sync,
 {
-    pub fn new(func: impl Fn(T, &mut CacheView<T, R>) -> R + 'f) -> Self {
+    pub fn new(func: impl Fn(T, &CacheView<'f, T, R, S>) -> R + Sync + 'f) -> Self {
         Self {
-            data: HashMap::new(),
-            func: Box::new(func),
+            data: Arc::new(DashMap::default()),
+            func: Arc::new(Box::new(func)),
         }
     }
 
-    pub fn view(&mut self) -> CacheView<'_, T, R> {
-        let Cache { data, func } = self;
-        CacheView { data, func }
+    pub fn view(&self) -> CacheView<'f, T, R, S> {
+        CacheView {
+            data: Arc::clone(&self.data),
+            func: Arc::clone(&self.func),
+        }
     }
 }
 
-struct CacheView<'c, T, R>
+/// A cheaply cloneable handle onto a [`Cache`]'s memo table and recurrence.
+/// Unlike a plain borrow, a `CacheView` can be handed to several rayon
+/// workers at once, which is what lets [`Self::par_get_or_calc`] recurse back
+/// into the same shared table from any thread.
+pub struct CacheView<'f, T, R, S = FastHasher>
 where
-    T: Clone + Hash + Eq,
+    T: Clone + Hash + Eq + Send + Sync,
+    R: Clone + Send + Sync,
+    S: BuildHasher + Clone + Send + Sync,
 {
-    pub data: &'c mut HashMap<T, R>,
-    pub func: &'c dyn Fn(T, &mut Self) -> R,
+    data: Arc<DashMap<T, R, S>>,
+    func: Arc<CacheFunc<'f, T, R, S>>,
 }
 
-impl<T, R> CacheView<'_, T, R>
+impl<T, R, S> CacheView<'_, T, R, S>
 where
-    T: Clone + Hash + Eq,
+    T: Clone + Hash + Eq + Send + Sync,
+    R: Clone + Send + Sync,
+    S: BuildHasher + Clone + Send + Sync,
 {
-    pub fn get_or_calc(&mut self, k: T) -> &R {
-        let f = self.func;
-        if self.data.contains_key(&k) {
-            self.data.get(&k).unwrap()
-        } else {
-            let v = f(k.clone(), self);
-            self.data.entry(k).or_insert(v)
+    /// Looks up `k`, computing and memoizing it via the cache's recurrence if
+    /// it's not already present. If another worker races to compute the same
+    /// key, both computations are allowed to run — the recurrence is pure, so
+    /// that's just wasted work, not a correctness problem — and the table
+    /// keeps whichever result is inserted first rather than double-inserting.
+    pub fn get_or_calc(&self, k: T) -> R {
+        if let Some(v) = self.data.get(&k) {
+            return v.clone();
         }
+        let v = (self.func)(k.clone(), self);
+        self.data.entry(k).or_insert(v).clone()
+    }
+
+    /// Evaluates every key in `keys` across a rayon thread pool, returning
+    /// the results in the same order. Sub-results computed by one key's
+    /// recursion are visible to every other key through the shared table, so
+    /// this is a genuine parallel fold over the recurrence, not N independent
+    /// single-threaded caches.
+    pub fn par_get_or_calc<I>(&self, keys: I) -> Vec<R>
+    where
+        I: IntoParallelIterator<Item = T>,
+    {
+        keys.into_par_iter().map(|k| self.get_or_calc(k)).collect()
+    }
+
+    /// Like [`Self::par_get_or_calc`], but sums the per-key results instead
+    /// of collecting them, for callers that only want the aggregate.
+    pub fn par_compute_all<I>(&self, keys: I) -> R
+    where
+        I: IntoParallelIterator<Item = T>,
+        R: Sum<R>,
+    {
+        keys.into_par_iter().map(|k| self.get_or_calc(k)).sum()
     }
 }
 
 pub fn cached<'f, T, R, F>(func: F) -> impl FnMut(T) -> R + use<'f, T, R, F>
 where
-    T: Hash + Eq + Clone + 'f,
+    T: Hash + Eq + Clone + Send + Sync + 'f,
+    R: Clone + Send + Sync + 'f,
+    F: Fn(T, &mut dyn FnMut(T) -> R) -> R + Sync + 'f,
+{
+    let cache: Cache<T, R> =
+        Cache::new(move |x, cache: &CacheView<T, R>| func(x, &mut |y| cache.get_or_calc(y)));
+    move |x| cache.view().get_or_calc(x.clone())
+}
+
+/// A cycle [`try_cached`] found mid-recursion: `path` is the chain of keys
+/// from the repeated key back to itself (both ends included), in the order
+/// they were visited, so the caller can see exactly which edge closes the
+/// loop instead of just that one exists.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("cycle detected in cached recursion: {path:?}")]
+pub struct CycleDetected<T: std::fmt::Debug> {
+    pub path: Vec<T>,
+}
+
+/// The state a [`try_cached`] closure threads through its own recursive
+/// calls: `memo` holds finished results, same as [`cached`]'s table, while
+/// `stack` additionally tracks which keys are *currently being computed*, in
+/// call order, so a key recursing back into itself can be recognized as a
+/// cycle instead of recursing forever.
+struct TryCacheState<T, R> {
+    memo: HashMap<T, R>,
+    stack: Vec<T>,
+}
+
+fn try_cached_step<T, R, F>(
+    state: &Rc<RefCell<TryCacheState<T, R>>>,
+    func: &F,
+    key: T,
+) -> Result<R, CycleDetected<T>>
+where
+    T: Hash + Eq + Clone + std::fmt::Debug,
+    R: Clone,
+    F: Fn(T, &mut dyn FnMut(T) -> Result<R, CycleDetected<T>>) -> Result<R, CycleDetected<T>>,
+{
+    if let Some(v) = state.borrow().memo.get(&key) {
+        return Ok(v.clone());
+    }
+    if let Some(pos) = state
+        .borrow()
+        .stack
+        .iter()
+        .position(|on_stack| on_stack == &key)
+    {
+        let path = state.borrow().stack[pos..]
+            .iter()
+            .cloned()
+            .chain(std::iter::once(key))
+            .collect();
+        return Err(CycleDetected { path });
+    }
+
+    state.borrow_mut().stack.push(key.clone());
+    let result = func(key.clone(), &mut |next| try_cached_step(state, func, next));
+    state.borrow_mut().stack.pop();
+
+    let value = result?;
+    state.borrow_mut().memo.insert(key, value.clone());
+    Ok(value)
+}
+
+/// Like [`cached`], but for a recurrence whose input isn't known to be
+/// acyclic: `recurse`-ing back into a key that's still on the call stack
+/// returns `Err(CycleDetected)` — carrying the offending chain of keys —
+/// instead of overflowing the stack. Single-threaded (unlike [`cached`],
+/// which runs on top of the same concurrency-ready [`Cache`] as
+/// [`CacheView::par_get_or_calc`]), since the in-progress stack this relies
+/// on to spot a cycle is inherently a single call chain.
+pub fn try_cached<'f, T, R, F>(
+    func: F,
+) -> impl FnMut(T) -> Result<R, CycleDetected<T>> + use<'f, T, R, F>
+where
+    T: Hash + Eq + Clone + std::fmt::Debug + 'f,
     R: Clone + 'f,
-    F: Fn(T, &mut dyn FnMut(T) -> R) -> R + 'f,
+    F: Fn(T, &mut dyn FnMut(T) -> Result<R, CycleDetected<T>>) -> Result<R, CycleDetected<T>> + 'f,
 {
-    let mut cache = Cache::new(move |x, cache: &mut CacheView<T, R>| {
-        func(x, &mut |y| cache.get_or_calc(y).clone())
-    });
-    move |x| cache.view().get_or_calc(x.clone()).clone()
+    let state = Rc::new(RefCell::new(TryCacheState {
+        memo: HashMap::new(),
+        stack: Vec::new(),
+    }));
+    move |k| try_cached_step(&state, &func, k)
 }
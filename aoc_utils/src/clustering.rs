@@ -0,0 +1,136 @@
+//! Kruskal's algorithm, generalized into a single clustering primitive on
+//! top of [`DisjointSet`]: sort candidate edges by weight, then union their
+//! endpoints one at a time, stopping either once a target number of
+//! clusters remains or once a fixed number of candidate edges has been
+//! considered. A minimum spanning tree and single-linkage clustering with a
+//! fixed edge budget are both just this loop with a different stop
+//! condition, so days doing either don't need to hand-roll the
+//! disjoint-set bookkeeping themselves.
+
+use crate::disjoint_set::DisjointSet;
+use crate::iter::IterUtils as _;
+
+/// When [`cluster`] should stop folding edges into the forest.
+#[derive(Debug, Clone, Copy)]
+pub enum Stop {
+    /// Stop as soon as only this many clusters remain (`1` for a full
+    /// minimum spanning tree/forest).
+    AtClusterCount(usize),
+    /// Stop after this many candidate edges have been considered, whether
+    /// or not each one actually merged two distinct components.
+    AfterEdges(usize),
+}
+
+/// The result of folding a weighted edge set into a forest via [`cluster`]:
+/// the edges that were accepted (in the order they were unioned), and each
+/// point's cluster representative, ready to be fed into e.g.
+/// `itertools::Itertools::counts`.
+pub struct Clusters {
+    pub edges: Vec<(usize, usize)>,
+    pub groups: Vec<usize>,
+}
+
+/// Runs Kruskal's algorithm over `n` points (`0..n`) and a candidate edge
+/// set given as `(i, j, weight)` triples, accepting edges in ascending
+/// weight order until `stop` is reached. `weight` can be any `Ord`
+/// distance/cost — callers decide what "close" means, so this isn't tied
+/// to any particular metric.
+///
+/// When `stop` is [`Stop::AfterEdges`], the candidate edges are narrowed
+/// down to the smallest `limit` via [`IterUtils::k_smallest_by_key`]'s
+/// bounded heap rather than sorting the whole set, since only those can
+/// ever be considered.
+pub fn cluster<W: Ord + Copy>(
+    n: usize,
+    edges: impl IntoIterator<Item = (usize, usize, W)>,
+    stop: Stop,
+) -> Clusters {
+    let edges = match stop {
+        Stop::AfterEdges(limit) => edges.into_iter().k_smallest_by_key(limit, |&(_, _, w)| w),
+        Stop::AtClusterCount(_) => {
+            let mut edges: Vec<_> = edges.into_iter().collect();
+            edges.sort_by_key(|&(_, _, w)| w);
+            edges
+        }
+    };
+
+    let mut dsu = DisjointSet::new(n);
+    let mut accepted = Vec::new();
+    for (i, j, _) in edges {
+        if dsu.union(i, j) {
+            accepted.push((i, j));
+        }
+        if matches!(stop, Stop::AtClusterCount(k) if dsu.component_count() == k) {
+            break;
+        }
+    }
+
+    Clusters {
+        edges: accepted,
+        groups: (0..n).map(|i| dsu.find(i)).collect(),
+    }
+}
+
+/// Runs Kruskal's algorithm to completion, returning the accepted edges of
+/// the minimum spanning forest (a tree if the edge set connects every
+/// point) in the order they were unioned.
+pub fn minimum_spanning_tree<W: Ord + Copy>(
+    n: usize,
+    edges: impl IntoIterator<Item = (usize, usize, W)>,
+) -> Vec<(usize, usize)> {
+    cluster(n, edges, Stop::AtClusterCount(1)).edges
+}
+
+/// Runs Kruskal's algorithm until only `k` clusters remain, returning the
+/// accepted edges and final cluster assignment (single-linkage
+/// clustering).
+pub fn single_linkage_clusters<W: Ord + Copy>(
+    n: usize,
+    edges: impl IntoIterator<Item = (usize, usize, W)>,
+    k: usize,
+) -> Clusters {
+    cluster(n, edges, Stop::AtClusterCount(k))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A square with both diagonals: 0-1, 1-2, 2-3, 3-0 have weight 1, the
+    // diagonals 0-2 and 1-3 have weight 2.
+    fn square_edges() -> Vec<(usize, usize, u32)> {
+        vec![
+            (0, 1, 1),
+            (1, 2, 1),
+            (2, 3, 1),
+            (3, 0, 1),
+            (0, 2, 2),
+            (1, 3, 2),
+        ]
+    }
+
+    #[test]
+    fn minimum_spanning_tree_skips_the_diagonals() {
+        let mst = minimum_spanning_tree(4, square_edges());
+        itertools::assert_equal(mst, [(0, 1), (1, 2), (2, 3)]);
+    }
+
+    #[test]
+    fn single_linkage_clusters_stops_early_at_the_requested_cluster_count() {
+        let clusters = single_linkage_clusters(4, square_edges(), 2);
+        assert_eq!(clusters.groups[0], clusters.groups[1]);
+        assert_ne!(clusters.groups[0], clusters.groups[2]);
+        assert_eq!(clusters.groups[2], clusters.groups[3]);
+    }
+
+    #[test]
+    fn cluster_after_edges_caps_the_number_of_edges_considered_not_accepted() {
+        // The third edge (2, 3) duplicates an already-connected pair via
+        // (0, 1) and (1, 2), so it's considered but doesn't merge anything;
+        // capping at 3 considered edges should still leave 2 components.
+        let edges: Vec<(usize, usize, u32)> = vec![(0, 1, 1), (1, 2, 1), (0, 2, 1), (2, 3, 5)];
+        let result = cluster(4, edges, Stop::AfterEdges(3));
+        assert_eq!(result.edges, [(0, 1), (1, 2)]);
+        assert_ne!(result.groups[0], result.groups[3]);
+    }
+}
@@ -0,0 +1,134 @@
+//! Helpers for picking subsets of a slice that satisfy some numeric
+//! condition, in better-than-brute-force time.
+
+use num_traits::Num;
+
+/// Finds `N` entries from `entries` (with repetition allowed by index, not
+/// by value) summing to exactly `target`, or `None` if no such subset
+/// exists. Runs in `O(n log n)` for `N <= 2` and `O(n^(N-1))` thereafter, by
+/// sorting once and then fixing the outermost element of each `k`-subset in
+/// an outer loop while resolving the remaining `k-1` via two-pointer
+/// recursion. Assumes `entries` are non-negative, which lets the outer loop
+/// stop as soon as a fixed element alone exceeds `target`.
+pub fn subset_sum<T, const N: usize>(entries: &[T], target: T) -> Option<[T; N]>
+where
+    T: Num + Ord + Copy,
+{
+    let mut sorted = entries.to_vec();
+    sorted.sort_unstable();
+    k_sum(&sorted, N, target).and_then(|values| values.try_into().ok())
+}
+
+fn k_sum<T>(sorted: &[T], k: usize, target: T) -> Option<Vec<T>>
+where
+    T: Num + Ord + Copy,
+{
+    match k {
+        0 => (target == T::zero()).then(Vec::new),
+        1 => sorted.iter().find(|&&x| x == target).map(|&x| vec![x]),
+        2 => two_sum(sorted, target).map(|(a, b)| vec![a, b]),
+        _ => sorted.iter().enumerate().find_map(|(i, &fixed)| {
+            if fixed > target {
+                return None;
+            }
+            let mut rest = k_sum(&sorted[i + 1..], k - 1, target - fixed)?;
+            rest.insert(0, fixed);
+            Some(rest)
+        }),
+    }
+}
+
+fn two_sum<T>(sorted: &[T], target: T) -> Option<(T, T)>
+where
+    T: Num + Ord + Copy,
+{
+    if sorted.is_empty() {
+        return None;
+    }
+    let (mut left, mut right) = (0, sorted.len() - 1);
+    while left < right {
+        match (sorted[left] + sorted[right]).cmp(&target) {
+            std::cmp::Ordering::Less => left += 1,
+            std::cmp::Ordering::Greater => right -= 1,
+            std::cmp::Ordering::Equal => return Some((sorted[left], sorted[right])),
+        }
+    }
+    None
+}
+
+/// Picks `k` of `digits`, keeping their relative order, such that the
+/// resulting length-`k` sequence is lexicographically (and since every
+/// candidate has the same length, numerically) the largest possible. Runs in
+/// `O(n)` via a monotonic stack: each digit pops any smaller digit already on
+/// the stack as long as there's still slack (`to_remove`) to spend, so the
+/// stack only ever holds a non-increasing run of "can't be beaten yet"
+/// digits; once the slack is used up, every remaining digit is kept
+/// verbatim.
+pub fn max_value_subsequence(digits: &[u32], k: usize) -> Vec<u32> {
+    if k == 0 {
+        return Vec::new();
+    }
+
+    let mut to_remove = digits.len() - k;
+    let mut stack: Vec<u32> = Vec::with_capacity(digits.len());
+    for &digit in digits {
+        while to_remove > 0 && stack.last().is_some_and(|&top| top < digit) {
+            stack.pop();
+            to_remove -= 1;
+        }
+        stack.push(digit);
+    }
+
+    stack.truncate(k);
+    stack
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ENTRIES: &[i32] = &[1721, 979, 366, 299, 675, 1456];
+
+    #[test]
+    fn finds_pair_summing_to_target() {
+        assert_eq!(subset_sum::<i32, 2>(ENTRIES, 2020), Some([299, 1721]));
+    }
+
+    #[test]
+    fn finds_triple_summing_to_target() {
+        assert_eq!(subset_sum::<i32, 3>(ENTRIES, 2020), Some([366, 675, 979]));
+    }
+
+    #[test]
+    fn reports_none_when_no_subset_sums_to_target() {
+        assert_eq!(subset_sum::<i32, 2>(ENTRIES, 1), None);
+    }
+
+    #[test]
+    fn empty_subset_matches_zero_target() {
+        assert_eq!(subset_sum::<i32, 0>(ENTRIES, 0), Some([]));
+    }
+
+    #[test]
+    fn max_value_subsequence_picks_the_largest_digits_in_order() {
+        assert_eq!(max_value_subsequence(&[9, 9, 1], 2), [9, 9]);
+        assert_eq!(
+            max_value_subsequence(&[9, 8, 7, 6, 5, 4, 3, 2, 1], 2),
+            [9, 8]
+        );
+        assert_eq!(
+            max_value_subsequence(&[1, 2, 3, 4, 5, 6, 7, 8, 9], 2),
+            [8, 9]
+        );
+    }
+
+    #[test]
+    fn max_value_subsequence_keeps_leftover_digits_verbatim_once_slack_runs_out() {
+        assert_eq!(max_value_subsequence(&[2, 1, 3, 4], 3), [2, 3, 4]);
+    }
+
+    #[test]
+    fn max_value_subsequence_of_zero_is_empty() {
+        assert_eq!(max_value_subsequence(&[9, 9, 1], 0), []);
+    }
+}
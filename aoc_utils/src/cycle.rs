@@ -0,0 +1,65 @@
+//! Brent's cycle-detection algorithm: finds where the orbit `x0, f(x0),
+//! f(f(x0)), ...` first starts repeating, using O(1) extra state instead of
+//! recording every visited state in a `HashSet`/`HashMap`.
+
+/// Finds `(mu, lambda)` for the sequence `x0, f(x0), f(f(x0)), ...`: `mu` is
+/// how many steps precede the first repeated state, and `lambda` is the
+/// length of the cycle that state begins. Only ever holds a handful of `T`s
+/// at once (a "tortoise", a "hare", and `f`'s own working clone), unlike the
+/// naive approach of hashing every intermediate state into a set.
+pub fn brent<T: Clone + PartialEq>(x0: T, f: impl Fn(&T) -> T) -> (usize, usize) {
+    let mut power = 1;
+    let mut lam = 1;
+    let mut tortoise = x0.clone();
+    let mut hare = f(&x0);
+    while tortoise != hare {
+        if power == lam {
+            tortoise = hare.clone();
+            power *= 2;
+            lam = 0;
+        }
+        hare = f(&hare);
+        lam += 1;
+    }
+
+    let mut tortoise = x0.clone();
+    let mut hare = x0;
+    for _ in 0..lam {
+        hare = f(&hare);
+    }
+    let mut mu = 0;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        mu += 1;
+    }
+
+    (mu, lam)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_a_cycle_with_a_non_trivial_tail() {
+        // 0 -> 1 -> 2 -> 3 -> 4 -> 2 -> 3 -> 4 -> ...
+        let next = |&x: &u32| match x {
+            4 => 2,
+            x => x + 1,
+        };
+        assert_eq!(brent(0, next), (2, 3));
+    }
+
+    #[test]
+    fn detects_a_purely_periodic_sequence() {
+        let next = |&x: &u32| (x + 1) % 5;
+        assert_eq!(brent(0, next), (0, 5));
+    }
+
+    #[test]
+    fn detects_a_fixed_point_as_a_cycle_of_length_one() {
+        let next = |&x: &u32| if x == 0 { 0 } else { x - 1 };
+        assert_eq!(brent(3, next), (3, 1));
+    }
+}
@@ -0,0 +1,142 @@
+//! A union-find over the integers `0..n`, for days that need to fold a
+//! stream of "these two things are connected" edges into connected
+//! components without paying to relabel everything on every merge (the
+//! naive approach of keeping a `Vec` of group ids and rewriting every
+//! matching entry on each union is O(n) per union, O(n²) overall).
+
+/// A disjoint-set forest over `0..len()`, represented (as is conventional)
+/// as a parent pointer per element — a root points to itself — plus, for
+/// union-by-size, the size of the tree rooted at each root.
+pub struct DisjointSet {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    component_count: usize,
+}
+
+impl DisjointSet {
+    /// Creates `n` singleton sets, one per element `0..n`.
+    pub fn new(n: usize) -> Self {
+        DisjointSet {
+            parent: (0..n).collect(),
+            size: vec![1; n],
+            component_count: n,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// The number of disjoint sets remaining; starts at `len()` and
+    /// decreases by one on every [`union`](Self::union) that actually
+    /// merges two previously-distinct sets.
+    pub fn component_count(&self) -> usize {
+        self.component_count
+    }
+
+    /// Finds `x`'s set's representative, fully path-compressing every node
+    /// visited along the way so subsequent lookups through them are O(1).
+    pub fn find(&mut self, x: usize) -> usize {
+        if self.parent[x] != x {
+            self.parent[x] = self.find(self.parent[x]);
+        }
+        self.parent[x]
+    }
+
+    /// Merges the sets containing `i` and `j`, attaching the smaller tree
+    /// under the larger one's root, and returns whether they were
+    /// previously distinct (a no-op union on an already-joined pair
+    /// returns `false` and leaves [`component_count`](Self::component_count)
+    /// unchanged).
+    pub fn union(&mut self, i: usize, j: usize) -> bool {
+        let (mut root_i, mut root_j) = (self.find(i), self.find(j));
+        if root_i == root_j {
+            return false;
+        }
+        if self.size[root_i] < self.size[root_j] {
+            std::mem::swap(&mut root_i, &mut root_j);
+        }
+        self.parent[root_j] = root_i;
+        self.size[root_i] += self.size[root_j];
+        self.component_count -= 1;
+        true
+    }
+
+    /// Whether `i` and `j` currently belong to the same set.
+    pub fn connected(&mut self, i: usize, j: usize) -> bool {
+        self.find(i) == self.find(j)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_set_starts_with_one_component_per_element() {
+        let dsu = DisjointSet::new(5);
+        assert_eq!(dsu.component_count(), 5);
+    }
+
+    #[test]
+    fn union_of_distinct_sets_returns_true_and_decrements_component_count() {
+        let mut dsu = DisjointSet::new(5);
+        assert!(dsu.union(0, 1));
+        assert_eq!(dsu.component_count(), 4);
+    }
+
+    #[test]
+    fn union_of_already_joined_elements_returns_false_and_leaves_count_unchanged() {
+        let mut dsu = DisjointSet::new(5);
+        assert!(dsu.union(0, 1));
+        assert!(!dsu.union(0, 1));
+        assert!(!dsu.union(1, 0));
+        assert_eq!(dsu.component_count(), 4);
+    }
+
+    #[test]
+    fn find_agrees_for_every_element_of_a_merged_set() {
+        let mut dsu = DisjointSet::new(5);
+        dsu.union(0, 1);
+        dsu.union(1, 2);
+        dsu.union(3, 4);
+        assert_eq!(dsu.find(0), dsu.find(1));
+        assert_eq!(dsu.find(1), dsu.find(2));
+        assert_ne!(dsu.find(0), dsu.find(3));
+        assert_eq!(dsu.find(3), dsu.find(4));
+    }
+
+    #[test]
+    fn chain_of_unions_collapses_to_a_single_component() {
+        let mut dsu = DisjointSet::new(10);
+        for i in 1..10 {
+            dsu.union(i - 1, i);
+        }
+        assert_eq!(dsu.component_count(), 1);
+        let root = dsu.find(0);
+        assert!((1..10).all(|i| dsu.find(i) == root));
+    }
+
+    #[test]
+    fn connected_reports_membership_in_the_same_set() {
+        let mut dsu = DisjointSet::new(5);
+        dsu.union(0, 1);
+        dsu.union(1, 2);
+        assert!(dsu.connected(0, 2));
+        assert!(!dsu.connected(0, 3));
+    }
+
+    #[test]
+    fn find_path_compresses_intermediate_nodes() {
+        let mut dsu = DisjointSet::new(4);
+        dsu.union(0, 1);
+        dsu.union(1, 2);
+        dsu.union(2, 3);
+        let root = dsu.find(3);
+        assert_eq!(dsu.parent, vec![root, root, root, root]);
+    }
+}
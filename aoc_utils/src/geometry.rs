@@ -1,4 +1,10 @@
+use std::collections::HashSet;
+
 use itertools::Itertools;
+use num_traits::{Num, Signed};
+
+use crate::array;
+use crate::linalg::Vector;
 
 pub trait Point
 where
@@ -29,6 +35,145 @@ where
     }
 }
 
+/// A heading for walking a 2D grid one cell at a time, usable with
+/// [`Vector::step`] and rotated via [`Direction::turn_left`]/
+/// [`Direction::turn_right`] without re-deriving the sign arithmetic at
+/// every call site. Follows the same row-major convention as this module's
+/// other grid helpers (e.g. [`parse_ascii_map`]): `x` grows rightward, `y`
+/// grows *downward*, as in a 2D array indexed `[row, col]` with row 0
+/// printed first — so `Up` is `(0, -1)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    /// All four headings, starting `Up` and proceeding clockwise.
+    pub const ALL: [Direction; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    /// The unit offset that stepping one cell in this heading adds to a point.
+    pub fn offset<T: Num + Signed>(self) -> Vector<T, 2> {
+        match self {
+            Direction::Up => Vector([T::zero(), -T::one()]),
+            Direction::Down => Vector([T::zero(), T::one()]),
+            Direction::Left => Vector([-T::one(), T::zero()]),
+            Direction::Right => Vector([T::one(), T::zero()]),
+        }
+    }
+
+    /// The heading 90° clockwise from this one.
+    pub fn turn_right(self) -> Self {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+
+    /// The heading 90° counter-clockwise from this one; the inverse of
+    /// [`Direction::turn_right`].
+    pub fn turn_left(self) -> Self {
+        match self {
+            Direction::Up => Direction::Left,
+            Direction::Left => Direction::Down,
+            Direction::Down => Direction::Right,
+            Direction::Right => Direction::Up,
+        }
+    }
+}
+
+impl<T: Num + Signed + Copy> Vector<T, 2> {
+    /// Rotates this relative offset 90° clockwise: `(dx, dy)` becomes
+    /// `(-dy, dx)`. Consistent with [`Direction::turn_right`], e.g.
+    /// rotating `Direction::Up.offset()` gives `Direction::Right.offset()`.
+    pub fn rotate_right(self) -> Self {
+        Vector([-self[1], self[0]])
+    }
+
+    /// Rotates this relative offset 90° counter-clockwise; the inverse of
+    /// [`Vector::rotate_right`].
+    pub fn rotate_left(self) -> Self {
+        Vector([self[1], -self[0]])
+    }
+
+    /// Advances this point by one unit in `heading`.
+    pub fn step(self, heading: Direction) -> Self {
+        self + heading.offset()
+    }
+}
+
+/// A relative turn driving a [`Turtle`], as distinct from [`Direction`]'s
+/// absolute headings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Turn {
+    Left,
+    Right,
+}
+
+/// One instruction in a path traced by [`turtle`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TurtleMove {
+    Turn(Turn),
+    Advance(usize),
+}
+
+/// Iterator returned by [`turtle`]; see there for details.
+pub struct Turtle<T, I> {
+    pos: Vector<T, 2>,
+    heading: Direction,
+    remaining: usize,
+    instructions: I,
+}
+
+impl<T, I> Iterator for Turtle<T, I>
+where
+    T: Num + Signed + Copy,
+    I: Iterator<Item = TurtleMove>,
+{
+    type Item = Vector<T, 2>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.remaining > 0 {
+                self.remaining -= 1;
+                self.pos = self.pos.step(self.heading);
+                return Some(self.pos);
+            }
+            match self.instructions.next()? {
+                TurtleMove::Turn(Turn::Left) => self.heading = self.heading.turn_left(),
+                TurtleMove::Turn(Turn::Right) => self.heading = self.heading.turn_right(),
+                TurtleMove::Advance(steps) => self.remaining = steps,
+            }
+        }
+    }
+}
+
+/// Walks a path starting at `start` facing `heading`, driven by
+/// `instructions`, and yields every point visited along the way (one per
+/// unit advanced; turning in place yields nothing). So day solutions that
+/// trace a path turn-by-turn (or spiral outward) can reuse this instead of
+/// re-deriving the heading/rotation arithmetic [`Direction`] already
+/// encapsulates.
+pub fn turtle<T>(
+    start: Vector<T, 2>,
+    heading: Direction,
+    instructions: impl IntoIterator<IntoIter = impl Iterator<Item = TurtleMove>>,
+) -> Turtle<T, impl Iterator<Item = TurtleMove>>
+where
+    T: Num + Signed + Copy,
+{
+    Turtle {
+        pos: start,
+        heading,
+        remaining: 0,
+        instructions: instructions.into_iter(),
+    }
+}
+
 pub fn map_bounds(input: &str) -> [std::ops::Range<usize>; 2] {
     let rows = input.lines().count();
     let cols = input.lines().next().map(|line| line.len()).unwrap_or(0);
@@ -60,6 +205,44 @@ pub enum ParseMapError<E> {
     ConversionError(#[from] E),
 }
 
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum GridInputError {
+    #[error("row {row} has width {found}, expected {expected} (based on the first row)")]
+    RaggedRow {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+/// Normalizes raw map input before it's sliced into a grid: strips `\r` (so
+/// CRLF-terminated files parse the same as LF-terminated ones) and drops a
+/// single trailing blank line (left behind by a file ending in two
+/// newlines), then checks that every remaining row has the same width.
+/// Without this, such input silently throws off the row/column counts
+/// [`map_bounds`] and [`parse_ascii_map`] derive from `input.lines()`,
+/// leading to a confusing [`ndarray::ShapeError`] (or, for a ragged map, a
+/// would-be out-of-bounds panic) far from the actual malformed input.
+pub fn normalize_grid_input(input: &str) -> Result<String, GridInputError> {
+    let stripped = input.replace('\r', "");
+    let stripped = stripped.strip_suffix('\n').unwrap_or(&stripped);
+
+    let expected = stripped.lines().next().map(str::len).unwrap_or(0);
+    if let Some((row, line)) = stripped
+        .lines()
+        .enumerate()
+        .find(|&(_, line)| line.len() != expected)
+    {
+        return Err(GridInputError::RaggedRow {
+            row,
+            expected,
+            found: line.len(),
+        });
+    }
+
+    Ok(stripped.to_owned())
+}
+
 pub fn try_parse_map<T, E>(
     input: &str,
     f: impl FnMut(u8) -> Result<T, E>,
@@ -73,15 +256,706 @@ pub fn try_parse_map<T, E>(
     .map_err(ParseMapError::ShapeError)
 }
 
+/// Counts the connected components (w.r.t. `P::nearest_neighbors`) among the
+/// `candidates` for which `contains` returns `true`. `contains` is
+/// responsible for reporting `false` on out-of-bounds points.
+pub fn connected_components<P>(
+    candidates: impl IntoIterator<Item = P>,
+    mut contains: impl FnMut(P) -> bool,
+) -> usize
+where
+    P: Point + Eq + std::hash::Hash,
+{
+    let mut visited = HashSet::new();
+    candidates
+        .into_iter()
+        .filter(|&p| contains(p))
+        .filter(|&p| {
+            let new_region = !visited.contains(&p);
+            if new_region {
+                let mut queue = vec![p];
+                while let Some(q) = queue.pop() {
+                    if contains(q) && visited.insert(q) {
+                        queue.extend(q.nearest_neighbors());
+                    }
+                }
+            }
+            new_region
+        })
+        .count()
+}
+
+/// A 4-connected region of a labeled 2D grid, as produced by [`segment_by`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cluster(HashSet<Vector<usize, 2>>);
+
+impl Cluster {
+    /// The number of cells making up the region.
+    pub fn area(&self) -> usize {
+        self.0.len()
+    }
+
+    /// The number of unit edges bordering a cell outside the region.
+    pub fn perimeter(&self) -> usize {
+        self.0
+            .iter()
+            .flat_map(|p| p.cast_as::<isize>().nearest_neighbors())
+            .filter(|p| !p.try_cast_as::<usize>().is_ok_and(|q| self.0.contains(&q)))
+            .count()
+    }
+
+    /// The number of straight boundary segments (i.e. sides, rather than
+    /// unit edges) enclosing the region.
+    ///
+    /// Relies on the Euler characteristic (vertices - edges + faces = 1),
+    /// which for a one-faced region implies the number of edges (here:
+    /// straight sides) equals the number of vertices (corners). Corners are
+    /// counted locally by examining each cell's eight neighbors, since a
+    /// corner is only ever shared by at most four cells; the threefold
+    /// overcounting this produces is divided back out at the end.
+    pub fn corner_count(&self) -> usize {
+        let total_vertex_value: usize = self
+            .0
+            .iter()
+            .map(|p| p.cast_as::<isize>())
+            .map(|p| {
+                array::from_iter_exact::<bool, 8>(
+                    p.neighbors()
+                        .map(|q| q.try_cast_as::<usize>().is_ok_and(|q| self.0.contains(&q))),
+                )
+                .expect("point should have exactly eight neighbors")
+            })
+            .map(vertex_value)
+            .sum();
+
+        assert!(
+            total_vertex_value.is_multiple_of(3),
+            "corner value should be divisible by three; got {total_vertex_value}"
+        );
+
+        total_vertex_value / 3
+    }
+}
+
+fn vertex_value(neighbors: [bool; 8]) -> usize {
+    // Each point in the cluster can contribute 0 to 4 corners to the overall shape
+    // (4 being possible only if the cluster is just a single point).
+    // Due to triple counting, the value returned by this function is trice the actual
+    // (fractional) contribution of this point. The total "vertex value" over all points
+    // in the cluster then has to be divisible by three to produce the integer number of
+    // vertices.
+    // `vertex_contributions` yields the contribution of each of the four potential
+    // corners of the cluster "pixel", considering the neighbor diagonally across and
+    // two adjacent neighbors which share the edge segments which meet in the potential
+    // corner.
+    // `neighbors` assumes the order which the 2D `neighbors()` impls use.
+    vertex_contribution(neighbors[7], neighbors[0], neighbors[6])
+        + vertex_contribution(neighbors[1], neighbors[2], neighbors[0])
+        + vertex_contribution(neighbors[3], neighbors[4], neighbors[2])
+        + vertex_contribution(neighbors[5], neighbors[6], neighbors[4])
+}
+
+fn vertex_contribution(diag: bool, adj1: bool, adj2: bool) -> usize {
+    match (diag, adj1, adj2) {
+        (true, true, true) => 0,
+        (true, true, false) => 1,
+        (true, false, true) => 1,
+        (true, false, false) => 3,
+        (false, true, true) => 1,
+        (false, true, false) => 0,
+        (false, false, true) => 0,
+        (false, false, false) => 3,
+    }
+}
+
+/// Partitions every cell of `points` into 4-connected clusters, where two
+/// neighboring cells belong to the same cluster iff `same_label` returns
+/// `true` for their pair.
+pub fn segment_by<T: Copy>(
+    points: impl IntoIterator<Item = (Vector<usize, 2>, T)>,
+    mut same_label: impl FnMut(T, T) -> bool,
+) -> Vec<Cluster> {
+    let mut not_visited: std::collections::HashMap<_, _> = points.into_iter().collect();
+
+    std::iter::from_fn(|| {
+        let (&seed, &label) = not_visited.iter().next()?;
+        not_visited.remove(&seed);
+        let mut cluster = HashSet::from([seed]);
+        let mut wave = HashSet::from([seed]);
+        while !wave.is_empty() {
+            wave = wave
+                .iter()
+                .flat_map(|p| p.nearest_neighbors())
+                .filter(|p| {
+                    not_visited
+                        .get(p)
+                        .is_some_and(|&other| same_label(label, other))
+                })
+                .filter(|p| not_visited.remove(p).is_some())
+                .collect();
+            cluster.extend(wave.iter().cloned());
+        }
+        Some(Cluster(cluster))
+    })
+    .collect()
+}
+
+/// Finds the shortest distance from `start` to every cell reachable through
+/// `passable` cells, stopping early once a cell satisfying `goal` is popped
+/// (pass `|_| false` to compute the full reachable distance field instead).
+/// `cost` gives the weight of stepping from a cell to one of its
+/// `nearest_neighbors`.
+pub fn dijkstra<P>(
+    start: P,
+    goal: impl Fn(P) -> bool,
+    passable: impl Fn(P) -> bool,
+    cost: impl Fn(P, P) -> usize,
+) -> std::collections::HashMap<P, usize>
+where
+    P: Point + Ord + std::hash::Hash,
+{
+    use std::cmp::Reverse;
+    use std::collections::{BinaryHeap, HashMap};
+
+    let mut dist = HashMap::from([(start, 0)]);
+    let mut heap = BinaryHeap::from([Reverse((0, start))]);
+
+    while let Some(Reverse((d, p))) = heap.pop() {
+        if dist.get(&p).is_some_and(|&best| d > best) {
+            continue;
+        }
+        if goal(p) {
+            break;
+        }
+        for n in p.nearest_neighbors().filter(|&n| passable(n)) {
+            let nd = d + cost(p, n);
+            if dist.get(&n).is_none_or(|&best| nd < best) {
+                dist.insert(n, nd);
+                heap.push(Reverse((nd, n)));
+            }
+        }
+    }
+
+    dist
+}
+
+/// Like [`dijkstra`], but for the common case where every step costs the
+/// same: a breadth-first search that never needs a priority queue.
+pub fn bfs<P>(
+    start: P,
+    goal: impl Fn(P) -> bool,
+    passable: impl Fn(P) -> bool,
+) -> std::collections::HashMap<P, usize>
+where
+    P: Point + Eq + std::hash::Hash,
+{
+    use std::collections::{HashMap, VecDeque};
+
+    let mut dist = HashMap::from([(start, 0)]);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(p) = queue.pop_front() {
+        if goal(p) {
+            break;
+        }
+        let d = dist[&p];
+        for n in p
+            .nearest_neighbors()
+            .filter(|&n| passable(n) && !dist.contains_key(&n))
+        {
+            dist.insert(n, d + 1);
+            queue.push_back(n);
+        }
+    }
+
+    dist
+}
+
+/// Expands outward from `start` along `neighbors`, keeping only cells that
+/// satisfy `accept`, and returns every such cell reached exactly once.
+/// Unlike [`bfs`], which always steps to [`Point::nearest_neighbors`],
+/// `neighbors` here can encode an arbitrary step rule — e.g. `day10`'s
+/// trailhead climb, which only steps to a cell exactly one height higher —
+/// so callers with a custom adjacency don't need to reinvent the expansion
+/// loop.
+pub fn bfs_reachable<P, N>(
+    start: P,
+    mut neighbors: impl FnMut(P) -> N,
+    accept: impl Fn(P) -> bool,
+) -> HashSet<P>
+where
+    P: Eq + std::hash::Hash + Copy,
+    N: IntoIterator<Item = P>,
+{
+    let mut visited = HashSet::from([start]);
+    let mut frontier = vec![start];
+
+    while !frontier.is_empty() {
+        frontier = frontier
+            .into_iter()
+            .flat_map(&mut neighbors)
+            .filter(|&p| accept(p) && visited.insert(p))
+            .collect();
+    }
+
+    visited
+}
+
+/// Counts the distinct monotone paths from `start` to a cell satisfying
+/// `is_end`, walked one `step` at a time, memoizing the count per cell so
+/// that cells shared by many paths (as happens whenever the steps re-merge,
+/// e.g. two trailheads climbing into the same saddle) are only counted once
+/// each instead of revisited per path.
+pub fn count_distinct_paths<P, N>(
+    start: P,
+    mut step: impl FnMut(P) -> N,
+    is_end: impl Fn(P) -> bool,
+) -> usize
+where
+    P: Eq + std::hash::Hash + Copy,
+    N: IntoIterator<Item = P>,
+{
+    fn count<P, N>(
+        p: P,
+        step: &mut impl FnMut(P) -> N,
+        is_end: &impl Fn(P) -> bool,
+        memo: &mut std::collections::HashMap<P, usize>,
+    ) -> usize
+    where
+        P: Eq + std::hash::Hash + Copy,
+        N: IntoIterator<Item = P>,
+    {
+        if let Some(&n) = memo.get(&p) {
+            return n;
+        }
+        let n = if is_end(p) {
+            1
+        } else {
+            step(p)
+                .into_iter()
+                .map(|next| count(next, step, is_end, memo))
+                .sum()
+        };
+        memo.insert(p, n);
+        n
+    }
+
+    count(
+        start,
+        &mut step,
+        &is_end,
+        &mut std::collections::HashMap::new(),
+    )
+}
+
+/// Yields `point`'s [`Point::nearest_neighbors`] that lie within `grid`'s
+/// bounds, so a grid produced by [`parse_ascii_map`]/[`try_parse_map`] can be
+/// searched directly with [`crate::graph::dijkstra_path`] or
+/// [`crate::graph::a_star_path`] — whose `neighbors` closure only knows
+/// about nodes and edge costs, not grid bounds — without reinventing a
+/// bounds check at every call site.
+pub fn grid_neighbors<T>(
+    point: Vector<usize, 2>,
+    grid: &ndarray::Array2<T>,
+) -> impl Iterator<Item = Vector<usize, 2>> + '_ {
+    let shape = grid.shape();
+    let bound = [0..shape[0], 0..shape[1]];
+    point
+        .nearest_neighbors()
+        .filter(move |p| p.in_bounds(&bound))
+}
+
+/// A padded char grid: ragged input (as produced by Advent of Code maps,
+/// whose trailing blanks on a row are often simply left off) is parsed by
+/// padding every row out to the longest one with `b' '` before converting
+/// each byte to `T` via [`From<u8>`]. Backed by an [`ndarray::Array2`],
+/// indexed `[row, col]` by a `Vector<usize, 2>` the same way the rest of
+/// this module's grid helpers are, so days that would otherwise hand-roll
+/// their own ragged-line padding and `Array2` bookkeeping (as the cube-map
+/// parsing here used to) can build directly on this instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CharGrid<T> {
+    data: ndarray::Array2<T>,
+}
+
+impl<T> CharGrid<T> {
+    /// `[height, width]`, i.e. the number of rows followed by the number of
+    /// columns.
+    pub fn shape(&self) -> Vector<usize, 2> {
+        let &[height, width] = self.data.shape() else {
+            unreachable!("Array2 always has two axes")
+        };
+        Vector([height, width])
+    }
+
+    pub fn in_bounds(&self, pos: Vector<isize, 2>) -> bool {
+        let Vector([height, width]) = self.shape();
+        pos.in_bounds(&[0..height as isize, 0..width as isize])
+    }
+
+    /// Lane-wise iteration over each row, left to right.
+    pub fn rows(&self) -> ndarray::iter::Lanes<'_, T, ndarray::Ix1> {
+        self.data.rows()
+    }
+
+    /// Lane-wise iteration over each column, top to bottom.
+    pub fn columns(&self) -> ndarray::iter::Lanes<'_, T, ndarray::Ix1> {
+        self.data.columns()
+    }
+
+    /// `pos`'s [`Point::nearest_neighbors`] that lie within this grid's
+    /// bounds.
+    pub fn neighbors(&self, pos: Vector<usize, 2>) -> impl Iterator<Item = Vector<usize, 2>> + '_ {
+        grid_neighbors(pos, &self.data)
+    }
+
+    pub fn indexed_iter(&self) -> ndarray::iter::IndexedIter<'_, T, ndarray::Ix2> {
+        self.data.indexed_iter()
+    }
+
+    pub fn iter(&self) -> ndarray::iter::Iter<'_, T, ndarray::Ix2> {
+        self.data.iter()
+    }
+}
+
+impl<T> std::ops::Index<Vector<usize, 2>> for CharGrid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Vector<usize, 2>) -> &T {
+        &self.data[pos]
+    }
+}
+
+impl<T> std::ops::IndexMut<Vector<usize, 2>> for CharGrid<T> {
+    fn index_mut(&mut self, pos: Vector<usize, 2>) -> &mut T {
+        &mut self.data[pos]
+    }
+}
+
+impl<T: From<u8>> std::str::FromStr for CharGrid<T> {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let height = s.lines().count();
+        let width = s.lines().map(str::len).max().unwrap_or(0);
+
+        let data = s
+            .lines()
+            .flat_map(|line| {
+                line.bytes()
+                    .chain(std::iter::repeat(b' '))
+                    .take(width)
+                    .map(T::from)
+            })
+            .collect();
+
+        Ok(CharGrid {
+            data: ndarray::Array2::from_shape_vec((height, width), data)
+                .expect("collected exactly height * width elements"),
+        })
+    }
+}
+
+/// One axis of a [`DynamicGrid`]: maps a signed coordinate to a flat index
+/// via `offset + pos`, valid while the result falls in `0..size`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Dimension {
+    offset: isize,
+    size: usize,
+}
+
+impl Dimension {
+    fn index(&self, pos: isize) -> Option<usize> {
+        let i = pos + self.offset;
+        (0..self.size as isize).contains(&i).then_some(i as usize)
+    }
+
+    /// The smallest dimension that still maps `pos` to a valid index,
+    /// extending the current extents rather than recentering them.
+    fn grown_to_include(&self, pos: isize) -> Dimension {
+        if self.size == 0 {
+            return Dimension {
+                offset: -pos,
+                size: 1,
+            };
+        }
+        let lo = (-self.offset).min(pos);
+        let hi = (self.size as isize - self.offset - 1).max(pos);
+        Dimension {
+            offset: -lo,
+            size: (hi - lo + 1) as usize,
+        }
+    }
+
+    /// Widens this dimension by one cell on each side, so a coordinate just
+    /// past the current extents (as a cellular automaton's next generation
+    /// might activate) becomes representable.
+    fn extended(&self) -> Dimension {
+        Dimension {
+            offset: self.offset + 1,
+            size: self.size + 2,
+        }
+    }
+}
+
+impl IntoIterator for Dimension {
+    type Item = isize;
+    type IntoIter = std::ops::Range<isize>;
+
+    /// The signed coordinates this dimension currently spans, in the order
+    /// [`Dimension::index`] maps them to increasing storage indices.
+    fn into_iter(self) -> Self::IntoIter {
+        -self.offset..(self.size as isize - self.offset)
+    }
+}
+
+/// An unbounded `N`-dimensional grid backed by a flat `Vec<T>`, growing to
+/// fit whichever coordinates are actually visited instead of requiring the
+/// bounds to be known up front. Cells that have never been written read as
+/// `T::default()`.
+///
+/// Intended for simulations that walk a grid one cell at a time and would
+/// otherwise pay hashing costs on every step for a `HashMap<Vector<_, N>,
+/// _>`: a visited coordinate translates to a flat index with no hashing,
+/// and since each step moves to an adjacent cell, growth (and the one-time
+/// copy it requires) is amortized against however many steps occurred
+/// since the grid last had to grow. For `T = bool`, [`DynamicGrid::step`]
+/// offers the same growth amortization to a whole-grid cellular-automaton
+/// generation, rather than a single cell write.
+#[derive(Debug, Clone)]
+pub struct DynamicGrid<T, const N: usize> {
+    dims: [Dimension; N],
+    cells: Vec<T>,
+}
+
+impl<T: Clone + Default, const N: usize> Default for DynamicGrid<T, N> {
+    fn default() -> Self {
+        DynamicGrid {
+            dims: [Dimension { offset: 0, size: 0 }; N],
+            cells: Vec::new(),
+        }
+    }
+}
+
+impl<T: Clone + Default, const N: usize> DynamicGrid<T, N> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn flat_index(dims: &[Dimension; N], pos: Vector<isize, N>) -> Option<usize> {
+        dims.iter()
+            .zip(pos.0)
+            .try_fold(0, |acc, (dim, p)| Some(acc * dim.size + dim.index(p)?))
+    }
+
+    pub fn get(&self, pos: Vector<isize, N>) -> T {
+        Self::flat_index(&self.dims, pos)
+            .map(|i| self.cells[i].clone())
+            .unwrap_or_default()
+    }
+
+    /// Returns a mutable reference to the cell at `pos`, growing the grid
+    /// to include it first if necessary.
+    pub fn get_mut(&mut self, pos: Vector<isize, N>) -> &mut T {
+        self.grow_to_include(pos);
+        let i = Self::flat_index(&self.dims, pos).expect("just grown to include pos");
+        &mut self.cells[i]
+    }
+
+    pub fn set(&mut self, pos: Vector<isize, N>, value: T) {
+        *self.get_mut(pos) = value;
+    }
+
+    fn grow_to_include(&mut self, pos: Vector<isize, N>) {
+        let new_dims: [Dimension; N] =
+            std::array::from_fn(|axis| self.dims[axis].grown_to_include(pos[axis]));
+        if new_dims == self.dims {
+            return;
+        }
+
+        let new_size = new_dims.iter().map(|d| d.size).product();
+        let mut new_cells = vec![T::default(); new_size];
+        let old_ranges = self.dims.iter().map(|d| 0..d.size);
+        for old_local in old_ranges.multi_cartesian_product() {
+            let old_flat = old_local
+                .iter()
+                .zip(&self.dims)
+                .fold(0, |acc, (&l, dim)| acc * dim.size + l);
+            let new_local: Vec<usize> = old_local
+                .iter()
+                .zip(self.dims.iter().zip(&new_dims))
+                .map(|(&l, (old_dim, new_dim))| {
+                    (l as isize - old_dim.offset + new_dim.offset) as usize
+                })
+                .collect();
+            let new_flat = new_local
+                .iter()
+                .zip(&new_dims)
+                .fold(0, |acc, (&l, dim)| acc * dim.size + l);
+            new_cells[new_flat] = self.cells[old_flat].clone();
+        }
+
+        self.dims = new_dims;
+        self.cells = new_cells;
+    }
+}
+
+impl<const N: usize> DynamicGrid<bool, N> {
+    /// Every cell currently holding `true`.
+    pub fn active(&self) -> impl Iterator<Item = Vector<isize, N>> + '_ {
+        self.dims
+            .iter()
+            .map(|&dim| dim.into_iter())
+            .multi_cartesian_product()
+            .map(|coords| {
+                Vector(
+                    array::from_iter_exact(coords)
+                        .expect("multi_cartesian_product yields exactly N coordinates"),
+                )
+            })
+            .filter(|&pos| self.get(pos))
+    }
+
+    /// Advances a cellular automaton living on this grid by one generation.
+    /// The new extents are the tight bounding box of [`DynamicGrid::active`]
+    /// cells, [`Dimension::extended`] by one cell on every side of every
+    /// axis so a cell just outside it (which might come alive this
+    /// generation) is representable; `rule` is then evaluated, against the
+    /// *old* grid, at every coordinate of those padded extents to produce
+    /// the next generation's cells. Rederiving the bounding box from the
+    /// active cells each time (rather than simply padding the previous
+    /// extents) keeps a stable or oscillating pattern from accumulating dead
+    /// padding generation after generation.
+    pub fn step(&self, rule: impl Fn(&Self, Vector<isize, N>) -> bool) -> Self {
+        let tight_dims = self
+            .active()
+            .fold([Dimension { offset: 0, size: 0 }; N], |dims, pos| {
+                std::array::from_fn(|axis| dims[axis].grown_to_include(pos[axis]))
+            });
+        let new_dims: [Dimension; N] = std::array::from_fn(|axis| tight_dims[axis].extended());
+
+        let cells = new_dims
+            .iter()
+            .map(|&dim| dim.into_iter())
+            .multi_cartesian_product()
+            .map(|coords| {
+                let pos = Vector(
+                    array::from_iter_exact(coords)
+                        .expect("multi_cartesian_product yields exactly N coordinates"),
+                );
+                rule(self, pos)
+            })
+            .collect();
+
+        DynamicGrid {
+            dims: new_dims,
+            cells,
+        }
+    }
+}
+
+/// Renders a set of lit points, bounded by `bounds`, as one text line per
+/// row: `on` for a lit cell, `off` otherwise. Useful for eyeballing e.g. a
+/// `HashSet<Vector<i16, 2>>` of robot positions to confirm a clustering
+/// heuristic found the configuration it claims to have found.
+///
+/// PNG export isn't implemented here: it would pull in an image-encoding
+/// dependency this crate doesn't otherwise need, for a purely visual aid.
+pub fn render_points(
+    points: &std::collections::HashSet<Vector<i16, 2>>,
+    bounds: Vector<i16, 2>,
+    on: char,
+    off: char,
+) -> String {
+    (0..bounds[1])
+        .map(|y| {
+            (0..bounds[0])
+                .map(|x| {
+                    if points.contains(&Vector([x, y])) {
+                        on
+                    } else {
+                        off
+                    }
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Renders a byte-labeled map (as produced by [`parse_ascii_map`]) as one
+/// text line per row, passing each byte through `glyph`.
+pub fn render_map(map: &ndarray::Array2<u8>, mut glyph: impl FnMut(u8) -> char) -> String {
+    map.rows()
+        .into_iter()
+        .map(|row| row.iter().map(|&b| glyph(b)).collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Writes the frame rendered from each item `frames` yields to
+/// `{dir}/{index:04}.txt`, so a sequence produced by repeatedly stepping a
+/// simulation (e.g. via `step_n`) can be dumped to disk and inspected frame
+/// by frame. Returns the number of frames written.
+pub fn dump_frames<T>(
+    frames: impl Iterator<Item = T>,
+    dir: &std::path::Path,
+    mut render: impl FnMut(&T) -> String,
+) -> std::io::Result<usize> {
+    std::fs::create_dir_all(dir)?;
+    let mut written = 0;
+    for (i, frame) in frames.enumerate() {
+        std::fs::write(dir.join(format!("{i:04}.txt")), render(&frame))?;
+        written += 1;
+    }
+    Ok(written)
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::linalg::Vector;
+
     use super::*;
 
+    #[test]
+    fn count_connected_components() {
+        let live: HashSet<_> = [[0, 0], [1, 0], [3, 0], [3, 1], [0, 3]]
+            .into_iter()
+            .map(Vector::from)
+            .collect();
+        let candidates = (0..4)
+            .cartesian_product(0..4)
+            .map(|(x, y)| Vector::from([x, y]));
+        assert_eq!(connected_components(candidates, |p| live.contains(&p)), 3);
+    }
+
     #[test]
     fn find_bounds_of_map_input() {
         assert_eq!(map_bounds("ABC\nDEF\n"), [0..3, 0..2]);
     }
 
+    #[test]
+    fn normalize_strips_crlf_line_endings() {
+        assert_eq!(normalize_grid_input("AB\r\nCD\r\n").unwrap(), "AB\nCD");
+    }
+
+    #[test]
+    fn normalize_drops_a_single_trailing_blank_line() {
+        assert_eq!(normalize_grid_input("AB\nCD\n\n").unwrap(), "AB\nCD");
+    }
+
+    #[test]
+    fn normalize_rejects_ragged_rows() {
+        assert_eq!(
+            normalize_grid_input("AB\nC\n"),
+            Err(GridInputError::RaggedRow {
+                row: 1,
+                expected: 2,
+                found: 1,
+            })
+        );
+    }
+
     #[test]
     fn ascii_map() {
         assert_eq!(
@@ -117,4 +991,347 @@ mod tests {
             Err(ParseMapError::ConversionError(NotADigit))
         );
     }
+
+    #[test]
+    fn char_grid_pads_ragged_rows() {
+        let grid: CharGrid<u8> = "AB\nCDE\nF\n".parse().unwrap();
+        assert_eq!(grid.shape(), Vector([3, 3]));
+        assert_eq!(grid[Vector([0, 0])], b'A');
+        assert_eq!(grid[Vector([0, 2])], b' ');
+        assert_eq!(grid[Vector([1, 2])], b'E');
+        assert_eq!(grid[Vector([2, 1])], b' ');
+    }
+
+    #[test]
+    fn char_grid_bounds_and_neighbors() {
+        let grid: CharGrid<u8> = "AB\nCD\n".parse().unwrap();
+        assert!(grid.in_bounds(Vector([0, 0])));
+        assert!(!grid.in_bounds(Vector([-1, 0])));
+        assert!(!grid.in_bounds(Vector([2, 0])));
+
+        let mut neighbors: Vec<_> = grid.neighbors(Vector([0, 0])).collect();
+        neighbors.sort();
+        assert_eq!(neighbors, vec![Vector([0, 1]), Vector([1, 0])]);
+    }
+
+    #[test]
+    fn dynamic_grid_reads_default_for_untouched_cells() {
+        let grid: DynamicGrid<bool, 2> = DynamicGrid::new();
+        assert!(!grid.get(Vector([3, -7])));
+    }
+
+    #[test]
+    fn dynamic_grid_grows_to_fit_cells_visited_in_either_direction() {
+        let mut grid: DynamicGrid<i32, 2> = DynamicGrid::new();
+        grid.set(Vector([0, 0]), 1);
+        grid.set(Vector([-3, 5]), 2);
+        grid.set(Vector([2, -1]), 3);
+
+        assert_eq!(grid.get(Vector([0, 0])), 1);
+        assert_eq!(grid.get(Vector([-3, 5])), 2);
+        assert_eq!(grid.get(Vector([2, -1])), 3);
+        assert_eq!(grid.get(Vector([100, 100])), 0);
+    }
+
+    #[test]
+    fn dynamic_grid_step_advances_a_cellular_automaton_one_generation() {
+        let mut grid: DynamicGrid<bool, 2> = DynamicGrid::new();
+        for p in [[1, 0], [1, 1], [1, 2]] {
+            grid.set(Vector(p), true);
+        }
+
+        let life_rule = |grid: &DynamicGrid<bool, 2>, p: Vector<isize, 2>| {
+            let live_neighbors = p.neighbors().filter(|&n| grid.get(n)).count();
+            live_neighbors == 3 || (grid.get(p) && live_neighbors == 2)
+        };
+
+        // A blinker oscillates between a horizontal and a vertical bar.
+        let after_one = grid.step(life_rule);
+        assert_eq!(
+            after_one.active().collect::<HashSet<_>>(),
+            HashSet::from([Vector([0, 1]), Vector([1, 1]), Vector([2, 1])])
+        );
+
+        let after_two = after_one.step(life_rule);
+        assert_eq!(
+            after_two.active().collect::<HashSet<_>>(),
+            HashSet::from([Vector([1, 0]), Vector([1, 1]), Vector([1, 2])])
+        );
+    }
+
+    fn cluster(points: impl IntoIterator<Item = [usize; 2]>) -> Cluster {
+        Cluster(points.into_iter().map(Vector::from).collect())
+    }
+
+    #[test]
+    fn segment_by_groups_equal_neighbors() {
+        let map = parse_ascii_map("AAAA\nBBCD\nBBCC\nEEEC").unwrap();
+        let clusters = segment_by(
+            map.indexed_iter()
+                .map(|((x, y), &label)| (Vector([x, y]), label)),
+            |a, b| a == b,
+        );
+        assert_eq!(clusters.len(), 5);
+        assert_eq!(clusters.iter().map(Cluster::area).sum::<usize>(), 16);
+    }
+
+    #[test]
+    fn single_point_corner_count() {
+        assert_eq!(cluster([[0, 0]]).corner_count(), 4);
+    }
+
+    #[test]
+    fn square_corner_count() {
+        assert_eq!(cluster([[0, 0], [0, 1], [1, 0], [1, 1]]).corner_count(), 4);
+    }
+
+    #[test]
+    fn l_shaped_cluster_corner_count() {
+        assert_eq!(cluster([[0, 0], [0, 1], [1, 0]]).corner_count(), 6);
+    }
+
+    #[test]
+    fn o_shaped_cluster_corner_count() {
+        assert_eq!(
+            cluster([
+                [0, 0],
+                [0, 1],
+                [0, 2],
+                [1, 2],
+                [2, 2],
+                [2, 1],
+                [2, 0],
+                [1, 0],
+            ])
+            .corner_count(),
+            8
+        );
+    }
+
+    #[test]
+    fn c_shaped_cluster_corner_count() {
+        assert_eq!(
+            cluster([[2, 1], [3, 1], [3, 2], [3, 3], [2, 3]]).corner_count(),
+            8
+        );
+    }
+
+    #[test]
+    fn e_shaped_cluster_corner_count() {
+        assert_eq!(
+            cluster([
+                [0, 0],
+                [1, 0],
+                [2, 0],
+                [3, 0],
+                [4, 0],
+                [0, 1],
+                [0, 2],
+                [1, 2],
+                [2, 2],
+                [3, 2],
+                [4, 2],
+                [0, 3],
+                [0, 4],
+                [1, 4],
+                [2, 4],
+                [3, 4],
+                [4, 4],
+            ])
+            .corner_count(),
+            12
+        );
+    }
+
+    #[test]
+    fn bfs_finds_distances_along_a_corridor() {
+        let open: HashSet<_> = [[0, 0], [1, 0], [2, 0], [2, 1], [2, 2]]
+            .into_iter()
+            .map(Vector::from)
+            .collect();
+        let dist = bfs(Vector([0, 0]), |_| false, |p| open.contains(&p));
+        assert_eq!(dist.len(), 5);
+        assert_eq!(dist[&Vector([2, 2])], 4);
+    }
+
+    #[test]
+    fn bfs_stops_early_once_goal_is_reached() {
+        let open: HashSet<_> = (0..5).map(|x| Vector([x, 0])).collect();
+        let dist = bfs(
+            Vector([0, 0]),
+            |p| p == Vector([2, 0]),
+            |p| open.contains(&p),
+        );
+        assert_eq!(dist[&Vector([2, 0])], 2);
+        assert!(!dist.contains_key(&Vector([4, 0])));
+    }
+
+    const TOPOGRAPHIC_MAP: &str = "\
+89010123
+78121874
+87430965
+96549874
+45678903
+32019012
+01329801
+10456732";
+
+    fn climb(map: &ndarray::Array2<u8>, p: Vector<usize, 2>) -> Vec<Vector<usize, 2>> {
+        let next_height = map[p] + 1;
+        grid_neighbors(p, map)
+            .filter(move |&n| map[n] == next_height)
+            .collect_vec()
+    }
+
+    #[test]
+    fn bfs_reachable_climbs_one_height_at_a_time() {
+        let map = parse_ascii_map(TOPOGRAPHIC_MAP).unwrap();
+        let summits: HashSet<_> = bfs_reachable(Vector([0, 2]), |p| climb(&map, p), |_| true)
+            .into_iter()
+            .filter(|&p| map[p] == b'9')
+            .collect();
+        assert_eq!(summits.len(), 5);
+    }
+
+    #[test]
+    fn count_distinct_paths_sums_over_every_merging_route() {
+        let map = parse_ascii_map(TOPOGRAPHIC_MAP).unwrap();
+        assert_eq!(
+            count_distinct_paths(Vector([0, 2]), |p| climb(&map, p), |p| map[p] == b'9'),
+            20
+        );
+    }
+
+    #[test]
+    fn count_distinct_paths_ignores_dead_ends_that_never_reach_the_target() {
+        let map = parse_ascii_map("01\n90").unwrap();
+        assert_eq!(
+            count_distinct_paths(Vector([0, 0]), |p| climb(&map, p), |p| map[p] == b'9'),
+            0
+        );
+    }
+
+    #[test]
+    fn render_points_marks_lit_cells_within_bounds() {
+        let points = HashSet::from([Vector([0, 0]), Vector([2, 1])]);
+        assert_eq!(render_points(&points, Vector([3, 2]), '#', '.'), "#..\n..#");
+    }
+
+    #[test]
+    fn render_map_applies_glyph_per_row() {
+        let map = ndarray::array![[b'A', b'B'], [b'C', b'D']];
+        assert_eq!(
+            render_map(&map, |b| b.to_ascii_lowercase() as char),
+            "ab\ncd"
+        );
+    }
+
+    #[test]
+    fn grid_neighbors_excludes_out_of_bounds_points() {
+        let grid = parse_ascii_map("AB\nCD").unwrap();
+        assert_eq!(
+            grid_neighbors(Vector([0, 0]), &grid).sorted().collect_vec(),
+            vec![Vector([0, 1]), Vector([1, 0])],
+        );
+    }
+
+    #[test]
+    fn grid_neighbors_enables_pathfinding_over_a_parsed_map() {
+        let grid = parse_ascii_map("S..\n.#.\n..E").unwrap();
+        let start = Vector([0, 0]);
+        let end = Vector([2, 2]);
+        let (cost, path) = crate::graph::dijkstra_path(
+            start,
+            |&p| {
+                grid_neighbors(p, &grid)
+                    .filter(|&n| grid[n] != b'#')
+                    .map(|n| (n, 1))
+                    .collect::<Vec<_>>()
+            },
+            |&p| p == end,
+        )
+        .unwrap();
+        assert_eq!(cost, 4);
+        assert_eq!(path.first(), Some(&start));
+        assert_eq!(path.last(), Some(&end));
+    }
+
+    #[test]
+    fn dijkstra_prefers_cheaper_detour_over_expensive_direct_route() {
+        let open: HashSet<_> = [[0, 0], [1, 0], [2, 0], [0, 1], [1, 1], [2, 1]]
+            .into_iter()
+            .map(Vector::from)
+            .collect();
+        let dist = dijkstra(
+            Vector([0, 0]),
+            |p| p == Vector([2, 0]),
+            |p| open.contains(&p),
+            |_, to| if to == Vector([1, 0]) { 10 } else { 1 },
+        );
+        assert_eq!(dist[&Vector([2, 0])], 4);
+    }
+
+    #[test]
+    fn turn_right_cycles_through_all_four_headings_clockwise() {
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Right.turn_right(), Direction::Down);
+        assert_eq!(Direction::Down.turn_right(), Direction::Left);
+        assert_eq!(Direction::Left.turn_right(), Direction::Up);
+    }
+
+    #[test]
+    fn turn_left_is_the_inverse_of_turn_right() {
+        for dir in Direction::ALL {
+            assert_eq!(dir.turn_right().turn_left(), dir);
+        }
+    }
+
+    #[test]
+    fn rotate_right_matches_turning_the_corresponding_heading() {
+        for dir in Direction::ALL {
+            assert_eq!(
+                dir.offset::<i32>().rotate_right(),
+                dir.turn_right().offset()
+            );
+        }
+    }
+
+    #[test]
+    fn step_advances_by_one_unit_in_heading() {
+        assert_eq!(Vector([0, 0]).step(Direction::Up), Vector([0, -1]));
+        assert_eq!(Vector([0, 0]).step(Direction::Right), Vector([1, 0]));
+    }
+
+    #[test]
+    fn turtle_traces_the_corners_of_a_square() {
+        use Turn::*;
+        use TurtleMove::*;
+        let path: Vec<Vector<i32, 2>> = turtle(
+            Vector([0, 0]),
+            Direction::Right,
+            [
+                Advance(2),
+                Turn(Right),
+                Advance(2),
+                Turn(Right),
+                Advance(2),
+                Turn(Right),
+                Advance(2),
+            ],
+        )
+        .collect();
+        assert_eq!(
+            path,
+            [
+                Vector([1, 0]),
+                Vector([2, 0]),
+                Vector([2, 1]),
+                Vector([2, 2]),
+                Vector([1, 2]),
+                Vector([0, 2]),
+                Vector([0, 1]),
+                Vector([0, 0]),
+            ]
+        );
+    }
 }
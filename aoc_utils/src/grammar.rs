@@ -0,0 +1,363 @@
+//! A small context-free-grammar recognizer for the AoC puzzles whose input
+//! includes a numbered rule table (`8: 42 | 42 31`, `4: "a"`) that messages
+//! or tokens are matched against. A [`Grammar`] is an indexed table of
+//! [`Term`] trees; [`Grammar::matches`]/[`Grammar::derivations`] recognize
+//! an input against one of its rules via a memoized, offset-based packrat
+//! search, so that rules referencing themselves (directly or through a
+//! cycle) stay tractable as long as every path through a cycle consumes at
+//! least one byte of input.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use itertools::Itertools;
+
+/// One node of a rule's body: a literal byte, a reference to another rule
+/// by index, a sequence that must match back-to-back, or a choice between
+/// alternatives.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Term {
+    Terminal(u8),
+    Reference(usize),
+    Concat(Vec<Term>),
+    Alternation(Vec<Term>),
+}
+
+/// A single symbol of a sentential form used by [`Grammar::enumerate`]:
+/// either a byte that's already fixed, or a rule still to be expanded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Symbol {
+    Terminal(u8),
+    Reference(usize),
+}
+
+/// Expands `term` into the list of symbol sequences it can produce,
+/// distributing [`Term::Concat`] over nested [`Term::Alternation`]s.
+fn productions_of(term: &Term) -> Vec<Vec<Symbol>> {
+    match term {
+        Term::Terminal(b) => vec![vec![Symbol::Terminal(*b)]],
+        Term::Reference(idx) => vec![vec![Symbol::Reference(*idx)]],
+        Term::Concat(terms) => terms.iter().fold(vec![vec![]], |prefixes, term| {
+            let productions = productions_of(term);
+            prefixes
+                .iter()
+                .cartesian_product(&productions)
+                .map(|(prefix, suffix)| prefix.iter().chain(suffix).copied().collect())
+                .collect()
+        }),
+        Term::Alternation(alternatives) => alternatives.iter().flat_map(productions_of).collect(),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum GrammarError {
+    #[error("missing colon separating rule index from body: {0:?}")]
+    MissingColon(String),
+    #[error("failed to parse rule index in: {0:?}")]
+    InvalidIndex(String),
+    #[error("missing closing quotation mark in string literal rule: {0:?}")]
+    UnterminatedLiteral(String),
+    #[error("string literal rule is more than one ASCII character: {0:?}")]
+    MultiByteLiteral(String),
+    #[error("failed to parse rule reference index in: {0:?}")]
+    InvalidReference(String),
+}
+
+/// An indexed table of grammar rules, parsed from the puzzles' `idx: body`
+/// textual format or assembled programmatically via [`Grammar::set_rule`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grammar {
+    rules: Vec<Term>,
+}
+
+impl Grammar {
+    /// Parses one `idx: body` rule per line, e.g. `0: 4 1 5`, `1: 2 3 | 3 2`,
+    /// or `4: "a"`. Rule indices don't need to appear in order; gaps are
+    /// filled with an empty [`Term::Alternation`].
+    pub fn parse(input: &str) -> Result<Self, GrammarError> {
+        let mut grammar = Self { rules: Vec::new() };
+        for line in input.lines() {
+            let (idx, body) = line
+                .split_once(':')
+                .ok_or_else(|| GrammarError::MissingColon(line.to_owned()))?;
+            let idx: usize = idx
+                .trim()
+                .parse()
+                .map_err(|_| GrammarError::InvalidIndex(line.to_owned()))?;
+            grammar.set_rule(idx, parse_body(body)?);
+        }
+        Ok(grammar)
+    }
+
+    /// Overwrites rule `idx`, resizing the rule table (with empty
+    /// [`Term::Alternation`]s) if `idx` hasn't been seen yet. Lets a caller
+    /// patch rules after parsing, e.g. to splice in a recursive rule.
+    pub fn set_rule(&mut self, idx: usize, term: Term) {
+        if self.rules.len() <= idx {
+            self.rules
+                .resize_with(idx + 1, || Term::Alternation(vec![]));
+        }
+        self.rules[idx] = term;
+    }
+
+    /// Whether `input` matches rule `rule` in its entirety.
+    pub fn matches<C: AsRef<[u8]>>(&self, rule: usize, input: &C) -> bool {
+        let input = input.as_ref();
+        self.ends(rule, input).contains(&input.len())
+    }
+
+    /// All suffixes of `input` left over after some derivation of rule
+    /// `rule` consumes a prefix; `input` matches `rule` in full iff one of
+    /// these is empty.
+    pub fn derivations<'c>(&self, rule: usize, input: &'c [u8]) -> impl Iterator<Item = &'c [u8]> {
+        self.ends(rule, input)
+            .into_iter()
+            .map(|end| &input[end..])
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    /// Enumerates every string of at most `max_len` bytes that rule `rule`
+    /// derives, via breadth-first expansion of partial sentential forms:
+    /// starting from a single [`Symbol::Reference`] to `rule`, each step
+    /// replaces the leftmost [`Symbol::Reference`] with one of its
+    /// productions, emitting a form once it's gone fully terminal and
+    /// pruning any form whose already-committed terminal bytes already
+    /// exceed `max_len`.
+    pub fn enumerate(&self, rule: usize, max_len: usize) -> impl Iterator<Item = Vec<u8>> + '_ {
+        let mut queue = VecDeque::from([vec![Symbol::Reference(rule)]]);
+        std::iter::from_fn(move || {
+            while let Some(form) = queue.pop_front() {
+                let terminal_len = form
+                    .iter()
+                    .filter(|symbol| matches!(symbol, Symbol::Terminal(_)))
+                    .count();
+                if terminal_len > max_len {
+                    continue;
+                }
+
+                let Some(leftmost) = form
+                    .iter()
+                    .position(|symbol| matches!(symbol, Symbol::Reference(_)))
+                else {
+                    return Some(
+                        form.into_iter()
+                            .map(|symbol| match symbol {
+                                Symbol::Terminal(b) => b,
+                                Symbol::Reference(_) => unreachable!("just checked"),
+                            })
+                            .collect(),
+                    );
+                };
+
+                let Symbol::Reference(idx) = form[leftmost] else {
+                    unreachable!("position matched a Reference")
+                };
+                for production in productions_of(&self.rules[idx]) {
+                    let mut next = form[..leftmost].to_vec();
+                    next.extend(production);
+                    next.extend_from_slice(&form[leftmost + 1..]);
+                    queue.push_back(next);
+                }
+            }
+            None
+        })
+    }
+
+    fn ends(&self, rule: usize, input: &[u8]) -> HashSet<usize> {
+        let mut memo = HashMap::new();
+        self.ends_of(&Term::Reference(rule), 0, input, &mut memo)
+    }
+
+    /// The set of offsets `term` can end a match at, starting from `start`.
+    /// [`Term::Reference`] results are memoized by `(rule, start)`; because
+    /// every [`Term::Terminal`] strictly advances the offset, a
+    /// self-referencing rule's entry is never re-queried while it's still
+    /// being computed, as long as no rule derives the empty string.
+    fn ends_of(
+        &self,
+        term: &Term,
+        start: usize,
+        input: &[u8],
+        memo: &mut HashMap<(usize, usize), HashSet<usize>>,
+    ) -> HashSet<usize> {
+        match term {
+            Term::Terminal(b) => match input.get(start) {
+                Some(byte) if byte == b => HashSet::from([start + 1]),
+                _ => HashSet::new(),
+            },
+            Term::Reference(idx) => {
+                if let Some(ends) = memo.get(&(*idx, start)) {
+                    return ends.clone();
+                }
+                let ends = self.ends_of(&self.rules[*idx], start, input, memo);
+                memo.insert((*idx, start), ends.clone());
+                ends
+            }
+            Term::Concat(terms) => terms.iter().fold(HashSet::from([start]), |starts, term| {
+                starts
+                    .into_iter()
+                    .flat_map(|start| self.ends_of(term, start, input, memo))
+                    .collect()
+            }),
+            Term::Alternation(alternatives) => alternatives
+                .iter()
+                .flat_map(|term| self.ends_of(term, start, input, memo))
+                .collect(),
+        }
+    }
+}
+
+fn parse_body(body: &str) -> Result<Term, GrammarError> {
+    if let Some(lit_str) = body.trim().strip_prefix('"') {
+        let lit_str = lit_str
+            .strip_suffix('"')
+            .ok_or_else(|| GrammarError::UnterminatedLiteral(body.to_owned()))?;
+        let &[byte] = lit_str.as_bytes() else {
+            return Err(GrammarError::MultiByteLiteral(body.to_owned()));
+        };
+        return Ok(Term::Terminal(byte));
+    }
+
+    let alternatives = body
+        .split('|')
+        .map(|alt| {
+            let terms = alt
+                .split_whitespace()
+                .map(|ref_str| {
+                    ref_str
+                        .parse()
+                        .map(Term::Reference)
+                        .map_err(|_| GrammarError::InvalidReference(body.to_owned()))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(Term::Concat(terms))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Term::Alternation(alternatives))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = r#"0: 4 1 5
+1: 2 3 | 3 2
+2: 4 4 | 5 5
+3: 4 5 | 5 4
+4: "a"
+5: "b""#;
+
+    fn example_grammar() -> Grammar {
+        Grammar {
+            rules: vec![
+                Term::Alternation(vec![Term::Concat(vec![
+                    Term::Reference(4),
+                    Term::Reference(1),
+                    Term::Reference(5),
+                ])]),
+                Term::Alternation(vec![
+                    Term::Concat(vec![Term::Reference(2), Term::Reference(3)]),
+                    Term::Concat(vec![Term::Reference(3), Term::Reference(2)]),
+                ]),
+                Term::Alternation(vec![
+                    Term::Concat(vec![Term::Reference(4), Term::Reference(4)]),
+                    Term::Concat(vec![Term::Reference(5), Term::Reference(5)]),
+                ]),
+                Term::Alternation(vec![
+                    Term::Concat(vec![Term::Reference(4), Term::Reference(5)]),
+                    Term::Concat(vec![Term::Reference(5), Term::Reference(4)]),
+                ]),
+                Term::Terminal(b'a'),
+                Term::Terminal(b'b'),
+            ],
+        }
+    }
+
+    #[test]
+    fn example_input_is_parsed() {
+        assert_eq!(Grammar::parse(EXAMPLE_INPUT).unwrap(), example_grammar());
+    }
+
+    #[test]
+    fn gaps_in_rule_indices_are_filled_with_an_empty_alternation() {
+        let grammar = Grammar::parse("2: \"a\"").unwrap();
+        assert_eq!(
+            grammar,
+            Grammar {
+                rules: vec![
+                    Term::Alternation(vec![]),
+                    Term::Alternation(vec![]),
+                    Term::Terminal(b'a'),
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn matches_against_example_messages() {
+        let grammar = example_grammar();
+        let messages: &[&[u8]] = &[b"ababbb", b"bababa", b"abbbab", b"aaabbb", b"aaaabbb"];
+        itertools::assert_equal(
+            messages.iter().map(|m| grammar.matches(0, m)),
+            [true, false, true, false, false],
+        );
+    }
+
+    #[test]
+    fn derivations_yield_every_leftover_suffix() {
+        let grammar = example_grammar();
+        itertools::assert_equal(grammar.derivations(4, b"ababbb"), [b"babbb".as_slice()]);
+        itertools::assert_equal(grammar.derivations(0, b"ababbb"), [b"".as_slice()]);
+    }
+
+    #[test]
+    fn enumerate_a_literal_rule() {
+        let grammar = example_grammar();
+        itertools::assert_equal(grammar.enumerate(4, 5), [b"a".to_vec()]);
+    }
+
+    #[test]
+    fn enumerate_prunes_forms_longer_than_max_len() {
+        let grammar = example_grammar();
+        assert_eq!(grammar.enumerate(0, 3).next(), None);
+    }
+
+    #[test]
+    fn enumerate_lists_every_string_up_to_the_max_length() {
+        let grammar = example_grammar();
+        let mut strings: Vec<Vec<u8>> = grammar.enumerate(0, 6).collect();
+        strings.sort();
+        assert_eq!(
+            strings,
+            [
+                b"aaaabb".to_vec(),
+                b"aaabab".to_vec(),
+                b"aabaab".to_vec(),
+                b"aabbbb".to_vec(),
+                b"abaaab".to_vec(),
+                b"ababbb".to_vec(),
+                b"abbabb".to_vec(),
+                b"abbbab".to_vec(),
+            ]
+        );
+    }
+
+    #[test]
+    fn set_rule_can_splice_in_a_recursive_rule() {
+        let mut grammar = Grammar::parse(
+            r#"0: 1 | 1 0
+1: "a""#,
+        )
+        .unwrap();
+        grammar.set_rule(
+            0,
+            Term::Alternation(vec![
+                Term::Concat(vec![Term::Reference(1)]),
+                Term::Concat(vec![Term::Reference(1), Term::Reference(0)]),
+            ]),
+        );
+        assert!(grammar.matches(0, b"aaa"));
+        assert!(!grammar.matches(0, b"aab"));
+    }
+}
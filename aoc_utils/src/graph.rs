@@ -0,0 +1,738 @@
+//! Generic weighted-graph shortest-path search, for callers with their own
+//! notion of "node" and "neighbor" (as opposed to [`crate::geometry`]'s
+//! `dijkstra`/`bfs`, which are specialized to [`crate::geometry::Point`]
+//! grids).
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::fmt::Display;
+use std::hash::Hash;
+use std::ops::Add;
+
+use crate::array;
+use crate::geometry::Point;
+
+/// Finds the shortest distance from `start` to every node reachable via
+/// `neighbors` (which, given a node, yields its successors paired with the
+/// cost of the edge leading to them), stopping early once a node satisfying
+/// `is_goal` is popped (pass `|_| false` to compute the full reachable
+/// distance field instead).
+pub fn dijkstra<Node, Cost, I>(
+    start: Node,
+    mut neighbors: impl FnMut(&Node) -> I,
+    mut is_goal: impl FnMut(&Node) -> bool,
+) -> HashMap<Node, Cost>
+where
+    Node: Eq + Hash + Clone + Ord,
+    Cost: Ord + Copy + Add<Output = Cost> + Default,
+    I: IntoIterator<Item = (Node, Cost)>,
+{
+    let mut dist = HashMap::from([(start.clone(), Cost::default())]);
+    let mut heap = BinaryHeap::from([Reverse((Cost::default(), start))]);
+
+    while let Some(Reverse((d, node))) = heap.pop() {
+        if dist.get(&node).is_some_and(|&best| d > best) {
+            continue;
+        }
+        if is_goal(&node) {
+            break;
+        }
+        for (next, edge_cost) in neighbors(&node) {
+            let next_dist = d + edge_cost;
+            if dist.get(&next).is_none_or(|&best| next_dist < best) {
+                dist.insert(next.clone(), next_dist);
+                heap.push(Reverse((next_dist, next)));
+            }
+        }
+    }
+
+    dist
+}
+
+/// Like [`dijkstra`], but stops at the first node satisfying `is_goal` and
+/// returns its distance together with the path taken to reach it,
+/// reconstructed from a predecessor map built up alongside the distances.
+pub fn dijkstra_path<Node, Cost, I>(
+    start: Node,
+    mut neighbors: impl FnMut(&Node) -> I,
+    mut is_goal: impl FnMut(&Node) -> bool,
+) -> Option<(Cost, Vec<Node>)>
+where
+    Node: Eq + Hash + Clone + Ord,
+    Cost: Ord + Copy + Add<Output = Cost> + Default,
+    I: IntoIterator<Item = (Node, Cost)>,
+{
+    let mut dist = HashMap::from([(start.clone(), Cost::default())]);
+    let mut came_from: HashMap<Node, Node> = HashMap::new();
+    let mut heap = BinaryHeap::from([Reverse((Cost::default(), start))]);
+
+    while let Some(Reverse((d, node))) = heap.pop() {
+        if dist.get(&node).is_some_and(|&best| d > best) {
+            continue;
+        }
+        if is_goal(&node) {
+            return Some((d, reconstruct_path(&came_from, node)));
+        }
+        for (next, edge_cost) in neighbors(&node) {
+            let next_dist = d + edge_cost;
+            if dist.get(&next).is_none_or(|&best| next_dist < best) {
+                dist.insert(next.clone(), next_dist);
+                came_from.insert(next.clone(), node.clone());
+                heap.push(Reverse((next_dist, next)));
+            }
+        }
+    }
+
+    None
+}
+
+/// Finds the shortest distance from `start` to `goal` via `neighbors`,
+/// ordering the frontier by `cost-so-far + heuristic` rather than
+/// cost-so-far alone. `heuristic` must never overestimate the true
+/// remaining distance to `goal`, or the result may not be optimal.
+pub fn a_star<Node, Cost, I>(
+    start: Node,
+    goal: Node,
+    mut neighbors: impl FnMut(&Node) -> I,
+    heuristic: impl Fn(&Node) -> Cost,
+) -> Option<Cost>
+where
+    Node: Eq + Hash + Clone + Ord,
+    Cost: Ord + Copy + Add<Output = Cost> + Default,
+    I: IntoIterator<Item = (Node, Cost)>,
+{
+    let mut dist = HashMap::from([(start.clone(), Cost::default())]);
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), Cost::default(), start))]);
+
+    while let Some(Reverse((_, d, node))) = heap.pop() {
+        if dist.get(&node).is_some_and(|&best| d > best) {
+            continue;
+        }
+        if node == goal {
+            return Some(d);
+        }
+        for (next, edge_cost) in neighbors(&node) {
+            let next_dist = d + edge_cost;
+            if dist.get(&next).is_none_or(|&best| next_dist < best) {
+                dist.insert(next.clone(), next_dist);
+                heap.push(Reverse((next_dist + heuristic(&next), next_dist, next)));
+            }
+        }
+    }
+
+    None
+}
+
+/// Like [`a_star`], but also returns the path taken to `goal`,
+/// reconstructed the same way as [`dijkstra_path`].
+pub fn a_star_path<Node, Cost, I>(
+    start: Node,
+    goal: Node,
+    mut neighbors: impl FnMut(&Node) -> I,
+    heuristic: impl Fn(&Node) -> Cost,
+) -> Option<(Cost, Vec<Node>)>
+where
+    Node: Eq + Hash + Clone + Ord,
+    Cost: Ord + Copy + Add<Output = Cost> + Default,
+    I: IntoIterator<Item = (Node, Cost)>,
+{
+    let mut dist = HashMap::from([(start.clone(), Cost::default())]);
+    let mut came_from: HashMap<Node, Node> = HashMap::new();
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), Cost::default(), start))]);
+
+    while let Some(Reverse((_, d, node))) = heap.pop() {
+        if dist.get(&node).is_some_and(|&best| d > best) {
+            continue;
+        }
+        if node == goal {
+            return Some((d, reconstruct_path(&came_from, node)));
+        }
+        for (next, edge_cost) in neighbors(&node) {
+            let next_dist = d + edge_cost;
+            if dist.get(&next).is_none_or(|&best| next_dist < best) {
+                dist.insert(next.clone(), next_dist);
+                came_from.insert(next.clone(), node.clone());
+                heap.push(Reverse((next_dist + heuristic(&next), next_dist, next)));
+            }
+        }
+    }
+
+    None
+}
+
+/// Walks a `came_from` predecessor map backwards from `end` to whichever
+/// node has no predecessor (the search's start), then reverses the result
+/// into start-to-end order.
+fn reconstruct_path<Node: Eq + Hash + Clone>(
+    came_from: &HashMap<Node, Node>,
+    end: Node,
+) -> Vec<Node> {
+    let mut path = vec![end];
+    while let Some(prev) = came_from.get(path.last().unwrap()) {
+        path.push(prev.clone());
+    }
+    path.reverse();
+    path
+}
+
+/// Finds every maximal clique (a fully-connected vertex set not contained in
+/// any larger one) in an undirected graph given as an adjacency map, via
+/// Bron–Kerbosch with pivoting.
+pub fn maximal_cliques<N>(adjacency: &HashMap<N, HashSet<N>>) -> Vec<HashSet<N>>
+where
+    N: Eq + Hash + Clone,
+{
+    let mut cliques = Vec::new();
+    bron_kerbosch(
+        HashSet::new(),
+        adjacency.keys().cloned().collect(),
+        HashSet::new(),
+        adjacency,
+        &mut cliques,
+    );
+    cliques
+}
+
+/// Recursively extends the current clique `r` with candidates from `p`,
+/// moving each into `x` once explored so it isn't reconsidered by a later
+/// sibling call. To cut down on redundant branches, a pivot `u` (the vertex
+/// in `p ∪ x` with the most neighbors already in `p`) is chosen up front,
+/// and only `p`'s non-neighbors of `u` are tried -- any clique extension
+/// through a neighbor of `u` is guaranteed to be found via `u`'s own branch
+/// instead.
+fn bron_kerbosch<N>(
+    r: HashSet<N>,
+    mut p: HashSet<N>,
+    mut x: HashSet<N>,
+    adjacency: &HashMap<N, HashSet<N>>,
+    cliques: &mut Vec<HashSet<N>>,
+) where
+    N: Eq + Hash + Clone,
+{
+    if p.is_empty() && x.is_empty() {
+        cliques.push(r);
+        return;
+    }
+
+    let pivot = p
+        .iter()
+        .chain(x.iter())
+        .max_by_key(|u| adjacency[*u].intersection(&p).count())
+        .unwrap();
+    let candidates: Vec<N> = p.difference(&adjacency[pivot]).cloned().collect();
+
+    for v in candidates {
+        let neighbors = &adjacency[&v];
+        let mut extended = r.clone();
+        extended.insert(v.clone());
+        bron_kerbosch(
+            extended,
+            p.intersection(neighbors).cloned().collect(),
+            x.intersection(neighbors).cloned().collect(),
+            adjacency,
+            cliques,
+        );
+        p.remove(&v);
+        x.insert(v);
+    }
+}
+
+/// Renders an adjacency map as Graphviz DOT source, for dumping a graph-shaped
+/// `Door`'s input to a `.dot` file when a path count looks wrong and
+/// `{:?}`-printing the map isn't illuminating. `highlight` styles specific
+/// nodes (e.g. a puzzle's named endpoints) by mapping them to a DOT
+/// attribute list such as `"style=filled,fillcolor=red"`; nodes are emitted
+/// in `Display`-string order so the output is stable across runs despite
+/// `adjacency`'s hashing order.
+pub fn to_dot<N>(adjacency: &HashMap<N, HashSet<N>>, highlight: &HashMap<N, &str>) -> String
+where
+    N: Eq + Hash + Display,
+{
+    let mut nodes: Vec<&N> = adjacency.keys().collect();
+    nodes.sort_by_key(|n| n.to_string());
+
+    let mut dot = String::from("digraph {\n");
+    for node in &nodes {
+        if let Some(attrs) = highlight.get(*node) {
+            let label = node.to_string();
+            dot.push_str(&format!("    {label:?} [{attrs}];\n"));
+        }
+    }
+    for node in &nodes {
+        let mut targets: Vec<&N> = adjacency[*node].iter().collect();
+        targets.sort_by_key(|n| n.to_string());
+        for target in targets {
+            dot.push_str(&format!(
+                "    {:?} -> {:?};\n",
+                node.to_string(),
+                target.to_string()
+            ));
+        }
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+/// A facing-aware vertex in a junction-compressed grid graph: a junction
+/// point paired with the direction ("facing") it's entered or departed
+/// with, where a facing is the index into `position`'s
+/// [`Point::nearest_neighbors`] order.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Vertex<P> {
+    pub position: P,
+    pub facing: usize,
+}
+
+/// An edge between two junction vertices: the destination, the
+/// accumulated cost of the whole corridor between them, and every point
+/// walked over along the way (so an optimal path's tiles can be collected
+/// without re-walking the corridor).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Edge<P> {
+    pub target: Vertex<P>,
+    pub distance: usize,
+    pub nodes: HashSet<P>,
+}
+
+/// A grid contracted down to its junctions: vertices are (junction,
+/// facing) pairs, and each vertex has up to `N` outgoing edges, one per
+/// facing reachable from it (either by walking a corridor to the next
+/// junction, or by turning in place to face a different passable exit).
+pub type ContractedGraph<P, const N: usize> = HashMap<Vertex<P>, [Option<Edge<P>>; N]>;
+
+/// Compresses a grid into a [`ContractedGraph`]. `passable` marks which
+/// points may be walked on; `interesting` additionally marks points (e.g.
+/// the start/end of a maze) that must become vertices even where the
+/// corridor doesn't branch. `turn_cost(prev_facing, next_facing)` gives
+/// the penalty of changing from one facing to another on top of the
+/// unconditional unit cost of stepping onto an adjacent point; it should
+/// return `0` when the two facings are equal.
+///
+/// Every point reachable from `all_points` that is passable and either
+/// branches (more than two passable neighbors) or is `interesting`
+/// becomes a junction. Each junction vertex has one edge per facing:
+/// either the result of walking the corridor in that direction until the
+/// next junction is reached (turning, at `turn_cost`, wherever the
+/// corridor bends), or — if that facing's immediate neighbor isn't
+/// passable — no edge at all; rotating to a different facing in place
+/// (without moving) is modeled as a zero-distance edge back to the same
+/// position at `turn_cost(prev_facing, next_facing)`.
+pub fn contract_grid<P, const N: usize>(
+    all_points: impl IntoIterator<Item = P>,
+    passable: impl Fn(P) -> bool,
+    interesting: impl Fn(P) -> bool,
+    turn_cost: impl Fn(usize, usize) -> usize,
+) -> ContractedGraph<P, N>
+where
+    P: Point + Eq + Hash + Ord,
+{
+    let junctions: HashSet<P> = all_points
+        .into_iter()
+        .filter(|&p| passable(p))
+        .filter(|&p| interesting(p) || p.nearest_neighbors().filter(|&n| passable(n)).count() > 2)
+        .collect();
+
+    junctions
+        .iter()
+        .flat_map(|&p| {
+            (0..N).map(move |facing_from| {
+                (
+                    Vertex {
+                        position: p,
+                        facing: facing_from,
+                    },
+                    array::from_iter_exact(p.nearest_neighbors().enumerate().map(
+                        |(facing_to, n)| {
+                            if facing_from == facing_to {
+                                passable(n)
+                                    .then(|| {
+                                        std::iter::successors(
+                                            Some((facing_from, n, p)),
+                                            |&(_, current, prev)| {
+                                                current
+                                                    .nearest_neighbors()
+                                                    .enumerate()
+                                                    .filter(|&(_, nn)| passable(nn))
+                                                    .find(|&(_, nn)| nn != prev)
+                                                    .map(|(dir, nn)| (dir, nn, current))
+                                            },
+                                        )
+                                        .scan(
+                                            (facing_from, 0usize, HashSet::new()),
+                                            |(dd, dist, nodes), (dir, point, _)| {
+                                                *dist += 1 + turn_cost(*dd, dir);
+                                                *dd = dir;
+                                                nodes.insert(point);
+                                                Some(Edge {
+                                                    target: Vertex {
+                                                        position: point,
+                                                        facing: dir,
+                                                    },
+                                                    distance: *dist,
+                                                    nodes: nodes.clone(),
+                                                })
+                                            },
+                                        )
+                                        .find(|edge| junctions.contains(&edge.target.position))
+                                    })
+                                    .flatten()
+                            } else {
+                                passable(n).then(|| Edge {
+                                    target: Vertex {
+                                        position: p,
+                                        facing: facing_to,
+                                    },
+                                    distance: turn_cost(facing_from, facing_to),
+                                    nodes: HashSet::new(),
+                                })
+                            }
+                        },
+                    ))
+                    .unwrap(),
+                )
+            })
+        })
+        .collect()
+}
+
+/// The minimum distance from `start` to any vertex positioned at `end`
+/// (any entry facing), via [`dijkstra`] over a [`ContractedGraph`].
+pub fn shortest_path<P, const N: usize>(
+    graph: &ContractedGraph<P, N>,
+    start: Vertex<P>,
+    end: &P,
+) -> Option<usize>
+where
+    P: Eq + Hash + Clone + Ord,
+{
+    let dist = contracted_distances(graph, start);
+    (0..N)
+        .filter_map(|facing| {
+            dist.get(&Vertex {
+                position: end.clone(),
+                facing,
+            })
+            .copied()
+        })
+        .min()
+}
+
+/// Like [`shortest_path`], but orders the search frontier by
+/// `cost-so-far + heuristic(vertex)` via [`a_star`] instead of plain
+/// Dijkstra, letting a tight heuristic prune away corridors that can't lead
+/// to a shorter route. `heuristic` must never overestimate the true
+/// remaining distance to `end`, or the result may not be optimal.
+pub fn shortest_path_a_star<P, const N: usize>(
+    graph: &ContractedGraph<P, N>,
+    start: Vertex<P>,
+    end: &P,
+    heuristic: impl Fn(&Vertex<P>) -> usize,
+) -> Option<usize>
+where
+    P: Eq + Hash + Clone + Ord,
+{
+    (0..N)
+        .filter_map(|facing| {
+            a_star(
+                start.clone(),
+                Vertex {
+                    position: end.clone(),
+                    facing,
+                },
+                |current| {
+                    graph
+                        .get(current)
+                        .into_iter()
+                        .flatten()
+                        .flatten()
+                        .map(|edge| (edge.target.clone(), edge.distance))
+                        .collect::<Vec<_>>()
+                },
+                &heuristic,
+            )
+        })
+        .min()
+}
+
+/// Every point lying on *some* shortest path from `start` to `end` (any
+/// entry/exit facing): a [`dijkstra`] distance pass, followed by a
+/// backward walk from whichever end vertex achieves the optimum, keeping
+/// a predecessor edge `u -> v` iff `dist[u] + edge.distance == dist[v]`.
+pub fn optimal_path_nodes<P, const N: usize>(
+    graph: &ContractedGraph<P, N>,
+    start: Vertex<P>,
+    end: &P,
+) -> HashSet<P>
+where
+    P: Eq + Hash + Clone + Ord,
+{
+    let dist = contracted_distances(graph, start);
+    let Some(best) = (0..N)
+        .filter_map(|facing| {
+            dist.get(&Vertex {
+                position: end.clone(),
+                facing,
+            })
+            .copied()
+        })
+        .min()
+    else {
+        return HashSet::new();
+    };
+
+    let predecessors: HashMap<&Vertex<P>, Vec<(&Vertex<P>, &Edge<P>)>> = graph
+        .iter()
+        .flat_map(|(u, edges)| {
+            edges
+                .iter()
+                .flatten()
+                .map(move |edge| (&edge.target, (u, edge)))
+        })
+        .fold(HashMap::new(), |mut acc, (target, pred)| {
+            acc.entry(target).or_default().push(pred);
+            acc
+        });
+
+    let mut nodes = HashSet::new();
+    let mut stack: Vec<&Vertex<P>> = (0..N)
+        .filter_map(|facing| {
+            dist.get_key_value(&Vertex {
+                position: end.clone(),
+                facing,
+            })
+            .filter(|(_, &d)| d == best)
+            .map(|(v, _)| v)
+        })
+        .collect();
+    let mut visited = HashSet::new();
+    while let Some(v) = stack.pop() {
+        if !visited.insert(v) {
+            continue;
+        }
+        let Some(&dv) = dist.get(v) else { continue };
+        for &(u, edge) in predecessors.get(v).into_iter().flatten() {
+            if dist.get(u).is_some_and(|&du| du + edge.distance == dv) {
+                nodes.extend(edge.nodes.iter().cloned());
+                stack.push(u);
+            }
+        }
+    }
+    nodes
+}
+
+fn contracted_distances<P, const N: usize>(
+    graph: &ContractedGraph<P, N>,
+    start: Vertex<P>,
+) -> HashMap<Vertex<P>, usize>
+where
+    P: Eq + Hash + Clone + Ord,
+{
+    dijkstra(
+        start,
+        |current| {
+            graph
+                .get(current)
+                .into_iter()
+                .flatten()
+                .flatten()
+                .map(|edge| (edge.target.clone(), edge.distance))
+                .collect::<Vec<_>>()
+        },
+        |_| false,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use super::*;
+
+    #[test]
+    fn dijkstra_computes_full_distance_field_over_a_small_graph() {
+        let edges: HashMap<char, Vec<(char, usize)>> = HashMap::from([
+            ('a', vec![('b', 1), ('c', 4)]),
+            ('b', vec![('c', 1)]),
+            ('c', vec![]),
+        ]);
+        let dist = dijkstra('a', |n| edges[n].clone(), |_| false);
+        assert_eq!(dist[&'a'], 0);
+        assert_eq!(dist[&'b'], 1);
+        assert_eq!(dist[&'c'], 2);
+    }
+
+    #[test]
+    fn dijkstra_stops_early_at_goal() {
+        let edges: HashMap<char, Vec<(char, usize)>> =
+            HashMap::from([('a', vec![('b', 1)]), ('b', vec![('c', 1)]), ('c', vec![])]);
+        let dist = dijkstra('a', |n| edges[n].clone(), |&n| n == 'b');
+        assert_eq!(dist[&'b'], 1);
+        assert!(!dist.contains_key(&'c'));
+    }
+
+    #[test]
+    fn dijkstra_path_reconstructs_the_shortest_route() {
+        let edges: HashMap<char, Vec<(char, usize)>> = HashMap::from([
+            ('a', vec![('b', 1), ('c', 4)]),
+            ('b', vec![('c', 1)]),
+            ('c', vec![]),
+        ]);
+        let (dist, path) = dijkstra_path('a', |n| edges[n].clone(), |&n| n == 'c').unwrap();
+        assert_eq!(dist, 2);
+        assert_eq!(path, vec!['a', 'b', 'c']);
+    }
+
+    #[test]
+    fn a_star_finds_shortest_path_with_admissible_heuristic() {
+        // a -- 1 -- b -- 1 -- d
+        // \-------- 5 --------/
+        let edges: HashMap<&str, Vec<(&str, usize)>> = HashMap::from([
+            ("a", vec![("b", 1), ("d", 5)]),
+            ("b", vec![("d", 1)]),
+            ("d", vec![]),
+        ]);
+        let cost = a_star("a", "d", |n| edges[n].clone(), |_| 0);
+        assert_eq!(cost, Some(2));
+    }
+
+    #[test]
+    fn a_star_path_reconstructs_the_shortest_route() {
+        let edges: HashMap<&str, Vec<(&str, usize)>> = HashMap::from([
+            ("a", vec![("b", 1), ("d", 5)]),
+            ("b", vec![("d", 1)]),
+            ("d", vec![]),
+        ]);
+        let (dist, path) = a_star_path("a", "d", |n| edges[n].clone(), |_| 0).unwrap();
+        assert_eq!(dist, 2);
+        assert_eq!(path, vec!["a", "b", "d"]);
+    }
+
+    #[test]
+    fn maximal_cliques_finds_every_maximal_fully_connected_subset() {
+        // a - b - c
+        // | X |
+        // d - e   f - g
+        let adjacency: HashMap<char, HashSet<char>> = HashMap::from([
+            ('a', HashSet::from(['b', 'd', 'e'])),
+            ('b', HashSet::from(['a', 'c', 'd', 'e'])),
+            ('c', HashSet::from(['b'])),
+            ('d', HashSet::from(['a', 'b', 'e'])),
+            ('e', HashSet::from(['a', 'b', 'd'])),
+            ('f', HashSet::from(['g'])),
+            ('g', HashSet::from(['f'])),
+        ]);
+        let cliques: HashSet<Vec<char>> = maximal_cliques(&adjacency)
+            .into_iter()
+            .map(|clique| clique.into_iter().sorted().collect())
+            .collect();
+        assert_eq!(
+            cliques,
+            HashSet::from([vec!['b', 'c'], vec!['f', 'g'], vec!['a', 'b', 'd', 'e']])
+        );
+    }
+
+    #[test]
+    fn to_dot_renders_sorted_edges_with_highlighted_nodes() {
+        let adjacency: HashMap<&str, HashSet<&str>> = HashMap::from([
+            ("a", HashSet::from(["c", "b"])),
+            ("b", HashSet::from(["c"])),
+            ("c", HashSet::new()),
+        ]);
+        let highlight = HashMap::from([("a", "style=filled,fillcolor=green")]);
+        assert_eq!(
+            to_dot(&adjacency, &highlight),
+            "digraph {\n    \"a\" [style=filled,fillcolor=green];\n    \"a\" -> \"b\";\n    \"a\" -> \"c\";\n    \"b\" -> \"c\";\n}\n"
+        );
+    }
+
+    mod junction_graph {
+        use super::*;
+        use crate::linalg::Vector;
+
+        const MAZE: &str = "\
+#######
+#S....#
+#.###.#
+#.....#
+#.#.#.#
+#...#E#
+#######";
+
+        fn turn_cost(prev: usize, next: usize) -> usize {
+            if prev == next {
+                0
+            } else {
+                1000
+            }
+        }
+
+        fn maze_graph() -> (
+            ContractedGraph<Vector<usize, 2>, 4>,
+            Vector<usize, 2>,
+            Vector<usize, 2>,
+        ) {
+            let rows: Vec<&[u8]> = MAZE.lines().map(str::as_bytes).collect();
+            let at = |p: Vector<usize, 2>| rows[p.0[1]][p.0[0]];
+            let start = (0..7)
+                .flat_map(|x| (0..7).map(move |y| Vector([x, y])))
+                .find(|&p| at(p) == b'S')
+                .unwrap();
+            let end = (0..7)
+                .flat_map(|x| (0..7).map(move |y| Vector([x, y])))
+                .find(|&p| at(p) == b'E')
+                .unwrap();
+            let all_points = (0..7).flat_map(|x| (0..7).map(move |y| Vector([x, y])));
+            let graph = contract_grid(
+                all_points,
+                |p: Vector<usize, 2>| at(p) != b'#',
+                |p| p == start || p == end,
+                turn_cost,
+            );
+            (graph, start, end)
+        }
+
+        #[test]
+        fn shortest_path_avoids_unnecessary_turns() {
+            let (graph, start, end) = maze_graph();
+            let distance = shortest_path(
+                &graph,
+                Vertex {
+                    position: start,
+                    facing: 0,
+                },
+                &end,
+            );
+            assert_eq!(distance, Some(1008));
+        }
+
+        #[test]
+        fn a_star_agrees_with_dijkstra_given_a_trivial_heuristic() {
+            let (graph, start, end) = maze_graph();
+            let distance = shortest_path_a_star(
+                &graph,
+                Vertex {
+                    position: start,
+                    facing: 0,
+                },
+                &end,
+                |_| 0,
+            );
+            assert_eq!(distance, Some(1008));
+        }
+
+        #[test]
+        fn optimal_path_nodes_cover_the_shortest_route() {
+            let (graph, start, end) = maze_graph();
+            let nodes = optimal_path_nodes(
+                &graph,
+                Vertex {
+                    position: start,
+                    facing: 0,
+                },
+                &end,
+            );
+            assert!(nodes.contains(&end));
+            assert_eq!(nodes.len() + 1, 9);
+        }
+    }
+}
@@ -0,0 +1,15 @@
+//! A fast, non-cryptographic hasher for internal lookup tables where
+//! hash-flooding resistance doesn't matter — memoization caches, adjacency
+//! maps built from puzzle input, and the like. `std`'s default `HashMap`
+//! hasher (`SipHash`) is the right choice when an adversary controls the
+//! keys; here, we don't have one, and the extra mixing is wasted cycles on
+//! the hot path of every memoized recursive day.
+
+/// The hasher used by [`FastHashMap`]. An alias rather than a re-export of
+/// [`ahash::RandomState`] so callers don't need to depend on `ahash`
+/// directly to name the type.
+pub type FastHasher = ahash::RandomState;
+
+/// A drop-in [`std::collections::HashMap`] replacement backed by
+/// [`FastHasher`] instead of `SipHash`.
+pub type FastHashMap<K, V> = std::collections::HashMap<K, V, FastHasher>;
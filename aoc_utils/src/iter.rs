@@ -1,10 +1,47 @@
 use std::{
-    collections::{BTreeMap, HashMap},
+    cmp::Ordering,
+    collections::{BTreeMap, BinaryHeap, HashMap},
+    fmt,
     iter::{FusedIterator, Sum},
+    mem::MaybeUninit,
     rc::Rc,
 };
 
 pub trait IterUtils: Iterator {
+    /// The `k` smallest items by `key_fn`, in ascending order, without
+    /// collecting and sorting the whole iterator first: a bounded max-heap
+    /// of capacity `k` is filled, then each further item only replaces the
+    /// heap's current maximum if it's smaller, so memory stays `O(k)` and
+    /// time is `O(n log k)` rather than `O(n log n)`. Ties are broken by
+    /// original iteration order, same as a stable full sort followed by
+    /// `.take(k)` would.
+    fn k_smallest_by_key<K, F>(self, k: usize, mut key_fn: F) -> Vec<Self::Item>
+    where
+        Self: Sized,
+        K: Ord,
+        F: FnMut(&Self::Item) -> K,
+    {
+        if k == 0 {
+            return Vec::new();
+        }
+
+        let mut heap: BinaryHeap<ByKey<(K, usize), Self::Item>> = BinaryHeap::with_capacity(k);
+        for (index, item) in self.enumerate() {
+            let key = (key_fn(&item), index);
+            if heap.len() < k {
+                heap.push(ByKey(key, item));
+            } else if heap.peek().is_some_and(|top| key < top.0) {
+                heap.pop();
+                heap.push(ByKey(key, item));
+            }
+        }
+
+        heap.into_sorted_vec()
+            .into_iter()
+            .map(|ByKey(_, item)| item)
+            .collect()
+    }
+
     fn try_sum<T, E>(mut self) -> Result<T, E>
     where
         Self: Sized + Iterator<Item = Result<T, E>>,
@@ -15,6 +52,74 @@ pub trait IterUtils: Iterator {
         })
     }
 
+    /// Combines elements pairwise in a balanced binary tree rather than a
+    /// flat left fold: a stack of `(accumulator, height)` entries is kept,
+    /// and each incoming element is merged upward with stack entries of
+    /// equal height before being pushed, so the final reduction tree has
+    /// depth `O(log n)` instead of `O(n)`. For associative `f` the result
+    /// is identical to [`Iterator::reduce`], but with half the dependency
+    /// chain length, which matters for `f64` rounding error and for
+    /// eventual parallelization. Returns `None` for an empty iterator.
+    fn tree_reduce<F>(self, mut f: F) -> Option<Self::Item>
+    where
+        Self: Sized,
+        F: FnMut(Self::Item, Self::Item) -> Self::Item,
+    {
+        let mut stack: Vec<(Self::Item, u32)> = Vec::new();
+        for item in self {
+            let mut entry = (item, 0);
+            while stack.last().is_some_and(|&(_, height)| height == entry.1) {
+                let (acc, height) = stack.pop().unwrap();
+                entry = (f(acc, entry.0), height + 1);
+            }
+            stack.push(entry);
+        }
+
+        stack.into_iter().map(|(acc, _)| acc).reduce(|a, b| f(a, b))
+    }
+
+    /// Like [`IterUtils::tree_reduce`], but seeded with `init` so empty
+    /// iterators yield `init` rather than combining `Self::Item`s directly.
+    fn tree_fold<F>(self, init: Self::Item, mut f: F) -> Self::Item
+    where
+        Self: Sized,
+        F: FnMut(Self::Item, Self::Item) -> Self::Item,
+    {
+        self.tree_reduce(&mut f).map_or(init, |acc| f(init, acc))
+    }
+
+    /// Short-circuiting counterpart of [`IterUtils::tree_reduce`]: combines
+    /// `Ok` elements in the same balanced-tree order, returning the first
+    /// `Err` encountered.
+    fn try_tree_reduce<T, E, F>(self, mut f: F) -> Option<Result<T, E>>
+    where
+        Self: Sized + Iterator<Item = Result<T, E>>,
+        F: FnMut(T, T) -> Result<T, E>,
+    {
+        let mut stack: Vec<(T, u32)> = Vec::new();
+        for item in self {
+            let item = match item {
+                Ok(item) => item,
+                Err(e) => return Some(Err(e)),
+            };
+            let mut entry = (item, 0);
+            while stack.last().is_some_and(|&(_, height)| height == entry.1) {
+                let (acc, height) = stack.pop().unwrap();
+                entry = match f(acc, entry.0) {
+                    Ok(combined) => (combined, height + 1),
+                    Err(e) => return Some(Err(e)),
+                };
+            }
+            stack.push(entry);
+        }
+
+        let mut iter = stack.into_iter().map(|(acc, _)| acc);
+        let Some(first) = iter.next() else {
+            return None;
+        };
+        Some(iter.try_fold(first, |acc, item| f(acc, item)))
+    }
+
     fn try_unzip<A, B, E, FromA, FromB>(mut self) -> Result<(FromA, FromB), E>
     where
         Self: Sized + Iterator<Item = Result<(A, B), E>>,
@@ -72,10 +177,186 @@ pub trait IterUtils: Iterator {
             map
         })
     }
+
+    /// Groups `(K, V)` pairs by `K`, exposing terminal aggregations
+    /// (`sum`, `fold`, `reduce`, ...) that fuse the grouping and the
+    /// aggregation into one pass, unlike `Itertools::into_group_map`
+    /// followed by a second pass over each group's `Vec<V>`. Modeled on
+    /// `itertools::Itertools::into_grouping_map`.
+    fn grouping_map<K, V>(self) -> GroupingMap<Self>
+    where
+        Self: Sized + Iterator<Item = (K, V)>,
+        K: std::hash::Hash + Eq,
+    {
+        GroupingMap { iter: self }
+    }
+
+    /// Like [`IterUtils::grouping_map`], but derives the key from each item
+    /// via `key_fn` instead of requiring `(K, V)` pairs up front.
+    fn grouping_map_by<K, F>(
+        self,
+        mut key_fn: F,
+    ) -> GroupingMap<impl Iterator<Item = (K, Self::Item)>>
+    where
+        Self: Sized,
+        K: std::hash::Hash + Eq,
+        F: FnMut(&Self::Item) -> K,
+    {
+        self.map(move |item| (key_fn(&item), item)).grouping_map()
+    }
+}
+
+/// The aggregation half of [`IterUtils::grouping_map`]/[`IterUtils::grouping_map_by`]:
+/// an iterator of `(K, V)` pairs paired with a terminal operation that
+/// folds each group's values down to a single `HashMap<K, _>` entry in one
+/// pass over `self`.
+pub struct GroupingMap<I> {
+    iter: I,
+}
+
+impl<I, K, V> GroupingMap<I>
+where
+    I: Iterator<Item = (K, V)>,
+    K: std::hash::Hash + Eq,
+{
+    /// Folds each group's values with `init` as the starting accumulator,
+    /// the same way [`Iterator::fold`] does for the whole iterator.
+    pub fn fold<Acc, F>(self, init: Acc, mut f: F) -> HashMap<K, Acc>
+    where
+        Acc: Clone,
+        F: FnMut(Acc, V) -> Acc,
+    {
+        let mut map = HashMap::new();
+        for (k, v) in self.iter {
+            let acc = map.remove(&k).unwrap_or_else(|| init.clone());
+            map.insert(k, f(acc, v));
+        }
+        map
+    }
+
+    /// Reduces each group's values pairwise with `f`, seeded by that
+    /// group's first value, the same way [`Iterator::reduce`] does for the
+    /// whole iterator.
+    pub fn reduce<F>(self, mut f: F) -> HashMap<K, V>
+    where
+        F: FnMut(V, V) -> V,
+    {
+        let mut map = HashMap::new();
+        for (k, v) in self.iter {
+            let v = match map.remove(&k) {
+                Some(acc) => f(acc, v),
+                None => v,
+            };
+            map.insert(k, v);
+        }
+        map
+    }
+
+    /// Collects each group's values into a `C`, e.g. `collect::<Vec<_>>()`.
+    pub fn collect<C>(self) -> HashMap<K, C>
+    where
+        C: Default + Extend<V>,
+    {
+        let mut map: HashMap<K, C> = HashMap::new();
+        for (k, v) in self.iter {
+            map.entry(k).or_default().extend(std::iter::once(v));
+        }
+        map
+    }
+
+    /// Sums each group's values.
+    pub fn sum(self) -> HashMap<K, V>
+    where
+        V: std::ops::Add<Output = V>,
+    {
+        self.reduce(|a, b| a + b)
+    }
+
+    /// The smallest value in each group.
+    pub fn min(self) -> HashMap<K, V>
+    where
+        V: Ord,
+    {
+        self.reduce(V::min)
+    }
+
+    /// The largest value in each group.
+    pub fn max(self) -> HashMap<K, V>
+    where
+        V: Ord,
+    {
+        self.reduce(V::max)
+    }
+
+    /// The smallest and largest value in each group, as a `(min, max)` pair.
+    pub fn minmax(self) -> HashMap<K, (V, V)>
+    where
+        V: Ord + Clone,
+    {
+        let mut map: HashMap<K, (V, V)> = HashMap::new();
+        for (k, v) in self.iter {
+            map.entry(k)
+                .and_modify(|(lo, hi)| {
+                    if &v < lo {
+                        *lo = v.clone();
+                    }
+                    if &v > hi {
+                        *hi = v.clone();
+                    }
+                })
+                .or_insert_with(|| (v.clone(), v));
+        }
+        map
+    }
+
+    /// The value minimizing `key_fn` in each group, ties broken in favor of
+    /// the first-seen value (matching [`Iterator::min_by_key`]).
+    pub fn min_by_key<B, F>(self, mut key_fn: F) -> HashMap<K, V>
+    where
+        B: Ord,
+        F: FnMut(&V) -> B,
+    {
+        self.reduce(move |a, b| if key_fn(&b) < key_fn(&a) { b } else { a })
+    }
+
+    /// The value maximizing `key_fn` in each group, ties broken in favor of
+    /// the last-seen value (matching [`Iterator::max_by_key`]).
+    pub fn max_by_key<B, F>(self, mut key_fn: F) -> HashMap<K, V>
+    where
+        B: Ord,
+        F: FnMut(&V) -> B,
+    {
+        self.reduce(move |a, b| if key_fn(&b) >= key_fn(&a) { b } else { a })
+    }
 }
 
 impl<T> IterUtils for T where T: Iterator {}
 
+/// An item paired with a sort key, ordered (and compared for equality)
+/// solely by that key — lets [`IterUtils::k_smallest_by_key`] keep
+/// non-`Ord` payloads in a `BinaryHeap`.
+struct ByKey<K, T>(K, T);
+
+impl<K: PartialEq, T> PartialEq for ByKey<K, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<K: Eq, T> Eq for ByKey<K, T> {}
+
+impl<K: Ord, T> PartialOrd for ByKey<K, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<K: Ord, T> Ord for ByKey<K, T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
 pub struct RcIter<T> {
     slice: Rc<[T]>,
     idx: usize,
@@ -124,8 +405,17 @@ where
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub struct Few<T, const N: usize>([Option<T>; N]);
+/// A fixed-capacity, inline (no-heap-allocation) small vector of up to `N`
+/// elements. Unoccupied elements live in `items[head + len..]` and
+/// `items[..head]`; only `items[head..head + len]` is ever initialized,
+/// which lets `push`/`pop`-from-either-end run in `O(1)` without shifting
+/// elements around, unlike the `rotate_left`-per-`next` scheme this
+/// replaced.
+pub struct Few<T, const N: usize> {
+    items: [MaybeUninit<T>; N],
+    head: usize,
+    len: usize,
+}
 
 pub type AtMostTwo<T> = Few<T, 2>;
 pub type AtMostThree<T> = Few<T, 3>;
@@ -133,18 +423,19 @@ pub type AtMostThree<T> = Few<T, 3>;
 impl<T, const N: usize> Few<T, N> {
     pub fn new<const M: usize>(items: [T; M]) -> Self {
         assert!(M <= N);
-        Few(crate::array::from_iter(
-            items
-                .into_iter()
-                .map(Some)
-                .chain(std::iter::repeat_with(|| None)),
-        )
-        .ok()
-        .unwrap())
+        let mut few = Self::none();
+        for item in items {
+            few.push(item);
+        }
+        few
     }
 
     pub fn none() -> Self {
-        Few(std::array::from_fn(|_| None))
+        Few {
+            items: std::array::from_fn(|_| MaybeUninit::uninit()),
+            head: 0,
+            len: 0,
+        }
     }
 
     pub fn one(item: T) -> Self {
@@ -158,6 +449,59 @@ impl<T, const N: usize> Few<T, N> {
     pub fn three(item1: T, item2: T, item3: T) -> Self {
         Few::new([item1, item2, item3])
     }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Appends `item`, or hands it back if the capacity of `N` is already
+    /// in use.
+    pub fn try_push(&mut self, item: T) -> Result<(), T> {
+        if self.head + self.len >= N {
+            return Err(item);
+        }
+        self.items[self.head + self.len] = MaybeUninit::new(item);
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Appends `item`. Panics if the capacity of `N` is already in use.
+    pub fn push(&mut self, item: T) {
+        self.try_push(item)
+            .unwrap_or_else(|_| panic!("Few<_, {N}> is at capacity"));
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        // SAFETY: `items[head..head + len]` is the initialized prefix.
+        unsafe {
+            std::slice::from_raw_parts(self.items.as_ptr().add(self.head).cast::<T>(), self.len)
+        }
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        // SAFETY: `items[head..head + len]` is the initialized prefix.
+        unsafe {
+            std::slice::from_raw_parts_mut(
+                self.items.as_mut_ptr().add(self.head).cast::<T>(),
+                self.len,
+            )
+        }
+    }
+}
+
+impl<T, const N: usize> Drop for Few<T, N> {
+    fn drop(&mut self) {
+        // SAFETY: drops exactly the initialized prefix, once.
+        unsafe { std::ptr::drop_in_place(self.as_mut_slice()) }
+    }
 }
 
 impl<T, const N: usize> Default for Few<T, N> {
@@ -166,20 +510,91 @@ impl<T, const N: usize> Default for Few<T, N> {
     }
 }
 
+impl<T: Clone, const N: usize> Clone for Few<T, N> {
+    fn clone(&self) -> Self {
+        self.as_slice().iter().cloned().collect()
+    }
+}
+
+impl<T: PartialEq, const N: usize> PartialEq for Few<T, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<T: Eq, const N: usize> Eq for Few<T, N> {}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for Few<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.as_slice()).finish()
+    }
+}
+
+impl<T, const N: usize> std::ops::Index<usize> for Few<T, N> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        &self.as_slice()[index]
+    }
+}
+
+impl<T, const N: usize> std::ops::IndexMut<usize> for Few<T, N> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        &mut self.as_mut_slice()[index]
+    }
+}
+
+impl<T, const N: usize> Extend<T> for Few<T, N> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.push(item);
+        }
+    }
+}
+
+impl<T, const N: usize> FromIterator<T> for Few<T, N> {
+    /// Panics if `iter` yields more than `N` elements.
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut few = Self::none();
+        few.extend(iter);
+        few
+    }
+}
+
 impl<T, const N: usize> Iterator for Few<T, N> {
     type Item = T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if N > 0 {
-            let item = self.0[0].take();
-            self.0.rotate_left(1);
-            item
-        } else {
-            None
+        if self.len == 0 {
+            return None;
+        }
+        // SAFETY: `items[head]` is initialized, and is never read again:
+        // `head` is advanced past it and `len` shrunk to exclude it.
+        let item = unsafe { self.items[self.head].assume_init_read() };
+        self.head += 1;
+        self.len -= 1;
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T, const N: usize> DoubleEndedIterator for Few<T, N> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
         }
+        self.len -= 1;
+        // SAFETY: `items[head + len]` is initialized, and is never read
+        // again: `len` is shrunk to exclude it.
+        Some(unsafe { self.items[self.head + self.len].assume_init_read() })
     }
 }
 
+impl<T, const N: usize> ExactSizeIterator for Few<T, N> {}
+
 impl<T, const N: usize> FusedIterator for Few<T, N> {}
 
 #[cfg(test)]
@@ -239,6 +654,137 @@ mod tests {
         itertools::assert_equal(Few::<i32, 3>::default(), []);
     }
 
+    #[test]
+    fn few_len_is_empty_and_capacity() {
+        let mut few = AtMostThree::<i32>::none();
+        assert_eq!(few.len(), 0);
+        assert!(few.is_empty());
+        assert_eq!(few.capacity(), 3);
+
+        few.push(1);
+        assert_eq!(few.len(), 1);
+        assert!(!few.is_empty());
+        assert_eq!(few.capacity(), 3);
+    }
+
+    #[test]
+    fn few_try_push_fails_once_full() {
+        let mut few = AtMostTwo::one(1);
+        assert_eq!(few.try_push(2), Ok(()));
+        assert_eq!(few.try_push(3), Err(3));
+        itertools::assert_equal(few, [1, 2]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn few_push_past_capacity_panics() {
+        let mut few = AtMostTwo::two(1, 2);
+        few.push(3);
+    }
+
+    #[test]
+    fn few_as_slice_and_indexing() {
+        let mut few = AtMostThree::three(1, 2, 3);
+        assert_eq!(few.as_slice(), [1, 2, 3].as_slice());
+        assert_eq!(few[1], 2);
+        few[1] = 20;
+        assert_eq!(few.as_mut_slice(), [1, 20, 3].as_slice());
+    }
+
+    #[test]
+    fn few_extend_and_from_iterator() {
+        let mut few = AtMostThree::one(1);
+        few.extend([2, 3]);
+        assert_eq!(few.as_slice(), [1, 2, 3].as_slice());
+
+        let collected: AtMostThree<i32> = [1, 2, 3].into_iter().collect();
+        assert_eq!(collected.as_slice(), [1, 2, 3].as_slice());
+    }
+
+    #[test]
+    #[should_panic]
+    fn few_from_iterator_past_capacity_panics() {
+        let _: AtMostTwo<i32> = [1, 2, 3].into_iter().collect();
+    }
+
+    #[test]
+    fn few_double_ended_and_exact_size() {
+        let mut few = AtMostThree::three(1, 2, 3);
+        assert_eq!(few.len(), 3);
+        assert_eq!(few.next(), Some(1));
+        assert_eq!(few.next_back(), Some(3));
+        assert_eq!(few.len(), 1);
+        assert_eq!(few.next(), Some(2));
+        assert_eq!(few.next(), None);
+        assert_eq!(few.next_back(), None);
+    }
+
+    #[test]
+    fn few_clone_and_eq() {
+        let few = AtMostTwo::two("a".to_owned(), "b".to_owned());
+        let cloned = few.clone();
+        assert_eq!(few, cloned);
+        assert_eq!(format!("{few:?}"), r#"["a", "b"]"#);
+    }
+
+    #[test]
+    fn few_drop_runs_for_every_initialized_element_exactly_once() {
+        use std::rc::Rc;
+
+        let drop_count = Rc::new(std::cell::Cell::new(0));
+
+        struct DropCounter(Rc<std::cell::Cell<usize>>);
+        impl Drop for DropCounter {
+            fn drop(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+
+        {
+            let mut few = AtMostThree::three(
+                DropCounter(drop_count.clone()),
+                DropCounter(drop_count.clone()),
+                DropCounter(drop_count.clone()),
+            );
+            few.next();
+            few.next_back();
+        }
+
+        assert_eq!(drop_count.get(), 3);
+    }
+
+    #[test]
+    fn k_smallest_by_key_returns_the_smallest_items_ascending() {
+        let values = [5, 1, 9, 3, 7, 2, 8, 4, 6];
+        itertools::assert_equal(
+            values.into_iter().k_smallest_by_key(4, |&v| v),
+            [1, 2, 3, 4],
+        );
+    }
+
+    #[test]
+    fn k_smallest_by_key_saturates_at_the_number_of_items_available() {
+        let values = [3, 1, 2];
+        itertools::assert_equal(values.into_iter().k_smallest_by_key(10, |&v| v), [1, 2, 3]);
+    }
+
+    #[test]
+    fn k_smallest_by_key_breaks_ties_by_original_order() {
+        let values = [(1, 'a'), (1, 'b'), (1, 'c'), (2, 'd')];
+        itertools::assert_equal(
+            values.into_iter().k_smallest_by_key(2, |&(k, _)| k),
+            [(1, 'a'), (1, 'b')],
+        );
+    }
+
+    #[test]
+    fn k_smallest_by_key_of_zero_is_empty() {
+        itertools::assert_equal(
+            [1, 2, 3].into_iter().k_smallest_by_key(0, |&v| v),
+            Vec::<i32>::new(),
+        );
+    }
+
     #[test]
     fn merge_by_counts() {
         let pi_str = format!("{:.15}", core::f64::consts::PI);
@@ -255,4 +801,133 @@ mod tests {
         assert_eq!(hash_map, counts);
         itertools::assert_equal(btree_map, hash_map.into_iter().sorted());
     }
+
+    #[test]
+    fn grouping_map_sum_matches_group_map_then_sum() {
+        let pairs = [(0, 1), (1, 2), (0, 3), (1, 4), (0, 5)];
+        assert_eq!(
+            pairs.into_iter().grouping_map().sum(),
+            pairs
+                .into_iter()
+                .into_group_map()
+                .into_iter()
+                .map(|(k, vs)| (k, vs.into_iter().sum()))
+                .collect()
+        );
+    }
+
+    #[test]
+    fn grouping_map_by_groups_using_the_key_function() {
+        let words = ["a", "bb", "cc", "ddd", "e"];
+        assert_eq!(
+            words
+                .into_iter()
+                .grouping_map_by(|s| s.len())
+                .collect::<Vec<_>>(),
+            HashMap::from([(1, vec!["a", "e"]), (2, vec!["bb", "cc"]), (3, vec!["ddd"])])
+        );
+    }
+
+    #[test]
+    fn grouping_map_fold_seeds_every_group_with_init() {
+        let pairs = [('a', 1), ('b', 2), ('a', 3)];
+        assert_eq!(
+            pairs.into_iter().grouping_map().fold(10, |acc, v| acc + v),
+            HashMap::from([('a', 14), ('b', 12)])
+        );
+    }
+
+    #[test]
+    fn grouping_map_reduce_combines_pairwise() {
+        let pairs = [('a', 1), ('b', 2), ('a', 3), ('a', 4)];
+        assert_eq!(
+            pairs.into_iter().grouping_map().reduce(|a, b| a.max(b)),
+            HashMap::from([('a', 4), ('b', 2)])
+        );
+    }
+
+    #[test]
+    fn grouping_map_min_max_and_minmax() {
+        let pairs = [('a', 3), ('b', 2), ('a', 1), ('a', 4)];
+        assert_eq!(
+            pairs.into_iter().grouping_map().min(),
+            HashMap::from([('a', 1), ('b', 2)])
+        );
+        assert_eq!(
+            pairs.into_iter().grouping_map().max(),
+            HashMap::from([('a', 4), ('b', 2)])
+        );
+        assert_eq!(
+            pairs.into_iter().grouping_map().minmax(),
+            HashMap::from([('a', (1, 4)), ('b', (2, 2))])
+        );
+    }
+
+    #[test]
+    fn grouping_map_min_by_key_and_max_by_key() {
+        let pairs = [('a', -3), ('b', 2), ('a', 1), ('a', -4)];
+        assert_eq!(
+            pairs.into_iter().grouping_map().min_by_key(|&v| v.abs()),
+            HashMap::from([('a', 1), ('b', 2)])
+        );
+        assert_eq!(
+            pairs.into_iter().grouping_map().max_by_key(|&v| v.abs()),
+            HashMap::from([('a', -4), ('b', 2)])
+        );
+    }
+
+    #[test]
+    fn tree_reduce_matches_flat_reduce_for_an_associative_op() {
+        let values = 1..=100;
+        assert_eq!(
+            values.clone().tree_reduce(|a, b| a + b),
+            values.reduce(|a, b| a + b)
+        );
+    }
+
+    #[test]
+    fn tree_reduce_of_empty_is_none() {
+        assert_eq!(std::iter::empty::<i32>().tree_reduce(|a, b| a + b), None);
+    }
+
+    #[test]
+    fn tree_fold_matches_flat_fold_for_an_associative_op() {
+        let values = 1..=100;
+        assert_eq!(
+            values.clone().tree_fold(0, |a, b| a + b),
+            values.fold(0, |a, b| a + b)
+        );
+    }
+
+    #[test]
+    fn tree_fold_of_empty_is_init() {
+        assert_eq!(std::iter::empty::<i32>().tree_fold(42, |a, b| a + b), 42);
+    }
+
+    #[test]
+    fn try_tree_reduce_short_circuits_on_first_err() {
+        let values = [Ok(1), Ok(2), Err("too big"), Ok(4)];
+        assert_eq!(
+            values
+                .into_iter()
+                .try_tree_reduce(|a: i32, b| if a + b > 100 {
+                    Err("too big")
+                } else {
+                    Ok(a + b)
+                }),
+            Some(Err("too big"))
+        );
+    }
+
+    #[test]
+    fn try_tree_reduce_matches_tree_reduce_when_all_ok() {
+        let values = 1..=100;
+        assert_eq!(
+            values
+                .clone()
+                .map(Ok::<i32, &str>)
+                .try_tree_reduce(|a, b| Ok(a + b)),
+            values.tree_reduce(|a, b| a + b).map(Ok)
+        );
+    }
 }
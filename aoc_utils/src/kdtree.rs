@@ -0,0 +1,202 @@
+//! A dimension-generic k-d tree over [`Vector`] points, for days that need
+//! nearest/k-nearest-neighbor queries without paying for an O(n²) all-pairs
+//! comparison. Construction recursively splits the point set on its median
+//! along axes that cycle with tree depth (x, then y, then z, then back to
+//! x, ...), giving a balanced tree in O(n log n). Queries walk down to the
+//! leaf containing the query point first, then backtrack up the tree,
+//! descending into a sibling subtree only when the hypersphere of the
+//! current k-th best distance could still cross the splitting plane —
+//! that's what keeps a query sublinear instead of visiting every node.
+
+use crate::linalg::Vector;
+
+use num_traits::{Num, NumCast};
+
+struct Node<T, const N: usize> {
+    point: Vector<T, N>,
+    index: usize,
+    left: Option<usize>,
+    right: Option<usize>,
+}
+
+/// A k-d tree over `N`-dimensional points of coordinate type `T`, recording
+/// each point's original index (its position in the slice/iterator passed
+/// to [`KdTree::new`]) so query results can be matched back to whatever the
+/// caller associates with that point.
+pub struct KdTree<T, const N: usize> {
+    nodes: Vec<Node<T, N>>,
+    root: Option<usize>,
+}
+
+impl<T, const N: usize> KdTree<T, N>
+where
+    T: Num + NumCast + Copy + PartialOrd,
+{
+    pub fn new(points: impl IntoIterator<Item = Vector<T, N>>) -> Self {
+        let mut items: Vec<(Vector<T, N>, usize)> = points
+            .into_iter()
+            .enumerate()
+            .map(|(i, p)| (p, i))
+            .collect();
+        let mut nodes = Vec::with_capacity(items.len());
+        let root = Self::build(&mut items, 0, &mut nodes);
+        KdTree { nodes, root }
+    }
+
+    fn build(
+        items: &mut [(Vector<T, N>, usize)],
+        depth: usize,
+        nodes: &mut Vec<Node<T, N>>,
+    ) -> Option<usize> {
+        if items.is_empty() {
+            return None;
+        }
+        let axis = depth % N;
+        let mid = items.len() / 2;
+        items.select_nth_unstable_by(mid, |(a, _), (b, _)| {
+            a[axis]
+                .partial_cmp(&b[axis])
+                .expect("point coordinates should be comparable")
+        });
+
+        let (left_items, rest) = items.split_at_mut(mid);
+        let ((point, index), right_items) = rest.split_first_mut().expect("mid is in bounds");
+        let (point, index) = (*point, *index);
+
+        let left = Self::build(left_items, depth + 1, nodes);
+        let right = Self::build(right_items, depth + 1, nodes);
+        nodes.push(Node {
+            point,
+            index,
+            left,
+            right,
+        });
+        Some(nodes.len() - 1)
+    }
+
+    /// The single nearest point to `query`, paired with their squared
+    /// distance, or `None` if the tree is empty.
+    pub fn nearest(&self, query: Vector<T, N>) -> Option<(usize, T)> {
+        self.k_nearest(query, 1).into_iter().next()
+    }
+
+    /// The `k` nearest points to `query`, paired with their squared
+    /// distances and sorted nearest-first; fewer than `k` if the tree
+    /// holds fewer than `k` points.
+    pub fn k_nearest(&self, query: Vector<T, N>, k: usize) -> Vec<(usize, T)> {
+        let mut best = Vec::with_capacity(k);
+        if let Some(root) = self.root {
+            self.search(root, query, 0, k, &mut best);
+        }
+        best
+    }
+
+    fn search(
+        &self,
+        node_idx: usize,
+        query: Vector<T, N>,
+        depth: usize,
+        k: usize,
+        best: &mut Vec<(usize, T)>,
+    ) {
+        let node = &self.nodes[node_idx];
+        insert_candidate(best, k, node.index, (node.point - query).norm_l2_sq());
+
+        let axis = depth % N;
+        let axis_diff = query[axis] - node.point[axis];
+        let (near, far) = if axis_diff < T::zero() {
+            (node.left, node.right)
+        } else {
+            (node.right, node.left)
+        };
+
+        if let Some(near) = near {
+            self.search(near, query, depth + 1, k, best);
+        }
+
+        let axis_dist_sq = axis_diff * axis_diff;
+        let crosses_plane = best.len() < k || best.last().is_some_and(|&(_, d)| axis_dist_sq < d);
+        if crosses_plane {
+            if let Some(far) = far {
+                self.search(far, query, depth + 1, k, best);
+            }
+        }
+    }
+}
+
+/// Inserts `(index, dist)` into `best` (kept sorted ascending by distance
+/// and capped at `k` entries) if it belongs among the `k` smallest seen so
+/// far.
+fn insert_candidate<T: PartialOrd>(best: &mut Vec<(usize, T)>, k: usize, index: usize, dist: T) {
+    if k == 0 {
+        return;
+    }
+    let pos = best.partition_point(|(_, d)| *d < dist);
+    if pos < k {
+        best.insert(pos, (index, dist));
+        best.truncate(k);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POINTS: &[Vector<i64, 2>] = &[
+        Vector([2, 3]),
+        Vector([5, 4]),
+        Vector([9, 6]),
+        Vector([4, 7]),
+        Vector([8, 1]),
+        Vector([7, 2]),
+    ];
+
+    #[test]
+    fn nearest_finds_the_closest_point() {
+        let tree = KdTree::new(POINTS.iter().copied());
+        let (index, dist) = tree.nearest(Vector([9, 2])).unwrap();
+        assert_eq!(POINTS[index], Vector([8, 1]));
+        assert_eq!(dist, 2);
+    }
+
+    #[test]
+    fn nearest_on_empty_tree_is_none() {
+        let tree = KdTree::<i64, 2>::new(std::iter::empty());
+        assert_eq!(tree.nearest(Vector([0, 0])), None);
+    }
+
+    #[test]
+    fn k_nearest_returns_points_sorted_by_distance() {
+        let tree = KdTree::new(POINTS.iter().copied());
+        let neighbors = tree.k_nearest(Vector([6, 3]), 3);
+        let points: Vec<_> = neighbors.iter().map(|&(i, _)| POINTS[i]).collect();
+        assert_eq!(points, [Vector([5, 4]), Vector([7, 2]), Vector([8, 1])]);
+        assert!(neighbors.windows(2).all(|w| w[0].1 <= w[1].1));
+    }
+
+    #[test]
+    fn k_nearest_saturates_at_the_number_of_points_available() {
+        let tree = KdTree::new(POINTS.iter().copied());
+        assert_eq!(tree.k_nearest(Vector([0, 0]), 100).len(), POINTS.len());
+    }
+
+    #[test]
+    fn matches_brute_force_all_pairs_search() {
+        use itertools::Itertools as _;
+
+        let tree = KdTree::new(POINTS.iter().copied());
+        for &query in POINTS {
+            let expected: Vec<_> = POINTS
+                .iter()
+                .map(|p| (*p - query).norm_l2_sq())
+                .sorted()
+                .collect();
+            let actual: Vec<_> = tree
+                .k_nearest(query, POINTS.len())
+                .into_iter()
+                .map(|(_, d)| d)
+                .collect();
+            assert_eq!(actual, expected);
+        }
+    }
+}
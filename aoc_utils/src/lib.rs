@@ -0,0 +1,23 @@
+pub mod array;
+pub mod automaton;
+pub mod cache;
+pub mod clustering;
+pub mod combinatorics;
+pub mod cycle;
+pub mod disjoint_set;
+pub mod geometry;
+pub mod grammar;
+pub mod graph;
+pub mod hash;
+pub mod iter;
+pub mod kdtree;
+pub mod knot_hash;
+pub mod linalg;
+pub mod ocr;
+pub mod parse;
+pub mod population;
+pub mod range;
+pub mod register_machine;
+pub mod schema;
+pub mod vm;
+pub mod wrap;
@@ -662,6 +662,85 @@ where
     }
 }
 
+/// A matrix over GF(2) given as a list of column vectors, each packed into
+/// the low bits of a `u32` — the natural shape of an indicator-light style
+/// puzzle, where every button/switch is a column and every light is a row.
+/// [`solve_min_weight`](Self::solve_min_weight) finds the minimum-Hamming-
+/// weight `x` solving `self * x = target`, i.e. the fewest columns that
+/// XOR together to `target`.
+pub struct BitMatrix {
+    columns: Vec<u32>,
+}
+
+impl BitMatrix {
+    pub fn new(columns: Vec<u32>) -> Self {
+        BitMatrix { columns }
+    }
+
+    /// Finds a minimum-Hamming-weight `x: u64` (bit `i` set means column
+    /// `i` is selected) with `self * x = target` over GF(2), or `None` if
+    /// `target` isn't reachable as any XOR-combination of columns.
+    ///
+    /// Builds up a row-echelon basis of the columns one at a time; a column
+    /// that reduces to all-zeros against the existing basis is linearly
+    /// dependent, and the combination of original columns that produced the
+    /// zero is recorded as a null-space basis vector. `target` is then
+    /// reduced through the same basis to obtain *a* solution, and every one
+    /// of the `2^k` (`k` = nullity) combinations of null-space vectors is
+    /// XORed into it to find the one with the fewest bits set. Panics if
+    /// there are more than 64 columns, since a selection is packed into a
+    /// `u64`.
+    pub fn solve_min_weight(&self, target: u32) -> Option<u64> {
+        assert!(
+            self.columns.len() <= u64::BITS as usize,
+            "BitMatrix only supports up to {} columns",
+            u64::BITS
+        );
+
+        // `basis[bit]` is a column (reduced so its highest set bit is
+        // `bit`) paired with the combination of original columns XORed
+        // together to produce it.
+        let mut basis: [Option<(u32, u64)>; 32] = [None; 32];
+        let mut null_space = Vec::new();
+
+        for (i, &column) in self.columns.iter().enumerate() {
+            let (mut value, mut combo) = (column, 1u64 << i);
+            while value != 0 {
+                let bit = value.ilog2() as usize;
+                match basis[bit] {
+                    Some((basis_value, basis_combo)) => {
+                        value ^= basis_value;
+                        combo ^= basis_combo;
+                    }
+                    None => {
+                        basis[bit] = Some((value, combo));
+                        break;
+                    }
+                }
+            }
+            if value == 0 && combo != 0 {
+                null_space.push(combo);
+            }
+        }
+
+        let (mut value, mut particular_solution) = (target, 0u64);
+        while value != 0 {
+            let bit = value.ilog2() as usize;
+            let (basis_value, basis_combo) = basis[bit]?;
+            value ^= basis_value;
+            particular_solution ^= basis_combo;
+        }
+
+        (0..1u64 << null_space.len())
+            .map(|mask| {
+                (0..null_space.len())
+                    .filter(|&i| mask & (1 << i) != 0)
+                    .fold(particular_solution, |acc, i| acc ^ null_space[i])
+            })
+            .min_by_key(|combo| combo.count_ones())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashSet;
@@ -0,0 +1,165 @@
+//! Decodes AoC's standard block-letter "CRT font" out of a grid of
+//! lit/unlit cells into a `String`, for solutions that draw letters out of
+//! filled cells (CRT displays, folded-paper codes, ...) rather than
+//! spelling them out directly.
+
+use std::collections::HashMap;
+
+use ndarray::Array2;
+use thiserror::Error;
+
+use crate::linalg::Vector;
+
+/// A fixed-size block-letter font: every glyph is `rows` rows tall and
+/// `glyph_width` columns wide, with `gap` blank columns separating
+/// successive glyphs.
+pub struct Font {
+    rows: usize,
+    glyph_width: usize,
+    gap: usize,
+    alphabet: &'static [(&'static [&'static str], char)],
+}
+
+/// The 6-row-tall, 4-column-wide block font used by puzzles like the Day
+/// 10 "Cathode-Ray Tube" CRT display and the Day 13 paper-folding code.
+///
+/// Only the letters that have actually turned up in this font across AoC
+/// puzzles are included; an unrecognized glyph is reported as an error
+/// rather than silently misdecoded.
+pub const LARGE: Font = Font {
+    rows: 6,
+    glyph_width: 4,
+    gap: 1,
+    alphabet: &[
+        (&[".##.", "#..#", "#..#", "####", "#..#", "#..#"], 'A'),
+        (&["###.", "#..#", "###.", "#..#", "#..#", "###."], 'B'),
+        (&[".##.", "#..#", "#...", "#...", "#..#", ".##."], 'C'),
+        (&["####", "#...", "###.", "#...", "#...", "####"], 'E'),
+        (&["####", "#...", "###.", "#...", "#...", "#..."], 'F'),
+        (&[".##.", "#..#", "#...", "#.##", "#..#", ".###"], 'G'),
+        (&["#..#", "#..#", "####", "#..#", "#..#", "#..#"], 'H'),
+        (&[".###", "..#.", "..#.", "..#.", "..#.", ".###"], 'I'),
+        (&["..##", "...#", "...#", "...#", "#..#", ".##."], 'J'),
+        (&["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"], 'K'),
+        (&["#...", "#...", "#...", "#...", "#...", "####"], 'L'),
+        (&[".##.", "#..#", "#..#", "#..#", "#..#", ".##."], 'O'),
+        (&["###.", "#..#", "#..#", "###.", "#...", "#..."], 'P'),
+        (&["###.", "#..#", "#..#", "###.", "#.#.", "#..#"], 'R'),
+        (&[".###", "#...", "#...", ".##.", "...#", "###."], 'S'),
+        (&["#..#", "#..#", "#..#", "#..#", "#..#", ".##."], 'U'),
+        (&["#..#", "#..#", ".##.", "..#.", "..#.", "..#."], 'Y'),
+        (&["####", "...#", "..#.", ".#..", "#...", "####"], 'Z'),
+    ],
+};
+
+#[derive(Debug, Error)]
+pub enum OcrError {
+    #[error("no known glyph matches the bitmap:\n{0}")]
+    UnknownGlyph(String),
+}
+
+fn decode(
+    font: &Font,
+    width: usize,
+    lit: impl Fn(usize, usize) -> bool,
+) -> Result<String, OcrError> {
+    let pitch = font.glyph_width + font.gap;
+    let num_glyphs = width.div_ceil(pitch);
+    (0..num_glyphs)
+        .map(|i| {
+            let col0 = i * pitch;
+            let bitmap: Vec<String> = (0..font.rows)
+                .map(|row| {
+                    (0..font.glyph_width)
+                        .map(|dc| if lit(col0 + dc, row) { '#' } else { '.' })
+                        .collect()
+                })
+                .collect();
+            font.alphabet
+                .iter()
+                .find(|(rows, _)| rows.iter().zip(&bitmap).all(|(a, b)| a == b))
+                .map(|&(_, c)| c)
+                .ok_or_else(|| OcrError::UnknownGlyph(bitmap.join("\n")))
+        })
+        .collect()
+}
+
+/// Decodes letters out of a sparse point set (e.g. a `HashMap<Vector<usize,
+/// 2>, _>` grid like the Day 19 pipe door's `Grid`), where `lit` tests
+/// whether a stored value counts as an "on" pixel.
+pub fn decode_points<T>(
+    font: &Font,
+    points: &HashMap<Vector<usize, 2>, T>,
+    width: usize,
+    lit: impl Fn(&T) -> bool,
+) -> Result<String, OcrError> {
+    decode(font, width, |x, y| {
+        points.get(&Vector([x, y])).is_some_and(&lit)
+    })
+}
+
+/// Decodes letters out of a dense, `[row, col]`-indexed grid (e.g. the Day
+/// 12 `Map`'s `Array2<u8>`), where `lit` tests whether a cell value counts
+/// as an "on" pixel.
+pub fn decode_grid(
+    font: &Font,
+    grid: &Array2<u8>,
+    lit: impl Fn(u8) -> bool,
+) -> Result<String, OcrError> {
+    let width = grid.shape()[1];
+    decode(font, width, |x, y| lit(grid[(y, x)]))
+}
+
+/// Decodes letters directly out of a rendered screen, one line per row,
+/// with `'#'` marking an "on" pixel.
+pub fn decode_screen(font: &Font, screen: &str) -> Result<String, OcrError> {
+    let lines: Vec<&str> = screen.lines().collect();
+    let width = lines.first().map_or(0, |line| line.len());
+    decode(font, width, |x, y| {
+        lines.get(y).and_then(|line| line.as_bytes().get(x)) == Some(&b'#')
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_SCREEN: &str = "####...##.#..#.###..#..#.#....###..####.\n\
+                                  #.......#.#..#.#..#.#..#.#....#..#....#.\n\
+                                  ###.....#.#..#.###..#..#.#....#..#...#..\n\
+                                  #.......#.#..#.#..#.#..#.#....###...#...\n\
+                                  #....#..#.#..#.#..#.#..#.#....#.#..#....\n\
+                                  #.....##...##..###...##..####.#..#.####.";
+
+    #[test]
+    fn decode_screen_reads_off_known_letters() {
+        assert_eq!(decode_screen(&LARGE, EXAMPLE_SCREEN).unwrap(), "FJUBULRZ");
+    }
+
+    #[test]
+    fn decode_screen_reports_the_unmatched_bitmap() {
+        let blank = "....\n....\n....\n....\n....\n....";
+        let err = decode_screen(&LARGE, blank).unwrap_err();
+        assert!(
+            matches!(err, OcrError::UnknownGlyph(bitmap) if bitmap == "....\n....\n....\n....\n....\n....")
+        );
+    }
+
+    #[test]
+    fn decode_points_reads_a_sparse_grid() {
+        let points: HashMap<Vector<usize, 2>, bool> = EXAMPLE_SCREEN
+            .lines()
+            .enumerate()
+            .flat_map(|(y, line)| {
+                line.bytes()
+                    .enumerate()
+                    .filter(|&(_, b)| b == b'#')
+                    .map(move |(x, _)| (Vector([x, y]), true))
+            })
+            .collect();
+        assert_eq!(
+            decode_points(&LARGE, &points, 40, |&lit| lit).unwrap(),
+            "FJUBULRZ"
+        );
+    }
+}
@@ -0,0 +1,107 @@
+//! Simulates a multiset evolving under a fan-out transition, tracking only
+//! the count of each distinct value rather than materializing every member.
+//! This keeps "population after N generations" puzzles (lanternfish
+//! timers, Plutonian pebbles, ...) linear in the number of distinct values
+//! instead of exponential in the number of generations.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Population counts for a multiset, keyed by distinct value.
+#[derive(Debug, Clone)]
+pub struct Counts<T> {
+    counts: HashMap<T, usize>,
+}
+
+impl<T: Eq + Hash + Clone> Counts<T> {
+    /// Tallies an initial collection of values into their starting counts.
+    pub fn new(initial: impl IntoIterator<Item = T>) -> Self {
+        let mut counts = HashMap::new();
+        for value in initial {
+            *counts.entry(value).or_insert(0) += 1;
+        }
+        Counts { counts }
+    }
+
+    /// The total population across all distinct values.
+    pub fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+
+    /// Advances the population by one generation: every value present is
+    /// replaced by the values `transition` produces for it, carrying its
+    /// count forward (split across however many successors it fans out to).
+    pub fn step<I>(&self, transition: impl Fn(&T) -> I) -> Counts<T>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut next = HashMap::new();
+        for (value, &count) in &self.counts {
+            for successor in transition(value) {
+                *next.entry(successor).or_insert(0) += count;
+            }
+        }
+        Counts { counts: next }
+    }
+
+    /// Advances the population by `steps` generations.
+    pub fn run<I>(&self, steps: usize, transition: impl Fn(&T) -> I) -> Counts<T>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        (0..steps).fold(self.clone(), |counts, _| counts.step(&transition))
+    }
+}
+
+/// Successive generations of `initial` evolving under `transition`, one
+/// step apart, starting with the un-stepped initial population.
+pub fn counts_seq<T, I>(
+    initial: impl IntoIterator<Item = T>,
+    transition: impl Fn(&T) -> I,
+) -> impl Iterator<Item = Counts<T>>
+where
+    T: Eq + Hash + Clone,
+    I: IntoIterator<Item = T>,
+{
+    itertools::iterate(Counts::new(initial), move |counts| counts.step(&transition))
+}
+
+/// Total population of `initial` after `steps` generations under `transition`.
+pub fn simulate_counts<T, I>(
+    initial: impl IntoIterator<Item = T>,
+    transition: impl Fn(&T) -> I,
+    steps: usize,
+) -> usize
+where
+    T: Eq + Hash + Clone,
+    I: IntoIterator<Item = T>,
+{
+    Counts::new(initial).run(steps, transition).total()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lanternfish_transition(timer: &u8) -> Vec<u8> {
+        if *timer == 0 {
+            vec![6, 8]
+        } else {
+            vec![timer - 1]
+        }
+    }
+
+    #[test]
+    fn lanternfish_population_grows_as_expected() {
+        let initial = [3u8, 4, 3, 1, 2];
+        assert_eq!(simulate_counts(initial, lanternfish_transition, 18), 26);
+        assert_eq!(simulate_counts(initial, lanternfish_transition, 80), 5934);
+    }
+
+    #[test]
+    fn counts_seq_yields_initial_population_at_step_zero() {
+        let initial = [3u8, 4, 3, 1, 2];
+        let first = counts_seq(initial, lanternfish_transition).next().unwrap();
+        assert_eq!(first.total(), 5);
+    }
+}
@@ -1,12 +1,14 @@
 use std::ops::RangeInclusive;
 
-/// Extension trait for set operations on range types
-pub trait RangeSet: Sized {
+use num_traits::PrimInt;
+
+/// Extension trait for pairwise set operations on range types
+pub trait IntervalOps: Sized {
     fn try_union(&self, other: &Self) -> Option<Self>;
     fn intersection(&self, other: &Self) -> Option<Self>;
 }
 
-impl<T> RangeSet for RangeInclusive<T>
+impl<T> IntervalOps for RangeInclusive<T>
 where
     T: Copy + PartialOrd + Ord,
 {
@@ -26,6 +28,149 @@ where
     }
 }
 
+/// A set of values represented as a sorted list of mutually disjoint,
+/// coalesced `RangeInclusive`s. Inserting a range folds it into any ranges it
+/// overlaps *or* touches (e.g. `1..=5` and `6..=8` merge into `1..=8`,
+/// since every integer in between is already covered), keeping the set
+/// minimal.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RangeSet<T> {
+    ranges: Vec<RangeInclusive<T>>,
+}
+
+impl<T> RangeSet<T>
+where
+    T: PrimInt,
+{
+    pub fn new() -> Self {
+        RangeSet { ranges: Vec::new() }
+    }
+
+    /// Binary-searches for the first range that could possibly touch or
+    /// overlap `range` (the first whose end is at least `range`'s start,
+    /// minus one to also catch an adjacent range ending right before it),
+    /// then merges forward while ranges keep touching or overlapping.
+    pub fn insert(&mut self, range: RangeInclusive<T>) {
+        if range.is_empty() {
+            return;
+        }
+        let first = self
+            .ranges
+            .partition_point(|r| *r.end() + T::one() < *range.start());
+        let last = self.ranges[first..].partition_point(|r| *r.start() <= *range.end() + T::one());
+        let merged = self.ranges[first..first + last]
+            .iter()
+            .fold(range, |acc, r| {
+                (*acc.start().min(r.start()))..=(*acc.end().max(r.end()))
+            });
+        self.ranges.splice(first..first + last, [merged]);
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        self.ranges.iter().any(|r| r.contains(value))
+    }
+
+    pub fn ranges(&self) -> &[RangeInclusive<T>] {
+        &self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The set of all values contained in either `self` or `other`.
+    pub fn union(&self, other: &Self) -> Self {
+        let mut merged = self.clone();
+        for range in other.ranges.iter().cloned() {
+            merged.insert(range);
+        }
+        merged
+    }
+
+    /// The set of all values contained in both `self` and `other`.
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.ranges
+            .iter()
+            .flat_map(|a| other.ranges.iter().filter_map(|b| a.intersection(b)))
+            .collect()
+    }
+
+    /// The set of all values contained in `self` but not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        self.ranges
+            .iter()
+            .cloned()
+            .flat_map(|range| {
+                other
+                    .ranges
+                    .iter()
+                    .fold(vec![range], |remaining, excluded| {
+                        remaining
+                            .into_iter()
+                            .flat_map(|r| subtract(r, excluded))
+                            .collect()
+                    })
+            })
+            .collect()
+    }
+
+    /// The set of all values within `bounds` that `self` does *not* cover.
+    pub fn gaps_within(&self, bounds: RangeInclusive<T>) -> Self {
+        RangeSet::from_iter([bounds]).difference(self)
+    }
+
+    /// The total number of values covered across all ranges in the set.
+    pub fn total_len(&self) -> usize {
+        self.ranges
+            .iter()
+            .map(|r| (*r.end() - *r.start() + T::one()).to_usize().unwrap())
+            .sum()
+    }
+}
+
+impl<T> FromIterator<RangeInclusive<T>> for RangeSet<T>
+where
+    T: PrimInt,
+{
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(iter: I) -> Self {
+        let mut set = RangeSet::new();
+        for range in iter {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+impl<T> IntoIterator for RangeSet<T> {
+    type Item = RangeInclusive<T>;
+    type IntoIter = std::vec::IntoIter<RangeInclusive<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.ranges.into_iter()
+    }
+}
+
+/// Splits `range` around its overlap with `excluded`, yielding the leftover
+/// piece(s) that remain on either side (zero, one, or two ranges).
+fn subtract<T: PrimInt>(
+    range: RangeInclusive<T>,
+    excluded: &RangeInclusive<T>,
+) -> Vec<RangeInclusive<T>> {
+    match range.intersection(excluded) {
+        None => vec![range],
+        Some(overlap) => {
+            let mut remaining = Vec::new();
+            if range.start() < overlap.start() {
+                remaining.push(*range.start()..=(*overlap.start() - T::one()));
+            }
+            if range.end() > overlap.end() {
+                remaining.push((*overlap.end() + T::one())..=*range.end());
+            }
+            remaining
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -35,32 +180,32 @@ mod tests {
         // 1 2 3 4 5 6 7 8
         // ---------
         //     -----------
-        assert_eq!(RangeSet::try_union(&(1..=5), &(3..=8)), Some(1..=8));
+        assert_eq!(IntervalOps::try_union(&(1..=5), &(3..=8)), Some(1..=8));
 
         // 1 2 3 4 5 6 7 8
         //     -----------
         // ---------
-        assert_eq!(RangeSet::try_union(&(3..=8), &(1..=5)), Some(1..=8));
+        assert_eq!(IntervalOps::try_union(&(3..=8), &(1..=5)), Some(1..=8));
 
         // 1 2 3 4 5 6 7 8
         // ---------------
         //     -----
-        assert_eq!(RangeSet::try_union(&(1..=8), &(3..=5)), Some(1..=8));
+        assert_eq!(IntervalOps::try_union(&(1..=8), &(3..=5)), Some(1..=8));
 
         // 1 2 3 4 5 6 7 8
         //     -----
         // ---------------
-        assert_eq!(RangeSet::try_union(&(3..=5), &(1..=8)), Some(1..=8));
+        assert_eq!(IntervalOps::try_union(&(3..=5), &(1..=8)), Some(1..=8));
 
         // 1 2 3 4 5 6 7 8
         // -----
         //         -------
-        assert_eq!(RangeSet::try_union(&(1..=3), &(5..=8)), None);
+        assert_eq!(IntervalOps::try_union(&(1..=3), &(5..=8)), None);
 
         // 1 2 3 4 5 6 7 8
         //         -------
         // -----
-        assert_eq!(RangeSet::try_union(&(5..=8), &(1..=3)), None);
+        assert_eq!(IntervalOps::try_union(&(5..=8), &(1..=3)), None);
     }
 
     #[test]
@@ -68,31 +213,90 @@ mod tests {
         // 1 2 3 4 5 6 7 8
         // ---------
         //     -----------
-        assert_eq!(RangeSet::intersection(&(1..=5), &(3..=8)), Some(3..=5));
+        assert_eq!(IntervalOps::intersection(&(1..=5), &(3..=8)), Some(3..=5));
 
         // 1 2 3 4 5 6 7 8
         //     -----------
         // ---------
-        assert_eq!(RangeSet::intersection(&(3..=8), &(1..=5)), Some(3..=5));
+        assert_eq!(IntervalOps::intersection(&(3..=8), &(1..=5)), Some(3..=5));
 
         // 1 2 3 4 5 6 7 8
         // ---------------
         //     -----
-        assert_eq!(RangeSet::intersection(&(1..=8), &(3..=5)), Some(3..=5));
+        assert_eq!(IntervalOps::intersection(&(1..=8), &(3..=5)), Some(3..=5));
 
         // 1 2 3 4 5 6 7 8
         //     -----
         // ---------------
-        assert_eq!(RangeSet::intersection(&(3..=5), &(1..=8)), Some(3..=5));
+        assert_eq!(IntervalOps::intersection(&(3..=5), &(1..=8)), Some(3..=5));
 
         // 1 2 3 4 5 6 7 8
         // -----
         //         -------
-        assert_eq!(RangeSet::intersection(&(1..=3), &(5..=8)), None);
+        assert_eq!(IntervalOps::intersection(&(1..=3), &(5..=8)), None);
 
         // 1 2 3 4 5 6 7 8
         //         -------
         // -----
-        assert_eq!(RangeSet::intersection(&(5..=8), &(1..=3)), None);
+        assert_eq!(IntervalOps::intersection(&(5..=8), &(1..=3)), None);
+    }
+
+    #[test]
+    fn range_set_merges_overlapping_ranges() {
+        let set: RangeSet<u64> = [3..=5, 10..=14, 16..=20, 12..=18].into_iter().collect();
+        assert_eq!(set.ranges(), &[3..=5, 10..=20]);
+    }
+
+    #[test]
+    fn range_set_keeps_disjoint_ranges_sorted_and_separate() {
+        let set: RangeSet<u64> = [16..=20, 3..=5].into_iter().collect();
+        assert_eq!(set.ranges(), &[3..=5, 16..=20]);
+    }
+
+    #[test]
+    fn range_set_reports_containment() {
+        let set: RangeSet<u64> = [3..=5, 10..=20].into_iter().collect();
+        assert!(set.contains(&4));
+        assert!(set.contains(&15));
+        assert!(!set.contains(&8));
+    }
+
+    #[test]
+    fn range_set_merges_touching_ranges() {
+        let set: RangeSet<u64> = [1..=5, 6..=8, 20..=25].into_iter().collect();
+        assert_eq!(set.ranges(), &[1..=8, 20..=25]);
+    }
+
+    #[test]
+    fn range_set_union() {
+        let a: RangeSet<u64> = [1..=5, 20..=25].into_iter().collect();
+        let b: RangeSet<u64> = [4..=10].into_iter().collect();
+        assert_eq!(a.union(&b).ranges(), &[1..=10, 20..=25]);
+    }
+
+    #[test]
+    fn range_set_intersection() {
+        let a: RangeSet<u64> = [1..=5, 20..=25].into_iter().collect();
+        let b: RangeSet<u64> = [4..=10, 22..=30].into_iter().collect();
+        assert_eq!(a.intersection(&b).ranges(), &[4..=5, 22..=25]);
+    }
+
+    #[test]
+    fn range_set_difference() {
+        let a: RangeSet<u64> = [1..=10].into_iter().collect();
+        let b: RangeSet<u64> = [3..=4, 8..=8].into_iter().collect();
+        assert_eq!(a.difference(&b).ranges(), &[1..=2, 5..=7, 9..=10]);
+    }
+
+    #[test]
+    fn range_set_gaps_within() {
+        let set: RangeSet<u64> = [3..=5, 10..=20].into_iter().collect();
+        assert_eq!(set.gaps_within(0..=20).ranges(), &[0..=2, 6..=9]);
+    }
+
+    #[test]
+    fn range_set_total_len() {
+        let set: RangeSet<u64> = [3..=5, 10..=20].into_iter().collect();
+        assert_eq!(set.total_len(), 3 + 11);
     }
 }
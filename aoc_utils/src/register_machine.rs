@@ -0,0 +1,320 @@
+//! A small register machine shared by the handful of AoC puzzles whose
+//! input reads like assembly over named registers: simple arithmetic,
+//! conditional guards (`inc b by 5 if a > 1`), and relative jumps. A
+//! [`Vm`] [`Vm::step`]s one [`Instruction`] at a time or [`Vm::run`]s to
+//! completion, with an [`Observer`] trait for folding over the resulting
+//! states without collecting every one of them.
+
+use std::collections::HashMap;
+
+/// A named register. Registers spring into existence (defaulting to `0`)
+/// the first time they're read or written, rather than being declared up
+/// front, matching how puzzles like "inc a" introduce them on the fly.
+pub type Register = String;
+
+/// A register machine's working memory.
+pub type Registers = HashMap<Register, i64>;
+
+/// An instruction operand: either an immediate value or another register's
+/// current value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    Literal(i64),
+    Register(Register),
+}
+
+impl Value {
+    fn resolve(&self, registers: &Registers) -> i64 {
+        match self {
+            Value::Literal(n) => *n,
+            Value::Register(r) => *registers.get(r).unwrap_or(&0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Lt,
+    Gt,
+    Le,
+    Ge,
+    Eq,
+    Ne,
+}
+
+impl Comparison {
+    pub fn eval(&self, lhs: i64, rhs: i64) -> bool {
+        match self {
+            Comparison::Lt => lhs < rhs,
+            Comparison::Gt => lhs > rhs,
+            Comparison::Le => lhs <= rhs,
+            Comparison::Ge => lhs >= rhs,
+            Comparison::Eq => lhs == rhs,
+            Comparison::Ne => lhs != rhs,
+        }
+    }
+}
+
+/// Gates an [`Instruction`]: it only executes when `lhs` compares true
+/// against `rhs` under `cmp`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Condition {
+    pub lhs: Register,
+    pub cmp: Comparison,
+    pub rhs: Value,
+}
+
+/// The shared ALU-style instruction set: arithmetic into a target register,
+/// an equality test, reading external input, and a relative jump.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Op {
+    Inp(Register),
+    Add(Register, Value),
+    Mul(Register, Value),
+    Div(Register, Value),
+    Mod(Register, Value),
+    Eql(Register, Value),
+    Jmp(Value),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Instruction {
+    pub op: Op,
+    pub guard: Option<Condition>,
+}
+
+impl Instruction {
+    pub fn new(op: Op) -> Self {
+        Instruction { op, guard: None }
+    }
+
+    pub fn guarded(op: Op, guard: Condition) -> Self {
+        Instruction {
+            op,
+            guard: Some(guard),
+        }
+    }
+}
+
+/// A snapshot of the machine state right after a [`Vm::step`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct State {
+    pub pc: i64,
+    pub registers: Registers,
+}
+
+/// Folds over the sequence of [`State`]s a running [`Vm`] produces, so a
+/// caller can e.g. track the largest intermediate register value without
+/// collecting every snapshot into memory.
+pub trait Observer {
+    type Output;
+
+    fn observe(&mut self, state: &State);
+    fn finish(self) -> Self::Output;
+}
+
+/// An [`Observer`] tracking the running maximum of `selector(&state)`,
+/// ignoring states it maps to `None`.
+pub struct MaxBy<F> {
+    max: Option<i64>,
+    selector: F,
+}
+
+impl<F: FnMut(&State) -> Option<i64>> MaxBy<F> {
+    pub fn new(selector: F) -> Self {
+        Self {
+            max: None,
+            selector,
+        }
+    }
+}
+
+impl<F: FnMut(&State) -> Option<i64>> Observer for MaxBy<F> {
+    type Output = Option<i64>;
+
+    fn observe(&mut self, state: &State) {
+        if let Some(value) = (self.selector)(state) {
+            self.max = Some(self.max.map_or(value, |max| max.max(value)));
+        }
+    }
+
+    fn finish(self) -> Self::Output {
+        self.max
+    }
+}
+
+/// A tiny register machine: named, dynamically-created registers, a
+/// program counter, and the [`Op`] set with optional [`Condition`] guards
+/// and relative [`Op::Jmp`]s. `on_jump`, when set, is consulted with a
+/// `Op::Jmp(Value::Register(_))`'s offset right after it's read and its
+/// return value is written back to that same register, letting
+/// self-modifying-code puzzles (a maze of jump offsets that increment or
+/// decrement themselves on use) plug that rewrite in without the core ALU
+/// needing to know about it.
+#[derive(Default)]
+pub struct Vm {
+    pub pc: i64,
+    pub registers: Registers,
+    pub on_jump: Option<Box<dyn FnMut(i64) -> i64>>,
+}
+
+impl Vm {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn guard_passes(&self, guard: &Option<Condition>) -> bool {
+        match guard {
+            None => true,
+            Some(Condition { lhs, cmp, rhs }) => {
+                let lhs = *self.registers.get(lhs).unwrap_or(&0);
+                cmp.eval(lhs, rhs.resolve(&self.registers))
+            }
+        }
+    }
+
+    fn apply(&mut self, r: &Register, v: &Value, f: impl Fn(i64, i64) -> i64) {
+        let rhs = v.resolve(&self.registers);
+        let lhs = *self.registers.get(r).unwrap_or(&0);
+        self.registers.insert(r.clone(), f(lhs, rhs));
+    }
+
+    /// Executes the instruction at `pc` against `program`, consuming the
+    /// next `inputs` value for an [`Op::Inp`], advancing `pc`, and
+    /// returning the post-step state, or `None` once `pc` has walked off
+    /// either end of `program`.
+    pub fn step(
+        &mut self,
+        program: &[Instruction],
+        inputs: &mut impl Iterator<Item = i64>,
+    ) -> Option<State> {
+        let index = usize::try_from(self.pc).ok()?;
+        let instr = program.get(index)?;
+        let mut advance = 1;
+
+        if self.guard_passes(&instr.guard) {
+            match &instr.op {
+                Op::Inp(r) => {
+                    self.registers.insert(r.clone(), inputs.next().unwrap_or(0));
+                }
+                Op::Add(r, v) => self.apply(r, v, |lhs, rhs| lhs + rhs),
+                Op::Mul(r, v) => self.apply(r, v, |lhs, rhs| lhs * rhs),
+                Op::Div(r, v) => self.apply(r, v, |lhs, rhs| lhs / rhs),
+                Op::Mod(r, v) => self.apply(r, v, |lhs, rhs| lhs % rhs),
+                Op::Eql(r, v) => self.apply(r, v, |lhs, rhs| (lhs == rhs) as i64),
+                Op::Jmp(v) => {
+                    let offset = v.resolve(&self.registers);
+                    advance = offset;
+                    if let (Value::Register(r), Some(on_jump)) = (v, self.on_jump.as_mut()) {
+                        self.registers.insert(r.clone(), on_jump(offset));
+                    }
+                }
+            }
+        }
+
+        self.pc += advance;
+        Some(State {
+            pc: self.pc,
+            registers: self.registers.clone(),
+        })
+    }
+
+    /// Runs to completion, discarding every intermediate state.
+    pub fn run(&mut self, program: &[Instruction], inputs: &mut impl Iterator<Item = i64>) {
+        while self.step(program, inputs).is_some() {}
+    }
+
+    /// Runs to completion, folding every intermediate state through
+    /// `observer`.
+    pub fn run_with_observer<O: Observer>(
+        &mut self,
+        program: &[Instruction],
+        inputs: &mut impl Iterator<Item = i64>,
+        mut observer: O,
+    ) -> O::Output {
+        while let Some(state) = self.step(program, inputs) {
+            observer.observe(&state);
+        }
+        observer.finish()
+    }
+
+    /// Lazily yields the full sequence of post-step [`State`]s, for
+    /// debugging a program instruction by instruction.
+    pub fn trace<'prog>(
+        mut self,
+        program: &'prog [Instruction],
+        mut inputs: impl Iterator<Item = i64> + 'prog,
+    ) -> impl Iterator<Item = State> + 'prog {
+        std::iter::from_fn(move || self.step(program, &mut inputs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unconditional_arithmetic_updates_the_target_register() {
+        let program = vec![Instruction::new(Op::Add("a".to_owned(), Value::Literal(5)))];
+        let mut vm = Vm::new();
+        vm.run(&program, &mut std::iter::empty());
+        assert_eq!(vm.registers.get("a"), Some(&5));
+    }
+
+    #[test]
+    fn guard_suppresses_the_instruction_when_false() {
+        let program = vec![Instruction::guarded(
+            Op::Add("a".to_owned(), Value::Literal(5)),
+            Condition {
+                lhs: "b".to_owned(),
+                cmp: Comparison::Gt,
+                rhs: Value::Literal(1),
+            },
+        )];
+        let mut vm = Vm::new();
+        vm.run(&program, &mut std::iter::empty());
+        assert_eq!(vm.registers.get("a"), None);
+    }
+
+    #[test]
+    fn jump_moves_the_program_counter_by_a_register_value() {
+        let program = vec![
+            Instruction::new(Op::Jmp(Value::Register("skip".to_owned()))),
+            Instruction::new(Op::Add("a".to_owned(), Value::Literal(1))),
+            Instruction::new(Op::Add("a".to_owned(), Value::Literal(2))),
+        ];
+        let mut vm = Vm::new();
+        vm.registers.insert("skip".to_owned(), 2);
+        vm.run(&program, &mut std::iter::empty());
+        assert_eq!(vm.registers.get("a"), Some(&2));
+    }
+
+    #[test]
+    fn on_jump_rewrites_the_register_the_offset_was_read_from() {
+        // Offset 0 re-executes the same jump once more (now reading the
+        // rewritten 1), so the register ends up incremented twice before an
+        // offset of 2 finally moves `pc` past the single-instruction
+        // program.
+        let program = vec![Instruction::new(Op::Jmp(Value::Register("j".to_owned())))];
+        let mut vm = Vm::new();
+        vm.registers.insert("j".to_owned(), 0);
+        vm.on_jump = Some(Box::new(|offset| offset + 1));
+        vm.run(&program, &mut std::iter::empty());
+        assert_eq!(vm.registers.get("j"), Some(&2));
+    }
+
+    #[test]
+    fn max_by_observer_tracks_the_running_maximum() {
+        let program = vec![
+            Instruction::new(Op::Add("a".to_owned(), Value::Literal(3))),
+            Instruction::new(Op::Add("a".to_owned(), Value::Literal(-5))),
+        ];
+        let mut vm = Vm::new();
+        let max = vm.run_with_observer(
+            &program,
+            &mut std::iter::empty(),
+            MaxBy::new(|state: &State| state.registers.values().max().copied()),
+        );
+        assert_eq!(max, Some(3));
+    }
+}
@@ -0,0 +1,215 @@
+//! Declarative parsing/validation for AoC's common "batch of blank-line
+//! separated paragraphs, each a bag of whitespace-separated `key:value`
+//! pairs" input shape (e.g. the Day 4 passport batches), so puzzles only
+//! need to describe their fields rather than re-implement the
+//! tokenization and per-field validation every time.
+
+use std::collections::HashMap;
+use std::ops::RangeBounds;
+use std::str::FromStr;
+
+use anyhow::{anyhow, Result};
+use regex::Regex;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum SchemaError {
+    #[error("missing colon delimiting property key from value in paragraph: {0:?}")]
+    MissingColon(String),
+    #[error("unknown key {0:?}")]
+    UnknownKey(String),
+}
+
+/// One parsed record: the raw key/value pairs of a single paragraph.
+#[derive(Debug, Clone)]
+pub struct Record<'a> {
+    fields: HashMap<&'a str, &'a str>,
+}
+
+impl<'a> Record<'a> {
+    pub fn get(&self, key: &str) -> Option<&'a str> {
+        self.fields.get(key).copied()
+    }
+}
+
+struct Field {
+    required: bool,
+    validate: Box<dyn Fn(&str) -> bool>,
+}
+
+/// A declarative schema for records of `key:value` fields: which keys are
+/// required vs. optional, and how to validate each one's value.
+#[derive(Default)]
+pub struct Schema {
+    fields: HashMap<&'static str, Field>,
+}
+
+impl Schema {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a field by key, along with a parser/validator that
+    /// succeeds iff the value is well-formed.
+    pub fn field<V>(
+        mut self,
+        key: &'static str,
+        required: bool,
+        validate: impl Fn(&str) -> Result<V> + 'static,
+    ) -> Self {
+        self.fields.insert(
+            key,
+            Field {
+                required,
+                validate: Box::new(move |s| validate(s).is_ok()),
+            },
+        );
+        self
+    }
+
+    /// Splits `input` into blank-line separated paragraphs and tokenizes
+    /// each into a [`Record`], rejecting paragraphs with malformed or
+    /// unrecognized fields.
+    pub fn parse_records<'a>(&self, input: &'a str) -> Result<Vec<Record<'a>>, SchemaError> {
+        input.split("\n\n").map(|p| self.parse_record(p)).collect()
+    }
+
+    fn parse_record<'a>(&self, paragraph: &'a str) -> Result<Record<'a>, SchemaError> {
+        let fields: HashMap<&str, &str> = paragraph
+            .split_whitespace()
+            .map(|prop| {
+                prop.split_once(':')
+                    .ok_or_else(|| SchemaError::MissingColon(paragraph.to_owned()))
+            })
+            .collect::<Result<_, _>>()?;
+
+        for &key in fields.keys() {
+            if !self.fields.contains_key(key) {
+                return Err(SchemaError::UnknownKey(key.to_owned()));
+            }
+        }
+
+        Ok(Record { fields })
+    }
+
+    /// Whether every required key is present in `record` (ignoring
+    /// whether present values are actually valid).
+    pub fn is_complete(&self, record: &Record) -> bool {
+        self.fields
+            .iter()
+            .filter(|(_, field)| field.required)
+            .all(|(key, _)| record.fields.contains_key(key))
+    }
+
+    /// Whether every key present in `record` is complete and valid.
+    pub fn is_valid(&self, record: &Record) -> bool {
+        self.is_complete(record)
+            && record
+                .fields
+                .iter()
+                .all(|(key, &val)| (self.fields[key].validate)(val))
+    }
+}
+
+/// Validates that a value parses as `T` and falls within `range`.
+pub fn integer_in_range<T>(range: impl RangeBounds<T> + 'static) -> impl Fn(&str) -> Result<T>
+where
+    T: FromStr + PartialOrd + std::fmt::Display + 'static,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    move |s| {
+        let value: T = s.parse()?;
+        range
+            .contains(&value)
+            .then_some(value)
+            .ok_or_else(|| anyhow!("{value} is out of range"))
+    }
+}
+
+/// Validates that a value is an integer suffixed with `unit` (e.g. the
+/// `cm`/`in` height case) and that the integer part falls within `range`.
+pub fn suffixed_unit_in_range(
+    unit: &'static str,
+    range: impl RangeBounds<i64> + 'static,
+) -> impl Fn(&str) -> Result<i64> {
+    move |s| {
+        let digits = s
+            .strip_suffix(unit)
+            .ok_or_else(|| anyhow!("{s:?} does not end in {unit:?}"))?;
+        let value: i64 = digits.parse()?;
+        range
+            .contains(&value)
+            .then_some(value)
+            .ok_or_else(|| anyhow!("{value}{unit} is out of range"))
+    }
+}
+
+/// Validates that a value matches `pattern` in full.
+pub fn matches_regex(pattern: &str) -> impl Fn(&str) -> Result<()> {
+    let re = Regex::new(pattern).expect("valid regex pattern");
+    move |s| {
+        re.is_match(s)
+            .then_some(())
+            .ok_or_else(|| anyhow!("{s:?} does not match /{re}/"))
+    }
+}
+
+/// Validates that a value is one of the given `set`.
+pub fn one_of(set: &'static [&'static str]) -> impl Fn(&str) -> Result<()> {
+    move |s| {
+        set.contains(&s)
+            .then_some(())
+            .ok_or_else(|| anyhow!("{s:?} is not one of {set:?}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example_schema() -> Schema {
+        Schema::new()
+            .field("byr", true, integer_in_range(1920..=2002))
+            .field("ecl", true, one_of(&["amb", "blu", "brn"]))
+            .field("cid", false, |_: &str| Ok(()))
+    }
+
+    #[test]
+    fn parses_blank_line_separated_paragraphs() {
+        let records = example_schema()
+            .parse_records("byr:1989 ecl:blu\n\nbyr:1999 ecl:brn cid:147")
+            .unwrap();
+        assert_eq!(records[0].get("byr"), Some("1989"));
+        assert_eq!(records[1].get("cid"), Some("147"));
+    }
+
+    #[test]
+    fn rejects_unknown_keys() {
+        let err = example_schema()
+            .parse_records("byr:1989 wat:huh")
+            .unwrap_err();
+        assert!(matches!(err, SchemaError::UnknownKey(key) if key == "wat"));
+    }
+
+    #[test]
+    fn rejects_missing_colon() {
+        let err = example_schema().parse_records("byr 1989").unwrap_err();
+        assert!(matches!(err, SchemaError::MissingColon(_)));
+    }
+
+    #[test]
+    fn completeness_ignores_validity() {
+        let schema = example_schema();
+        let records = schema.parse_records("byr:not-a-number ecl:blu").unwrap();
+        assert!(schema.is_complete(&records[0]));
+        assert!(!schema.is_valid(&records[0]));
+    }
+
+    #[test]
+    fn missing_required_key_is_incomplete() {
+        let schema = example_schema();
+        let records = schema.parse_records("ecl:blu").unwrap();
+        assert!(!schema.is_complete(&records[0]));
+        assert!(!schema.is_valid(&records[0]));
+    }
+}
@@ -0,0 +1,140 @@
+//! A tiny accumulator-register VM shared by the handful of AoC puzzles that
+//! describe their input as `acc`/`jmp`/`nop`-style assembly: a [`Program`]
+//! parsed line by line, and a [`Machine`] that [`Machine::run`]s it to
+//! either a normal finish or the first repeated instruction pointer.
+
+use std::collections::HashSet;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+/// One instruction. `Jmp`/`Nop` carry a relative offset instead of an
+/// absolute target, since that's how every VM-shaped AoC input spells them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Acc(isize),
+    Jmp(isize),
+    Nop(isize),
+}
+
+/// A line failed to parse as an [`Op`]: either the opcode wasn't recognized,
+/// or the argument following it wasn't a valid `isize`.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ParseOpError {
+    #[error("expected \"<op> <arg>\", found {0:?}")]
+    Malformed(String),
+    #[error("unknown opcode {0:?}")]
+    UnknownOp(String),
+    #[error("invalid argument: {0}")]
+    InvalidArg(#[from] std::num::ParseIntError),
+}
+
+impl FromStr for Op {
+    type Err = ParseOpError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let (op, arg) = line
+            .split_once(' ')
+            .ok_or_else(|| ParseOpError::Malformed(line.to_owned()))?;
+        let arg = arg.parse()?;
+        match op {
+            "acc" => Ok(Op::Acc(arg)),
+            "jmp" => Ok(Op::Jmp(arg)),
+            "nop" => Ok(Op::Nop(arg)),
+            _ => Err(ParseOpError::UnknownOp(op.to_owned())),
+        }
+    }
+}
+
+/// A parsed program, addressed by instruction index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Program(pub Vec<Op>);
+
+impl FromStr for Program {
+    type Err = ParseOpError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        input
+            .lines()
+            .map(str::parse)
+            .collect::<Result<_, _>>()
+            .map(Program)
+    }
+}
+
+/// How a [`Machine::run`] ended: either it walked off the end of the
+/// program, or it was about to re-execute an instruction pointer it had
+/// already visited. Both carry the accumulator at the moment execution
+/// stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunResult {
+    Finish(isize),
+    Loop(isize),
+}
+
+/// An in-progress execution of a [`Program`]: just the instruction pointer
+/// and accumulator, so a caller can single-[`Machine::step`] it themselves
+/// instead of always running to completion via [`Machine::run`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Machine {
+    pub ip: isize,
+    pub acc: isize,
+}
+
+impl Machine {
+    /// Executes the instruction at `ip` against `program`, if there is one.
+    /// Returns whether it did (`false` once `ip` has walked off either end).
+    pub fn step(&mut self, program: &Program) -> bool {
+        let Some(&op) = usize::try_from(self.ip)
+            .ok()
+            .and_then(|ip| program.0.get(ip))
+        else {
+            return false;
+        };
+
+        match op {
+            Op::Acc(i) => {
+                self.acc += i;
+                self.ip += 1;
+            }
+            Op::Jmp(i) => self.ip += i,
+            Op::Nop(_) => self.ip += 1,
+        }
+        true
+    }
+
+    /// Runs from the current state until `program` either finishes or
+    /// revisits an instruction pointer, tracking visited pointers in a
+    /// [`HashSet`] so the second case is caught on its first repeat rather
+    /// than looping forever.
+    pub fn run(mut self, program: &Program) -> RunResult {
+        let mut visited = HashSet::new();
+        while visited.insert(self.ip) {
+            if !self.step(program) {
+                return RunResult::Finish(self.acc);
+            }
+        }
+        RunResult::Loop(self.acc)
+    }
+}
+
+/// Searches for a single `Jmp`↔`Nop` flip that turns a looping `program`
+/// into one that finishes, returning the accumulator it finishes with. Tries
+/// each candidate instruction in turn rather than reasoning about which one
+/// closes the loop, since the programs these puzzles hand out are small
+/// enough that brute force is instant.
+pub fn repair_by_flipping_one_instruction(program: &Program) -> Option<isize> {
+    (0..program.0.len()).find_map(|i| {
+        let flipped = match program.0[i] {
+            Op::Jmp(arg) => Op::Nop(arg),
+            Op::Nop(arg) => Op::Jmp(arg),
+            Op::Acc(_) => return None,
+        };
+        let mut candidate = program.clone();
+        candidate.0[i] = flipped;
+        match Machine::default().run(&candidate) {
+            RunResult::Finish(acc) => Some(acc),
+            RunResult::Loop(_) => None,
+        }
+    })
+}